@@ -13,13 +13,14 @@ use zksync_block_reverter::{
 use zksync_config::{
     configs::{
         chain::NetworkConfig, wallets::Wallets, DatabaseSecrets, GeneralConfig, L1Secrets,
-        ObservabilityConfig,
+        MintlayerConfig, MintlayerSecrets, ObservabilityConfig,
     },
     ContractsConfig, DBConfig, EthConfig, GenesisConfig, PostgresConfig,
 };
 use zksync_core_leftovers::temp_config_store::decode_yaml_repr;
 use zksync_dal::{ConnectionPool, Core};
 use zksync_env_config::{object_store::SnapshotsObjectStoreConfig, FromEnv};
+use zksync_mintlayer_client::{clients::HttpMintlayerClient, DepositFeePolicy};
 use zksync_object_store::ObjectStoreFactory;
 use zksync_types::{Address, L1BatchNumber};
 
@@ -91,6 +92,10 @@ enum Command {
         /// Flag that specifies if snapshot files in GCS should be rolled back.
         #[arg(long, requires = "rollback_postgres")]
         rollback_snapshots: bool,
+        /// Flag that specifies if revocation records for Mintlayer anchors covering rolled back batches
+        /// should be published on Mintlayer.
+        #[arg(long, requires = "rollback_postgres")]
+        publish_mintlayer_revocations: bool,
         /// Flag that allows to roll back already executed blocks. It's ultra dangerous and required only for fixing external nodes.
         #[arg(long)]
         allow_executed_block_reversion: bool,
@@ -297,6 +302,7 @@ async fn main() -> anyhow::Result<()> {
             rollback_tree,
             rollback_sk_cache,
             rollback_snapshots,
+            publish_mintlayer_revocations,
             allow_executed_block_reversion,
         } => {
             if !rollback_tree && rollback_postgres {
@@ -338,6 +344,33 @@ async fn main() -> anyhow::Result<()> {
                             .await?,
                     );
                 }
+                if publish_mintlayer_revocations {
+                    let mintlayer_config = match &general_config {
+                        Some(general_config) => general_config
+                            .mintlayer_config
+                            .clone()
+                            .context("Failed to find mintlayer config")?,
+                        None => {
+                            MintlayerConfig::from_env().context("MintlayerConfig::from_env()")?
+                        }
+                    };
+                    let mintlayer_secrets = match &secrets_config {
+                        Some(secrets_config) => secrets_config
+                            .mintlayer
+                            .clone()
+                            .context("Failed to find mintlayer secrets")?,
+                        None => {
+                            MintlayerSecrets::from_env().context("MintlayerSecrets::from_env()")?
+                        }
+                    };
+                    block_reverter.enable_publishing_mintlayer_revocations(
+                        Box::new(HttpMintlayerClient::new(
+                            &mintlayer_config,
+                            &mintlayer_secrets,
+                        )?),
+                        DepositFeePolicy::new(&mintlayer_config)?.options(0),
+                    );
+                }
             }
             if rollback_tree {
                 block_reverter.enable_rolling_back_merkle_tree(db_config.merkle_tree.path);
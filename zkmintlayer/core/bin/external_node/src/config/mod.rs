@@ -13,7 +13,7 @@ use zksync_config::{
         api::{MaxResponseSize, MaxResponseSizeOverrides},
         consensus::{ConsensusConfig, ConsensusSecrets},
         en_config::ENConfig,
-        GeneralConfig, Secrets,
+        GeneralConfig, MintlayerConfig, MintlayerSecrets, Secrets,
     },
     ObjectStoreConfig,
 };
@@ -254,6 +254,9 @@ pub(crate) struct OptionalENConfig {
     /// Limit for fee history block range.
     #[serde(default = "OptionalENConfig::default_fee_history_limit")]
     pub fee_history_limit: u64,
+    /// Maximum total number of calls in all blocks simulated by a single `eth_simulateV1` request.
+    #[serde(default = "OptionalENConfig::default_simulate_calls_limit")]
+    pub simulate_calls_limit: usize,
     /// Maximum number of requests in a single batch JSON RPC request. Default is 500.
     #[serde(default = "OptionalENConfig::default_max_batch_request_size")]
     pub max_batch_request_size: usize,
@@ -480,6 +483,11 @@ impl OptionalENConfig {
                 web3_json_rpc.fee_history_limit,
                 default_fee_history_limit
             ),
+            simulate_calls_limit: load_optional_config_or_default!(
+                general_config.api_config,
+                web3_json_rpc.simulate_calls_limit,
+                default_simulate_calls_limit
+            ),
             max_batch_request_size: load_optional_config_or_default!(
                 general_config.api_config,
                 web3_json_rpc.max_batch_request_size,
@@ -752,6 +760,10 @@ impl OptionalENConfig {
         1_024
     }
 
+    const fn default_simulate_calls_limit() -> usize {
+        1_000
+    }
+
     const fn default_max_batch_request_size() -> usize {
         500 // The default limit is chosen to be reasonably permissive.
     }
@@ -1154,6 +1166,40 @@ pub(crate) fn snapshot_recovery_object_store_config() -> anyhow::Result<ObjectSt
         .context("failed loading snapshot object store config from env variables")
 }
 
+/// Configuration of the Mintlayer anchor verifier. The verifier is enabled only if the Mintlayer RPC URL is specified.
+#[derive(Debug)]
+pub(crate) struct MintlayerENConfig {
+    pub config: MintlayerConfig,
+    pub secrets: MintlayerSecrets,
+}
+
+impl MintlayerENConfig {
+    fn from_env() -> anyhow::Result<Option<Self>> {
+        if env::var("EN_MINTLAYER_RPC_URL").is_err() {
+            return Ok(None);
+        }
+        let config = envy::prefixed("EN_MINTLAYER_")
+            .from_env::<MintlayerConfig>()
+            .context("could not load external node config (Mintlayer params)")?;
+        let secrets = MintlayerSecrets {
+            rpc_password: env::var("EN_MINTLAYER_RPC_PASSWORD").ok().map(Into::into),
+            ipfs_api_key: None,
+            ipfs_secret_key: None,
+        };
+        Ok(Some(Self { config, secrets }))
+    }
+
+    fn from_configs(general_config: &GeneralConfig, secrets: &Secrets) -> Option<Self> {
+        let config = general_config.mintlayer_config.clone()?;
+        let secrets = secrets.mintlayer.clone().unwrap_or(MintlayerSecrets {
+            rpc_password: None,
+            ipfs_api_key: None,
+            ipfs_secret_key: None,
+        });
+        Some(Self { config, secrets })
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ApiComponentConfig {
     /// Address of the tree API used by this EN in case it does not have a
@@ -1198,6 +1244,7 @@ pub(crate) struct ExternalNodeConfig<R = RemoteENConfig> {
     pub observability: ObservabilityENConfig,
     pub experimental: ExperimentalENConfig,
     pub consensus: Option<ConsensusConfig>,
+    pub mintlayer: Option<MintlayerENConfig>,
     pub api_component: ApiComponentConfig,
     pub tree_component: TreeComponentConfig,
     pub remote: R,
@@ -1215,6 +1262,7 @@ impl ExternalNodeConfig<()> {
                 .from_env::<ExperimentalENConfig>()
                 .context("could not load external node config (experimental params)")?,
             consensus: read_consensus_config().context("read_consensus_config()")?,
+            mintlayer: MintlayerENConfig::from_env()?,
             api_component: envy::prefixed("EN_API_")
                 .from_env::<ApiComponentConfig>()
                 .context("could not load external node config (API component params)")?,
@@ -1267,6 +1315,8 @@ impl ExternalNodeConfig<()> {
         let observability = ObservabilityENConfig::from_configs(&general_config)?;
         let experimental = ExperimentalENConfig::from_configs(&general_config)?;
 
+        let mintlayer = MintlayerENConfig::from_configs(&general_config, &secrets_config);
+
         let api_component = ApiComponentConfig::from_configs(&general_config);
         let tree_component = TreeComponentConfig::from_configs(&general_config);
 
@@ -1277,6 +1327,7 @@ impl ExternalNodeConfig<()> {
             observability,
             experimental,
             consensus,
+            mintlayer,
             api_component,
             tree_component,
             remote: (),
@@ -1298,6 +1349,7 @@ impl ExternalNodeConfig<()> {
             observability: self.observability,
             experimental: self.experimental,
             consensus: self.consensus,
+            mintlayer: self.mintlayer,
             tree_component: self.tree_component,
             api_component: self.api_component,
             remote,
@@ -1316,6 +1368,7 @@ impl ExternalNodeConfig {
             observability: ObservabilityENConfig::default(),
             experimental: ExperimentalENConfig::mock(),
             consensus: None,
+            mintlayer: None,
             api_component: ApiComponentConfig {
                 tree_api_remote_url: None,
             },
@@ -1349,6 +1402,7 @@ impl From<&ExternalNodeConfig> for InternalApiConfig {
             l2_testnet_paymaster_addr: config.remote.l2_testnet_paymaster_addr,
             req_entities_limit: config.optional.req_entities_limit,
             fee_history_limit: config.optional.fee_history_limit,
+            simulate_calls_limit: config.optional.simulate_calls_limit,
             base_token_address: Some(config.remote.base_token_addr),
             filters_disabled: config.optional.filters_disabled,
             dummy_verifier: config.remote.dummy_verifier,
@@ -21,10 +21,13 @@ use zksync_db_connection::{
     connection_pool::ConnectionPoolBuilder, healthcheck::ConnectionPoolHealthCheck,
 };
 use zksync_health_check::{AppHealthCheck, HealthStatus, ReactiveHealthCheck};
+use zksync_ipfs_store::create_ipfs_reader;
 use zksync_metadata_calculator::{
     api_server::{TreeApiClient, TreeApiHttpClient},
     MetadataCalculator, MetadataCalculatorConfig, MetadataCalculatorRecoveryConfig,
 };
+use zksync_mintlayer_anchor_verifier::MintlayerAnchorVerifier;
+use zksync_mintlayer_client::clients::HttpMintlayerClient;
 use zksync_node_api_server::{
     execution_sandbox::VmConcurrencyLimiter,
     healthcheck::HealthCheckHandle,
@@ -362,6 +365,25 @@ async fn run_core(
     app_health.insert_component(consistency_checker.health_check().clone())?;
     let consistency_checker_handle = tokio::spawn(consistency_checker.run(stop_receiver.clone()));
 
+    if let Some(mintlayer) = &config.mintlayer {
+        let anchor_verifier = MintlayerAnchorVerifier::new(
+            main_node_client.clone(),
+            Box::new(HttpMintlayerClient::new(
+                &mintlayer.config,
+                &mintlayer.secrets,
+            )?),
+            create_ipfs_reader(&mintlayer.config)?,
+            mintlayer.config.confirmations(),
+            10, // TODO (BFT-97): Make it a part of a proper EN config
+            singleton_pool_builder
+                .build()
+                .await
+                .context("failed to build connection pool for MintlayerAnchorVerifier")?,
+        );
+        app_health.insert_component(anchor_verifier.health_check().clone())?;
+        task_handles.push(tokio::spawn(anchor_verifier.run(stop_receiver.clone())));
+    }
+
     let batch_status_updater = BatchStatusUpdater::new(
         main_node_client.clone(),
         singleton_pool_builder
@@ -23,6 +23,7 @@ use zksync_node_framework::{
         main_node_client::MainNodeClientLayer,
         main_node_fee_params_fetcher::MainNodeFeeParamsFetcherLayer,
         metadata_calculator::MetadataCalculatorLayer,
+        mintlayer_anchor_verifier::MintlayerAnchorVerifierLayer,
         pools_layer::PoolsLayerBuilder,
         postgres_metrics::PostgresMetricsLayer,
         prometheus_exporter::PrometheusExporterLayer,
@@ -257,6 +258,20 @@ impl ExternalNodeBuilder {
         Ok(self)
     }
 
+    fn add_mintlayer_anchor_verifier_layer(mut self) -> anyhow::Result<Self> {
+        let Some(mintlayer) = &self.config.mintlayer else {
+            return Ok(self);
+        };
+        let max_batches_to_recheck = 10; // TODO (BFT-97): Make it a part of a proper EN config
+        let layer = MintlayerAnchorVerifierLayer::new(
+            mintlayer.config.clone(),
+            mintlayer.secrets.clone(),
+            max_batches_to_recheck,
+        );
+        self.node.add_layer(layer);
+        Ok(self)
+    }
+
     fn add_commitment_generator_layer(mut self) -> anyhow::Result<Self> {
         let layer =
             CommitmentGeneratorLayer::new(self.config.optional.l1_batch_commit_data_generator_mode)
@@ -497,6 +512,7 @@ impl ExternalNodeBuilder {
                         .add_consensus_layer()?
                         .add_pruning_layer()?
                         .add_consistency_checker_layer()?
+                        .add_mintlayer_anchor_verifier_layer()?
                         .add_commitment_generator_layer()?
                         .add_batch_status_updater_layer()?;
                 }
@@ -0,0 +1,96 @@
+//! Utility anchoring historical L1 batches on Mintlayer.
+//!
+//! L1 batches whose operations were sent before anchoring was enabled (or were lost from the anchoring queue)
+//! have no IPFS documents or Mintlayer anchors. The backfill regenerates commit / prove / execute documents
+//! for such batches from Postgres, uploads them to IPFS and anchors them on Mintlayer. Operations already present
+//! in the anchoring queue are skipped, so the backfill can be safely repeated for overlapping ranges.
+//!
+//! Confirmations of the created anchors are tracked by the server once it's running.
+
+use anyhow::Context as _;
+use clap::Parser;
+use zksync_config::{
+    configs::{DatabaseSecrets, MintlayerConfig, MintlayerSecrets, ObservabilityConfig},
+    EthConfig, ObjectStoreConfig,
+};
+use zksync_dal::{ConnectionPool, Core};
+use zksync_env_config::FromEnv;
+use zksync_ipfs_store::create_ipfs_store;
+use zksync_mintlayer_anchor::MintlayerAnchorer;
+use zksync_mintlayer_client::clients::HttpMintlayerClient;
+use zksync_object_store::ObjectStoreFactory;
+use zksync_types::L1BatchNumber;
+
+#[derive(Debug, Parser)]
+#[command(
+    author = "Matter Labs",
+    version,
+    about = "Anchoring of historical L1 batches on Mintlayer",
+    long_about = None
+)]
+struct Cli {
+    /// First L1 batch to backfill.
+    #[arg(long)]
+    from_batch: u32,
+    /// Last L1 batch to backfill (inclusive).
+    #[arg(long)]
+    to_batch: u32,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opts = Cli::parse();
+    anyhow::ensure!(
+        opts.from_batch <= opts.to_batch,
+        "`--from-batch` must not exceed `--to-batch`"
+    );
+    let observability_config =
+        ObservabilityConfig::from_env().context("ObservabilityConfig::from_env()")?;
+    let log_format: zksync_vlog::LogFormat = observability_config
+        .log_format
+        .parse()
+        .context("Invalid log format")?;
+    let mut builder = zksync_vlog::ObservabilityBuilder::new().with_log_format(log_format);
+    if let Some(sentry_url) = observability_config.sentry_url {
+        builder = builder
+            .with_sentry_url(&sentry_url)
+            .context("Invalid Sentry URL")?
+            .with_sentry_environment(observability_config.sentry_environment);
+    }
+    let _guard = builder.build();
+
+    let mintlayer_config = MintlayerConfig::from_env().context("MintlayerConfig::from_env()")?;
+    let mintlayer_secrets = MintlayerSecrets::from_env().context("MintlayerSecrets::from_env()")?;
+    let eth_config = EthConfig::from_env().context("EthConfig::from_env()")?;
+    let sender_config = eth_config.sender.context("sender")?;
+    let object_store_config =
+        ObjectStoreConfig::from_env().context("ObjectStoreConfig::from_env()")?;
+    let database_secrets = DatabaseSecrets::from_env().context("DatabaseSecrets::from_env()")?;
+
+    let pool = ConnectionPool::<Core>::singleton(database_secrets.master_url()?)
+        .build()
+        .await
+        .context("failed to build a connection pool")?;
+    let blob_store = ObjectStoreFactory::new(object_store_config)
+        .create_store()
+        .await?;
+    let anchorer = MintlayerAnchorer::new(
+        pool,
+        blob_store,
+        sender_config.pubdata_sending_mode.into(),
+        create_ipfs_store(&mintlayer_config, &mintlayer_secrets)?,
+        Box::new(HttpMintlayerClient::new(
+            &mintlayer_config,
+            &mintlayer_secrets,
+        )?),
+        sender_config.aggregate_tx_poll_period(),
+        &mintlayer_config,
+    )?;
+
+    let l1_batch_range = L1BatchNumber(opts.from_batch)..=L1BatchNumber(opts.to_batch);
+    let uploaded_operations = anchorer.backfill(l1_batch_range.clone()).await?;
+    tracing::info!(
+        "Finished backfilling L1 batches {l1_batch_range:?}: uploaded and anchored {uploaded_operations} operations"
+    );
+    Ok(())
+}
@@ -0,0 +1,321 @@
+//! Collection of L1 batch data from Mintlayer anchors.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Arc,
+};
+
+use anyhow::Context as _;
+use zksync_ipfs_store::IpfsStore;
+use zksync_mintlayer_client::{MintlayerInterface, TxState};
+use zksync_mintlayer_documents::{CommitOperation, OperationDocument};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType,
+    commitment::L1BatchWithMetadata,
+    mintlayer::{
+        MintlayerAnchorCommitment, MintlayerAnchorLeaf, MintlayerAnchorPayload,
+        MintlayerAnchorRevocation,
+    },
+    L1BatchNumber,
+};
+
+/// Data deposited by a Mintlayer transaction used for recovery.
+#[derive(Debug)]
+enum Deposit {
+    Anchor(MintlayerAnchorPayload),
+    Revocation(MintlayerAnchorRevocation),
+}
+
+impl Deposit {
+    fn decode(data: &[u8]) -> anyhow::Result<Self> {
+        if let Ok(revocation) = MintlayerAnchorRevocation::decode(data) {
+            return Ok(Self::Revocation(revocation));
+        }
+        let payload =
+            MintlayerAnchorPayload::decode(data).context("failed decoding anchor payload")?;
+        Ok(Self::Anchor(payload))
+    }
+}
+
+/// L1 batches collected from anchored commit documents.
+#[derive(Debug, Default)]
+pub(crate) struct AnchoredBatches {
+    /// Genesis L1 batch as referenced by the commit operation of L1 batch #1.
+    pub genesis: Option<L1BatchWithMetadata>,
+    pub committed: BTreeMap<L1BatchNumber, L1BatchWithMetadata>,
+    /// Last L1 batch covered by an anchored execute operation.
+    pub last_executed: Option<L1BatchNumber>,
+    /// IDs of anchor transactions that committed each L1 batch.
+    committed_by: BTreeMap<L1BatchNumber, String>,
+}
+
+impl AnchoredBatches {
+    fn add_commit(&mut self, tx_id: &str, operation: CommitOperation) -> anyhow::Result<()> {
+        if operation.prev_l1_batch.header.number == L1BatchNumber(0) {
+            self.genesis = Some(operation.prev_l1_batch);
+        }
+        for l1_batch in operation.l1_batches {
+            let number = l1_batch.header.number;
+            if let Some(prev) = self.committed.get(&number) {
+                anyhow::ensure!(
+                    *prev == l1_batch,
+                    "L1 batch #{number} is committed with different data by anchors {} and {tx_id}; \
+                     the stale anchor must be revoked, or its transaction excluded from recovery",
+                    self.committed_by[&number]
+                );
+                continue;
+            }
+            self.committed.insert(number, l1_batch);
+            self.committed_by.insert(number, tx_id.to_owned());
+        }
+        Ok(())
+    }
+
+    fn add_execute(&mut self, last_l1_batch: L1BatchNumber) {
+        self.last_executed = self.last_executed.max(Some(last_l1_batch));
+    }
+
+    /// Returns contiguous L1 batches starting from #1 that can be recovered. If `executed_only` is set,
+    /// only L1 batches covered by anchored execute operations are returned.
+    pub fn recoverable_l1_batches(&self, executed_only: bool) -> Vec<&L1BatchWithMetadata> {
+        let last_number = if executed_only {
+            self.last_executed.unwrap_or(L1BatchNumber(0))
+        } else {
+            L1BatchNumber(u32::MAX)
+        };
+        let mut expected_number = L1BatchNumber(1);
+        let mut l1_batches = vec![];
+        for (&number, l1_batch) in self.committed.range(..=last_number) {
+            if number != expected_number {
+                tracing::warn!(
+                    "Commit operation for L1 batch #{expected_number} is not anchored; \
+                     L1 batches starting from #{number} will not be recovered"
+                );
+                break;
+            }
+            l1_batches.push(l1_batch);
+            expected_number += 1;
+        }
+        l1_batches
+    }
+}
+
+/// Collects L1 batch data from Mintlayer anchor transactions and IPFS.
+#[derive(Debug)]
+pub(crate) struct AnchorCollector {
+    mintlayer_client: Box<dyn MintlayerInterface>,
+    ipfs_store: Arc<dyn IpfsStore>,
+    confirmations: u64,
+}
+
+impl AnchorCollector {
+    pub fn new(
+        mintlayer_client: Box<dyn MintlayerInterface>,
+        ipfs_store: Arc<dyn IpfsStore>,
+        confirmations: u32,
+    ) -> Self {
+        Self {
+            mintlayer_client,
+            ipfs_store,
+            confirmations: confirmations.into(),
+        }
+    }
+
+    /// Collects data from the specified transactions, which may deposit both anchors and revocation records
+    /// published by the block reverter. Revoked anchors are skipped. If an L1 batch is committed with different data
+    /// by several anchors that are not revoked, collection fails, since it's impossible to determine which data is valid.
+    pub async fn collect(&self, tx_ids: &[String]) -> anyhow::Result<AnchoredBatches> {
+        let mut deposits = vec![];
+        for tx_id in tx_ids {
+            let deposit = self
+                .load_deposited_data(tx_id)
+                .await
+                .and_then(|data| {
+                    Deposit::decode(&data.context("transaction doesn't deposit data")?)
+                })
+                .with_context(|| format!("failed loading Mintlayer transaction {tx_id}"))?;
+            deposits.push((tx_id.clone(), deposit));
+        }
+        self.collect_deposits(deposits).await
+    }
+
+    /// Collects data from confirmed transactions of the wallet account involving `address` (or any address
+    /// of the account if it's not specified) in the order of their inclusion into blocks. Transactions that deposit
+    /// neither anchors nor revocation records are skipped. Unlike [`Self::collect()`], this doesn't require
+    /// any information from the main node.
+    pub async fn collect_from_wallet(
+        &self,
+        address: Option<&str>,
+        max_transactions: usize,
+    ) -> anyhow::Result<AnchoredBatches> {
+        let mut txs = self
+            .mintlayer_client
+            .transaction_list_by_address(address, max_transactions)
+            .await
+            .context("failed listing wallet transactions")?;
+        anyhow::ensure!(
+            txs.len() < max_transactions,
+            "wallet has at least {max_transactions} transactions, so some anchors may be missing; \
+             increase the maximum number of inspected transactions"
+        );
+        // Sorting is stable, so the wallet order is retained for transactions included into the same block.
+        txs.sort_by_key(|tx| tx.block_height);
+        tracing::info!("Inspecting {} wallet transactions", txs.len());
+
+        let mut deposits = vec![];
+        for tx in txs {
+            let data = self
+                .load_deposited_data(&tx.tx_id)
+                .await
+                .with_context(|| format!("failed loading Mintlayer transaction {}", tx.tx_id))?;
+            match data.as_deref().map(Deposit::decode) {
+                Some(Ok(deposit)) => deposits.push((tx.tx_id, deposit)),
+                Some(Err(_)) | None => {
+                    tracing::debug!(
+                        "Skipping transaction {} that doesn't deposit anchoring data",
+                        tx.tx_id
+                    );
+                }
+            }
+        }
+        tracing::info!("Found {} anchoring transactions", deposits.len());
+        self.collect_deposits(deposits).await
+    }
+
+    async fn collect_deposits(
+        &self,
+        deposits: Vec<(String, Deposit)>,
+    ) -> anyhow::Result<AnchoredBatches> {
+        let mut anchors = vec![];
+        let mut revoked = HashSet::new();
+        for (tx_id, deposit) in deposits {
+            match deposit {
+                Deposit::Anchor(payload) => anchors.push((tx_id, payload)),
+                Deposit::Revocation(revocation) => {
+                    tracing::info!(
+                        "Transaction {tx_id} revokes anchor with root document {}",
+                        revocation.revoked.root_cid
+                    );
+                    revoked.insert(revocation.revoked.encode());
+                }
+            }
+        }
+
+        let mut batches = AnchoredBatches::default();
+        for (tx_id, payload) in anchors {
+            if revoked.contains(&payload.encode()) {
+                tracing::info!("Skipping revoked anchor {tx_id}");
+                continue;
+            }
+            self.collect_anchor(&tx_id, payload, &mut batches)
+                .await
+                .with_context(|| format!("failed collecting Mintlayer anchor {tx_id}"))?;
+        }
+        Ok(batches)
+    }
+
+    async fn collect_anchor(
+        &self,
+        tx_id: &str,
+        payload: MintlayerAnchorPayload,
+        batches: &mut AnchoredBatches,
+    ) -> anyhow::Result<()> {
+        let root_document = self.ipfs_store.get(&payload.root_cid).await?;
+
+        let Some(merkle_root) = payload.merkle_root else {
+            // Legacy anchor: the root document lists CIDs of operation documents without their types.
+            let cids: Vec<String> = serde_json::from_slice(&root_document)
+                .context("failed parsing legacy root document")?;
+            tracing::info!(
+                "Anchor {tx_id} is a legacy anchor with {} operations",
+                cids.len()
+            );
+            for cid in &cids {
+                let bytes = self.ipfs_store.get(cid).await?;
+                if let Ok(OperationDocument::Commit(operation)) =
+                    OperationDocument::decode(&bytes, AggregatedActionType::Commit)
+                {
+                    batches.add_commit(tx_id, operation)?;
+                } else if let Ok(OperationDocument::Execute(operation)) =
+                    OperationDocument::decode(&bytes, AggregatedActionType::Execute)
+                {
+                    if let Some(last) = operation.l1_batches.last() {
+                        batches.add_execute(last.header.number);
+                    }
+                }
+            }
+            return Ok(());
+        };
+
+        let leaves: Vec<MintlayerAnchorLeaf> =
+            serde_json::from_slice(&root_document).context("failed parsing root document")?;
+        let commitment = MintlayerAnchorCommitment::new(leaves);
+        anyhow::ensure!(
+            commitment.merkle_root() == merkle_root,
+            "Merkle root of root document {} differs from the one deposited on Mintlayer; \
+             computed: {:?}, deposited: {merkle_root:?}",
+            payload.root_cid,
+            commitment.merkle_root()
+        );
+        tracing::info!(
+            "Anchor {tx_id} covers {} operations",
+            commitment.leaves().len()
+        );
+
+        for leaf in commitment.leaves() {
+            match leaf.action_type {
+                AggregatedActionType::Commit => {
+                    let bytes = self.ipfs_store.get(&leaf.cid).await?;
+                    let OperationDocument::Commit(operation) =
+                        OperationDocument::decode(&bytes, AggregatedActionType::Commit)
+                            .with_context(|| {
+                                format!("failed decoding commit document {}", leaf.cid)
+                            })?
+                    else {
+                        unreachable!("decoded document type is checked");
+                    };
+                    let numbers: Vec<_> = operation
+                        .l1_batches
+                        .iter()
+                        .map(|l1_batch| l1_batch.header.number)
+                        .collect();
+                    anyhow::ensure!(
+                        numbers.first() == Some(leaf.l1_batch_range.start())
+                            && numbers.last() == Some(leaf.l1_batch_range.end()),
+                        "commit document {} covers L1 batches {numbers:?}, while anchored range is {:?}",
+                        leaf.cid,
+                        leaf.l1_batch_range
+                    );
+                    batches.add_commit(tx_id, operation)?;
+                }
+                AggregatedActionType::Execute => {
+                    batches.add_execute(*leaf.l1_batch_range.end());
+                }
+                AggregatedActionType::PublishProofOnchain => { /* proofs are not needed for recovery */
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Loads data deposited by the specified transaction, checking that the transaction is confirmed.
+    /// Returns `None` if the transaction doesn't deposit data.
+    async fn load_deposited_data(&self, tx_id: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        let tx = self
+            .mintlayer_client
+            .transaction_get(tx_id)
+            .await?
+            .context("transaction is unknown to the wallet; make sure the wallet tracks the anchoring address")?;
+        let TxState::Confirmed { block_height } = tx.state else {
+            anyhow::bail!("transaction is not included into a block: {:?}", tx.state);
+        };
+        let best_block = self.mintlayer_client.wallet_best_block().await?;
+        anyhow::ensure!(
+            best_block.height + 1 >= block_height + self.confirmations,
+            "transaction included at block {block_height} has less than {} confirmations (best block: {})",
+            self.confirmations,
+            best_block.height
+        );
+        Ok(tx.deposited_data)
+    }
+}
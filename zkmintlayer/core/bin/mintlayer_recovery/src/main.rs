@@ -0,0 +1,156 @@
+//! Utility restoring the L2 state from data anchored on Mintlayer.
+//!
+//! The recovery walks Mintlayer anchor transactions found among confirmed transactions of the wallet account
+//! (or specified explicitly), downloads anchored commit documents from IPFS, decodes pubdata of the committed
+//! L1 batches and replays their state diffs on top of the genesis state.
+//! The Merkle tree root hash is verified against the anchored metadata after each L1 batch.
+//!
+//! # Assumptions
+//!
+//! - Postgres and the Merkle tree must be empty. The recovered state is persisted in the same way as
+//!   for snapshot recovery, so that the node can continue from the last recovered L1 batch.
+//! - Pubdata contains neither transactions nor L2 blocks, so the block history before the last recovered
+//!   L1 batch is not restored. Token metadata is not restored either.
+//! - The Mintlayer wallet must track the anchoring address, so that it can return anchor transactions.
+//! - Anchors covering reverted L1 batches must be revoked by the block reverter; otherwise, conflicting commits
+//!   of the same L1 batch cannot be resolved and the recovery fails.
+
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use clap::Parser;
+use zksync_config::{
+    configs::{DatabaseSecrets, MintlayerConfig, MintlayerSecrets, ObservabilityConfig},
+    DBConfig, GenesisConfig,
+};
+use zksync_core_leftovers::temp_config_store::decode_yaml_repr;
+use zksync_dal::{ConnectionPool, Core};
+use zksync_env_config::FromEnv;
+use zksync_ipfs_store::create_ipfs_reader;
+use zksync_mintlayer_client::clients::HttpMintlayerClient;
+use zksync_node_genesis::{GenesisParams, GenesisState};
+
+use crate::{anchors::AnchorCollector, recovery::Recovery};
+
+mod anchors;
+mod pubdata;
+mod recovery;
+mod state;
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Parser)]
+#[command(
+    author = "Matter Labs",
+    version,
+    about = "Recovery of L2 state from Mintlayer anchors",
+    long_about = None
+)]
+struct Cli {
+    /// Path to a file with IDs of Mintlayer anchor transactions, one per line, in the order they were anchored.
+    /// Transactions depositing revocation records published by the block reverter should be listed as well,
+    /// so that revoked anchors are skipped. Empty lines and lines starting with `#` are ignored.
+    /// If not set, anchor and revocation transactions are found by walking confirmed transactions of the wallet.
+    #[arg(long)]
+    anchors: Option<PathBuf>,
+    /// Mintlayer address used for anchoring. If set, only wallet transactions involving this address are inspected
+    /// when looking for anchors; otherwise, all transactions of the wallet account are inspected.
+    #[arg(long, conflicts_with = "anchors")]
+    anchoring_address: Option<String>,
+    /// Maximum number of wallet transactions inspected when looking for anchors.
+    #[arg(long, default_value_t = 100_000, conflicts_with = "anchors")]
+    max_wallet_transactions: usize,
+    /// Recover L1 batches that were committed, but not executed. By default, only L1 batches covered
+    /// by anchored execute operations are recovered.
+    #[arg(long)]
+    include_unexecuted: bool,
+    /// Path to yaml genesis config. If set, it will be used instead of env vars.
+    #[arg(long)]
+    genesis_path: Option<PathBuf>,
+}
+
+fn read_anchor_tx_ids(path: &PathBuf) -> anyhow::Result<Vec<String>> {
+    let contents = std::fs::read_to_string(path).with_context(|| path.display().to_string())?;
+    Ok(contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_owned)
+        .collect())
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opts = Cli::parse();
+    let observability_config =
+        ObservabilityConfig::from_env().context("ObservabilityConfig::from_env()")?;
+    let log_format: zksync_vlog::LogFormat = observability_config
+        .log_format
+        .parse()
+        .context("Invalid log format")?;
+    let mut builder = zksync_vlog::ObservabilityBuilder::new().with_log_format(log_format);
+    if let Some(sentry_url) = observability_config.sentry_url {
+        builder = builder
+            .with_sentry_url(&sentry_url)
+            .context("Invalid Sentry URL")?
+            .with_sentry_environment(observability_config.sentry_environment);
+    }
+    let _guard = builder.build();
+
+    let genesis_config = match &opts.genesis_path {
+        Some(path) => {
+            let yaml = std::fs::read_to_string(path).with_context(|| path.display().to_string())?;
+            decode_yaml_repr::<zksync_protobuf_config::proto::genesis::Genesis>(&yaml)
+                .context("failed decoding genesis YAML config")?
+        }
+        None => GenesisConfig::from_env().context("GenesisConfig::from_env()")?,
+    };
+    let mintlayer_config = MintlayerConfig::from_env().context("MintlayerConfig::from_env()")?;
+    let mintlayer_secrets = MintlayerSecrets::from_env().context("MintlayerSecrets::from_env()")?;
+    let db_config = DBConfig::from_env().context("DBConfig::from_env()")?;
+    let database_secrets = DatabaseSecrets::from_env().context("DatabaseSecrets::from_env()")?;
+
+    let collector = AnchorCollector::new(
+        Box::new(HttpMintlayerClient::new(
+            &mintlayer_config,
+            &mintlayer_secrets,
+        )?),
+        create_ipfs_reader(&mintlayer_config)?,
+        mintlayer_config.confirmations(),
+    );
+    let anchored_batches = if let Some(anchors_path) = &opts.anchors {
+        let tx_ids = read_anchor_tx_ids(anchors_path)?;
+        tracing::info!("Collecting data from {} Mintlayer anchors", tx_ids.len());
+        collector.collect(&tx_ids).await?
+    } else {
+        tracing::info!("Collecting data from Mintlayer anchors found in the wallet");
+        collector
+            .collect_from_wallet(
+                opts.anchoring_address.as_deref(),
+                opts.max_wallet_transactions,
+            )
+            .await?
+    };
+    let l1_batches = anchored_batches.recoverable_l1_batches(!opts.include_unexecuted);
+    tracing::info!(
+        "Collected {} committed L1 batches; recovering {} L1 batches",
+        anchored_batches.committed.len(),
+        l1_batches.len()
+    );
+
+    let genesis_params = GenesisParams::load_genesis_params(genesis_config)?;
+    let pool = ConnectionPool::<Core>::singleton(database_secrets.master_url()?)
+        .build()
+        .await
+        .context("failed to build a connection pool")?;
+    Recovery::new(pool, db_config.merkle_tree.path.into())
+        .run(
+            GenesisState::new(&genesis_params),
+            anchored_batches.genesis.as_ref(),
+            &l1_batches,
+        )
+        .await?;
+
+    tracing::info!("Finished recovery from Mintlayer anchors");
+    Ok(())
+}
@@ -0,0 +1,246 @@
+//! Decoding of L1 batch pubdata.
+
+use anyhow::Context as _;
+use zksync_types::{
+    commitment::L1BatchWithMetadata,
+    l2_to_l1_log::L2ToL1Log,
+    writes::{BYTES_PER_DERIVED_KEY, BYTES_PER_ENUMERATION_INDEX},
+    Address, H256, U256,
+};
+
+/// Version of the state diffs compression supported by the decoder.
+const STATE_DIFFS_COMPRESSION_VERSION: u8 = 1;
+/// Size of a serialized L2-to-L1 log.
+const L2_TO_L1_LOG_SIZE: usize = 88;
+
+/// Compressed new value of a storage slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CompressedValue {
+    /// Full new value.
+    Full(U256),
+    /// New value is the previous value plus the specified diff.
+    Add(U256),
+    /// New value is the previous value minus the specified diff.
+    Sub(U256),
+    /// New value is specified in its shortened form.
+    Transform(U256),
+}
+
+impl CompressedValue {
+    /// Restores the new value given the previous value of the slot (zero for initial writes).
+    pub fn apply(self, prev_value: U256) -> U256 {
+        match self {
+            Self::Full(value) | Self::Transform(value) => value,
+            Self::Add(diff) => prev_value.overflowing_add(diff).0,
+            Self::Sub(diff) => prev_value.overflowing_sub(diff).0,
+        }
+    }
+}
+
+/// Write to a slot that was never written to before.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct InitialWrite {
+    pub derived_key: H256,
+    pub value: CompressedValue,
+}
+
+/// Write to a slot identified by its enumeration index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RepeatedWrite {
+    pub enumeration_index: u64,
+    pub value: CompressedValue,
+}
+
+/// Pubdata of an L1 batch as published by the `commitBatches` operation.
+#[derive(Debug, Default)]
+pub(crate) struct Pubdata {
+    pub l2_to_l1_logs: Vec<L2ToL1Log>,
+    pub l2_to_l1_messages: Vec<Vec<u8>>,
+    pub published_bytecodes: Vec<Vec<u8>>,
+    /// Initial writes in the order of their enumeration indices.
+    pub initial_writes: Vec<InitialWrite>,
+    pub repeated_writes: Vec<RepeatedWrite>,
+}
+
+impl Pubdata {
+    /// Extracts pubdata from an L1 batch. Commit documents contain full L1 batch data regardless
+    /// of the DA mode the pubdata was sent with, so the same pubdata is decoded in all cases.
+    pub fn for_l1_batch(l1_batch: &L1BatchWithMetadata) -> anyhow::Result<Self> {
+        let protocol_version = l1_batch.header.protocol_version.unwrap_or_default();
+        anyhow::ensure!(
+            !protocol_version.is_pre_boojum(),
+            "L1 batch #{} uses pre-boojum protocol version {protocol_version:?}; its pubdata cannot be decoded",
+            l1_batch.header.number
+        );
+        let raw = match &l1_batch.header.pubdata_input {
+            Some(pubdata_input) => pubdata_input.clone(),
+            None => l1_batch.construct_pubdata(),
+        };
+        Self::decode(&raw).with_context(|| {
+            format!(
+                "failed decoding pubdata for L1 batch #{}",
+                l1_batch.header.number
+            )
+        })
+    }
+
+    /// Decodes pubdata: logs, messages, bytecodes and compressed state diffs.
+    pub fn decode(bytes: &[u8]) -> anyhow::Result<Self> {
+        let mut reader = Reader::new(bytes);
+
+        let log_count = reader.read_u32().context("log count")?;
+        let l2_to_l1_logs = (0..log_count)
+            .map(|_| {
+                reader
+                    .read_bytes(L2_TO_L1_LOG_SIZE)
+                    .map(decode_l2_to_l1_log)
+            })
+            .collect::<anyhow::Result<_>>()
+            .context("L2-to-L1 logs")?;
+
+        let message_count = reader.read_u32().context("message count")?;
+        let l2_to_l1_messages = (0..message_count)
+            .map(|_| reader.read_prefixed_bytes())
+            .collect::<anyhow::Result<_>>()
+            .context("L2-to-L1 messages")?;
+
+        let bytecode_count = reader.read_u32().context("bytecode count")?;
+        let published_bytecodes = (0..bytecode_count)
+            .map(|_| reader.read_prefixed_bytes())
+            .collect::<anyhow::Result<_>>()
+            .context("published bytecodes")?;
+
+        let (initial_writes, repeated_writes) =
+            decode_state_diffs(&mut reader).context("state diffs")?;
+        anyhow::ensure!(
+            reader.is_empty(),
+            "{} trailing bytes after state diffs",
+            reader.bytes.len()
+        );
+
+        Ok(Self {
+            l2_to_l1_logs,
+            l2_to_l1_messages,
+            published_bytecodes,
+            initial_writes,
+            repeated_writes,
+        })
+    }
+}
+
+fn decode_l2_to_l1_log(bytes: &[u8]) -> L2ToL1Log {
+    L2ToL1Log {
+        shard_id: bytes[0],
+        is_service: bytes[1] != 0,
+        tx_number_in_block: u16::from_be_bytes([bytes[2], bytes[3]]),
+        sender: Address::from_slice(&bytes[4..24]),
+        key: H256::from_slice(&bytes[24..56]),
+        value: H256::from_slice(&bytes[56..88]),
+    }
+}
+
+/// Decodes compressed state diffs, as produced by `zksync_types::writes::compress_state_diffs()`.
+fn decode_state_diffs(
+    reader: &mut Reader<'_>,
+) -> anyhow::Result<(Vec<InitialWrite>, Vec<RepeatedWrite>)> {
+    let version = reader.read_u8().context("compression version")?;
+    anyhow::ensure!(
+        version == STATE_DIFFS_COMPRESSION_VERSION,
+        "unsupported state diffs compression version: {version}"
+    );
+    let len = reader.read_u24().context("compressed state diffs length")?;
+    let index_size = reader.read_u8().context("enumeration index size")?;
+    anyhow::ensure!(
+        index_size == BYTES_PER_ENUMERATION_INDEX,
+        "unsupported enumeration index size: {index_size}"
+    );
+
+    let mut reader = Reader::new(reader.read_bytes(len as usize)?);
+    let initial_write_count = reader.read_u16().context("initial writes count")?;
+    let initial_writes = (0..initial_write_count)
+        .map(|_| {
+            let derived_key = reader.read_bytes(BYTES_PER_DERIVED_KEY.into())?;
+            Ok(InitialWrite {
+                derived_key: H256::from_slice(derived_key),
+                value: reader.read_compressed_value()?,
+            })
+        })
+        .collect::<anyhow::Result<_>>()
+        .context("initial writes")?;
+
+    let mut repeated_writes = vec![];
+    while !reader.is_empty() {
+        let write = RepeatedWrite {
+            enumeration_index: reader.read_u32()?.into(),
+            value: reader.read_compressed_value()?,
+        };
+        repeated_writes.push(write);
+    }
+    Ok((initial_writes, repeated_writes))
+}
+
+#[derive(Debug)]
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    fn read_bytes(&mut self, len: usize) -> anyhow::Result<&'a [u8]> {
+        anyhow::ensure!(
+            self.bytes.len() >= len,
+            "unexpected end of data: expected {len} bytes, got {}",
+            self.bytes.len()
+        );
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn read_u8(&mut self) -> anyhow::Result<u8> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> anyhow::Result<u16> {
+        let bytes = self.read_bytes(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_u24(&mut self) -> anyhow::Result<u32> {
+        let bytes = self.read_bytes(3)?;
+        Ok(u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]))
+    }
+
+    fn read_u32(&mut self) -> anyhow::Result<u32> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_be_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn read_prefixed_bytes(&mut self) -> anyhow::Result<Vec<u8>> {
+        let len = self.read_u32()?;
+        Ok(self.read_bytes(len as usize)?.to_vec())
+    }
+
+    /// Reads a compressed value prefixed with the metadata byte (5 bits for the length and 3 bits for the operation).
+    fn read_compressed_value(&mut self) -> anyhow::Result<CompressedValue> {
+        let metadata = self.read_u8()?;
+        let (len, operation) = (usize::from(metadata >> 3), metadata & 7);
+        Ok(match operation {
+            0 => {
+                anyhow::ensure!(len == 0, "invalid length for uncompressed value: {len}");
+                CompressedValue::Full(U256::from_big_endian(self.read_bytes(32)?))
+            }
+            1 => CompressedValue::Add(U256::from_big_endian(self.read_bytes(len)?)),
+            2 => CompressedValue::Sub(U256::from_big_endian(self.read_bytes(len)?)),
+            3 => CompressedValue::Transform(U256::from_big_endian(self.read_bytes(len)?)),
+            _ => anyhow::bail!("unknown compression operation: {operation}"),
+        })
+    }
+}
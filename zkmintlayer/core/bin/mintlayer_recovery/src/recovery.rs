@@ -0,0 +1,204 @@
+//! Replaying anchored L1 batches into the Merkle tree and Postgres.
+
+use std::{path::PathBuf, time::Instant};
+
+use anyhow::Context as _;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_merkle_tree::{domain::ZkSyncTree, RocksDBWrapper};
+use zksync_node_genesis::GenesisState;
+use zksync_types::{
+    commitment::L1BatchWithMetadata, snapshots::SnapshotRecoveryStatus, L1BatchNumber,
+};
+
+use crate::{
+    pubdata::Pubdata,
+    state::{LastL2Block, RecoveredState},
+};
+
+/// Number of storage logs inserted into Postgres at once.
+const STORAGE_LOGS_CHUNK_SIZE: usize = 100_000;
+/// Number of L1 batches after which the Merkle tree changes are flushed to RocksDB.
+const TREE_SAVE_INTERVAL: usize = 100;
+
+/// Replays pubdata of anchored L1 batches on top of the genesis state.
+#[derive(Debug)]
+pub(crate) struct Recovery {
+    pool: ConnectionPool<Core>,
+    merkle_tree_path: PathBuf,
+}
+
+impl Recovery {
+    pub fn new(pool: ConnectionPool<Core>, merkle_tree_path: PathBuf) -> Self {
+        Self {
+            pool,
+            merkle_tree_path,
+        }
+    }
+
+    /// Checks that Postgres and the Merkle tree are empty, so that the recovered state can be written to them.
+    async fn ensure_empty_storage(&self, tree: &ZkSyncTree) -> anyhow::Result<()> {
+        let mut storage = self.pool.connection_tagged("mintlayer_recovery").await?;
+        anyhow::ensure!(
+            storage.blocks_dal().is_genesis_needed().await?,
+            "Postgres already contains L1 batches; recovery requires an empty database"
+        );
+        anyhow::ensure!(
+            storage
+                .snapshot_recovery_dal()
+                .get_applied_snapshot_status()
+                .await?
+                .is_none(),
+            "Postgres already contains a recovered snapshot; recovery requires an empty database"
+        );
+        anyhow::ensure!(
+            tree.is_empty() && tree.next_l1_batch_number() == L1BatchNumber(0),
+            "Merkle tree at {} is not empty; recovery requires an empty tree",
+            self.merkle_tree_path.display()
+        );
+        Ok(())
+    }
+
+    /// Replays the specified L1 batches (which must start from L1 batch #1 and be contiguous), verifies Merkle tree
+    /// root hashes against the anchored metadata and persists the recovered state.
+    pub async fn run(
+        self,
+        genesis_state: GenesisState,
+        anchored_genesis: Option<&L1BatchWithMetadata>,
+        l1_batches: &[&L1BatchWithMetadata],
+    ) -> anyhow::Result<()> {
+        let last_l1_batch = *l1_batches
+            .last()
+            .context("no anchored L1 batches to recover")?;
+
+        let db = RocksDBWrapper::new(&self.merkle_tree_path).with_context(|| {
+            format!(
+                "failed initializing Merkle tree RocksDB at {}",
+                self.merkle_tree_path.display()
+            )
+        })?;
+        let mut tree = ZkSyncTree::new_lightweight(db).context("cannot initialize Merkle tree")?;
+        self.ensure_empty_storage(&tree).await?;
+
+        let (mut state, genesis_instructions) = RecoveredState::genesis(genesis_state);
+        let genesis_metadata = tree.process_l1_batch(&genesis_instructions)?;
+        if let Some(anchored_genesis) = anchored_genesis {
+            anyhow::ensure!(
+                genesis_metadata.root_hash == anchored_genesis.metadata.root_hash,
+                "genesis root hash {:?} differs from the anchored one {:?}; check the genesis config",
+                genesis_metadata.root_hash,
+                anchored_genesis.metadata.root_hash
+            );
+        }
+        tracing::info!(
+            "Processed genesis L1 batch with {} storage slots; root hash: {:?}",
+            state.len(),
+            genesis_metadata.root_hash
+        );
+
+        let started_at = Instant::now();
+        for (i, l1_batch) in l1_batches.iter().enumerate() {
+            let number = l1_batch.header.number;
+            let pubdata = Pubdata::for_l1_batch(l1_batch)?;
+            anyhow::ensure!(
+                pubdata.l2_to_l1_logs.len() == l1_batch.header.l2_to_l1_logs.len()
+                    && pubdata.l2_to_l1_messages == l1_batch.header.l2_to_l1_messages,
+                "L2-to-L1 logs or messages in pubdata of L1 batch #{number} differ from its header"
+            );
+            let instructions = state
+                .apply(number, pubdata)
+                .with_context(|| format!("failed applying pubdata of L1 batch #{number}"))?;
+            let metadata = tree.process_l1_batch(&instructions)?;
+
+            anyhow::ensure!(
+                metadata.root_hash == l1_batch.metadata.root_hash,
+                "root hash mismatch for L1 batch #{number}: recovered {:?}, committed {:?}",
+                metadata.root_hash,
+                l1_batch.metadata.root_hash
+            );
+            anyhow::ensure!(
+                metadata.rollup_last_leaf_index == l1_batch.metadata.rollup_last_leaf_index,
+                "last leaf index mismatch for L1 batch #{number}: recovered {}, committed {}",
+                metadata.rollup_last_leaf_index,
+                l1_batch.metadata.rollup_last_leaf_index
+            );
+            tracing::info!(
+                "Recovered L1 batch #{number} with {} writes; root hash: {:?}",
+                instructions.len(),
+                metadata.root_hash
+            );
+
+            if (i + 1) % TREE_SAVE_INTERVAL == 0 {
+                tree.save()?;
+            }
+        }
+        tree.save()?;
+        tracing::info!(
+            "Recovered Merkle tree for {} L1 batches in {:?}",
+            l1_batches.len(),
+            started_at.elapsed()
+        );
+
+        let protocol_version = last_l1_batch.header.protocol_version.unwrap_or_default();
+        let last_l2_block = state.last_l2_block(protocol_version)?;
+        self.persist(&state, last_l1_batch, last_l2_block).await
+    }
+
+    /// Persists the recovered state in Postgres in the same way the snapshot applier does.
+    async fn persist(
+        &self,
+        state: &RecoveredState,
+        last_l1_batch: &L1BatchWithMetadata,
+        last_l2_block: LastL2Block,
+    ) -> anyhow::Result<()> {
+        let status = SnapshotRecoveryStatus {
+            l1_batch_number: last_l1_batch.header.number,
+            l1_batch_root_hash: last_l1_batch.metadata.root_hash,
+            l1_batch_timestamp: last_l1_batch.header.timestamp,
+            l2_block_number: last_l2_block.number,
+            l2_block_hash: last_l2_block.hash,
+            l2_block_timestamp: last_l2_block.timestamp,
+            protocol_version: last_l1_batch.header.protocol_version.unwrap_or_default(),
+            storage_logs_chunks_processed: vec![true],
+        };
+        tracing::info!("Persisting recovered state: {status:?}");
+
+        let mut storage = self.pool.connection_tagged("mintlayer_recovery").await?;
+        let mut transaction = storage.start_transaction().await?;
+        transaction
+            .snapshot_recovery_dal()
+            .insert_initial_recovery_status(&status)
+            .await?;
+        transaction
+            .pruning_dal()
+            .soft_prune_batches_range(status.l1_batch_number, status.l2_block_number)
+            .await?;
+        transaction
+            .pruning_dal()
+            .hard_prune_batches_range(status.l1_batch_number, status.l2_block_number)
+            .await?;
+        transaction
+            .factory_deps_dal()
+            .insert_factory_deps(status.l2_block_number, state.factory_deps())
+            .await?;
+
+        let storage_logs: Vec<_> = state.storage_logs().collect();
+        for chunk in storage_logs.chunks(STORAGE_LOGS_CHUNK_SIZE) {
+            transaction
+                .storage_logs_dedup_dal()
+                .insert_initial_writes_from_snapshot(chunk)
+                .await?;
+            transaction
+                .storage_logs_dal()
+                .insert_storage_logs_from_snapshot(status.l2_block_number, chunk)
+                .await?;
+        }
+        transaction.commit().await?;
+
+        tracing::info!(
+            "Persisted {} storage logs and {} factory deps",
+            storage_logs.len(),
+            state.factory_deps().len()
+        );
+        Ok(())
+    }
+}
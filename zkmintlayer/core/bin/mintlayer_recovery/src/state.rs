@@ -0,0 +1,190 @@
+//! Storage state reconstructed from pubdata.
+
+use std::collections::HashMap;
+
+use anyhow::Context as _;
+use zksync_merkle_tree::TreeInstruction;
+use zksync_node_genesis::GenesisState;
+use zksync_system_constants::{
+    SYSTEM_CONTEXT_ADDRESS, SYSTEM_CONTEXT_CURRENT_L2_BLOCK_HASHES_POSITION,
+    SYSTEM_CONTEXT_CURRENT_L2_BLOCK_INFO_POSITION, SYSTEM_CONTEXT_CURRENT_TX_ROLLING_HASH_POSITION,
+    SYSTEM_CONTEXT_STORED_L2_BLOCK_HASHES,
+};
+use zksync_types::{
+    block::{unpack_block_info, L2BlockHasher},
+    snapshots::SnapshotStorageLog,
+    AccountTreeId, L1BatchNumber, L2BlockNumber, ProtocolVersionId, StorageKey, H256, U256,
+};
+use zksync_utils::{bytecode::hash_bytecode, h256_to_u256, u256_to_h256};
+
+use crate::pubdata::Pubdata;
+
+#[derive(Debug, Clone, Copy)]
+struct StateEntry {
+    value: H256,
+    enumeration_index: u64,
+    l1_batch_number_of_initial_write: L1BatchNumber,
+}
+
+/// Last L2 block of an L1 batch restored from the `SystemContext` storage.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct LastL2Block {
+    pub number: L2BlockNumber,
+    pub timestamp: u64,
+    pub hash: H256,
+}
+
+/// Storage state reconstructed by replaying pubdata of L1 batches on top of the genesis state.
+#[derive(Debug)]
+pub(crate) struct RecoveredState {
+    /// Hashed keys ordered by their enumeration indices: the key with index `i` is stored at position `i - 1`.
+    keys: Vec<H256>,
+    entries: HashMap<H256, StateEntry>,
+    factory_deps: HashMap<H256, Vec<u8>>,
+}
+
+impl RecoveredState {
+    /// Creates the genesis state and returns Merkle tree instructions for the genesis L1 batch.
+    pub fn genesis(genesis: GenesisState) -> (Self, Vec<TreeInstruction>) {
+        let mut this = Self {
+            keys: Vec::with_capacity(genesis.storage_writes.len()),
+            entries: HashMap::with_capacity(genesis.storage_writes.len()),
+            factory_deps: genesis.factory_deps,
+        };
+        let instructions = genesis
+            .storage_writes
+            .into_iter()
+            .map(|(key, value)| this.insert(key.hashed_key(), value, L1BatchNumber(0)))
+            .collect();
+        (this, instructions)
+    }
+
+    fn insert(
+        &mut self,
+        hashed_key: H256,
+        value: H256,
+        l1_batch_number: L1BatchNumber,
+    ) -> TreeInstruction {
+        self.keys.push(hashed_key);
+        let enumeration_index = self.keys.len() as u64;
+        self.entries.insert(
+            hashed_key,
+            StateEntry {
+                value,
+                enumeration_index,
+                l1_batch_number_of_initial_write: l1_batch_number,
+            },
+        );
+        tree_write(hashed_key, enumeration_index, value)
+    }
+
+    /// Returns the number of storage slots with an assigned enumeration index.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn factory_deps(&self) -> &HashMap<H256, Vec<u8>> {
+        &self.factory_deps
+    }
+
+    /// Applies pubdata of an L1 batch and returns Merkle tree instructions for it.
+    pub fn apply(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        pubdata: Pubdata,
+    ) -> anyhow::Result<Vec<TreeInstruction>> {
+        let mut instructions =
+            Vec::with_capacity(pubdata.initial_writes.len() + pubdata.repeated_writes.len());
+        for write in pubdata.initial_writes {
+            anyhow::ensure!(
+                !self.entries.contains_key(&write.derived_key),
+                "initial write to slot {:?} that was already written to",
+                write.derived_key
+            );
+            let value = u256_to_h256(write.value.apply(U256::zero()));
+            instructions.push(self.insert(write.derived_key, value, l1_batch_number));
+        }
+
+        for write in pubdata.repeated_writes {
+            let index = write.enumeration_index;
+            let hashed_key = index
+                .checked_sub(1)
+                .and_then(|i| self.keys.get(i as usize))
+                .copied()
+                .with_context(|| format!("repeated write to unknown enumeration index {index}"))?;
+            let entry = self.entries.get_mut(&hashed_key).unwrap();
+            entry.value = u256_to_h256(write.value.apply(h256_to_u256(entry.value)));
+            instructions.push(tree_write(hashed_key, index, entry.value));
+        }
+
+        for bytecode in pubdata.published_bytecodes {
+            self.factory_deps.insert(hash_bytecode(&bytecode), bytecode);
+        }
+        Ok(instructions)
+    }
+
+    /// Reads the current value of a storage slot.
+    pub fn read(&self, key: &StorageKey) -> H256 {
+        self.entries
+            .get(&key.hashed_key())
+            .map_or_else(H256::zero, |entry| entry.value)
+    }
+
+    /// Restores the last L2 block from the `SystemContext` contract storage, the same way the VM loads it.
+    pub fn last_l2_block(
+        &self,
+        protocol_version: ProtocolVersionId,
+    ) -> anyhow::Result<LastL2Block> {
+        let system_context = AccountTreeId::new(SYSTEM_CONTEXT_ADDRESS);
+        let info_key = StorageKey::new(
+            system_context,
+            SYSTEM_CONTEXT_CURRENT_L2_BLOCK_INFO_POSITION,
+        );
+        let (number, timestamp) = unpack_block_info(h256_to_u256(self.read(&info_key)));
+        let number = u32::try_from(number).context("L2 block number overflow")?;
+        anyhow::ensure!(number > 0, "no L2 blocks recorded in SystemContext storage");
+
+        let prev_hash_position = h256_to_u256(SYSTEM_CONTEXT_CURRENT_L2_BLOCK_HASHES_POSITION)
+            + U256::from((number - 1) % SYSTEM_CONTEXT_STORED_L2_BLOCK_HASHES);
+        let prev_hash_key = StorageKey::new(system_context, u256_to_h256(prev_hash_position));
+        let rolling_hash_key = StorageKey::new(
+            system_context,
+            SYSTEM_CONTEXT_CURRENT_TX_ROLLING_HASH_POSITION,
+        );
+
+        let number = L2BlockNumber(number);
+        let hash = L2BlockHasher::hash(
+            number,
+            timestamp,
+            self.read(&prev_hash_key),
+            self.read(&rolling_hash_key),
+            protocol_version,
+        );
+        Ok(LastL2Block {
+            number,
+            timestamp,
+            hash,
+        })
+    }
+
+    /// Returns all storage slots ordered by enumeration indices.
+    pub fn storage_logs(&self) -> impl Iterator<Item = SnapshotStorageLog> + '_ {
+        self.keys.iter().map(|hashed_key| {
+            let entry = &self.entries[hashed_key];
+            SnapshotStorageLog {
+                key: *hashed_key,
+                value: entry.value,
+                l1_batch_number_of_initial_write: entry.l1_batch_number_of_initial_write,
+                enumeration_index: entry.enumeration_index,
+            }
+        })
+    }
+}
+
+fn tree_write(hashed_key: H256, enumeration_index: u64, value: H256) -> TreeInstruction {
+    TreeInstruction::write(
+        U256::from_little_endian(hashed_key.as_bytes()),
+        enumeration_index,
+        value,
+    )
+}
@@ -0,0 +1,561 @@
+//! Tests for Mintlayer recovery.
+
+use std::{collections::HashMap, sync::Arc};
+
+use assert_matches::assert_matches;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_ipfs_store::{IpfsStore, MockIpfsStore};
+use zksync_merkle_tree::{domain::ZkSyncTree, RocksDBWrapper};
+use zksync_mintlayer_client::{clients::MockMintlayerClient, DepositOptions, MintlayerInterface};
+use zksync_mintlayer_documents::{
+    CommitOperation, Compression, ExecuteOperation, OperationDocument,
+};
+use zksync_node_genesis::GenesisState;
+use zksync_node_test_utils::{create_l1_batch, create_l1_batch_metadata};
+use zksync_system_constants::{
+    SYSTEM_CONTEXT_ADDRESS, SYSTEM_CONTEXT_CURRENT_L2_BLOCK_INFO_POSITION,
+};
+use zksync_types::{
+    block::{pack_block_info, L2BlockHasher},
+    commitment::L1BatchWithMetadata,
+    l2_to_l1_log::L2ToL1Log,
+    mintlayer::{
+        MintlayerAnchorCommitment, MintlayerAnchorLeaf, MintlayerAnchorPayload,
+        MintlayerAnchorRevocation,
+    },
+    pubdata_da::PubdataDA,
+    writes::{compress_state_diffs, StateDiffRecord},
+    AccountTreeId, Address, L1BatchNumber, L2BlockNumber, ProtocolVersionId, StorageKey, H256,
+    U256,
+};
+use zksync_utils::{bytecode::hash_bytecode, h256_to_u256, u256_to_h256};
+
+use super::*;
+use crate::{
+    anchors::AnchoredBatches,
+    pubdata::{CompressedValue, Pubdata},
+    state::RecoveredState,
+};
+
+const CONFIRMATIONS: u32 = 2;
+
+fn storage_key(index: u64) -> StorageKey {
+    StorageKey::new(
+        AccountTreeId::new(Address::repeat_byte(1)),
+        H256::from_low_u64_be(index),
+    )
+}
+
+fn initial_diff(key: &StorageKey, value: U256) -> StateDiffRecord {
+    StateDiffRecord {
+        address: *key.address(),
+        key: h256_to_u256(*key.key()),
+        derived_key: key.hashed_key().0,
+        enumeration_index: 0,
+        initial_value: U256::zero(),
+        final_value: value,
+    }
+}
+
+fn repeated_diff(
+    key: &StorageKey,
+    enumeration_index: u64,
+    initial_value: U256,
+    final_value: U256,
+) -> StateDiffRecord {
+    StateDiffRecord {
+        enumeration_index,
+        initial_value,
+        ..initial_diff(key, final_value)
+    }
+}
+
+fn encode_pubdata(
+    logs: &[L2ToL1Log],
+    messages: &[Vec<u8>],
+    bytecodes: &[Vec<u8>],
+    state_diffs: Vec<StateDiffRecord>,
+) -> Vec<u8> {
+    let mut pubdata = vec![];
+    pubdata.extend((logs.len() as u32).to_be_bytes());
+    for log in logs {
+        pubdata.extend(log.to_bytes());
+    }
+    for items in [messages, bytecodes] {
+        pubdata.extend((items.len() as u32).to_be_bytes());
+        for item in items {
+            pubdata.extend((item.len() as u32).to_be_bytes());
+            pubdata.extend(item);
+        }
+    }
+    pubdata.extend(compress_state_diffs(state_diffs));
+    pubdata
+}
+
+#[test]
+fn decoding_pubdata() {
+    let log = L2ToL1Log {
+        shard_id: 0,
+        is_service: true,
+        tx_number_in_block: 5,
+        sender: Address::repeat_byte(0x80),
+        key: H256::repeat_byte(2),
+        value: H256::repeat_byte(3),
+    };
+    let messages = vec![vec![1, 2, 3]];
+    let bytecodes = vec![vec![0; 64]];
+    let state_diffs = vec![
+        initial_diff(&storage_key(1), U256::MAX),
+        initial_diff(&storage_key(2), 100.into()),
+        repeated_diff(&storage_key(3), 3, 1_000.into(), 1_001.into()),
+        repeated_diff(&storage_key(4), 4, 1_000.into(), 999.into()),
+    ];
+    let encoded = encode_pubdata(&[log.clone()], &messages, &bytecodes, state_diffs.clone());
+
+    let pubdata = Pubdata::decode(&encoded).unwrap();
+    assert_eq!(pubdata.l2_to_l1_logs, [log]);
+    assert_eq!(pubdata.l2_to_l1_messages, messages);
+    assert_eq!(pubdata.published_bytecodes, bytecodes);
+
+    assert_eq!(pubdata.initial_writes.len(), 2);
+    for (write, diff) in pubdata.initial_writes.iter().zip(&state_diffs) {
+        assert_eq!(write.derived_key, H256(diff.derived_key));
+        assert_eq!(write.value.apply(U256::zero()), diff.final_value);
+    }
+    assert_eq!(pubdata.repeated_writes.len(), 2);
+    for (write, diff) in pubdata.repeated_writes.iter().zip(&state_diffs[2..]) {
+        assert_eq!(write.enumeration_index, diff.enumeration_index);
+        assert_eq!(write.value.apply(diff.initial_value), diff.final_value);
+    }
+    assert_matches!(pubdata.repeated_writes[0].value, CompressedValue::Add(_));
+    assert_matches!(pubdata.repeated_writes[1].value, CompressedValue::Sub(_));
+}
+
+#[test]
+fn decoding_invalid_pubdata() {
+    let state_diffs = vec![initial_diff(&storage_key(1), 1.into())];
+    let encoded = encode_pubdata(&[], &[], &[], state_diffs);
+
+    Pubdata::decode(&encoded[..encoded.len() - 1]).unwrap_err();
+    let mut with_trailing_bytes = encoded.clone();
+    with_trailing_bytes.push(0);
+    Pubdata::decode(&with_trailing_bytes).unwrap_err();
+    let mut with_unknown_version = encoded;
+    with_unknown_version[12] = 2; // the compression version follows 3 empty sections
+    Pubdata::decode(&with_unknown_version).unwrap_err();
+}
+
+fn mock_genesis_state() -> GenesisState {
+    let block_info_key = StorageKey::new(
+        AccountTreeId::new(SYSTEM_CONTEXT_ADDRESS),
+        SYSTEM_CONTEXT_CURRENT_L2_BLOCK_INFO_POSITION,
+    );
+    GenesisState {
+        storage_writes: vec![
+            (storage_key(0), H256::repeat_byte(0xff)),
+            (block_info_key, u256_to_h256(pack_block_info(0, 0))),
+        ],
+        factory_deps: HashMap::from([(hash_bytecode(&[1; 32]), vec![1; 32])]),
+    }
+}
+
+/// Creates L1 batches with pubdata writing to a couple of slots, and computes their metadata using a reference tree.
+fn create_l1_batches(count: u32, tree: &mut ZkSyncTree) -> Vec<L1BatchWithMetadata> {
+    let (mut state, instructions) = RecoveredState::genesis(mock_genesis_state());
+    tree.process_l1_batch(&instructions).unwrap();
+
+    let block_info_key = StorageKey::new(
+        AccountTreeId::new(SYSTEM_CONTEXT_ADDRESS),
+        SYSTEM_CONTEXT_CURRENT_L2_BLOCK_INFO_POSITION,
+    );
+    (1..=count)
+        .map(|number| {
+            let prev_block_info = h256_to_u256(state.read(&block_info_key));
+            let state_diffs = vec![
+                initial_diff(&storage_key(number.into()), number.into()),
+                repeated_diff(
+                    &block_info_key,
+                    2,
+                    prev_block_info,
+                    pack_block_info(number.into(), u64::from(number) * 10),
+                ),
+            ];
+            let bytecode = vec![number as u8; 32];
+            let pubdata = encode_pubdata(&[], &[], &[bytecode], state_diffs);
+
+            let instructions = state
+                .apply(L1BatchNumber(number), Pubdata::decode(&pubdata).unwrap())
+                .unwrap();
+            let tree_metadata = tree.process_l1_batch(&instructions).unwrap();
+
+            let mut header = create_l1_batch(number);
+            header.pubdata_input = Some(pubdata);
+            let mut metadata = create_l1_batch_metadata(number);
+            metadata.root_hash = tree_metadata.root_hash;
+            metadata.rollup_last_leaf_index = tree_metadata.rollup_last_leaf_index;
+            L1BatchWithMetadata {
+                header,
+                metadata,
+                raw_published_factory_deps: vec![],
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn applying_pubdata_to_state() {
+    let (mut state, _) = RecoveredState::genesis(mock_genesis_state());
+    let pubdata = encode_pubdata(
+        &[],
+        &[],
+        &[],
+        vec![repeated_diff(&storage_key(0), 1, U256::MAX, 1.into())],
+    );
+    let instructions = state
+        .apply(L1BatchNumber(1), Pubdata::decode(&pubdata).unwrap())
+        .unwrap();
+    assert_eq!(instructions.len(), 1);
+    assert_eq!(state.read(&storage_key(0)), H256::from_low_u64_be(1));
+    assert_eq!(state.len(), 2);
+
+    let pubdata = encode_pubdata(
+        &[],
+        &[],
+        &[],
+        vec![repeated_diff(&storage_key(0), 10, 0.into(), 1.into())],
+    );
+    let err = state
+        .apply(L1BatchNumber(2), Pubdata::decode(&pubdata).unwrap())
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("unknown enumeration index"),
+        "{err}"
+    );
+}
+
+#[tokio::test]
+async fn recovering_state() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let reference_dir = tempfile::TempDir::new().unwrap();
+    let mut reference_tree =
+        ZkSyncTree::new_lightweight(RocksDBWrapper::new(reference_dir.path()).unwrap()).unwrap();
+    let l1_batches = create_l1_batches(3, &mut reference_tree);
+    let l1_batch_refs: Vec<_> = l1_batches.iter().collect();
+
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    Recovery::new(pool.clone(), temp_dir.path().to_owned())
+        .run(mock_genesis_state(), None, &l1_batch_refs)
+        .await
+        .unwrap();
+
+    let mut storage = pool.connection().await.unwrap();
+    let status = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await
+        .unwrap()
+        .expect("no recovery status");
+    assert_eq!(status.l1_batch_number, L1BatchNumber(3));
+    assert_eq!(status.l1_batch_root_hash, l1_batches[2].metadata.root_hash);
+    assert_eq!(status.l2_block_number, L2BlockNumber(3));
+    assert_eq!(status.l2_block_timestamp, 30);
+    let expected_hash = L2BlockHasher::hash(
+        L2BlockNumber(3),
+        30,
+        H256::zero(),
+        H256::zero(),
+        ProtocolVersionId::latest(),
+    );
+    assert_eq!(status.l2_block_hash, expected_hash);
+
+    let storage_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert_eq!(storage_logs.len(), 5); // 2 genesis slots + 1 new slot per L1 batch
+    let factory_deps = storage
+        .factory_deps_dal()
+        .dump_all_factory_deps_for_tests()
+        .await;
+    assert_eq!(factory_deps.len(), 4);
+
+    let tree = ZkSyncTree::new_lightweight(RocksDBWrapper::new(temp_dir.path()).unwrap()).unwrap();
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(4));
+    assert_eq!(tree.root_hash(), l1_batches[2].metadata.root_hash);
+}
+
+#[tokio::test]
+async fn recovery_detects_root_hash_mismatch() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let reference_dir = tempfile::TempDir::new().unwrap();
+    let mut reference_tree =
+        ZkSyncTree::new_lightweight(RocksDBWrapper::new(reference_dir.path()).unwrap()).unwrap();
+    let mut l1_batches = create_l1_batches(2, &mut reference_tree);
+    l1_batches[1].metadata.root_hash = H256::repeat_byte(1);
+    let l1_batch_refs: Vec<_> = l1_batches.iter().collect();
+
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let err = Recovery::new(pool.clone(), temp_dir.path().to_owned())
+        .run(mock_genesis_state(), None, &l1_batch_refs)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("L1 batch #2"), "{err}");
+
+    let mut storage = pool.connection().await.unwrap();
+    let status = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await
+        .unwrap();
+    assert!(status.is_none());
+}
+
+async fn anchor(
+    mintlayer_client: &MockMintlayerClient,
+    ipfs_store: &MockIpfsStore,
+    documents: &[OperationDocument],
+) -> String {
+    let mut leaves = vec![];
+    for document in documents {
+        let contents = document.encode(Compression::Zstd).unwrap();
+        let l1_batches = document.l1_batches();
+        leaves.push(MintlayerAnchorLeaf {
+            action_type: document.action_type(),
+            l1_batch_range: l1_batches[0].header.number
+                ..=l1_batches[l1_batches.len() - 1].header.number,
+            cid: ipfs_store.put("operation", contents).await.unwrap(),
+        });
+    }
+    let commitment = MintlayerAnchorCommitment::new(leaves);
+    let root_document = serde_json::to_vec(commitment.leaves()).unwrap();
+    let payload = MintlayerAnchorPayload {
+        merkle_root: Some(commitment.merkle_root()),
+        root_cid: ipfs_store.put("root", root_document).await.unwrap(),
+    };
+    let tx = mintlayer_client
+        .address_deposit_data(&payload.encode(), DepositOptions::default())
+        .await
+        .unwrap();
+    mintlayer_client.confirm_tx(&tx.tx_id, 1);
+    tx.tx_id
+}
+
+/// Deposits a revocation record for the specified anchor, returning the ID of the revocation transaction.
+async fn revoke(mintlayer_client: &MockMintlayerClient, anchor_tx_id: &str) -> String {
+    let anchor_tx = mintlayer_client
+        .transaction_get(anchor_tx_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let revocation = MintlayerAnchorRevocation {
+        revoked: MintlayerAnchorPayload::decode(&anchor_tx.deposited_data.unwrap()).unwrap(),
+    };
+    let tx = mintlayer_client
+        .address_deposit_data(&revocation.encode(), DepositOptions::default())
+        .await
+        .unwrap();
+    mintlayer_client.confirm_tx(&tx.tx_id, 1);
+    tx.tx_id
+}
+
+fn commit(
+    l1_batches: &[L1BatchWithMetadata],
+    prev_l1_batch: L1BatchWithMetadata,
+) -> OperationDocument {
+    OperationDocument::Commit(CommitOperation {
+        prev_l1_batch,
+        l1_batches: l1_batches.to_vec(),
+        pubdata_da: PubdataDA::Calldata,
+    })
+}
+
+fn mock_l1_batch(number: u32) -> L1BatchWithMetadata {
+    L1BatchWithMetadata {
+        header: create_l1_batch(number),
+        metadata: create_l1_batch_metadata(number),
+        raw_published_factory_deps: vec![],
+    }
+}
+
+#[tokio::test]
+async fn collecting_anchored_batches() {
+    let mintlayer_client = MockMintlayerClient::default();
+    let ipfs_store = MockIpfsStore::default();
+    let l1_batches: Vec<_> = (0..=4).map(mock_l1_batch).collect();
+
+    let first_anchor = anchor(
+        &mintlayer_client,
+        &ipfs_store,
+        &[
+            commit(&l1_batches[1..=2], l1_batches[0].clone()),
+            OperationDocument::Execute(ExecuteOperation {
+                l1_batches: l1_batches[1..=2].to_vec(),
+            }),
+        ],
+    )
+    .await;
+    let second_anchor = anchor(
+        &mintlayer_client,
+        &ipfs_store,
+        &[commit(&l1_batches[3..=3], l1_batches[2].clone())],
+    )
+    .await;
+    let tx_ids = [first_anchor, second_anchor];
+
+    let collector = AnchorCollector::new(
+        Box::new(mintlayer_client.clone()),
+        Arc::new(ipfs_store.clone()),
+        CONFIRMATIONS,
+    );
+    // Anchors are not confirmed yet.
+    collector.collect(&tx_ids).await.unwrap_err();
+
+    mintlayer_client.set_best_block_height(u64::from(CONFIRMATIONS));
+    let batches: AnchoredBatches = collector.collect(&tx_ids).await.unwrap();
+    assert_eq!(batches.genesis.as_ref(), Some(&l1_batches[0]));
+    assert_eq!(batches.committed.len(), 3);
+    assert_eq!(batches.last_executed, Some(L1BatchNumber(2)));
+
+    let recoverable = batches.recoverable_l1_batches(true);
+    assert_eq!(recoverable, [&l1_batches[1], &l1_batches[2]]);
+    let recoverable = batches.recoverable_l1_batches(false);
+    assert_eq!(
+        recoverable,
+        [&l1_batches[1], &l1_batches[2], &l1_batches[3]]
+    );
+}
+
+/// Anchors L1 batches #1 and #2, then anchors different data for L1 batch #2 (e.g., after a revert),
+/// returning the original L1 batches, the replacement L1 batch #2 and IDs of all anchor transactions.
+async fn anchor_conflicting_batches(
+    mintlayer_client: &MockMintlayerClient,
+    ipfs_store: &MockIpfsStore,
+) -> (Vec<L1BatchWithMetadata>, L1BatchWithMetadata, [String; 3]) {
+    let l1_batches: Vec<_> = (0..=2).map(mock_l1_batch).collect();
+    let mut replacement_l1_batch = mock_l1_batch(2);
+    replacement_l1_batch.metadata.root_hash = H256::repeat_byte(0xff);
+
+    let first_anchor = anchor(
+        mintlayer_client,
+        ipfs_store,
+        &[commit(&l1_batches[1..=1], l1_batches[0].clone())],
+    )
+    .await;
+    let reverted_anchor = anchor(
+        mintlayer_client,
+        ipfs_store,
+        &[commit(&l1_batches[2..=2], l1_batches[1].clone())],
+    )
+    .await;
+    let replacement_anchor = anchor(
+        mintlayer_client,
+        ipfs_store,
+        &[commit(
+            &[replacement_l1_batch.clone()],
+            l1_batches[1].clone(),
+        )],
+    )
+    .await;
+    let tx_ids = [first_anchor, reverted_anchor, replacement_anchor];
+    (l1_batches, replacement_l1_batch, tx_ids)
+}
+
+#[tokio::test]
+async fn collecting_batches_with_revoked_anchor() {
+    let mintlayer_client = MockMintlayerClient::default();
+    let ipfs_store = MockIpfsStore::default();
+    let (l1_batches, replacement_l1_batch, tx_ids) =
+        anchor_conflicting_batches(&mintlayer_client, &ipfs_store).await;
+    let revocation_tx_id = revoke(&mintlayer_client, &tx_ids[1]).await;
+    mintlayer_client.set_best_block_height(u64::from(CONFIRMATIONS));
+
+    let collector = AnchorCollector::new(
+        Box::new(mintlayer_client.clone()),
+        Arc::new(ipfs_store.clone()),
+        CONFIRMATIONS,
+    );
+    let mut all_tx_ids = tx_ids.to_vec();
+    all_tx_ids.push(revocation_tx_id);
+    let batches = collector.collect(&all_tx_ids).await.unwrap();
+    let recoverable = batches.recoverable_l1_batches(false);
+    assert_eq!(recoverable, [&l1_batches[1], &replacement_l1_batch]);
+
+    // The revocation should be taken into account regardless of its position in the list.
+    all_tx_ids.rotate_right(1);
+    let batches = collector.collect(&all_tx_ids).await.unwrap();
+    let recoverable = batches.recoverable_l1_batches(false);
+    assert_eq!(recoverable, [&l1_batches[1], &replacement_l1_batch]);
+}
+
+#[tokio::test]
+async fn collecting_batches_fails_on_conflicting_commits() {
+    let mintlayer_client = MockMintlayerClient::default();
+    let ipfs_store = MockIpfsStore::default();
+    let (_, _, tx_ids) = anchor_conflicting_batches(&mintlayer_client, &ipfs_store).await;
+    mintlayer_client.set_best_block_height(u64::from(CONFIRMATIONS));
+
+    let collector = AnchorCollector::new(
+        Box::new(mintlayer_client.clone()),
+        Arc::new(ipfs_store.clone()),
+        CONFIRMATIONS,
+    );
+    let err = collector.collect(&tx_ids).await.unwrap_err();
+    let err = format!("{err:#}");
+    assert!(
+        err.contains("L1 batch #2 is committed with different data"),
+        "{err}"
+    );
+}
+
+#[tokio::test]
+async fn collecting_batches_from_wallet_without_tx_ids() {
+    let mintlayer_client = MockMintlayerClient::default();
+    let ipfs_store = MockIpfsStore::default();
+    let (l1_batches, replacement_l1_batch, tx_ids) =
+        anchor_conflicting_batches(&mintlayer_client, &ipfs_store).await;
+    let revocation_tx_id = revoke(&mintlayer_client, &tx_ids[1]).await;
+    let unrelated_tx = mintlayer_client
+        .address_deposit_data(b"not an anchor", DepositOptions::default())
+        .await
+        .unwrap();
+    // Transactions are listed by the wallet in an arbitrary order, so they must be ordered by block height.
+    for (i, tx_id) in tx_ids.iter().chain([&revocation_tx_id]).enumerate() {
+        mintlayer_client.confirm_tx(tx_id, i as u64 + 1);
+    }
+    mintlayer_client.confirm_tx(&unrelated_tx.tx_id, 2);
+    let best_block_height = tx_ids.len() as u64 + u64::from(CONFIRMATIONS);
+    mintlayer_client.set_best_block_height(best_block_height);
+
+    let collector = AnchorCollector::new(
+        Box::new(mintlayer_client.clone()),
+        Arc::new(ipfs_store.clone()),
+        CONFIRMATIONS,
+    );
+    let batches = collector.collect_from_wallet(None, 100).await.unwrap();
+    assert_eq!(batches.genesis.as_ref(), Some(&l1_batches[0]));
+    let recoverable = batches.recoverable_l1_batches(false);
+    assert_eq!(recoverable, [&l1_batches[1], &replacement_l1_batch]);
+
+    // The wallet has 5 transactions, so some of them would be missed.
+    let err = collector.collect_from_wallet(None, 5).await.unwrap_err();
+    assert!(
+        err.to_string().contains("some anchors may be missing"),
+        "{err}"
+    );
+}
+
+#[tokio::test]
+async fn recoverable_batches_stop_at_gap() {
+    let mut batches = AnchoredBatches::default();
+    for number in [1, 2, 4] {
+        batches
+            .committed
+            .insert(L1BatchNumber(number), mock_l1_batch(number));
+    }
+    let recoverable = batches.recoverable_l1_batches(false);
+    let numbers: Vec<_> = recoverable
+        .iter()
+        .map(|batch| batch.header.number.0)
+        .collect();
+    assert_eq!(numbers, [1, 2]);
+}
@@ -4,13 +4,14 @@
 use anyhow::Context;
 use zksync_config::{
     configs::{
-        consensus::ConsensusConfig, eth_sender::PubdataSendingMode, wallets::Wallets,
-        GeneralConfig, Secrets,
+        consensus::ConsensusConfig, da_dispatcher::DAClient, eth_sender::PubdataSendingMode,
+        wallets::Wallets, GeneralConfig, Secrets,
     },
     ContractsConfig, GenesisConfig,
 };
 use zksync_core_leftovers::Component;
 use zksync_default_da_clients::{
+    mintlayer::wiring_layer::MintlayerDAClientWiringLayer,
     no_da::wiring_layer::NoDAClientWiringLayer,
     object_store::{config::DAObjectStoreConfig, wiring_layer::ObjectStorageClientWiringLayer},
 };
@@ -35,6 +36,7 @@ use zksync_node_framework::{
         l1_batch_commitment_mode_validation::L1BatchCommitmentModeValidationLayer,
         l1_gas::SequencerL1GasLayer,
         metadata_calculator::MetadataCalculatorLayer,
+        mintlayer_anchor::MintlayerAnchorLayer,
         object_store::ObjectStoreLayer,
         pk_signing_eth_client::PKSigningEthClientLayer,
         pools_layer::PoolsLayerBuilder,
@@ -468,16 +470,24 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
-    fn add_no_da_client_layer(mut self) -> anyhow::Result<Self> {
-        self.node.add_layer(NoDAClientWiringLayer);
-        Ok(self)
-    }
-
-    #[allow(dead_code)]
-    fn add_object_storage_da_client_layer(mut self) -> anyhow::Result<Self> {
-        let object_store_config = DAObjectStoreConfig::from_env()?;
-        self.node
-            .add_layer(ObjectStorageClientWiringLayer::new(object_store_config.0));
+    fn add_da_client_layer(mut self) -> anyhow::Result<Self> {
+        let da_config = try_load_config!(self.configs.da_dispatcher_config);
+        match da_config.client() {
+            DAClient::NoDA => {
+                self.node.add_layer(NoDAClientWiringLayer);
+            }
+            DAClient::ObjectStore => {
+                let object_store_config = DAObjectStoreConfig::from_env()?;
+                self.node
+                    .add_layer(ObjectStorageClientWiringLayer::new(object_store_config.0));
+            }
+            DAClient::Mintlayer => {
+                let config = try_load_config!(self.configs.mintlayer_config);
+                let secrets = try_load_config!(self.secrets.mintlayer);
+                self.node
+                    .add_layer(MintlayerDAClientWiringLayer::new(config, secrets));
+            }
+        }
         Ok(self)
     }
 
@@ -530,6 +540,19 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_mintlayer_anchor_layer(mut self) -> anyhow::Result<Self> {
+        let config = try_load_config!(self.configs.mintlayer_config);
+        let secrets = try_load_config!(self.secrets.mintlayer);
+        let eth_sender_config = try_load_config!(self.configs.eth);
+        self.node.add_layer(MintlayerAnchorLayer::new(
+            config,
+            secrets,
+            eth_sender_config,
+        ));
+
+        Ok(self)
+    }
+
     pub fn build(mut self, mut components: Vec<Component>) -> anyhow::Result<ZkStackService> {
         // Add "base" layers (resources and helper tasks).
         self = self
@@ -615,7 +638,7 @@ impl MainNodeBuilder {
                     self = self.add_commitment_generator_layer()?;
                 }
                 Component::DADispatcher => {
-                    self = self.add_no_da_client_layer()?.add_da_dispatcher_layer()?;
+                    self = self.add_da_client_layer()?.add_da_dispatcher_layer()?;
                 }
                 Component::VmRunnerProtectiveReads => {
                     self = self.add_vm_runner_protective_reads_layer()?;
@@ -626,6 +649,9 @@ impl MainNodeBuilder {
                 Component::VmRunnerBwip => {
                     self = self.add_vm_runner_bwip_layer()?;
                 }
+                Component::MintlayerAnchor => {
+                    self = self.add_mintlayer_anchor_layer()?;
+                }
             }
         }
         Ok(self.node.build()?)
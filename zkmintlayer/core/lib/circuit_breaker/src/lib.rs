@@ -5,6 +5,7 @@ use tokio::sync::{watch, Mutex};
 
 pub mod l1_txs;
 mod metrics;
+pub mod mintlayer_balance;
 pub mod replication_lag;
 
 #[derive(Default, Debug)]
@@ -35,6 +36,10 @@ pub enum CircuitBreakerError {
     FailedL1Transaction,
     #[error("Replication lag ({lag:?}) is above the threshold ({threshold:?})")]
     ReplicationLag { lag: Duration, threshold: Duration },
+    #[error(
+        "Mintlayer account balance ({balance} atoms) is below the threshold ({threshold} atoms)"
+    )]
+    LowMintlayerBalance { balance: u128, threshold: u128 },
     #[error("Internal error running circuit breaker checks")]
     Internal(#[from] anyhow::Error),
 }
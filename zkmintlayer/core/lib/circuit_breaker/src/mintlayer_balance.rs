@@ -0,0 +1,43 @@
+use anyhow::Context as _;
+use zksync_mintlayer_client::MintlayerInterface;
+
+use crate::{CircuitBreaker, CircuitBreakerError};
+
+/// Trips if the confirmed coin balance of the Mintlayer account used for anchoring drops below the threshold,
+/// since the node cannot anchor L1 operations without paying fees.
+#[derive(Debug)]
+pub struct MintlayerBalanceChecker {
+    pub client: Box<dyn MintlayerInterface>,
+    /// Minimum balance in atoms.
+    pub min_balance: u128,
+}
+
+#[async_trait::async_trait]
+impl CircuitBreaker for MintlayerBalanceChecker {
+    fn name(&self) -> &'static str {
+        "mintlayer_balance"
+    }
+
+    async fn check(&self) -> Result<(), CircuitBreakerError> {
+        let balances = match self.client.account_balance().await {
+            Ok(balances) => balances,
+            Err(err) if err.is_transient() => {
+                // Wallet unavailability is not a reason to stop the node; the check will be repeated later.
+                tracing::warn!("Cannot get Mintlayer account balance: {err}");
+                return Ok(());
+            }
+            Err(err) => return Err(anyhow::Error::from(err).into()),
+        };
+        let balance = balances
+            .coins
+            .atoms()
+            .context("cannot parse Mintlayer account balance")?;
+        if balance < self.min_balance {
+            return Err(CircuitBreakerError::LowMintlayerBalance {
+                balance,
+                threshold: self.min_balance,
+            });
+        }
+        Ok(())
+    }
+}
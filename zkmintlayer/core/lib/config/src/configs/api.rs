@@ -191,6 +191,8 @@ pub struct Web3JsonRpcConfig {
     pub latest_values_cache_size_mb: Option<usize>,
     /// Limit for fee history block range.
     pub fee_history_limit: Option<u64>,
+    /// Maximum total number of calls in all blocks simulated by a single `eth_simulateV1` request. Default is 1000.
+    pub simulate_calls_limit: Option<usize>,
     /// Maximum number of requests in a single batch JSON RPC request. Default is 500.
     pub max_batch_request_size: Option<usize>,
     /// Maximum response body size in MiBs. Default is 10 MiB.
@@ -250,6 +252,7 @@ impl Web3JsonRpcConfig {
             initial_writes_cache_size_mb: Default::default(),
             latest_values_cache_size_mb: Default::default(),
             fee_history_limit: Default::default(),
+            simulate_calls_limit: Default::default(),
             max_batch_request_size: Default::default(),
             max_response_body_size_mb: Default::default(),
             max_response_body_size_overrides_mb: MaxResponseSizeOverrides::empty(),
@@ -321,6 +324,10 @@ impl Web3JsonRpcConfig {
         self.fee_history_limit.unwrap_or(1024)
     }
 
+    pub fn simulate_calls_limit(&self) -> usize {
+        self.simulate_calls_limit.unwrap_or(1_000)
+    }
+
     pub fn max_batch_request_size(&self) -> usize {
         // The default limit is chosen to be reasonably permissive.
         self.max_batch_request_size.unwrap_or(500)
@@ -6,6 +6,18 @@ pub const DEFAULT_POLLING_INTERVAL_MS: u32 = 5000;
 pub const DEFAULT_MAX_ROWS_TO_DISPATCH: u32 = 100;
 pub const DEFAULT_MAX_RETRIES: u16 = 5;
 
+/// Data availability client used by the `da_dispatcher`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum DAClient {
+    /// Doesn't publish pubdata anywhere.
+    #[default]
+    NoDA,
+    /// Stores pubdata in the object store configured via `DA_CLIENT_OBJECT_STORE_*` env vars.
+    ObjectStore,
+    /// Uploads pubdata to IPFS and deposits its CID on Mintlayer, using the Mintlayer config and secrets.
+    Mintlayer,
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct DADispatcherConfig {
     /// The interval between the `da_dispatcher's` iterations.
@@ -14,6 +26,8 @@ pub struct DADispatcherConfig {
     pub max_rows_to_dispatch: Option<u32>,
     /// The maximum number of retries for the dispatch of a blob.
     pub max_retries: Option<u16>,
+    /// The DA client pubdata is dispatched to. Defaults to [`DAClient::NoDA`].
+    pub client: Option<DAClient>,
 }
 
 impl DADispatcherConfig {
@@ -22,6 +36,7 @@ impl DADispatcherConfig {
             polling_interval_ms: Some(DEFAULT_POLLING_INTERVAL_MS),
             max_rows_to_dispatch: Some(DEFAULT_MAX_ROWS_TO_DISPATCH),
             max_retries: Some(DEFAULT_MAX_RETRIES),
+            client: Some(DAClient::NoDA),
         }
     }
 
@@ -40,4 +55,8 @@ impl DADispatcherConfig {
     pub fn max_retries(&self) -> u16 {
         self.max_retries.unwrap_or(DEFAULT_MAX_RETRIES)
     }
+
+    pub fn client(&self) -> DAClient {
+        self.client.unwrap_or_default()
+    }
 }
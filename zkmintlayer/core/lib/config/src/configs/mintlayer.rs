@@ -0,0 +1,194 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+
+pub const DEFAULT_ACCOUNT_INDEX: u32 = 0;
+pub const DEFAULT_BATCH_SIZE: u32 = 10;
+pub const DEFAULT_CONFIRMATIONS: u32 = 6;
+pub const DEFAULT_RESUBMISSION_GRACE_BLOCKS: u32 = 5;
+pub const DEFAULT_ANCHOR_DEADLINE_SEC: u64 = 3_600;
+/// Fee rate target used by the Mintlayer wallet by default.
+pub const DEFAULT_FEE_IN_TOP_X_MB: u32 = 5;
+pub const DEFAULT_FEE_BUMP_DIVISOR: u32 = 2;
+pub const DEFAULT_BALANCE_CHECK_INTERVAL_SEC: u64 = 60;
+/// Endpoint of the 4EVERLAND S3-compatible gateway.
+pub const DEFAULT_IPFS_ENDPOINT: &str = "https://endpoint.4everland.co";
+pub const DEFAULT_IPFS_REGION: &str = "us-east-1";
+/// Public HTTP gateway of 4EVERLAND used to read documents by CID.
+pub const DEFAULT_IPFS_GATEWAY_URL: &str = "https://ipfs.4everland.link";
+/// Default address of the Kubo RPC API.
+pub const DEFAULT_IPFS_API_URL: &str = "http://127.0.0.1:5001";
+
+/// IPFS backend anchored documents are pinned to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum IpfsBackend {
+    /// S3-compatible gateway pinning uploaded objects to IPFS (e.g., 4EVERLAND).
+    #[default]
+    S3,
+    /// Kubo node accessed via its RPC API.
+    Kubo,
+}
+
+/// Configuration for anchoring aggregated L1 operations on Mintlayer.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct MintlayerConfig {
+    /// URL of the Mintlayer wallet RPC.
+    pub rpc_url: String,
+    /// Username for the basic auth of the Mintlayer wallet RPC. The password is stored in
+    /// [`MintlayerSecrets`](crate::configs::secrets::MintlayerSecrets).
+    pub rpc_username: Option<String>,
+    /// Index of the wallet account used to deposit anchor roots.
+    pub account_index: Option<u32>,
+    /// Number of commit / prove / execute operation triples anchored in a single Mintlayer transaction.
+    pub batch_size: Option<u32>,
+    /// Maximum number of operations anchored in a single Mintlayer transaction. Once this many operations
+    /// are queued, they are anchored immediately. Defaults to 3 × [`Self::batch_size`].
+    pub max_operations_per_anchor: Option<u32>,
+    /// Maximum age of the oldest queued operation in seconds. Once reached, all queued operations are anchored
+    /// even if there are fewer of them than [`Self::max_operations_per_anchor`].
+    pub anchor_deadline_sec: Option<u64>,
+    /// Whether to anchor all queued operations when the anchorer is stopped. Enabled by default.
+    pub flush_on_shutdown: Option<bool>,
+    /// First L1 batch anchored by the anchorer when it starts for the first time on a chain. If not set, only operations
+    /// saved after the anchorer is first started are anchored. Operations covering earlier L1 batches should be
+    /// anchored with the backfill tool. Ignored once the anchorer has started.
+    pub first_anchored_l1_batch: Option<u32>,
+    /// Number of Mintlayer blocks on top of the block including an anchor transaction (including that block)
+    /// after which the anchor is considered confirmed.
+    pub confirmations: Option<u32>,
+    /// Number of Mintlayer blocks during which an anchor transaction may stay unknown to the wallet or inactive
+    /// (i.e., not in the mempool) before the anchor root is re-deposited in a new transaction. Inactive transactions
+    /// are rebroadcast in the meantime. Transactions that are abandoned or conflict with another transaction
+    /// are re-deposited immediately.
+    pub resubmission_grace_blocks: Option<u32>,
+    /// Whether to compress uploaded operation documents with zstd. Enabled by default.
+    pub compress_documents: Option<bool>,
+    /// Fee rate target for deposit transactions: the wallet chooses the fee rate so that the transaction gets
+    /// into the top X MB of the mempool. Smaller values mean higher fees.
+    pub fee_in_top_x_mb: Option<u32>,
+    /// Divisor applied to [`Self::fee_in_top_x_mb`] each time a dropped anchor transaction is re-deposited,
+    /// so that re-deposited transactions pay higher fees. 1 disables fee bumps.
+    pub fee_bump_divisor: Option<u32>,
+    /// Interval between checks of the account balance.
+    pub balance_check_interval_sec: Option<u64>,
+    /// Minimum balance of the account in atoms. If set, a circuit breaker stops the server once the balance
+    /// drops below this value.
+    pub min_balance_atoms: Option<u64>,
+    /// IPFS backend documents are pinned to. Defaults to [`IpfsBackend::S3`].
+    pub ipfs_backend: Option<IpfsBackend>,
+    /// Endpoint of the S3-compatible IPFS gateway documents are uploaded to.
+    pub ipfs_endpoint: Option<String>,
+    /// Region of the S3-compatible IPFS gateway.
+    pub ipfs_region: Option<String>,
+    /// Name of the bucket documents are uploaded to. Required for the S3 backend.
+    pub ipfs_bucket_name: Option<String>,
+    /// HTTP gateway used to read documents from the S3 backend by CID.
+    pub ipfs_gateway_url: Option<String>,
+    /// URL of the Kubo RPC API used by the Kubo backend.
+    pub ipfs_api_url: Option<String>,
+}
+
+impl MintlayerConfig {
+    pub fn for_tests() -> Self {
+        Self {
+            rpc_url: "http://127.0.0.1:13034".to_owned(),
+            rpc_username: None,
+            account_index: Some(DEFAULT_ACCOUNT_INDEX),
+            batch_size: Some(DEFAULT_BATCH_SIZE),
+            max_operations_per_anchor: None,
+            anchor_deadline_sec: Some(DEFAULT_ANCHOR_DEADLINE_SEC),
+            flush_on_shutdown: Some(true),
+            first_anchored_l1_batch: None,
+            confirmations: Some(DEFAULT_CONFIRMATIONS),
+            resubmission_grace_blocks: Some(DEFAULT_RESUBMISSION_GRACE_BLOCKS),
+            compress_documents: Some(true),
+            fee_in_top_x_mb: Some(DEFAULT_FEE_IN_TOP_X_MB),
+            fee_bump_divisor: Some(DEFAULT_FEE_BUMP_DIVISOR),
+            balance_check_interval_sec: Some(DEFAULT_BALANCE_CHECK_INTERVAL_SEC),
+            min_balance_atoms: None,
+            ipfs_backend: Some(IpfsBackend::S3),
+            ipfs_endpoint: Some(DEFAULT_IPFS_ENDPOINT.to_owned()),
+            ipfs_region: Some(DEFAULT_IPFS_REGION.to_owned()),
+            ipfs_bucket_name: Some("zkmintlayer".to_owned()),
+            ipfs_gateway_url: Some(DEFAULT_IPFS_GATEWAY_URL.to_owned()),
+            ipfs_api_url: Some(DEFAULT_IPFS_API_URL.to_owned()),
+        }
+    }
+
+    pub fn account_index(&self) -> u32 {
+        self.account_index.unwrap_or(DEFAULT_ACCOUNT_INDEX)
+    }
+
+    pub fn batch_size(&self) -> u32 {
+        self.batch_size.unwrap_or(DEFAULT_BATCH_SIZE)
+    }
+
+    pub fn max_operations_per_anchor(&self) -> u32 {
+        // Each batch of L1 operations consists of commit, prove and execute operations.
+        self.max_operations_per_anchor
+            .unwrap_or_else(|| self.batch_size().saturating_mul(3))
+    }
+
+    pub fn anchor_deadline(&self) -> Duration {
+        Duration::from_secs(
+            self.anchor_deadline_sec
+                .unwrap_or(DEFAULT_ANCHOR_DEADLINE_SEC),
+        )
+    }
+
+    pub fn flush_on_shutdown(&self) -> bool {
+        self.flush_on_shutdown.unwrap_or(true)
+    }
+
+    pub fn confirmations(&self) -> u32 {
+        self.confirmations.unwrap_or(DEFAULT_CONFIRMATIONS)
+    }
+
+    pub fn resubmission_grace_blocks(&self) -> u32 {
+        self.resubmission_grace_blocks
+            .unwrap_or(DEFAULT_RESUBMISSION_GRACE_BLOCKS)
+    }
+
+    pub fn compress_documents(&self) -> bool {
+        self.compress_documents.unwrap_or(true)
+    }
+
+    pub fn fee_in_top_x_mb(&self) -> u32 {
+        self.fee_in_top_x_mb.unwrap_or(DEFAULT_FEE_IN_TOP_X_MB)
+    }
+
+    pub fn fee_bump_divisor(&self) -> u32 {
+        self.fee_bump_divisor.unwrap_or(DEFAULT_FEE_BUMP_DIVISOR)
+    }
+
+    pub fn balance_check_interval(&self) -> Duration {
+        Duration::from_secs(
+            self.balance_check_interval_sec
+                .unwrap_or(DEFAULT_BALANCE_CHECK_INTERVAL_SEC),
+        )
+    }
+
+    pub fn ipfs_backend(&self) -> IpfsBackend {
+        self.ipfs_backend.unwrap_or_default()
+    }
+
+    pub fn ipfs_endpoint(&self) -> &str {
+        self.ipfs_endpoint
+            .as_deref()
+            .unwrap_or(DEFAULT_IPFS_ENDPOINT)
+    }
+
+    pub fn ipfs_region(&self) -> &str {
+        self.ipfs_region.as_deref().unwrap_or(DEFAULT_IPFS_REGION)
+    }
+
+    pub fn ipfs_gateway_url(&self) -> &str {
+        self.ipfs_gateway_url
+            .as_deref()
+            .unwrap_or(DEFAULT_IPFS_GATEWAY_URL)
+    }
+
+    pub fn ipfs_api_url(&self) -> &str {
+        self.ipfs_api_url.as_deref().unwrap_or(DEFAULT_IPFS_API_URL)
+    }
+}
@@ -17,11 +17,12 @@ pub use self::{
     fri_witness_vector_generator::FriWitnessVectorGeneratorConfig,
     general::GeneralConfig,
     genesis::GenesisConfig,
+    mintlayer::MintlayerConfig,
     object_store::ObjectStoreConfig,
     observability::{ObservabilityConfig, OpentelemetryConfig},
     proof_data_handler::ProofDataHandlerConfig,
     pruning::PruningConfig,
-    secrets::{DatabaseSecrets, L1Secrets, Secrets},
+    secrets::{DatabaseSecrets, L1Secrets, MintlayerSecrets, Secrets},
     snapshot_recovery::SnapshotRecoveryConfig,
     snapshots_creator::SnapshotsCreatorConfig,
     utils::PrometheusConfig,
@@ -50,6 +51,7 @@ pub mod fri_witness_vector_generator;
 mod general;
 pub mod genesis;
 pub mod house_keeper;
+pub mod mintlayer;
 pub mod object_store;
 pub mod observability;
 pub mod proof_data_handler;
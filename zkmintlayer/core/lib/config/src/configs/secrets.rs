@@ -1,4 +1,5 @@
 use anyhow::Context;
+use secrecy::{ExposeSecret, Secret};
 use zksync_basic_types::url::SensitiveUrl;
 
 use crate::configs::consensus::ConsensusSecrets;
@@ -15,11 +16,35 @@ pub struct L1Secrets {
     pub l1_rpc_url: SensitiveUrl,
 }
 
+/// Secrets needed for anchoring on Mintlayer.
+#[derive(Debug, Clone)]
+pub struct MintlayerSecrets {
+    /// Password for the basic auth of the Mintlayer wallet RPC.
+    pub rpc_password: Option<Secret<String>>,
+    /// Access key of the IPFS gateway bucket.
+    pub ipfs_api_key: Option<Secret<String>>,
+    /// Secret key of the IPFS gateway bucket.
+    pub ipfs_secret_key: Option<Secret<String>>,
+}
+
+impl PartialEq for MintlayerSecrets {
+    fn eq(&self, other: &Self) -> bool {
+        fn expose(secret: &Option<Secret<String>>) -> Option<&String> {
+            secret.as_ref().map(ExposeSecret::expose_secret)
+        }
+
+        expose(&self.rpc_password) == expose(&other.rpc_password)
+            && expose(&self.ipfs_api_key) == expose(&other.ipfs_api_key)
+            && expose(&self.ipfs_secret_key) == expose(&other.ipfs_secret_key)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Secrets {
     pub consensus: Option<ConsensusSecrets>,
     pub database: Option<DatabaseSecrets>,
     pub l1: Option<L1Secrets>,
+    pub mintlayer: Option<MintlayerSecrets>,
 }
 
 impl DatabaseSecrets {
@@ -40,3 +65,28 @@ impl DatabaseSecrets {
         self.prover_url.clone().context("Prover DB URL is absent")
     }
 }
+
+impl MintlayerSecrets {
+    /// Returns the password for the Mintlayer wallet RPC, if any.
+    pub fn rpc_password(&self) -> Option<&str> {
+        self.rpc_password
+            .as_ref()
+            .map(|password| password.expose_secret().as_str())
+    }
+
+    /// Returns the IPFS gateway access key as a `Result` to simplify error propagation.
+    pub fn ipfs_api_key(&self) -> anyhow::Result<&str> {
+        self.ipfs_api_key
+            .as_ref()
+            .map(|key| key.expose_secret().as_str())
+            .context("IPFS API key is absent")
+    }
+
+    /// Returns the IPFS gateway secret key as a `Result` to simplify error propagation.
+    pub fn ipfs_secret_key(&self) -> anyhow::Result<&str> {
+        self.ipfs_secret_key
+            .as_ref()
+            .map(|key| key.expose_secret().as_str())
+            .context("IPFS secret key is absent")
+    }
+}
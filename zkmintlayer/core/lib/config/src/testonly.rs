@@ -79,6 +79,7 @@ impl Distribution<configs::api::Web3JsonRpcConfig> for EncodeDist {
             initial_writes_cache_size_mb: self.sample(rng),
             latest_values_cache_size_mb: self.sample(rng),
             fee_history_limit: self.sample(rng),
+            simulate_calls_limit: self.sample(rng),
             max_batch_request_size: self.sample(rng),
             max_response_body_size_mb: self.sample(rng),
             max_response_body_size_overrides_mb: [
@@ -631,6 +632,44 @@ impl Distribution<configs::ObjectStoreConfig> for EncodeDist {
     }
 }
 
+impl Distribution<configs::mintlayer::IpfsBackend> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::mintlayer::IpfsBackend {
+        type T = configs::mintlayer::IpfsBackend;
+        match rng.gen_range(0..2) {
+            0 => T::S3,
+            _ => T::Kubo,
+        }
+    }
+}
+
+impl Distribution<configs::MintlayerConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::MintlayerConfig {
+        configs::MintlayerConfig {
+            rpc_url: self.sample(rng),
+            rpc_username: self.sample(rng),
+            account_index: self.sample(rng),
+            batch_size: self.sample(rng),
+            max_operations_per_anchor: self.sample(rng),
+            anchor_deadline_sec: self.sample(rng),
+            flush_on_shutdown: self.sample(rng),
+            first_anchored_l1_batch: self.sample(rng),
+            confirmations: self.sample(rng),
+            resubmission_grace_blocks: self.sample(rng),
+            compress_documents: self.sample(rng),
+            fee_in_top_x_mb: self.sample(rng),
+            fee_bump_divisor: self.sample(rng),
+            balance_check_interval_sec: self.sample(rng),
+            min_balance_atoms: self.sample(rng),
+            ipfs_backend: self.sample_opt(|| self.sample(rng)),
+            ipfs_endpoint: self.sample(rng),
+            ipfs_region: self.sample(rng),
+            ipfs_bucket_name: self.sample(rng),
+            ipfs_gateway_url: self.sample(rng),
+            ipfs_api_url: self.sample(rng),
+        }
+    }
+}
+
 impl Distribution<configs::ProofDataHandlerConfig> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::ProofDataHandlerConfig {
         configs::ProofDataHandlerConfig {
@@ -797,6 +836,17 @@ impl Distribution<configs::secrets::DatabaseSecrets> for EncodeDist {
     }
 }
 
+impl Distribution<configs::secrets::MintlayerSecrets> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::secrets::MintlayerSecrets {
+        use configs::secrets::MintlayerSecrets;
+        MintlayerSecrets {
+            rpc_password: self.sample_opt(|| String::into(self.sample(rng))),
+            ipfs_api_key: self.sample_opt(|| String::into(self.sample(rng))),
+            ipfs_secret_key: self.sample_opt(|| String::into(self.sample(rng))),
+        }
+    }
+}
+
 impl Distribution<configs::secrets::Secrets> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::secrets::Secrets {
         use configs::secrets::Secrets;
@@ -804,6 +854,7 @@ impl Distribution<configs::secrets::Secrets> for EncodeDist {
             consensus: self.sample_opt(|| self.sample(rng)),
             database: self.sample_opt(|| self.sample(rng)),
             l1: self.sample_opt(|| self.sample(rng)),
+            mintlayer: self.sample_opt(|| self.sample(rng)),
         }
     }
 }
@@ -15,7 +15,8 @@ pub trait DataAvailabilityClient: Sync + Send + fmt::Debug {
         data: Vec<u8>,
     ) -> Result<DispatchResponse, DAError>;
 
-    /// Fetches the inclusion data for a given blob_id.
+    /// Fetches the inclusion data for a given blob_id. If the DA layer dropped the blob, returns
+    /// an error created with [`DAError::dropped()`](types::DAError::dropped()), so that the blob is dispatched again.
     async fn get_inclusion_data(&self, blob_id: &str) -> Result<Option<InclusionData>, DAError>;
 
     /// Clones the client and wraps it in a Box.
@@ -1,4 +1,7 @@
-use std::{error, fmt::Display};
+use std::{
+    error,
+    fmt::{self, Display},
+};
 
 use serde::Serialize;
 
@@ -10,13 +13,40 @@ pub struct DAError {
 }
 
 impl DAError {
+    /// Creates an error signalling that the DA layer dropped the blob with the specified ID, so that it will never
+    /// be included. The dispatcher dispatches such blobs again.
+    pub fn dropped(blob_id: &str, reason: impl Display) -> Self {
+        Self {
+            error: anyhow::Error::new(BlobDropped)
+                .context(format!("blob {blob_id} was dropped: {reason}")),
+            is_transient: false,
+        }
+    }
+
     pub fn is_transient(&self) -> bool {
         self.is_transient
     }
+
+    /// Checks whether this error was created using [`Self::dropped()`].
+    pub fn is_dropped(&self) -> bool {
+        self.error.downcast_ref::<BlobDropped>().is_some()
+    }
+}
+
+/// Root cause of errors created by [`DAError::dropped()`].
+#[derive(Debug)]
+struct BlobDropped;
+
+impl Display for BlobDropped {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("blob was dropped by the DA layer")
+    }
 }
 
+impl error::Error for BlobDropped {}
+
 impl Display for DAError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let kind = if self.is_transient {
             "transient"
         } else {
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS mintlayer_anchor_operations;
+DROP TABLE IF EXISTS mintlayer_anchors;
@@ -0,0 +1,32 @@
+CREATE TABLE IF NOT EXISTS mintlayer_anchors
+(
+    id              SERIAL PRIMARY KEY,
+    -- CID of the IPFS document listing all operations covered by this anchor
+    root_cid        TEXT      NOT NULL,
+    -- ID of the Mintlayer transaction that deposited `root_cid`; NULL until the deposit succeeds
+    mintlayer_tx_id TEXT,
+
+    created_at      TIMESTAMP NOT NULL,
+    updated_at      TIMESTAMP NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS mintlayer_anchor_operations
+(
+    id                   SERIAL PRIMARY KEY,
+    eth_tx_id            INT UNIQUE REFERENCES eth_txs (id) ON DELETE SET NULL,
+    op_type              TEXT      NOT NULL,
+    from_l1_batch_number BIGINT    NOT NULL,
+    to_l1_batch_number   BIGINT    NOT NULL,
+    -- CID of the IPFS document with the aggregated operation data
+    cid                  TEXT      NOT NULL,
+    -- NULL while the operation is queued and waiting to be anchored
+    anchor_id            INT REFERENCES mintlayer_anchors (id) ON DELETE SET NULL,
+
+    created_at           TIMESTAMP NOT NULL,
+    updated_at           TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS mintlayer_anchor_operations_pending_idx
+    ON mintlayer_anchor_operations (id) WHERE anchor_id IS NULL;
+CREATE INDEX IF NOT EXISTS mintlayer_anchor_operations_anchor_id_idx
+    ON mintlayer_anchor_operations (anchor_id);
@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS mintlayer_anchors_unconfirmed_idx;
+
+ALTER TABLE mintlayer_anchors DROP COLUMN IF EXISTS resubmissions;
+ALTER TABLE mintlayer_anchors DROP COLUMN IF EXISTS confirmed_at;
+ALTER TABLE mintlayer_anchors DROP COLUMN IF EXISTS included_at_block;
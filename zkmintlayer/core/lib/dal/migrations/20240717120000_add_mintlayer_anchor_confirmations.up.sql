@@ -0,0 +1,9 @@
+-- Height of the Mintlayer block including `mintlayer_tx_id`; NULL while the transaction is not included
+ALTER TABLE mintlayer_anchors ADD COLUMN IF NOT EXISTS included_at_block BIGINT;
+-- Set once the including block gets enough confirmations
+ALTER TABLE mintlayer_anchors ADD COLUMN IF NOT EXISTS confirmed_at TIMESTAMP;
+-- Number of times the root was re-deposited because the previous transaction was dropped
+ALTER TABLE mintlayer_anchors ADD COLUMN IF NOT EXISTS resubmissions INT NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS mintlayer_anchors_unconfirmed_idx
+    ON mintlayer_anchors (id) WHERE confirmed_at IS NULL;
@@ -0,0 +1 @@
+ALTER TABLE mintlayer_anchors DROP COLUMN IF EXISTS merkle_root;
@@ -0,0 +1,2 @@
+-- Root of the Merkle tree over operations covered by the anchor; NULL for legacy anchors committing to `root_cid` only
+ALTER TABLE mintlayer_anchors ADD COLUMN IF NOT EXISTS merkle_root BYTEA;
@@ -0,0 +1 @@
+DROP INDEX IF EXISTS mintlayer_anchor_operations_l1_batch_idx;
@@ -0,0 +1,2 @@
+CREATE INDEX IF NOT EXISTS mintlayer_anchor_operations_l1_batch_idx
+    ON mintlayer_anchor_operations (to_l1_batch_number, from_l1_batch_number);
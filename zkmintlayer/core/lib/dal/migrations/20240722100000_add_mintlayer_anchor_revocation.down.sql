@@ -0,0 +1,2 @@
+ALTER TABLE mintlayer_anchors DROP COLUMN IF EXISTS revocation_tx_id;
+ALTER TABLE mintlayer_anchors DROP COLUMN IF EXISTS revoked_at;
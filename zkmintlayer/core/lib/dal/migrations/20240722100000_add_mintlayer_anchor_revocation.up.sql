@@ -0,0 +1,4 @@
+-- Set when the anchor covers L1 batches removed by the block reverter
+ALTER TABLE mintlayer_anchors ADD COLUMN IF NOT EXISTS revoked_at TIMESTAMP;
+-- ID of the Mintlayer transaction that deposited the revocation record for the anchor
+ALTER TABLE mintlayer_anchors ADD COLUMN IF NOT EXISTS revocation_tx_id TEXT;
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS mintlayer_anchoring_start;
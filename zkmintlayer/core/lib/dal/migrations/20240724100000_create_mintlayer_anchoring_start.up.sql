@@ -0,0 +1,8 @@
+-- Single-row table with the last L1 transaction that is *not* uploaded by the anchorer. It is initialized when
+-- the anchorer first starts, so that enabling anchoring on an existing chain doesn't anchor its entire history;
+-- historical operations are anchored by the backfill tool instead.
+CREATE TABLE IF NOT EXISTS mintlayer_anchoring_start
+(
+    eth_tx_id  INT       NOT NULL PRIMARY KEY,
+    created_at TIMESTAMP NOT NULL
+);
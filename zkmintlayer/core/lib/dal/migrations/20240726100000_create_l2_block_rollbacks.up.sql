@@ -0,0 +1,10 @@
+-- Rolled back L2 blocks together with the logs emitted in them. Used by the API server to notify log subscribers
+-- about removed logs, including for rollbacks performed while the server is stopped.
+CREATE TABLE IF NOT EXISTS l2_block_rollbacks
+(
+    id             BIGSERIAL PRIMARY KEY,
+    first_l2_block BIGINT    NOT NULL,
+    last_l2_block  BIGINT    NOT NULL,
+    logs           JSONB     NOT NULL,
+    created_at     TIMESTAMP NOT NULL
+);
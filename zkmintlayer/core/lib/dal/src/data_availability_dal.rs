@@ -147,6 +147,32 @@ impl DataAvailabilityDal<'_, '_> {
         Ok(())
     }
 
+    /// Removes the blob_id for the given L1 batch if the blob was dropped by the DA layer, so that the L1 batch
+    /// is dispatched again. This is a no-op if the L1 batch has another blob_id or already has inclusion data.
+    pub async fn remove_dropped_l1_batch_da(
+        &mut self,
+        number: L1BatchNumber,
+        blob_id: &str,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM data_availability
+            WHERE
+                l1_batch_number = $1
+                AND blob_id = $2
+                AND inclusion_data IS NULL
+            "#,
+            i64::from(number.0),
+            blob_id,
+        )
+        .instrument("remove_dropped_l1_batch_da")
+        .with_arg("number", &number)
+        .with_arg("blob_id", &blob_id)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
     /// Assumes that the L1 batches are sorted by number, and returns the first one that is ready for DA dispatch.
     pub async fn get_first_da_blob_awaiting_inclusion(
         &mut self,
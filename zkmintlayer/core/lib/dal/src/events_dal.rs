@@ -4,7 +4,7 @@ use sqlx::types::chrono::Utc;
 use zksync_db_connection::{
     connection::Connection,
     error::DalResult,
-    instrument::{CopyStatement, InstrumentExt},
+    instrument::{CopyStatement, InstrumentExt, Instrumented},
     write_str, writeln_str,
 };
 use zksync_system_constants::L1_MESSENGER_ADDRESS;
@@ -35,6 +35,40 @@ impl fmt::LowerHex for EventTopic<'_> {
     }
 }
 
+/// Rollback of L2 blocks persisted by [`EventsDal::save_l2_block_rollback()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct L2BlockRollback {
+    pub id: u64,
+    pub first_l2_block: L2BlockNumber,
+    pub last_l2_block: L2BlockNumber,
+    /// Logs emitted in the rolled back L2 blocks, ordered by L2 block and by index in the block.
+    pub logs: Vec<api::Log>,
+}
+
+#[derive(Debug)]
+struct StorageL2BlockRollback {
+    id: i64,
+    first_l2_block: i64,
+    last_l2_block: i64,
+    logs: serde_json::Value,
+}
+
+impl StorageL2BlockRollback {
+    fn into_rollback(self, instrumentation: &Instrumented<'_, ()>) -> DalResult<L2BlockRollback> {
+        let logs = serde_json::from_value(self.logs).map_err(|err| {
+            instrumentation.constraint_error(
+                anyhow::Error::from(err).context(format!("invalid logs for rollback #{}", self.id)),
+            )
+        })?;
+        Ok(L2BlockRollback {
+            id: self.id as u64,
+            first_l2_block: L2BlockNumber(self.first_l2_block as u32),
+            last_l2_block: L2BlockNumber(self.last_l2_block as u32),
+            logs,
+        })
+    }
+}
+
 #[derive(Debug)]
 pub struct EventsDal<'a, 'c> {
     pub(crate) storage: &'a mut Connection<'c, Core>,
@@ -118,6 +152,135 @@ impl EventsDal<'_, '_> {
         Ok(())
     }
 
+    /// Persists logs from L2 blocks with a number strictly greater than `last_l2_block_to_keep`, so that
+    /// they can be reported to log subscribers as removed. Must be called before the events are rolled back.
+    /// Does nothing if there are no L2 blocks to roll back.
+    pub async fn save_l2_block_rollback(
+        &mut self,
+        last_l2_block_to_keep: L2BlockNumber,
+    ) -> DalResult<()> {
+        let Some(last_l2_block) = self
+            .storage
+            .blocks_dal()
+            .get_sealed_l2_block_number()
+            .await?
+        else {
+            return Ok(());
+        };
+        if last_l2_block <= last_l2_block_to_keep {
+            return Ok(());
+        }
+        let logs = self
+            .storage
+            .events_web3_dal()
+            .get_all_logs(last_l2_block_to_keep)
+            .await?;
+
+        let instrumentation = Instrumented::new("save_l2_block_rollback")
+            .with_arg("last_l2_block_to_keep", &last_l2_block_to_keep)
+            .with_arg("last_l2_block", &last_l2_block);
+        let logs =
+            serde_json::to_value(&logs).map_err(|err| instrumentation.arg_error("logs", err))?;
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO
+                l2_block_rollbacks (first_l2_block, last_l2_block, logs, created_at)
+            VALUES
+                ($1, $2, $3, NOW())
+            "#,
+            i64::from(last_l2_block_to_keep.0) + 1,
+            i64::from(last_l2_block.0),
+            logs
+        );
+        instrumentation.with(query).execute(self.storage).await?;
+        Ok(())
+    }
+
+    /// Returns the ID of the latest persisted L2 block rollback, or 0 if there are no rollbacks.
+    pub async fn get_last_l2_block_rollback_id(&mut self) -> DalResult<u64> {
+        let id = sqlx::query!(
+            r#"
+            SELECT
+                MAX(id) AS "id?"
+            FROM
+                l2_block_rollbacks
+            "#
+        )
+        .instrument("get_last_l2_block_rollback_id")
+        .fetch_one(self.storage)
+        .await?
+        .id;
+        Ok(id.map_or(0, |id| id as u64))
+    }
+
+    /// Returns L2 block rollbacks with an ID strictly greater than `id`, ordered by ID.
+    pub async fn get_l2_block_rollbacks_after(
+        &mut self,
+        id: u64,
+    ) -> DalResult<Vec<L2BlockRollback>> {
+        let instrumentation = Instrumented::new("get_l2_block_rollbacks_after").with_arg("id", &id);
+        let query = sqlx::query_as!(
+            StorageL2BlockRollback,
+            r#"
+            SELECT
+                id,
+                first_l2_block,
+                last_l2_block,
+                logs
+            FROM
+                l2_block_rollbacks
+            WHERE
+                id > $1
+            ORDER BY
+                id
+            "#,
+            id as i64
+        );
+        let rows = instrumentation
+            .clone()
+            .with(query)
+            .fetch_all(self.storage)
+            .await?;
+        rows.into_iter()
+            .map(|row| row.into_rollback(&instrumentation))
+            .collect()
+    }
+
+    /// Returns L2 block rollbacks starting after the specified L2 block (i.e., ones whose blocks haven't been
+    /// replaced by new blocks yet), ordered by ID.
+    pub async fn get_l2_block_rollbacks_after_l2_block(
+        &mut self,
+        l2_block_number: L2BlockNumber,
+    ) -> DalResult<Vec<L2BlockRollback>> {
+        let instrumentation = Instrumented::new("get_l2_block_rollbacks_after_l2_block")
+            .with_arg("l2_block_number", &l2_block_number);
+        let query = sqlx::query_as!(
+            StorageL2BlockRollback,
+            r#"
+            SELECT
+                id,
+                first_l2_block,
+                last_l2_block,
+                logs
+            FROM
+                l2_block_rollbacks
+            WHERE
+                first_l2_block > $1
+            ORDER BY
+                id
+            "#,
+            i64::from(l2_block_number.0)
+        );
+        let rows = instrumentation
+            .clone()
+            .with(query)
+            .fetch_all(self.storage)
+            .await?;
+        rows.into_iter()
+            .map(|row| row.into_rollback(&instrumentation))
+            .collect()
+    }
+
     /// Saves user L2-to-L1 logs from an L2 block. Logs must be ordered by transaction location
     /// and within each transaction.
     pub async fn save_user_l2_to_l1_logs(
@@ -501,6 +664,92 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn saving_l2_block_rollbacks() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        let location = IncludedTxLocation {
+            tx_hash: H256([1; 32]),
+            tx_index_in_l2_block: 0,
+            tx_initiator_address: Address::default(),
+        };
+        for number in 1..=3 {
+            conn.blocks_dal()
+                .insert_l2_block(&create_l2_block_header(number))
+                .await
+                .unwrap();
+            let events = [create_vm_event(number as u8, 1)];
+            conn.events_dal()
+                .save_events(
+                    L2BlockNumber(number),
+                    &[(location, events.iter().collect())],
+                )
+                .await
+                .unwrap();
+        }
+
+        let mut events_dal = conn.events_dal();
+        assert_eq!(events_dal.get_last_l2_block_rollback_id().await.unwrap(), 0);
+        events_dal
+            .save_l2_block_rollback(L2BlockNumber(3))
+            .await
+            .unwrap();
+        assert_eq!(events_dal.get_last_l2_block_rollback_id().await.unwrap(), 0);
+
+        let expected_logs = conn
+            .events_web3_dal()
+            .get_all_logs(L2BlockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(expected_logs.len(), 2);
+        conn.events_dal()
+            .save_l2_block_rollback(L2BlockNumber(1))
+            .await
+            .unwrap();
+        let rollback_id = conn
+            .events_dal()
+            .get_last_l2_block_rollback_id()
+            .await
+            .unwrap();
+        assert_ne!(rollback_id, 0);
+
+        let rollbacks = conn
+            .events_dal()
+            .get_l2_block_rollbacks_after(0)
+            .await
+            .unwrap();
+        let expected_rollback = L2BlockRollback {
+            id: rollback_id,
+            first_l2_block: L2BlockNumber(2),
+            last_l2_block: L2BlockNumber(3),
+            logs: expected_logs,
+        };
+        assert_eq!(rollbacks, [expected_rollback.clone()]);
+        let rollbacks = conn
+            .events_dal()
+            .get_l2_block_rollbacks_after(rollback_id)
+            .await
+            .unwrap();
+        assert!(rollbacks.is_empty());
+
+        let rollbacks = conn
+            .events_dal()
+            .get_l2_block_rollbacks_after_l2_block(L2BlockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(rollbacks, [expected_rollback]);
+        let rollbacks = conn
+            .events_dal()
+            .get_l2_block_rollbacks_after_l2_block(L2BlockNumber(2))
+            .await
+            .unwrap();
+        assert!(rollbacks.is_empty());
+    }
+
     #[tokio::test]
     async fn storing_l2_to_l1_logs() {
         let pool = ConnectionPool::<Core>::test_pool().await;
@@ -16,7 +16,8 @@ use crate::{
     consensus_dal::ConsensusDal, contract_verification_dal::ContractVerificationDal,
     data_availability_dal::DataAvailabilityDal, eth_sender_dal::EthSenderDal,
     events_dal::EventsDal, events_web3_dal::EventsWeb3Dal, factory_deps_dal::FactoryDepsDal,
-    proof_generation_dal::ProofGenerationDal, protocol_versions_dal::ProtocolVersionsDal,
+    mintlayer_anchor_dal::MintlayerAnchorDal, proof_generation_dal::ProofGenerationDal,
+    protocol_versions_dal::ProtocolVersionsDal,
     protocol_versions_web3_dal::ProtocolVersionsWeb3Dal, pruning_dal::PruningDal,
     snapshot_recovery_dal::SnapshotRecoveryDal, snapshots_creator_dal::SnapshotsCreatorDal,
     snapshots_dal::SnapshotsDal, storage_logs_dal::StorageLogsDal,
@@ -40,6 +41,7 @@ pub mod events_web3_dal;
 pub mod factory_deps_dal;
 pub mod helpers;
 pub mod metrics;
+pub mod mintlayer_anchor_dal;
 mod models;
 pub mod proof_generation_dal;
 pub mod protocol_versions_dal;
@@ -132,6 +134,8 @@ where
     fn vm_runner_dal(&mut self) -> VmRunnerDal<'_, 'a>;
 
     fn base_token_dal(&mut self) -> BaseTokenDal<'_, 'a>;
+
+    fn mintlayer_anchor_dal(&mut self) -> MintlayerAnchorDal<'_, 'a>;
 }
 
 #[derive(Clone, Debug)]
@@ -258,4 +262,8 @@ impl<'a> CoreDal<'a> for Connection<'a, Core> {
     fn base_token_dal(&mut self) -> BaseTokenDal<'_, 'a> {
         BaseTokenDal { storage: self }
     }
+
+    fn mintlayer_anchor_dal(&mut self) -> MintlayerAnchorDal<'_, 'a> {
+        MintlayerAnchorDal { storage: self }
+    }
 }
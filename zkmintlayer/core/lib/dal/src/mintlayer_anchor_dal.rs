@@ -0,0 +1,1253 @@
+use std::ops;
+
+use chrono::{DateTime, Utc};
+use zksync_db_connection::{
+    connection::Connection,
+    error::DalResult,
+    instrument::{InstrumentExt, Instrumented},
+};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType,
+    mintlayer::{MintlayerAnchor, MintlayerAnchorOperation, MintlayerOperationToUpload},
+    L1BatchNumber, H256,
+};
+
+use crate::{
+    models::storage_mintlayer_anchor::{StorageMintlayerAnchor, StorageMintlayerAnchorOperation},
+    Core,
+};
+
+#[derive(Debug)]
+pub struct MintlayerAnchorDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl MintlayerAnchorDal<'_, '_> {
+    /// Initializes the anchoring start if it isn't initialized yet, and returns the ID of the last L1 transaction
+    /// that is not uploaded by the anchorer. On initialization, the start is chosen as follows:
+    ///
+    /// - If the anchoring queue is not empty, anchoring continues after the last queued transaction.
+    /// - If `first_l1_batch` is specified, anchoring starts from the first transaction covering this or a later
+    ///   L1 batch.
+    /// - Otherwise, anchoring starts after the last transaction saved by the eth sender.
+    pub async fn init_anchoring_start(
+        &mut self,
+        first_l1_batch: Option<L1BatchNumber>,
+    ) -> DalResult<u32> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                mintlayer_anchoring_start (eth_tx_id, created_at)
+            SELECT
+                CASE
+                    WHEN EXISTS (
+                        SELECT
+                            1
+                        FROM
+                            mintlayer_anchor_operations
+                    ) THEN (
+                        SELECT
+                            COALESCE(MAX(eth_tx_id), 0)
+                        FROM
+                            mintlayer_anchor_operations
+                    )
+                    WHEN $1::BIGINT IS NULL THEN (
+                        SELECT
+                            COALESCE(MAX(id), 0)
+                        FROM
+                            eth_txs
+                    )
+                    ELSE COALESCE(
+                        (
+                            SELECT
+                                MIN(eth_txs.id) - 1
+                            FROM
+                                eth_txs
+                                JOIN l1_batches ON eth_txs.id IN (
+                                    l1_batches.eth_commit_tx_id,
+                                    l1_batches.eth_prove_tx_id,
+                                    l1_batches.eth_execute_tx_id
+                                )
+                            WHERE
+                                l1_batches.number >= $1
+                        ),
+                        (
+                            SELECT
+                                MAX(id)
+                            FROM
+                                eth_txs
+                        ),
+                        0
+                    )
+                END,
+                NOW()
+            WHERE
+                NOT EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        mintlayer_anchoring_start
+                )
+            "#,
+            first_l1_batch.map(|number| i64::from(number.0)),
+        )
+        .instrument("init_mintlayer_anchoring_start")
+        .with_arg("first_l1_batch", &first_l1_batch)
+        .execute(self.storage)
+        .await?;
+
+        let eth_tx_id = sqlx::query_scalar!(
+            r#"
+            SELECT
+                eth_tx_id
+            FROM
+                mintlayer_anchoring_start
+            "#
+        )
+        .instrument("get_mintlayer_anchoring_start")
+        .fetch_one(self.storage)
+        .await?;
+        Ok(eth_tx_id as u32)
+    }
+
+    /// Returns up to `limit` L1 transactions saved by the eth sender after the last transaction
+    /// present in the anchoring queue and after the anchoring start (see [`Self::init_anchoring_start()`]),
+    /// together with the L1 batches they cover.
+    pub async fn get_operations_to_upload(
+        &mut self,
+        limit: usize,
+    ) -> DalResult<Vec<MintlayerOperationToUpload>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                eth_txs.id,
+                eth_txs.tx_type,
+                MIN(l1_batches.number) AS "from_l1_batch_number!",
+                MAX(l1_batches.number) AS "to_l1_batch_number!"
+            FROM
+                eth_txs
+                JOIN l1_batches ON eth_txs.id IN (
+                    l1_batches.eth_commit_tx_id,
+                    l1_batches.eth_prove_tx_id,
+                    l1_batches.eth_execute_tx_id
+                )
+            WHERE
+                eth_txs.id > GREATEST(
+                    (
+                        SELECT
+                            COALESCE(MAX(eth_tx_id), 0)
+                        FROM
+                            mintlayer_anchor_operations
+                    ),
+                    (
+                        SELECT
+                            COALESCE(MAX(eth_tx_id), 0)
+                        FROM
+                            mintlayer_anchoring_start
+                    )
+                )
+            GROUP BY
+                eth_txs.id
+            ORDER BY
+                eth_txs.id
+            LIMIT
+                $1
+            "#,
+            limit as i64,
+        )
+        .instrument("get_mintlayer_operations_to_upload")
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| MintlayerOperationToUpload {
+                eth_tx_id: row.id as u32,
+                action_type: row.tx_type.parse().expect("Wrong agg type"),
+                l1_batch_range: L1BatchNumber(row.from_l1_batch_number as u32)
+                    ..=L1BatchNumber(row.to_l1_batch_number as u32),
+            })
+            .collect())
+    }
+
+    /// Returns L1 transactions saved by the eth sender that cover at least one L1 batch in the specified range,
+    /// but are not present in the anchoring queue (e.g., because they were saved before anchoring was enabled).
+    /// The returned L1 batch ranges are not truncated to the specified range.
+    pub async fn get_operations_to_backfill(
+        &mut self,
+        l1_batch_range: ops::RangeInclusive<L1BatchNumber>,
+    ) -> DalResult<Vec<MintlayerOperationToUpload>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                eth_txs.id,
+                eth_txs.tx_type,
+                MIN(l1_batches.number) AS "from_l1_batch_number!",
+                MAX(l1_batches.number) AS "to_l1_batch_number!"
+            FROM
+                eth_txs
+                JOIN l1_batches ON eth_txs.id IN (
+                    l1_batches.eth_commit_tx_id,
+                    l1_batches.eth_prove_tx_id,
+                    l1_batches.eth_execute_tx_id
+                )
+            WHERE
+                eth_txs.id IN (
+                    SELECT
+                        UNNEST(
+                            ARRAY[
+                                eth_commit_tx_id,
+                                eth_prove_tx_id,
+                                eth_execute_tx_id
+                            ]
+                        )
+                    FROM
+                        l1_batches
+                    WHERE
+                        number BETWEEN $1 AND $2
+                )
+                AND NOT EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        mintlayer_anchor_operations
+                    WHERE
+                        mintlayer_anchor_operations.eth_tx_id = eth_txs.id
+                )
+            GROUP BY
+                eth_txs.id
+            ORDER BY
+                eth_txs.id
+            "#,
+            i64::from(l1_batch_range.start().0),
+            i64::from(l1_batch_range.end().0),
+        )
+        .instrument("get_mintlayer_operations_to_backfill")
+        .with_arg("l1_batch_range", &l1_batch_range)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| MintlayerOperationToUpload {
+                eth_tx_id: row.id as u32,
+                action_type: row.tx_type.parse().expect("Wrong agg type"),
+                l1_batch_range: L1BatchNumber(row.from_l1_batch_number as u32)
+                    ..=L1BatchNumber(row.to_l1_batch_number as u32),
+            })
+            .collect())
+    }
+
+    /// Adds an aggregated operation uploaded to IPFS to the anchoring queue. If an operation for
+    /// the same `eth_tx_id` is already present, this is a no-op.
+    pub async fn insert_operation(
+        &mut self,
+        eth_tx_id: u32,
+        action_type: AggregatedActionType,
+        l1_batch_range: ops::RangeInclusive<L1BatchNumber>,
+        cid: &str,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                mintlayer_anchor_operations (
+                    eth_tx_id,
+                    op_type,
+                    from_l1_batch_number,
+                    to_l1_batch_number,
+                    cid,
+                    created_at,
+                    updated_at
+                )
+            VALUES
+                ($1, $2, $3, $4, $5, NOW(), NOW())
+            ON CONFLICT (eth_tx_id) DO NOTHING
+            "#,
+            eth_tx_id as i32,
+            action_type.to_string(),
+            i64::from(l1_batch_range.start().0),
+            i64::from(l1_batch_range.end().0),
+            cid,
+        )
+        .instrument("insert_mintlayer_anchor_operation")
+        .with_arg("eth_tx_id", &eth_tx_id)
+        .with_arg("l1_batch_range", &l1_batch_range)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns up to `limit` queued operations that are not covered by any anchor yet,
+    /// in the order they were added.
+    pub async fn get_pending_operations(
+        &mut self,
+        limit: usize,
+    ) -> DalResult<Vec<MintlayerAnchorOperation>> {
+        let operations = sqlx::query_as!(
+            StorageMintlayerAnchorOperation,
+            r#"
+            SELECT
+                id,
+                eth_tx_id,
+                op_type,
+                from_l1_batch_number,
+                to_l1_batch_number,
+                cid,
+                anchor_id,
+                created_at
+            FROM
+                mintlayer_anchor_operations
+            WHERE
+                anchor_id IS NULL
+            ORDER BY
+                id
+            LIMIT
+                $1
+            "#,
+            limit as i64,
+        )
+        .instrument("get_pending_mintlayer_anchor_operations")
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+        Ok(operations.into_iter().map(Into::into).collect())
+    }
+
+    /// Returns the number of operations in the anchoring queue.
+    pub async fn get_pending_operations_count(&mut self) -> DalResult<usize> {
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                mintlayer_anchor_operations
+            WHERE
+                anchor_id IS NULL
+            "#
+        )
+        .instrument("get_pending_mintlayer_anchor_operations_count")
+        .fetch_one(self.storage)
+        .await?;
+        Ok(count as usize)
+    }
+
+    /// Returns the times when L1 batches in the specified range were sealed.
+    pub async fn get_l1_batch_seal_times(
+        &mut self,
+        l1_batch_range: ops::RangeInclusive<L1BatchNumber>,
+    ) -> DalResult<Vec<(L1BatchNumber, DateTime<Utc>)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                number,
+                created_at
+            FROM
+                l1_batches
+            WHERE
+                number BETWEEN $1 AND $2
+            ORDER BY
+                number
+            "#,
+            i64::from(l1_batch_range.start().0),
+            i64::from(l1_batch_range.end().0),
+        )
+        .instrument("get_l1_batch_seal_times")
+        .with_arg("l1_batch_range", &l1_batch_range)
+        .fetch_all(self.storage)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| (L1BatchNumber(row.number as u32), row.created_at.and_utc()))
+            .collect())
+    }
+
+    /// Saves an anchor for the provided root CID and Merkle root, and assigns it to the specified queued operations.
+    /// Returns the ID of the created anchor.
+    pub async fn insert_anchor(
+        &mut self,
+        root_cid: &str,
+        merkle_root: H256,
+        operation_ids: &[u32],
+    ) -> DalResult<u32> {
+        let operation_ids: Vec<_> = operation_ids.iter().map(|&id| id as i32).collect();
+        let mut transaction = self.storage.start_transaction().await?;
+
+        let anchor_id = sqlx::query!(
+            r#"
+            INSERT INTO
+                mintlayer_anchors (root_cid, merkle_root, created_at, updated_at)
+            VALUES
+                ($1, $2, NOW(), NOW())
+            RETURNING
+                id
+            "#,
+            root_cid,
+            merkle_root.as_bytes(),
+        )
+        .instrument("insert_mintlayer_anchor")
+        .with_arg("root_cid", &root_cid)
+        .with_arg("merkle_root", &merkle_root)
+        .fetch_one(&mut transaction)
+        .await?
+        .id;
+
+        let instrumentation = Instrumented::new("assign_mintlayer_anchor_operations")
+            .with_arg("anchor_id", &anchor_id)
+            .with_arg("operation_ids", &operation_ids);
+        let query = sqlx::query!(
+            r#"
+            UPDATE mintlayer_anchor_operations
+            SET
+                anchor_id = $1,
+                updated_at = NOW()
+            WHERE
+                id = ANY ($2)
+                AND anchor_id IS NULL
+            "#,
+            anchor_id,
+            &operation_ids,
+        );
+        let result = instrumentation
+            .clone()
+            .with(query)
+            .execute(&mut transaction)
+            .await?;
+
+        if result.rows_affected() != operation_ids.len() as u64 {
+            let err = instrumentation.constraint_error(anyhow::anyhow!(
+                "Some of the operations are already anchored or do not exist"
+            ));
+            return Err(err);
+        }
+        transaction.commit().await?;
+        Ok(anchor_id as u32)
+    }
+
+    /// Records the Mintlayer transaction that deposited the anchor root.
+    pub async fn set_anchor_tx_id(
+        &mut self,
+        anchor_id: u32,
+        mintlayer_tx_id: &str,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE mintlayer_anchors
+            SET
+                mintlayer_tx_id = $1,
+                updated_at = NOW()
+            WHERE
+                id = $2
+            "#,
+            mintlayer_tx_id,
+            anchor_id as i32,
+        )
+        .instrument("set_mintlayer_anchor_tx_id")
+        .with_arg("anchor_id", &anchor_id)
+        .with_arg("mintlayer_tx_id", &mintlayer_tx_id)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns non-revoked anchors whose root was saved, but not deposited on Mintlayer yet
+    /// (e.g., because the server was restarted in between), in the order they were created.
+    pub async fn get_anchors_without_tx(&mut self) -> DalResult<Vec<MintlayerAnchor>> {
+        let anchors = sqlx::query_as!(
+            StorageMintlayerAnchor,
+            r#"
+            SELECT
+                id,
+                root_cid,
+                merkle_root,
+                mintlayer_tx_id,
+                included_at_block,
+                confirmed_at,
+                resubmissions,
+                revoked_at,
+                revocation_tx_id,
+                created_at
+            FROM
+                mintlayer_anchors
+            WHERE
+                mintlayer_tx_id IS NULL
+                AND revoked_at IS NULL
+            ORDER BY
+                id
+            "#,
+        )
+        .instrument("get_mintlayer_anchors_without_tx")
+        .fetch_all(self.storage)
+        .await?;
+        Ok(anchors.into_iter().map(Into::into).collect())
+    }
+
+    /// Returns deposited non-revoked anchors that are not confirmed on Mintlayer yet, in the order they were created.
+    pub async fn get_unconfirmed_anchors(&mut self) -> DalResult<Vec<MintlayerAnchor>> {
+        let anchors = sqlx::query_as!(
+            StorageMintlayerAnchor,
+            r#"
+            SELECT
+                id,
+                root_cid,
+                merkle_root,
+                mintlayer_tx_id,
+                included_at_block,
+                confirmed_at,
+                resubmissions,
+                revoked_at,
+                revocation_tx_id,
+                created_at
+            FROM
+                mintlayer_anchors
+            WHERE
+                mintlayer_tx_id IS NOT NULL
+                AND confirmed_at IS NULL
+                AND revoked_at IS NULL
+            ORDER BY
+                id
+            "#,
+        )
+        .instrument("get_unconfirmed_mintlayer_anchors")
+        .fetch_all(self.storage)
+        .await?;
+        Ok(anchors.into_iter().map(Into::into).collect())
+    }
+
+    /// Records the height of the Mintlayer block including the anchor transaction. `None` means that
+    /// the transaction is not included (e.g., because the including block was reorged).
+    pub async fn set_anchor_inclusion(
+        &mut self,
+        anchor_id: u32,
+        included_at_block: Option<u64>,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE mintlayer_anchors
+            SET
+                included_at_block = $1,
+                updated_at = NOW()
+            WHERE
+                id = $2
+            "#,
+            included_at_block.map(|block| block as i64),
+            anchor_id as i32,
+        )
+        .instrument("set_mintlayer_anchor_inclusion")
+        .with_arg("anchor_id", &anchor_id)
+        .with_arg("included_at_block", &included_at_block)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Marks the anchor as confirmed on Mintlayer.
+    pub async fn confirm_anchor(
+        &mut self,
+        anchor_id: u32,
+        included_at_block: u64,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE mintlayer_anchors
+            SET
+                included_at_block = $1,
+                confirmed_at = NOW(),
+                updated_at = NOW()
+            WHERE
+                id = $2
+            "#,
+            included_at_block as i64,
+            anchor_id as i32,
+        )
+        .instrument("confirm_mintlayer_anchor")
+        .with_arg("anchor_id", &anchor_id)
+        .with_arg("included_at_block", &included_at_block)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Replaces the dropped anchor transaction with a new one depositing the same root.
+    pub async fn record_anchor_resubmission(
+        &mut self,
+        anchor_id: u32,
+        mintlayer_tx_id: &str,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE mintlayer_anchors
+            SET
+                mintlayer_tx_id = $1,
+                included_at_block = NULL,
+                resubmissions = resubmissions + 1,
+                updated_at = NOW()
+            WHERE
+                id = $2
+            "#,
+            mintlayer_tx_id,
+            anchor_id as i32,
+        )
+        .instrument("record_mintlayer_anchor_resubmission")
+        .with_arg("anchor_id", &anchor_id)
+        .with_arg("mintlayer_tx_id", &mintlayer_tx_id)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the anchor with the specified ID.
+    pub async fn get_anchor(&mut self, anchor_id: u32) -> DalResult<Option<MintlayerAnchor>> {
+        let anchor = sqlx::query_as!(
+            StorageMintlayerAnchor,
+            r#"
+            SELECT
+                id,
+                root_cid,
+                merkle_root,
+                mintlayer_tx_id,
+                included_at_block,
+                confirmed_at,
+                resubmissions,
+                revoked_at,
+                revocation_tx_id,
+                created_at
+            FROM
+                mintlayer_anchors
+            WHERE
+                id = $1
+            "#,
+            anchor_id as i32,
+        )
+        .instrument("get_mintlayer_anchor")
+        .with_arg("anchor_id", &anchor_id)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(anchor.map(Into::into))
+    }
+
+    /// Returns all operations covered by the specified anchor in the order they were added, which is
+    /// the order of leaves in the anchor commitment.
+    pub async fn get_anchor_operations(
+        &mut self,
+        anchor_id: u32,
+    ) -> DalResult<Vec<MintlayerAnchorOperation>> {
+        let operations = sqlx::query_as!(
+            StorageMintlayerAnchorOperation,
+            r#"
+            SELECT
+                id,
+                eth_tx_id,
+                op_type,
+                from_l1_batch_number,
+                to_l1_batch_number,
+                cid,
+                anchor_id,
+                created_at
+            FROM
+                mintlayer_anchor_operations
+            WHERE
+                anchor_id = $1
+            ORDER BY
+                id
+            "#,
+            anchor_id as i32,
+        )
+        .instrument("get_mintlayer_anchor_operations")
+        .with_arg("anchor_id", &anchor_id)
+        .fetch_all(self.storage)
+        .await?;
+        Ok(operations.into_iter().map(Into::into).collect())
+    }
+
+    /// Returns all queued or anchored operations covering the specified L1 batch in the order they were added.
+    pub async fn get_l1_batch_operations(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Vec<MintlayerAnchorOperation>> {
+        let operations = sqlx::query_as!(
+            StorageMintlayerAnchorOperation,
+            r#"
+            SELECT
+                id,
+                eth_tx_id,
+                op_type,
+                from_l1_batch_number,
+                to_l1_batch_number,
+                cid,
+                anchor_id,
+                created_at
+            FROM
+                mintlayer_anchor_operations
+            WHERE
+                to_l1_batch_number >= $1
+                AND from_l1_batch_number <= $1
+            ORDER BY
+                id
+            "#,
+            i64::from(l1_batch_number.0),
+        )
+        .instrument("get_mintlayer_l1_batch_operations")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_all(self.storage)
+        .await?;
+        Ok(operations.into_iter().map(Into::into).collect())
+    }
+
+    /// Rolls back the anchoring state after L1 batches following `last_l1_batch_to_keep` were removed:
+    ///
+    /// - Queued operations covering removed L1 batches are deleted.
+    /// - Anchors covering removed L1 batches are revoked.
+    /// - Operations of revoked anchors that cover retained L1 batches only are queued again, so that they
+    ///   are covered by a new anchor. The original operations stay assigned to the revoked anchor.
+    ///
+    /// Returns IDs of the revoked anchors.
+    pub async fn revoke_anchors_after(
+        &mut self,
+        last_l1_batch_to_keep: L1BatchNumber,
+    ) -> DalResult<Vec<u32>> {
+        let mut transaction = self.storage.start_transaction().await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM mintlayer_anchor_operations
+            WHERE
+                anchor_id IS NULL
+                AND to_l1_batch_number > $1
+            "#,
+            i64::from(last_l1_batch_to_keep.0),
+        )
+        .instrument("delete_queued_mintlayer_anchor_operations")
+        .with_arg("last_l1_batch_to_keep", &last_l1_batch_to_keep)
+        .execute(&mut transaction)
+        .await?;
+
+        let anchor_ids: Vec<_> = sqlx::query_scalar!(
+            r#"
+            UPDATE mintlayer_anchors
+            SET
+                revoked_at = NOW(),
+                updated_at = NOW()
+            WHERE
+                revoked_at IS NULL
+                AND id IN (
+                    SELECT
+                        anchor_id
+                    FROM
+                        mintlayer_anchor_operations
+                    WHERE
+                        to_l1_batch_number > $1
+                )
+            RETURNING
+                id
+            "#,
+            i64::from(last_l1_batch_to_keep.0),
+        )
+        .instrument("revoke_mintlayer_anchors")
+        .with_arg("last_l1_batch_to_keep", &last_l1_batch_to_keep)
+        .fetch_all(&mut transaction)
+        .await?;
+
+        let retained_operations = sqlx::query_as!(
+            StorageMintlayerAnchorOperation,
+            r#"
+            SELECT
+                id,
+                eth_tx_id,
+                op_type,
+                from_l1_batch_number,
+                to_l1_batch_number,
+                cid,
+                anchor_id,
+                created_at
+            FROM
+                mintlayer_anchor_operations
+            WHERE
+                anchor_id = ANY ($1)
+                AND to_l1_batch_number <= $2
+            ORDER BY
+                id
+            "#,
+            &anchor_ids,
+            i64::from(last_l1_batch_to_keep.0),
+        )
+        .instrument("get_retained_mintlayer_anchor_operations")
+        .with_arg("anchor_ids", &anchor_ids)
+        .with_arg("last_l1_batch_to_keep", &last_l1_batch_to_keep)
+        .fetch_all(&mut transaction)
+        .await?;
+
+        // The queued copy takes over `eth_tx_id`, so that the operation is not uploaded again.
+        for operation in retained_operations {
+            sqlx::query!(
+                r#"
+                UPDATE mintlayer_anchor_operations
+                SET
+                    eth_tx_id = NULL,
+                    updated_at = NOW()
+                WHERE
+                    id = $1
+                "#,
+                operation.id,
+            )
+            .instrument("detach_revoked_mintlayer_anchor_operation")
+            .with_arg("id", &operation.id)
+            .execute(&mut transaction)
+            .await?;
+
+            sqlx::query!(
+                r#"
+                INSERT INTO
+                    mintlayer_anchor_operations (
+                        eth_tx_id,
+                        op_type,
+                        from_l1_batch_number,
+                        to_l1_batch_number,
+                        cid,
+                        created_at,
+                        updated_at
+                    )
+                VALUES
+                    ($1, $2, $3, $4, $5, NOW(), NOW())
+                "#,
+                operation.eth_tx_id,
+                operation.op_type,
+                operation.from_l1_batch_number,
+                operation.to_l1_batch_number,
+                operation.cid,
+            )
+            .instrument("requeue_mintlayer_anchor_operation")
+            .with_arg("id", &operation.id)
+            .execute(&mut transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+        Ok(anchor_ids.into_iter().map(|id| id as u32).collect())
+    }
+
+    /// Returns revoked anchors that were deposited on Mintlayer, but have no published revocation record,
+    /// in the order they were created.
+    pub async fn get_anchors_without_revocation_tx(&mut self) -> DalResult<Vec<MintlayerAnchor>> {
+        let anchors = sqlx::query_as!(
+            StorageMintlayerAnchor,
+            r#"
+            SELECT
+                id,
+                root_cid,
+                merkle_root,
+                mintlayer_tx_id,
+                included_at_block,
+                confirmed_at,
+                resubmissions,
+                revoked_at,
+                revocation_tx_id,
+                created_at
+            FROM
+                mintlayer_anchors
+            WHERE
+                revoked_at IS NOT NULL
+                AND mintlayer_tx_id IS NOT NULL
+                AND revocation_tx_id IS NULL
+            ORDER BY
+                id
+            "#,
+        )
+        .instrument("get_mintlayer_anchors_without_revocation_tx")
+        .fetch_all(self.storage)
+        .await?;
+        Ok(anchors.into_iter().map(Into::into).collect())
+    }
+
+    /// Records the Mintlayer transaction that deposited the revocation record for the anchor.
+    pub async fn set_anchor_revocation_tx_id(
+        &mut self,
+        anchor_id: u32,
+        revocation_tx_id: &str,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE mintlayer_anchors
+            SET
+                revocation_tx_id = $1,
+                updated_at = NOW()
+            WHERE
+                id = $2
+            "#,
+            revocation_tx_id,
+            anchor_id as i32,
+        )
+        .instrument("set_mintlayer_anchor_revocation_tx_id")
+        .with_arg("anchor_id", &anchor_id)
+        .with_arg("revocation_tx_id", &revocation_tx_id)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{Address, ProtocolVersion};
+
+    use super::*;
+    use crate::{tests::create_l1_batch_header, ConnectionPool, CoreDal};
+
+    async fn save_eth_tx(conn: &mut Connection<'_, Core>, tx_type: AggregatedActionType) -> u32 {
+        conn.eth_sender_dal()
+            .save_eth_tx(0, vec![], tx_type, Address::default(), 0, None, None)
+            .await
+            .unwrap()
+            .id
+    }
+
+    #[tokio::test]
+    async fn anchoring_queue_lifecycle() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+
+        let commit_tx_id = save_eth_tx(&mut conn, AggregatedActionType::Commit).await;
+        let prove_tx_id = save_eth_tx(&mut conn, AggregatedActionType::PublishProofOnchain).await;
+        let mut dal = conn.mintlayer_anchor_dal();
+        dal.insert_operation(
+            commit_tx_id,
+            AggregatedActionType::Commit,
+            L1BatchNumber(1)..=L1BatchNumber(2),
+            "commit_cid",
+        )
+        .await
+        .unwrap();
+        dal.insert_operation(
+            prove_tx_id,
+            AggregatedActionType::PublishProofOnchain,
+            L1BatchNumber(1)..=L1BatchNumber(2),
+            "prove_cid",
+        )
+        .await
+        .unwrap();
+        // Repeated insertion for the same `eth_tx_id` must be ignored.
+        dal.insert_operation(
+            commit_tx_id,
+            AggregatedActionType::Commit,
+            L1BatchNumber(1)..=L1BatchNumber(2),
+            "other_cid",
+        )
+        .await
+        .unwrap();
+
+        let pending = dal.get_pending_operations(10).await.unwrap();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].eth_tx_id, Some(commit_tx_id));
+        assert_eq!(pending[0].action_type, AggregatedActionType::Commit);
+        assert_eq!(pending[0].cid, "commit_cid");
+        assert_eq!(
+            pending[1].l1_batch_range,
+            L1BatchNumber(1)..=L1BatchNumber(2)
+        );
+        assert_eq!(dal.get_pending_operations(1).await.unwrap().len(), 1);
+        assert_eq!(dal.get_pending_operations_count().await.unwrap(), 2);
+
+        let anchor_id = dal
+            .insert_anchor("root_cid", H256::repeat_byte(1), &[pending[0].id])
+            .await
+            .unwrap();
+        let pending = dal.get_pending_operations(10).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].cid, "prove_cid");
+        assert_eq!(dal.get_pending_operations_count().await.unwrap(), 1);
+
+        let anchors = dal.get_anchors_without_tx().await.unwrap();
+        assert_eq!(anchors.len(), 1);
+        assert_eq!(anchors[0].id, anchor_id);
+        assert_eq!(anchors[0].root_cid, "root_cid");
+        assert_eq!(anchors[0].merkle_root, Some(H256::repeat_byte(1)));
+
+        dal.set_anchor_tx_id(anchor_id, "ml_tx_id").await.unwrap();
+        assert!(dal.get_anchors_without_tx().await.unwrap().is_empty());
+
+        let anchors = dal.get_unconfirmed_anchors().await.unwrap();
+        assert_eq!(anchors.len(), 1);
+        assert_eq!(anchors[0].mintlayer_tx_id.as_deref(), Some("ml_tx_id"));
+        assert_eq!(anchors[0].included_at_block, None);
+
+        dal.set_anchor_inclusion(anchor_id, Some(10)).await.unwrap();
+        dal.record_anchor_resubmission(anchor_id, "new_ml_tx_id")
+            .await
+            .unwrap();
+        let anchors = dal.get_unconfirmed_anchors().await.unwrap();
+        assert_eq!(anchors[0].mintlayer_tx_id.as_deref(), Some("new_ml_tx_id"));
+        assert_eq!(anchors[0].included_at_block, None);
+        assert_eq!(anchors[0].resubmissions, 1);
+
+        dal.confirm_anchor(anchor_id, 12).await.unwrap();
+        assert!(dal.get_unconfirmed_anchors().await.unwrap().is_empty());
+
+        let anchor = dal.get_anchor(anchor_id).await.unwrap().unwrap();
+        assert_eq!(anchor.included_at_block, Some(12));
+        assert!(anchor.confirmed_at.is_some());
+        let anchor_operations = dal.get_anchor_operations(anchor_id).await.unwrap();
+        assert_eq!(anchor_operations.len(), 1);
+        assert_eq!(anchor_operations[0].cid, "commit_cid");
+
+        let batch_operations = dal.get_l1_batch_operations(L1BatchNumber(2)).await.unwrap();
+        let cids: Vec<_> = batch_operations.iter().map(|op| op.cid.as_str()).collect();
+        assert_eq!(cids, ["commit_cid", "prove_cid"]);
+        assert!(dal
+            .get_l1_batch_operations(L1BatchNumber(3))
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn getting_operations_to_backfill() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        for number in 1..=4 {
+            conn.blocks_dal()
+                .insert_mock_l1_batch(&create_l1_batch_header(number))
+                .await
+                .unwrap();
+        }
+
+        let anchored_commit_tx_id = save_eth_tx(&mut conn, AggregatedActionType::Commit).await;
+        let commit_tx_id = save_eth_tx(&mut conn, AggregatedActionType::Commit).await;
+        let prove_tx_id = save_eth_tx(&mut conn, AggregatedActionType::PublishProofOnchain).await;
+        let ranges_and_txs = [
+            (1..=2, anchored_commit_tx_id, AggregatedActionType::Commit),
+            (3..=4, commit_tx_id, AggregatedActionType::Commit),
+            (
+                1..=1,
+                prove_tx_id,
+                AggregatedActionType::PublishProofOnchain,
+            ),
+        ];
+        for (range, tx_id, action_type) in ranges_and_txs {
+            let range = L1BatchNumber(*range.start())..=L1BatchNumber(*range.end());
+            conn.blocks_dal()
+                .set_eth_tx_id(range, tx_id, action_type)
+                .await
+                .unwrap();
+        }
+        conn.mintlayer_anchor_dal()
+            .insert_operation(
+                anchored_commit_tx_id,
+                AggregatedActionType::Commit,
+                L1BatchNumber(1)..=L1BatchNumber(2),
+                "commit_cid",
+            )
+            .await
+            .unwrap();
+
+        let operations = conn
+            .mintlayer_anchor_dal()
+            .get_operations_to_backfill(L1BatchNumber(2)..=L1BatchNumber(3))
+            .await
+            .unwrap();
+        assert_eq!(operations.len(), 1);
+        assert_eq!(operations[0].eth_tx_id, commit_tx_id);
+        assert_eq!(operations[0].action_type, AggregatedActionType::Commit);
+        assert_eq!(
+            operations[0].l1_batch_range,
+            L1BatchNumber(3)..=L1BatchNumber(4)
+        );
+
+        let operations = conn
+            .mintlayer_anchor_dal()
+            .get_operations_to_backfill(L1BatchNumber(1)..=L1BatchNumber(4))
+            .await
+            .unwrap();
+        let tx_ids: Vec<_> = operations.iter().map(|op| op.eth_tx_id).collect();
+        assert_eq!(tx_ids, [commit_tx_id, prove_tx_id]);
+    }
+
+    /// Creates L1 batches #1..=#4 committed by two L1 transactions, and returns IDs of these transactions.
+    async fn commit_l1_batches(conn: &mut Connection<'_, Core>) -> [u32; 2] {
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        for number in 1..=4 {
+            conn.blocks_dal()
+                .insert_mock_l1_batch(&create_l1_batch_header(number))
+                .await
+                .unwrap();
+        }
+        let first_tx_id = save_eth_tx(conn, AggregatedActionType::Commit).await;
+        let second_tx_id = save_eth_tx(conn, AggregatedActionType::Commit).await;
+        for (range, tx_id) in [(1..=2, first_tx_id), (3..=4, second_tx_id)] {
+            let range = L1BatchNumber(*range.start())..=L1BatchNumber(*range.end());
+            conn.blocks_dal()
+                .set_eth_tx_id(range, tx_id, AggregatedActionType::Commit)
+                .await
+                .unwrap();
+        }
+        [first_tx_id, second_tx_id]
+    }
+
+    async fn operations_to_upload(conn: &mut Connection<'_, Core>) -> Vec<u32> {
+        let operations = conn
+            .mintlayer_anchor_dal()
+            .get_operations_to_upload(10)
+            .await
+            .unwrap();
+        operations.iter().map(|op| op.eth_tx_id).collect()
+    }
+
+    #[tokio::test]
+    async fn initializing_anchoring_start() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let [_, second_tx_id] = commit_l1_batches(&mut conn).await;
+
+        // By default, anchoring starts after the existing L1 transactions.
+        let start = conn
+            .mintlayer_anchor_dal()
+            .init_anchoring_start(None)
+            .await
+            .unwrap();
+        assert_eq!(start, second_tx_id);
+        assert!(operations_to_upload(&mut conn).await.is_empty());
+        // The start must not change once initialized.
+        let start = conn
+            .mintlayer_anchor_dal()
+            .init_anchoring_start(Some(L1BatchNumber(1)))
+            .await
+            .unwrap();
+        assert_eq!(start, second_tx_id);
+
+        let prove_tx_id = save_eth_tx(&mut conn, AggregatedActionType::PublishProofOnchain).await;
+        conn.blocks_dal()
+            .set_eth_tx_id(
+                L1BatchNumber(1)..=L1BatchNumber(4),
+                prove_tx_id,
+                AggregatedActionType::PublishProofOnchain,
+            )
+            .await
+            .unwrap();
+        assert_eq!(operations_to_upload(&mut conn).await, [prove_tx_id]);
+
+        // Check anchoring from the configured L1 batch.
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let [first_tx_id, second_tx_id] = commit_l1_batches(&mut conn).await;
+        let start = conn
+            .mintlayer_anchor_dal()
+            .init_anchoring_start(Some(L1BatchNumber(3)))
+            .await
+            .unwrap();
+        assert_eq!(start, first_tx_id);
+        assert_eq!(operations_to_upload(&mut conn).await, [second_tx_id]);
+
+        // Check that anchoring continues after the existing anchoring queue.
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let [first_tx_id, second_tx_id] = commit_l1_batches(&mut conn).await;
+        conn.mintlayer_anchor_dal()
+            .insert_operation(
+                first_tx_id,
+                AggregatedActionType::Commit,
+                L1BatchNumber(1)..=L1BatchNumber(2),
+                "commit_cid",
+            )
+            .await
+            .unwrap();
+        let start = conn
+            .mintlayer_anchor_dal()
+            .init_anchoring_start(None)
+            .await
+            .unwrap();
+        assert_eq!(start, first_tx_id);
+        assert_eq!(operations_to_upload(&mut conn).await, [second_tx_id]);
+    }
+
+    #[tokio::test]
+    async fn revoking_anchors() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+
+        let operations = [
+            (AggregatedActionType::Commit, 1..=2, "commit_cid"),
+            (AggregatedActionType::Commit, 3..=3, "reverted_commit_cid"),
+            (
+                AggregatedActionType::PublishProofOnchain,
+                1..=1,
+                "prove_cid",
+            ),
+            (AggregatedActionType::Commit, 4..=4, "queued_commit_cid"),
+        ];
+        let mut eth_tx_ids = vec![];
+        for (action_type, range, cid) in operations {
+            let eth_tx_id = save_eth_tx(&mut conn, action_type).await;
+            let range = L1BatchNumber(*range.start())..=L1BatchNumber(*range.end());
+            conn.mintlayer_anchor_dal()
+                .insert_operation(eth_tx_id, action_type, range, cid)
+                .await
+                .unwrap();
+            eth_tx_ids.push(eth_tx_id);
+        }
+
+        let mut dal = conn.mintlayer_anchor_dal();
+        let pending = dal.get_pending_operations(10).await.unwrap();
+        let revoked_anchor_id = dal
+            .insert_anchor(
+                "root_cid",
+                H256::repeat_byte(1),
+                &[pending[0].id, pending[1].id],
+            )
+            .await
+            .unwrap();
+        dal.set_anchor_tx_id(revoked_anchor_id, "ml_tx_id")
+            .await
+            .unwrap();
+        let retained_anchor_id = dal
+            .insert_anchor("other_root_cid", H256::repeat_byte(2), &[pending[2].id])
+            .await
+            .unwrap();
+
+        let revoked = dal.revoke_anchors_after(L1BatchNumber(2)).await.unwrap();
+        assert_eq!(revoked, [revoked_anchor_id]);
+
+        // The queued operation for a reverted batch is purged, and the retained operation of the revoked anchor
+        // is queued again.
+        let pending = dal.get_pending_operations(10).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].cid, "commit_cid");
+        assert_eq!(pending[0].eth_tx_id, Some(eth_tx_ids[0]));
+        let anchor_operations = dal.get_anchor_operations(revoked_anchor_id).await.unwrap();
+        assert_eq!(anchor_operations.len(), 2);
+        assert_eq!(anchor_operations[0].eth_tx_id, None);
+
+        let anchor = dal.get_anchor(revoked_anchor_id).await.unwrap().unwrap();
+        assert!(anchor.revoked_at.is_some());
+        let anchor = dal.get_anchor(retained_anchor_id).await.unwrap().unwrap();
+        assert!(anchor.revoked_at.is_none());
+        assert!(dal.get_unconfirmed_anchors().await.unwrap().is_empty());
+        let anchors = dal.get_anchors_without_tx().await.unwrap();
+        assert_eq!(anchors.len(), 1);
+        assert_eq!(anchors[0].id, retained_anchor_id);
+
+        let anchors = dal.get_anchors_without_revocation_tx().await.unwrap();
+        assert_eq!(anchors.len(), 1);
+        assert_eq!(anchors[0].id, revoked_anchor_id);
+        dal.set_anchor_revocation_tx_id(revoked_anchor_id, "revocation_tx_id")
+            .await
+            .unwrap();
+        assert!(dal
+            .get_anchors_without_revocation_tx()
+            .await
+            .unwrap()
+            .is_empty());
+        let anchor = dal.get_anchor(revoked_anchor_id).await.unwrap().unwrap();
+        assert_eq!(anchor.revocation_tx_id.as_deref(), Some("revocation_tx_id"));
+
+        // Repeated revocation is a no-op.
+        let revoked = dal.revoke_anchors_after(L1BatchNumber(2)).await.unwrap();
+        assert!(revoked.is_empty());
+        assert_eq!(dal.get_pending_operations(10).await.unwrap().len(), 1);
+    }
+}
@@ -8,6 +8,7 @@ pub(crate) mod storage_data_availability;
 pub mod storage_eth_tx;
 pub mod storage_event;
 pub mod storage_log;
+pub(crate) mod storage_mintlayer_anchor;
 pub mod storage_oracle_info;
 pub mod storage_protocol_version;
 pub mod storage_sync;
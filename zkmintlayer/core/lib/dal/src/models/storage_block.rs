@@ -400,6 +400,7 @@ impl From<StorageL1BatchDetails> for api::L1BatchDetails {
         api::L1BatchDetails {
             base,
             number: L1BatchNumber(details.number as u32),
+            mintlayer_anchor: None,
         }
     }
 }
@@ -0,0 +1,68 @@
+use std::str::FromStr;
+
+use chrono::NaiveDateTime;
+use zksync_types::{
+    aggregated_operations::AggregatedActionType,
+    mintlayer::{MintlayerAnchor, MintlayerAnchorOperation},
+    L1BatchNumber, H256,
+};
+
+/// Aggregated operation queued for (or covered by) a Mintlayer anchor.
+#[derive(Debug, Clone)]
+pub(crate) struct StorageMintlayerAnchorOperation {
+    pub id: i32,
+    pub eth_tx_id: Option<i32>,
+    pub op_type: String,
+    pub from_l1_batch_number: i64,
+    pub to_l1_batch_number: i64,
+    pub cid: String,
+    pub anchor_id: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<StorageMintlayerAnchorOperation> for MintlayerAnchorOperation {
+    fn from(op: StorageMintlayerAnchorOperation) -> Self {
+        Self {
+            id: op.id as u32,
+            eth_tx_id: op.eth_tx_id.map(|id| id as u32),
+            action_type: AggregatedActionType::from_str(&op.op_type).expect("Wrong agg type"),
+            l1_batch_range: L1BatchNumber(op.from_l1_batch_number as u32)
+                ..=L1BatchNumber(op.to_l1_batch_number as u32),
+            cid: op.cid,
+            anchor_id: op.anchor_id.map(|id| id as u32),
+            created_at: op.created_at.and_utc(),
+        }
+    }
+}
+
+/// Root document deposited on Mintlayer.
+#[derive(Debug, Clone)]
+pub(crate) struct StorageMintlayerAnchor {
+    pub id: i32,
+    pub root_cid: String,
+    pub merkle_root: Option<Vec<u8>>,
+    pub mintlayer_tx_id: Option<String>,
+    pub included_at_block: Option<i64>,
+    pub confirmed_at: Option<NaiveDateTime>,
+    pub resubmissions: i32,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub revocation_tx_id: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<StorageMintlayerAnchor> for MintlayerAnchor {
+    fn from(anchor: StorageMintlayerAnchor) -> Self {
+        Self {
+            id: anchor.id as u32,
+            root_cid: anchor.root_cid,
+            merkle_root: anchor.merkle_root.as_deref().map(H256::from_slice),
+            mintlayer_tx_id: anchor.mintlayer_tx_id,
+            included_at_block: anchor.included_at_block.map(|block| block as u64),
+            confirmed_at: anchor.confirmed_at.map(|time| time.and_utc()),
+            resubmissions: anchor.resubmissions as u32,
+            revoked_at: anchor.revoked_at.map(|time| time.and_utc()),
+            revocation_tx_id: anchor.revocation_tx_id,
+            created_at: anchor.created_at.and_utc(),
+        }
+    }
+}
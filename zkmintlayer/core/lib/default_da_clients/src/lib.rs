@@ -1,2 +1,3 @@
+pub mod mintlayer;
 pub mod no_da;
 pub mod object_store;
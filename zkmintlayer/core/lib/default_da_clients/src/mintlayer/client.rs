@@ -0,0 +1,431 @@
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use async_trait::async_trait;
+use zksync_config::configs::MintlayerConfig;
+use zksync_da_client::{
+    types::{DAError, DispatchResponse, InclusionData},
+    DataAvailabilityClient,
+};
+use zksync_ipfs_store::{IpfsStore, IpfsStoreError};
+use zksync_mintlayer_client::{
+    DepositFeePolicy, MintlayerClientError, MintlayerInterface, TransactionInfo, TxState,
+};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType,
+    mintlayer::{MintlayerAnchorCommitment, MintlayerAnchorLeaf, MintlayerAnchorPayload},
+    L1BatchNumber, H256,
+};
+
+use crate::mintlayer::types::MintlayerInclusionData;
+
+/// An implementation of the `DataAvailabilityClient` trait that uploads the pubdata to IPFS and deposits
+/// its CID on Mintlayer.
+///
+/// Each blob is anchored by a separate Mintlayer transaction using the same format as the anchors of
+/// aggregated operations: the deposited payload contains the root of a commitment with a single commit leaf
+/// for the L1 batch, and the root document listing the leaf is uploaded to IPFS. The blob ID is the ID
+/// of the Mintlayer transaction.
+///
+/// Dropped transactions are handled similarly to anchor transactions: abandoned or conflicting transactions,
+/// and transactions that stay unknown to the wallet or inactive for the configured number of blocks are reported
+/// as dropped, so that the DA dispatcher dispatches the blob again. Inactive transactions are rebroadcast
+/// in the meantime. Each repeated dispatch of the same L1 batch bumps the fee according to the configured
+/// [`DepositFeePolicy`].
+#[derive(Clone, Debug)]
+pub struct MintlayerDAClient {
+    mintlayer_client: Arc<dyn MintlayerInterface>,
+    ipfs_store: Arc<dyn IpfsStore>,
+    confirmations: u64,
+    resubmission_grace_blocks: u64,
+    fee_policy: DepositFeePolicy,
+    /// Shared among clones, since the DA dispatcher clones the client. Not persisted, so a restart only delays
+    /// detecting dropped transactions and resets fee bumps.
+    state: Arc<Mutex<MintlayerDAState>>,
+}
+
+#[derive(Debug, Default)]
+struct MintlayerDAState {
+    /// L1 batches for dispatched blobs, keyed by the blob ID.
+    blob_batches: HashMap<String, L1BatchNumber>,
+    /// Number of times a blob for the L1 batch was reported as dropped.
+    resubmissions: HashMap<L1BatchNumber, u32>,
+    /// Best block heights at which blob transactions were first observed to be unknown or inactive, keyed by
+    /// the blob ID.
+    missing_since_block: HashMap<String, u64>,
+}
+
+impl MintlayerDAClient {
+    pub fn new(
+        mintlayer_client: Arc<dyn MintlayerInterface>,
+        ipfs_store: Arc<dyn IpfsStore>,
+        config: &MintlayerConfig,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            mintlayer_client,
+            ipfs_store,
+            confirmations: config.confirmations().into(),
+            resubmission_grace_blocks: config.resubmission_grace_blocks().into(),
+            fee_policy: DepositFeePolicy::new(config)?,
+            state: Arc::default(),
+        })
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, MintlayerDAState> {
+        self.state
+            .lock()
+            .expect("Mintlayer DA client state is poisoned")
+    }
+
+    /// Checks whether the blob transaction that is not confirmed yet is dropped. Inactive transactions
+    /// are rebroadcast.
+    async fn check_unconfirmed_tx(
+        &self,
+        blob_id: &str,
+        tx_state: Option<TxState>,
+    ) -> Result<(), DAError> {
+        let dropped = match tx_state {
+            Some(TxState::Confirmed { .. } | TxState::InMempool) => {
+                self.lock_state().missing_since_block.remove(blob_id);
+                return Ok(());
+            }
+            Some(TxState::Conflicted | TxState::Abandoned) => true,
+            None | Some(TxState::Inactive) => {
+                let best_block_height = self
+                    .mintlayer_client
+                    .wallet_best_block()
+                    .await
+                    .map_err(mintlayer_error)?
+                    .height;
+                let missing_since_block = *self
+                    .lock_state()
+                    .missing_since_block
+                    .entry(blob_id.to_owned())
+                    .or_insert(best_block_height);
+                let missing_blocks = best_block_height.saturating_sub(missing_since_block);
+                if missing_blocks < self.resubmission_grace_blocks && tx_state.is_some() {
+                    // Rebroadcasting is best-effort; if it fails, the blob is dropped after the grace period.
+                    if let Err(err) = self.mintlayer_client.transaction_rebroadcast(blob_id).await {
+                        tracing::warn!("Failed rebroadcasting Mintlayer tx {blob_id}: {err}");
+                    }
+                }
+                missing_blocks >= self.resubmission_grace_blocks
+            }
+        };
+
+        if dropped {
+            let mut state = self.lock_state();
+            state.missing_since_block.remove(blob_id);
+            if let Some(batch_number) = state.blob_batches.remove(blob_id) {
+                *state.resubmissions.entry(batch_number).or_default() += 1;
+            }
+            return Err(DAError::dropped(
+                blob_id,
+                format!("Mintlayer transaction state is {tx_state:?}"),
+            ));
+        }
+        Ok(())
+    }
+
+    fn pubdata_leaf(batch_number: L1BatchNumber, cid: String) -> MintlayerAnchorLeaf {
+        MintlayerAnchorLeaf {
+            action_type: AggregatedActionType::Commit,
+            l1_batch_range: batch_number..=batch_number,
+            cid,
+        }
+    }
+}
+
+fn ipfs_error(err: IpfsStoreError) -> DAError {
+    DAError {
+        is_transient: err.is_transient(),
+        error: anyhow::Error::from(err),
+    }
+}
+
+fn mintlayer_error(err: MintlayerClientError) -> DAError {
+    DAError {
+        is_transient: err.is_transient(),
+        error: anyhow::Error::from(err),
+    }
+}
+
+fn fatal_error(error: anyhow::Error) -> DAError {
+    DAError {
+        error,
+        is_transient: false,
+    }
+}
+
+#[async_trait]
+impl DataAvailabilityClient for MintlayerDAClient {
+    async fn dispatch_blob(
+        &self,
+        batch_number: u32,
+        data: Vec<u8>,
+    ) -> Result<DispatchResponse, DAError> {
+        let batch_number = L1BatchNumber(batch_number);
+        let cid = self
+            .ipfs_store
+            .put(&format!("l1_batch_{batch_number}_pubdata"), data)
+            .await
+            .map_err(ipfs_error)?;
+
+        let commitment =
+            MintlayerAnchorCommitment::new(vec![Self::pubdata_leaf(batch_number, cid)]);
+        let root_document =
+            serde_json::to_vec(commitment.leaves()).map_err(|err| fatal_error(err.into()))?;
+        let root_cid = self
+            .ipfs_store
+            .put(
+                &format!("l1_batch_{batch_number}_pubdata_root"),
+                root_document,
+            )
+            .await
+            .map_err(ipfs_error)?;
+
+        let payload = MintlayerAnchorPayload {
+            merkle_root: Some(commitment.merkle_root()),
+            root_cid,
+        };
+        let resubmissions = self
+            .lock_state()
+            .resubmissions
+            .get(&batch_number)
+            .copied()
+            .unwrap_or(0);
+        let options = self.fee_policy.options(resubmissions);
+        let tx = self
+            .mintlayer_client
+            .address_deposit_data(&payload.encode(), options)
+            .await
+            .map_err(mintlayer_error)?;
+        tracing::info!(
+            "Deposited pubdata CID for L1 batch #{batch_number} on Mintlayer in tx {} ({options:?})",
+            tx.tx_id
+        );
+        self.lock_state()
+            .blob_batches
+            .insert(tx.tx_id.clone(), batch_number);
+
+        Ok(DispatchResponse { blob_id: tx.tx_id })
+    }
+
+    async fn get_inclusion_data(&self, blob_id: &str) -> Result<Option<InclusionData>, DAError> {
+        let tx = self
+            .mintlayer_client
+            .transaction_get(blob_id)
+            .await
+            .map_err(mintlayer_error)?;
+        self.check_unconfirmed_tx(blob_id, tx.as_ref().map(|tx| tx.state))
+            .await?;
+        let Some(TransactionInfo {
+            state: TxState::Confirmed { block_height },
+            deposited_data,
+            ..
+        }) = tx
+        else {
+            return Ok(None);
+        };
+        let best_block = self
+            .mintlayer_client
+            .wallet_best_block()
+            .await
+            .map_err(mintlayer_error)?;
+        if best_block.height + 1 < block_height + self.confirmations {
+            return Ok(None);
+        }
+
+        let deposited_data = deposited_data.ok_or_else(|| {
+            fatal_error(anyhow::anyhow!(
+                "Mintlayer transaction {blob_id} doesn't deposit data"
+            ))
+        })?;
+        let payload = MintlayerAnchorPayload::decode(&deposited_data).map_err(fatal_error)?;
+        let merkle_root = payload.merkle_root.ok_or_else(|| {
+            fatal_error(anyhow::anyhow!(
+                "Mintlayer transaction {blob_id} deposits a legacy anchor payload"
+            ))
+        })?;
+        let root_document = self
+            .ipfs_store
+            .get(&payload.root_cid)
+            .await
+            .map_err(ipfs_error)?;
+        let leaves: Vec<MintlayerAnchorLeaf> =
+            serde_json::from_slice(&root_document).map_err(|err| fatal_error(err.into()))?;
+        let commitment = MintlayerAnchorCommitment::new(leaves);
+        if commitment.merkle_root() != merkle_root {
+            return Err(fatal_error(anyhow::anyhow!(
+                "Merkle root of root document {} differs from the one deposited in Mintlayer transaction {blob_id}",
+                payload.root_cid
+            )));
+        }
+        let (leaf, proof) = commitment
+            .leaves()
+            .iter()
+            .position(|leaf| leaf.action_type == AggregatedActionType::Commit)
+            .and_then(|index| Some((&commitment.leaves()[index], commitment.proof(index)?)))
+            .ok_or_else(|| {
+                fatal_error(anyhow::anyhow!(
+                    "root document {} doesn't contain a pubdata leaf",
+                    payload.root_cid
+                ))
+            })?;
+
+        let tx_id = H256::from_str(blob_id).map_err(|err| {
+            fatal_error(anyhow::anyhow!(
+                "invalid Mintlayer transaction ID {blob_id}: {err}"
+            ))
+        })?;
+        let inclusion_data = MintlayerInclusionData {
+            tx_id,
+            block_height,
+            merkle_root,
+            cid: leaf.cid.clone(),
+            proof,
+        };
+        // The blob is included, so its L1 batch won't be dispatched again.
+        let mut state = self.lock_state();
+        if let Some(batch_number) = state.blob_batches.remove(blob_id) {
+            state.resubmissions.remove(&batch_number);
+        }
+        Ok(Some(InclusionData {
+            data: inclusion_data.encode(),
+        }))
+    }
+
+    fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
+        Box::new(self.clone())
+    }
+
+    fn blob_size_limit(&self) -> Option<usize> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_ipfs_store::MockIpfsStore;
+    use zksync_mintlayer_client::clients::MockMintlayerClient;
+
+    use super::*;
+
+    fn create_client(
+        mintlayer_client: &MockMintlayerClient,
+        ipfs_store: &MockIpfsStore,
+    ) -> MintlayerDAClient {
+        let config = MintlayerConfig {
+            confirmations: Some(2),
+            resubmission_grace_blocks: Some(3),
+            fee_in_top_x_mb: Some(4),
+            fee_bump_divisor: Some(2),
+            ..MintlayerConfig::for_tests()
+        };
+        MintlayerDAClient::new(
+            Arc::new(mintlayer_client.clone()),
+            Arc::new(ipfs_store.clone()),
+            &config,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn dispatching_blob() {
+        let mintlayer_client = MockMintlayerClient::default();
+        let ipfs_store = MockIpfsStore::default();
+        let client = create_client(&mintlayer_client, &ipfs_store);
+
+        let pubdata = vec![1, 2, 3];
+        let response = client.dispatch_blob(5, pubdata.clone()).await.unwrap();
+        let blob_id = response.blob_id;
+        assert_eq!(ipfs_store.documents().len(), 2);
+        assert_eq!(mintlayer_client.deposited_data().len(), 1);
+        assert_eq!(mintlayer_client.deposit_options()[0].in_top_x_mb, Some(4));
+
+        let inclusion_data = client.get_inclusion_data(&blob_id).await.unwrap();
+        assert!(inclusion_data.is_none());
+        mintlayer_client.confirm_tx(&blob_id, 10);
+        let inclusion_data = client.get_inclusion_data(&blob_id).await.unwrap();
+        assert!(inclusion_data.is_none());
+        mintlayer_client.set_best_block_height(11);
+        let inclusion_data = client
+            .get_inclusion_data(&blob_id)
+            .await
+            .unwrap()
+            .expect("no inclusion data");
+
+        let inclusion_data = MintlayerInclusionData::decode(&inclusion_data.data).unwrap();
+        assert_eq!(inclusion_data.tx_id, H256::from_str(&blob_id).unwrap());
+        assert_eq!(inclusion_data.block_height, 10);
+        assert_eq!(ipfs_store.get(&inclusion_data.cid).await.unwrap(), pubdata);
+        let leaf = MintlayerDAClient::pubdata_leaf(L1BatchNumber(5), inclusion_data.cid.clone());
+        assert!(inclusion_data
+            .proof
+            .verify(&leaf, inclusion_data.merkle_root));
+    }
+
+    #[tokio::test]
+    async fn unknown_transaction_is_dropped_after_grace_period() {
+        let mintlayer_client = MockMintlayerClient::default();
+        let client = create_client(&mintlayer_client, &MockIpfsStore::default());
+        let response = client.dispatch_blob(1, vec![0; 32]).await.unwrap();
+        // The wallet hasn't indexed the transaction yet.
+        mintlayer_client.drop_tx(&response.blob_id);
+
+        for height in [10, 12] {
+            mintlayer_client.set_best_block_height(height);
+            let inclusion_data = client.get_inclusion_data(&response.blob_id).await.unwrap();
+            assert!(inclusion_data.is_none());
+        }
+        mintlayer_client.set_best_block_height(13);
+        let err = client
+            .get_inclusion_data(&response.blob_id)
+            .await
+            .unwrap_err();
+        assert!(err.is_dropped(), "{err}");
+        assert!(!err.is_transient(), "{err}");
+
+        // The dispatcher dispatches the blob again; the new transaction must pay a higher fee.
+        let new_response = client.dispatch_blob(1, vec![0; 32]).await.unwrap();
+        assert_ne!(new_response.blob_id, response.blob_id);
+        let options = mintlayer_client.deposit_options();
+        assert_eq!(options.len(), 2);
+        assert_eq!(options[1].in_top_x_mb, Some(2));
+    }
+
+    #[tokio::test]
+    async fn abandoned_transaction_is_dropped() {
+        let mintlayer_client = MockMintlayerClient::default();
+        let client = create_client(&mintlayer_client, &MockIpfsStore::default());
+        let response = client.dispatch_blob(1, vec![0; 32]).await.unwrap();
+        mintlayer_client.set_tx_state(&response.blob_id, TxState::Abandoned);
+
+        let err = client
+            .get_inclusion_data(&response.blob_id)
+            .await
+            .unwrap_err();
+        assert!(err.is_dropped(), "{err}");
+    }
+
+    #[tokio::test]
+    async fn inactive_transaction_is_rebroadcast() {
+        let mintlayer_client = MockMintlayerClient::default();
+        let client = create_client(&mintlayer_client, &MockIpfsStore::default());
+        let response = client.dispatch_blob(1, vec![0; 32]).await.unwrap();
+        mintlayer_client.set_tx_state(&response.blob_id, TxState::Inactive);
+
+        let inclusion_data = client.get_inclusion_data(&response.blob_id).await.unwrap();
+        assert!(inclusion_data.is_none());
+        assert_eq!(mintlayer_client.rebroadcasts(), [response.blob_id.clone()]);
+        assert_eq!(mintlayer_client.deposited_data().len(), 1);
+
+        mintlayer_client.confirm_tx(&response.blob_id, 10);
+        mintlayer_client.set_best_block_height(11);
+        let inclusion_data = client.get_inclusion_data(&response.blob_id).await.unwrap();
+        assert!(inclusion_data.is_some());
+    }
+}
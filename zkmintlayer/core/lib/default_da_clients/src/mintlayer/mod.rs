@@ -0,0 +1,3 @@
+pub mod client;
+pub mod types;
+pub mod wiring_layer;
@@ -0,0 +1,86 @@
+use anyhow::Context as _;
+use zksync_types::{
+    ethabi::{self, ParamType, Token},
+    mintlayer::MintlayerAnchorProof,
+    H256, U256,
+};
+
+/// Inclusion data returned by [`MintlayerDAClient`](super::client::MintlayerDAClient) once the anchor
+/// transaction is confirmed.
+///
+/// The data is ABI-encoded as `(bytes32 txId, uint256 blockHeight, bytes32 merkleRoot, string cid,
+/// uint256 leafIndex, bytes32[] merklePath)`, so that it can be decoded in Solidity. The leaf is the commit
+/// leaf for the dispatched L1 batch; together with the CID and the L1 batch number, the Merkle path proves
+/// that the pubdata CID is committed to by the root deposited in the Mintlayer transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MintlayerInclusionData {
+    pub tx_id: H256,
+    pub block_height: u64,
+    pub merkle_root: H256,
+    /// IPFS CID of the pubdata.
+    pub cid: String,
+    pub proof: MintlayerAnchorProof,
+}
+
+impl MintlayerInclusionData {
+    fn param_types() -> [ParamType; 6] {
+        [
+            ParamType::FixedBytes(32),
+            ParamType::Uint(256),
+            ParamType::FixedBytes(32),
+            ParamType::String,
+            ParamType::Uint(256),
+            ParamType::Array(Box::new(ParamType::FixedBytes(32))),
+        ]
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let merkle_path = self
+            .proof
+            .merkle_path
+            .iter()
+            .map(|hash| Token::FixedBytes(hash.0.to_vec()))
+            .collect();
+        ethabi::encode(&[
+            Token::FixedBytes(self.tx_id.0.to_vec()),
+            Token::Uint(self.block_height.into()),
+            Token::FixedBytes(self.merkle_root.0.to_vec()),
+            Token::String(self.cid.clone()),
+            Token::Uint(self.proof.leaf_index.into()),
+            Token::Array(merkle_path),
+        ])
+    }
+
+    pub fn decode(bytes: &[u8]) -> anyhow::Result<Self> {
+        // `ethabi::decode()` checks token types, so the conversions below cannot fail.
+        let mut tokens = ethabi::decode(&Self::param_types(), bytes)?.into_iter();
+        let tx_id = H256::from_slice(&tokens.next().unwrap().into_fixed_bytes().unwrap());
+        let block_height = tokens.next().unwrap().into_uint().unwrap();
+        let merkle_root = H256::from_slice(&tokens.next().unwrap().into_fixed_bytes().unwrap());
+        let cid = tokens.next().unwrap().into_string().unwrap();
+        let leaf_index = tokens.next().unwrap().into_uint().unwrap();
+        let merkle_path = tokens.next().unwrap().into_array().unwrap();
+        let merkle_path = merkle_path
+            .into_iter()
+            .map(|token| H256::from_slice(&token.into_fixed_bytes().unwrap()))
+            .collect();
+
+        Ok(Self {
+            tx_id,
+            block_height: u256_to_u64(block_height).context("block height")?,
+            merkle_root,
+            cid,
+            proof: MintlayerAnchorProof {
+                leaf_index: u256_to_u64(leaf_index)
+                    .and_then(|index| Ok(index.try_into()?))
+                    .context("leaf index")?,
+                merkle_path,
+            },
+        })
+    }
+}
+
+fn u256_to_u64(value: U256) -> anyhow::Result<u64> {
+    anyhow::ensure!(value <= U256::from(u64::MAX), "value {value} overflows u64");
+    Ok(value.as_u64())
+}
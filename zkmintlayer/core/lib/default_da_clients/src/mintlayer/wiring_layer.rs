@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use zksync_config::configs::{MintlayerConfig, MintlayerSecrets};
+use zksync_da_client::DataAvailabilityClient;
+use zksync_ipfs_store::create_ipfs_store;
+use zksync_mintlayer_client::clients::HttpMintlayerClient;
+use zksync_node_framework::{
+    implementations::resources::da_client::DAClientResource,
+    wiring_layer::{WiringError, WiringLayer},
+    IntoContext,
+};
+
+use crate::mintlayer::client::MintlayerDAClient;
+
+#[derive(Debug)]
+pub struct MintlayerDAClientWiringLayer {
+    config: MintlayerConfig,
+    secrets: MintlayerSecrets,
+}
+
+impl MintlayerDAClientWiringLayer {
+    pub fn new(config: MintlayerConfig, secrets: MintlayerSecrets) -> Self {
+        Self { config, secrets }
+    }
+}
+
+#[derive(Debug, IntoContext)]
+pub struct Output {
+    pub client: DAClientResource,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for MintlayerDAClientWiringLayer {
+    type Input = ();
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "mintlayer_da_layer"
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        let client: Box<dyn DataAvailabilityClient> = Box::new(MintlayerDAClient::new(
+            Arc::new(HttpMintlayerClient::new(&self.config, &self.secrets)?),
+            create_ipfs_store(&self.config, &self.secrets)?,
+            &self.config,
+        )?);
+
+        Ok(Output {
+            client: DAClientResource(client),
+        })
+    }
+}
@@ -81,6 +81,7 @@ mod tests {
                 initial_writes_cache_size_mb: Some(32),
                 latest_values_cache_size_mb: Some(256),
                 fee_history_limit: Some(100),
+                simulate_calls_limit: Some(500),
                 max_batch_request_size: Some(200),
                 max_response_body_size_mb: Some(10),
                 max_response_body_size_overrides_mb: [
@@ -143,6 +144,7 @@ mod tests {
             API_WEB3_JSON_RPC_INITIAL_WRITES_CACHE_SIZE_MB=32
             API_WEB3_JSON_RPC_LATEST_VALUES_CACHE_SIZE_MB=256
             API_WEB3_JSON_RPC_FEE_HISTORY_LIMIT=100
+            API_WEB3_JSON_RPC_SIMULATE_CALLS_LIMIT=500
             API_WEB3_JSON_RPC_MAX_BATCH_REQUEST_SIZE=200
             API_WEB3_JSON_RPC_WEBSOCKET_REQUESTS_PER_MINUTE_LIMIT=10
             API_WEB3_JSON_RPC_MEMPOOL_CACHE_SIZE=10000
@@ -10,7 +10,7 @@ impl FromEnv for DADispatcherConfig {
 
 #[cfg(test)]
 mod tests {
-    use zksync_config::configs::da_dispatcher::DADispatcherConfig;
+    use zksync_config::configs::da_dispatcher::{DAClient, DADispatcherConfig};
 
     use super::*;
     use crate::test_utils::EnvMutex;
@@ -21,11 +21,13 @@ mod tests {
         interval: u32,
         rows_limit: u32,
         max_retries: u16,
+        client: DAClient,
     ) -> DADispatcherConfig {
         DADispatcherConfig {
             polling_interval_ms: Some(interval),
             max_rows_to_dispatch: Some(rows_limit),
             max_retries: Some(max_retries),
+            client: Some(client),
         }
     }
 
@@ -36,9 +38,13 @@ mod tests {
             DA_DISPATCHER_POLLING_INTERVAL_MS=5000
             DA_DISPATCHER_MAX_ROWS_TO_DISPATCH=60
             DA_DISPATCHER_MAX_RETRIES=7
+            DA_DISPATCHER_CLIENT="Mintlayer"
         "#;
         lock.set_env(config);
         let actual = DADispatcherConfig::from_env().unwrap();
-        assert_eq!(actual, expected_da_layer_config(5000, 60, 7));
+        assert_eq!(
+            actual,
+            expected_da_layer_config(5000, 60, 7, DAClient::Mintlayer)
+        );
     }
 }
@@ -24,6 +24,7 @@ mod utils;
 mod base_token_adjuster;
 mod da_dispatcher;
 mod genesis;
+mod mintlayer;
 #[cfg(test)]
 mod test_utils;
 mod vm_runner;
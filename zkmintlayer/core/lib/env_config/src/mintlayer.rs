@@ -0,0 +1,102 @@
+use std::env;
+
+use zksync_config::configs::{MintlayerConfig, MintlayerSecrets};
+
+use crate::{envy_load, FromEnv};
+
+impl FromEnv for MintlayerConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("mintlayer", "MINTLAYER_")
+    }
+}
+
+impl FromEnv for MintlayerSecrets {
+    fn from_env() -> anyhow::Result<Self> {
+        Ok(Self {
+            rpc_password: env::var("MINTLAYER_RPC_PASSWORD").ok().map(Into::into),
+            ipfs_api_key: env::var("MINTLAYER_IPFS_API_KEY").ok().map(Into::into),
+            ipfs_secret_key: env::var("MINTLAYER_IPFS_SECRET_KEY").ok().map(Into::into),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_config::configs::mintlayer::IpfsBackend;
+
+    use super::*;
+    use crate::test_utils::EnvMutex;
+
+    static MUTEX: EnvMutex = EnvMutex::new();
+
+    fn expected_config() -> (MintlayerConfig, MintlayerSecrets) {
+        (
+            MintlayerConfig {
+                rpc_url: "http://127.0.0.1:13034".to_owned(),
+                rpc_username: Some("wallet".to_owned()),
+                account_index: Some(1),
+                batch_size: Some(5),
+                max_operations_per_anchor: Some(20),
+                anchor_deadline_sec: Some(600),
+                flush_on_shutdown: Some(false),
+                first_anchored_l1_batch: Some(100),
+                confirmations: Some(3),
+                resubmission_grace_blocks: Some(10),
+                compress_documents: Some(false),
+                fee_in_top_x_mb: Some(2),
+                fee_bump_divisor: Some(1),
+                balance_check_interval_sec: None,
+                min_balance_atoms: Some(100_000_000_000),
+                ipfs_backend: Some(IpfsBackend::Kubo),
+                ipfs_endpoint: None,
+                ipfs_region: None,
+                ipfs_bucket_name: Some("zkmintlayer".to_owned()),
+                ipfs_gateway_url: None,
+                ipfs_api_url: Some("http://127.0.0.1:5001".to_owned()),
+            },
+            MintlayerSecrets {
+                rpc_password: Some("password".to_owned().into()),
+                ipfs_api_key: Some("api_key".to_owned().into()),
+                ipfs_secret_key: Some("secret_key".to_owned().into()),
+            },
+        )
+    }
+
+    #[test]
+    fn from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            MINTLAYER_RPC_URL="http://127.0.0.1:13034"
+            MINTLAYER_RPC_USERNAME="wallet"
+            MINTLAYER_ACCOUNT_INDEX=1
+            MINTLAYER_BATCH_SIZE=5
+            MINTLAYER_MAX_OPERATIONS_PER_ANCHOR=20
+            MINTLAYER_ANCHOR_DEADLINE_SEC=600
+            MINTLAYER_FLUSH_ON_SHUTDOWN=false
+            MINTLAYER_FIRST_ANCHORED_L1_BATCH=100
+            MINTLAYER_CONFIRMATIONS=3
+            MINTLAYER_RESUBMISSION_GRACE_BLOCKS=10
+            MINTLAYER_COMPRESS_DOCUMENTS=false
+            MINTLAYER_FEE_IN_TOP_X_MB=2
+            MINTLAYER_FEE_BUMP_DIVISOR=1
+            MINTLAYER_MIN_BALANCE_ATOMS=100000000000
+            MINTLAYER_IPFS_BACKEND="Kubo"
+            MINTLAYER_IPFS_BUCKET_NAME="zkmintlayer"
+            MINTLAYER_IPFS_API_URL="http://127.0.0.1:5001"
+            MINTLAYER_RPC_PASSWORD="password"
+            MINTLAYER_IPFS_API_KEY="api_key"
+            MINTLAYER_IPFS_SECRET_KEY="secret_key"
+        "#;
+        lock.set_env(config);
+        lock.remove_env(&[
+            "MINTLAYER_IPFS_ENDPOINT",
+            "MINTLAYER_IPFS_REGION",
+            "MINTLAYER_IPFS_GATEWAY_URL",
+            "MINTLAYER_BALANCE_CHECK_INTERVAL_SEC",
+        ]);
+
+        let (expected_config, expected_secrets) = expected_config();
+        assert_eq!(MintlayerConfig::from_env().unwrap(), expected_config);
+        assert_eq!(MintlayerSecrets::from_env().unwrap(), expected_secrets);
+    }
+}
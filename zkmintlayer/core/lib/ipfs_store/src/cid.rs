@@ -0,0 +1,338 @@
+//! Local computation of IPFS CIDs.
+//!
+//! Documents are chunked and laid out the same way as the default Kubo importer does it: fixed-size
+//! 256 KiB chunks combined into a balanced UnixFS DAG with at most 174 links per node. CIDv1 documents
+//! use raw leaves (so a single-chunk document is addressed by a `raw` CID), while CIDv0 documents use
+//! `dag-pb` leaves. All CIDs use sha2-256 multihashes.
+//! CIDs themselves are encoded and parsed with the `cid` crate.
+
+use std::fmt;
+
+use cid::{multihash::Multihash, Version};
+use sha2::{Digest, Sha256};
+
+use crate::IpfsStoreError;
+
+/// Size of chunks documents are split into.
+const CHUNK_SIZE: usize = 256 * 1_024;
+/// Maximum number of links in a UnixFS node.
+const MAX_LINKS: usize = 174;
+
+/// Multicodec code of raw binary data.
+const RAW_CODEC: u64 = 0x55;
+/// Multicodec code of `dag-pb` (Merkle DAG protobuf) nodes.
+const DAG_PB_CODEC: u64 = 0x70;
+/// Multihash code of sha2-256.
+const SHA2_256_CODE: u64 = 0x12;
+/// UnixFS data type of file nodes.
+const UNIXFS_FILE_TYPE: u64 = 2;
+
+/// Parsed IPFS content identifier with a sha2-256 multihash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cid(cid::Cid);
+
+impl Cid {
+    /// Computes the CIDv1 of a document, as Kubo does with `cid-version=1`.
+    pub fn compute(contents: &[u8]) -> Self {
+        Layout::V1_RAW_LEAVES.build(contents)
+    }
+
+    /// Parses a CID in the string form. CIDv0 and CIDv1 with sha2-256 multihashes are supported.
+    pub fn parse(s: &str) -> Result<Self, IpfsStoreError> {
+        let invalid = |message: &str| IpfsStoreError::InvalidResponse(format!("{message}: `{s}`"));
+
+        let cid = cid::Cid::try_from(s).map_err(|err| invalid(&format!("invalid CID ({err})")))?;
+        let hash = cid.hash();
+        if hash.code() != SHA2_256_CODE || hash.size() != 32 {
+            return Err(invalid("unsupported CID multihash"));
+        }
+        Ok(Self(cid))
+    }
+
+    /// Checks whether this CID addresses the specified document. The document is laid out according
+    /// to the CID version and codec; both raw and `dag-pb` leaves are tried for `dag-pb` CIDv1.
+    pub fn matches(&self, contents: &[u8]) -> bool {
+        let layouts: &[Layout] = match (self.0.version(), self.0.codec()) {
+            (Version::V0, _) => &[Layout::V0],
+            (Version::V1, RAW_CODEC) => &[Layout::V1_RAW_LEAVES],
+            (Version::V1, DAG_PB_CODEC) => &[Layout::V1_RAW_LEAVES, Layout::V1_PB_LEAVES],
+            (Version::V1, _) => &[],
+        };
+        layouts.iter().any(|layout| layout.build(contents) == *self)
+    }
+}
+
+impl fmt::Display for Cid {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // CIDv0 is formatted in base58btc, and CIDv1 in lowercase base32, same as Kubo does.
+        fmt::Display::fmt(&self.0, formatter)
+    }
+}
+
+/// Checks that `cid` returned by an IPFS backend addresses the document.
+pub(crate) fn verify_cid(cid: &str, contents: &[u8]) -> Result<(), IpfsStoreError> {
+    if Cid::parse(cid)?.matches(contents) {
+        Ok(())
+    } else {
+        Err(IpfsStoreError::CidMismatch {
+            expected: Cid::compute(contents).to_string(),
+            actual: cid.to_owned(),
+        })
+    }
+}
+
+/// Parameters of the UnixFS DAG layout.
+#[derive(Debug, Clone, Copy)]
+struct Layout {
+    version: Version,
+    raw_leaves: bool,
+}
+
+impl Layout {
+    const V0: Self = Self {
+        version: Version::V0,
+        raw_leaves: false,
+    };
+    const V1_RAW_LEAVES: Self = Self {
+        version: Version::V1,
+        raw_leaves: true,
+    };
+    const V1_PB_LEAVES: Self = Self {
+        version: Version::V1,
+        raw_leaves: false,
+    };
+
+    fn build(self, contents: &[u8]) -> Cid {
+        let chunks: Vec<_> = if contents.is_empty() {
+            vec![contents]
+        } else {
+            contents.chunks(CHUNK_SIZE).collect()
+        };
+        let mut depth = 0;
+        let mut capacity = 1;
+        while capacity < chunks.len() {
+            depth += 1;
+            capacity *= MAX_LINKS;
+        }
+        self.build_node(&chunks, depth, capacity).cid
+    }
+
+    /// Builds a balanced subtree of the specified depth; `capacity` is the maximum number of chunks
+    /// in the subtree (i.e., `MAX_LINKS^depth`).
+    fn build_node(self, chunks: &[&[u8]], depth: u32, capacity: usize) -> DagNode {
+        if depth == 0 {
+            return self.build_leaf(chunks[0]);
+        }
+
+        let child_capacity = capacity / MAX_LINKS;
+        let children: Vec<_> = chunks
+            .chunks(child_capacity)
+            .map(|chunks| self.build_node(chunks, depth - 1, child_capacity))
+            .collect();
+
+        let mut unixfs_data = Vec::new();
+        write_varint_field(&mut unixfs_data, 1, UNIXFS_FILE_TYPE);
+        let file_size = children.iter().map(|child| child.file_size).sum();
+        write_varint_field(&mut unixfs_data, 3, file_size);
+        for child in &children {
+            write_varint_field(&mut unixfs_data, 4, child.file_size);
+        }
+
+        // `dag-pb` nodes serialize links before data.
+        let mut block = Vec::new();
+        for child in &children {
+            let mut link = Vec::new();
+            write_bytes_field(&mut link, 1, &child.cid.0.to_bytes());
+            // Kubo always writes the link name, even if it's empty.
+            write_bytes_field(&mut link, 2, &[]);
+            write_varint_field(&mut link, 3, child.tree_size);
+            write_bytes_field(&mut block, 2, &link);
+        }
+        write_bytes_field(&mut block, 1, &unixfs_data);
+
+        let tree_size =
+            block.len() as u64 + children.iter().map(|child| child.tree_size).sum::<u64>();
+        DagNode {
+            cid: self.cid(DAG_PB_CODEC, &block),
+            file_size,
+            tree_size,
+        }
+    }
+
+    fn build_leaf(self, chunk: &[u8]) -> DagNode {
+        if self.raw_leaves {
+            return DagNode {
+                cid: self.cid(RAW_CODEC, chunk),
+                file_size: chunk.len() as u64,
+                tree_size: chunk.len() as u64,
+            };
+        }
+
+        let mut unixfs_data = Vec::new();
+        write_varint_field(&mut unixfs_data, 1, UNIXFS_FILE_TYPE);
+        if !chunk.is_empty() {
+            write_bytes_field(&mut unixfs_data, 2, chunk);
+        }
+        write_varint_field(&mut unixfs_data, 3, chunk.len() as u64);
+        let mut block = Vec::new();
+        write_bytes_field(&mut block, 1, &unixfs_data);
+
+        DagNode {
+            cid: self.cid(DAG_PB_CODEC, &block),
+            file_size: chunk.len() as u64,
+            tree_size: block.len() as u64,
+        }
+    }
+
+    fn cid(self, codec: u64, block: &[u8]) -> Cid {
+        let hash = Multihash::wrap(SHA2_256_CODE, &Sha256::digest(block))
+            .expect("sha2-256 digest fits into multihash");
+        let cid = cid::Cid::new(self.version, codec, hash)
+            .expect("CIDv0 is only used for sha2-256 `dag-pb` nodes");
+        Cid(cid)
+    }
+}
+
+#[derive(Debug)]
+struct DagNode {
+    cid: Cid,
+    /// Size of the file data in the subtree.
+    file_size: u64,
+    /// Cumulative size of all blocks in the subtree.
+    tree_size: u64,
+}
+
+fn write_varint(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+fn write_varint_field(buffer: &mut Vec<u8>, field: u64, value: u64) {
+    write_varint(buffer, field << 3);
+    write_varint(buffer, value);
+}
+
+fn write_bytes_field(buffer: &mut Vec<u8>, field: u64, value: &[u8]) {
+    write_varint(buffer, (field << 3) | 2);
+    write_varint(buffer, value.len() as u64);
+    buffer.extend_from_slice(value);
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    #[test]
+    fn computing_single_chunk_cids() {
+        let cid = Cid::compute(b"hello world");
+        assert_eq!(
+            cid.to_string(),
+            "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e"
+        );
+        assert_eq!(
+            Layout::V0.build(b"hello world").to_string(),
+            "Qmf412jQZiuVUtdgnB36FXFX7xg5V6KEbSJ4dpQuhkLyfD"
+        );
+        assert_eq!(
+            Layout::V0.build(b"hello world\n").to_string(),
+            "QmT78zSuBmuS4z925WZfrqQ1qHaJ56DQaTfyMUF7F8ff5o"
+        );
+        assert_eq!(
+            Layout::V0.build(b"").to_string(),
+            "QmbFMke1KXqnYyBBWxB74N4c5SBnJMVAiMNRcGu6x1AwQH"
+        );
+    }
+
+    /// Documents with their CIDs as computed by `ipfs add --only-hash --cid-version=1`. Single-chunk and empty
+    /// documents are addressed by `raw` CIDs; larger documents are addressed by the root of a `dag-pb` DAG
+    /// with raw leaves. The multi-chunk CID was cross-checked with an independent implementation of the Kubo importer;
+    /// it can be reproduced with `head -c 600000 /dev/zero | ipfs add --only-hash --cid-version=1`.
+    fn kubo_fixtures() -> [(Vec<u8>, &'static str); 3] {
+        [
+            (
+                b"hello world".to_vec(),
+                "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e",
+            ),
+            (
+                vec![],
+                "bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku",
+            ),
+            (
+                vec![0; 600_000],
+                "bafybeib7pcuusxw7atqqc4apxvkezssdjbqkkhymjpo5x7bsj5sytn2ph4",
+            ),
+        ]
+    }
+
+    #[test]
+    fn computing_cids_as_kubo() {
+        for (contents, expected_cid) in kubo_fixtures() {
+            let cid = Cid::compute(&contents);
+            assert_eq!(cid.to_string(), expected_cid);
+            let expected_cid = Cid::parse(expected_cid).unwrap();
+            assert_eq!(cid.0.to_bytes(), expected_cid.0.to_bytes());
+            assert!(expected_cid.matches(&contents));
+        }
+    }
+
+    #[test]
+    fn parsing_cids() {
+        for s in [
+            "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e",
+            "Qmf412jQZiuVUtdgnB36FXFX7xg5V6KEbSJ4dpQuhkLyfD",
+        ] {
+            let cid = Cid::parse(s).unwrap();
+            assert_eq!(cid.to_string(), s);
+            assert!(cid.matches(b"hello world"));
+            assert!(!cid.matches(b"hello world\n"));
+        }
+
+        let uppercase = "BAFKREIFZJUT3TE2NHYEKKLSS27NH3K72YSCO7Y32KOAO5EEI66WOF36N5E";
+        assert!(Cid::parse(uppercase).unwrap().matches(b"hello world"));
+        assert_matches!(
+            Cid::parse("not a CID"),
+            Err(IpfsStoreError::InvalidResponse(_))
+        );
+    }
+
+    #[test]
+    fn computing_multi_chunk_cids() {
+        let contents: Vec<u8> = (0..CHUNK_SIZE * 3 + 42).map(|i| i as u8).collect();
+        for layout in [Layout::V0, Layout::V1_RAW_LEAVES, Layout::V1_PB_LEAVES] {
+            let cid = layout.build(&contents);
+            assert_eq!(cid.0.codec(), DAG_PB_CODEC);
+            let parsed = Cid::parse(&cid.to_string()).unwrap();
+            assert_eq!(parsed, cid);
+            assert!(parsed.matches(&contents), "{layout:?}");
+            assert!(!parsed.matches(&contents[1..]), "{layout:?}");
+        }
+        assert_ne!(
+            Layout::V1_RAW_LEAVES.build(&contents),
+            Layout::V1_PB_LEAVES.build(&contents)
+        );
+    }
+
+    #[test]
+    fn verifying_cids() {
+        verify_cid(
+            "Qmf412jQZiuVUtdgnB36FXFX7xg5V6KEbSJ4dpQuhkLyfD",
+            b"hello world",
+        )
+        .unwrap();
+        let err = verify_cid(
+            "QmT78zSuBmuS4z925WZfrqQ1qHaJ56DQaTfyMUF7F8ff5o",
+            b"hello world",
+        )
+        .unwrap_err();
+        assert_matches!(
+            err,
+            IpfsStoreError::CidMismatch { expected, .. }
+                if expected == "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e"
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+
+use crate::{cid::verify_cid, IpfsStore, IpfsStoreError};
+
+/// Timeout for gateway requests. Gateways may search for documents in the network, so requests must be bounded.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Read-only IPFS store that fetches documents through a public [HTTP gateway](https://docs.ipfs.tech/reference/http/gateway/).
+///
+/// This store is useful for components that only verify anchored documents (e.g., on external nodes)
+/// and thus don't have credentials to upload documents. Uploading documents always fails.
+#[derive(Debug)]
+pub struct GatewayIpfsStore {
+    http_client: reqwest::Client,
+    gateway_url: String,
+}
+
+impl GatewayIpfsStore {
+    /// Creates a store for the gateway with the specified URL (e.g., `https://ipfs.4everland.link`).
+    pub fn new(gateway_url: &str) -> anyhow::Result<Self> {
+        let http_client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .context("failed building IPFS gateway client")?;
+        Ok(Self {
+            http_client,
+            gateway_url: gateway_url.trim_end_matches('/').to_owned(),
+        })
+    }
+}
+
+#[async_trait]
+impl IpfsStore for GatewayIpfsStore {
+    async fn put(&self, name: &str, _contents: Vec<u8>) -> Result<String, IpfsStoreError> {
+        Err(IpfsStoreError::Other {
+            source: format!("cannot upload `{name}`: IPFS gateway store is read-only").into(),
+            is_transient: false,
+        })
+    }
+
+    async fn get(&self, cid: &str) -> Result<Vec<u8>, IpfsStoreError> {
+        let url = format!("{}/ipfs/{cid}", self.gateway_url);
+        let response = self
+            .http_client
+            .get(&url)
+            .send()
+            .await
+            .map_err(IpfsStoreError::from_reqwest)?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(IpfsStoreError::NotFound(cid.to_owned()));
+        }
+        let response = response
+            .error_for_status()
+            .map_err(IpfsStoreError::from_reqwest)?;
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(IpfsStoreError::from_reqwest)?;
+        verify_cid(cid, &bytes)?;
+        Ok(bytes.to_vec())
+    }
+}
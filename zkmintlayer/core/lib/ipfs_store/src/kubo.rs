@@ -0,0 +1,211 @@
+use std::time::Duration;
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use reqwest::{multipart, StatusCode};
+use serde::Deserialize;
+
+use crate::{
+    cid::{verify_cid, Cid},
+    is_transient_status, IpfsStore, IpfsStoreError,
+};
+
+/// Timeout for Kubo RPC requests. Fetching a document not available on the node makes Kubo
+/// search for it in the network, so requests must be bounded.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Response of `/api/v0/add` for a single file.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct AddResponse {
+    hash: String,
+}
+
+/// Response of `/api/v0/pin/add`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct PinResponse {
+    #[serde(default)]
+    pins: Vec<String>,
+}
+
+/// Error returned by the Kubo RPC API.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ErrorResponse {
+    message: String,
+}
+
+/// IPFS store backed by a Kubo node accessed via its [RPC API](https://docs.ipfs.tech/reference/kubo/rpc/).
+///
+/// Documents are added as CIDv1 and explicitly pinned, so that they survive garbage collection on the node.
+#[derive(Debug)]
+pub struct KuboIpfsStore {
+    http_client: reqwest::Client,
+    api_url: String,
+}
+
+impl KuboIpfsStore {
+    /// Creates a store for the Kubo node with the specified RPC API URL (e.g., `http://127.0.0.1:5001`).
+    pub fn new(api_url: &str) -> anyhow::Result<Self> {
+        let http_client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .context("failed building Kubo RPC client")?;
+        Ok(Self {
+            http_client,
+            api_url: api_url.trim_end_matches('/').to_owned(),
+        })
+    }
+
+    /// Calls a Kubo RPC method. All RPC methods use `POST` requests.
+    async fn call(
+        &self,
+        method: &str,
+        query: &[(&str, &str)],
+        form: Option<multipart::Form>,
+    ) -> Result<reqwest::Response, IpfsStoreError> {
+        let url = format!("{}/api/v0/{method}", self.api_url);
+        let mut request = self.http_client.post(url).query(query);
+        if let Some(form) = form {
+            request = request.multipart(form);
+        }
+        let response = request.send().await.map_err(IpfsStoreError::from_reqwest)?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        let body = response.text().await.unwrap_or_default();
+        Err(map_error_response(method, status, &body))
+    }
+}
+
+fn map_error_response(method: &str, status: StatusCode, body: &str) -> IpfsStoreError {
+    let message = serde_json::from_str::<ErrorResponse>(body)
+        .map(|response| response.message)
+        .unwrap_or_else(|_| body.to_owned());
+    // Kubo reports most errors (including invalid arguments) with the 500 status code,
+    // so we have to look at the error message.
+    let lowercase_message = message.to_ascii_lowercase();
+    let is_transient = is_transient_status(status)
+        && !lowercase_message.contains("invalid")
+        && !lowercase_message.contains("failed to decode");
+    IpfsStoreError::Other {
+        source: format!("Kubo RPC method `{method}` failed ({status}): {message}").into(),
+        is_transient,
+    }
+}
+
+/// Parses the output of `/api/v0/add`. The output is newline-delimited JSON; with progress reporting disabled,
+/// the last entry describes the added file.
+fn parse_add_response(body: &str) -> Result<String, IpfsStoreError> {
+    let last_line = body
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .last()
+        .ok_or_else(|| IpfsStoreError::InvalidResponse("empty response to `add`".to_owned()))?;
+    let response: AddResponse = serde_json::from_str(last_line).map_err(|err| {
+        IpfsStoreError::InvalidResponse(format!("cannot parse response to `add`: {err}"))
+    })?;
+    Ok(response.hash)
+}
+
+#[async_trait]
+impl IpfsStore for KuboIpfsStore {
+    async fn put(&self, name: &str, contents: Vec<u8>) -> Result<String, IpfsStoreError> {
+        let local_cid = Cid::compute(&contents).to_string();
+        let part = multipart::Part::bytes(contents).file_name(name.to_owned());
+        let form = multipart::Form::new().part("file", part);
+        let query = [
+            ("cid-version", "1"),
+            ("pin", "false"),
+            ("progress", "false"),
+        ];
+        let response = self.call("add", &query, Some(form)).await?;
+        let body = response
+            .text()
+            .await
+            .map_err(IpfsStoreError::from_reqwest)?;
+        let cid = parse_add_response(&body)?;
+        // We request the same CID version and layout that is used for local computation.
+        if cid != local_cid {
+            return Err(IpfsStoreError::CidMismatch {
+                expected: local_cid,
+                actual: cid,
+            });
+        }
+
+        let response = self.call("pin/add", &[("arg", &cid)], None).await?;
+        let pins: PinResponse = response
+            .json()
+            .await
+            .map_err(IpfsStoreError::from_reqwest)?;
+        if !pins.pins.contains(&cid) {
+            return Err(IpfsStoreError::InvalidResponse(format!(
+                "`pin/add` did not pin {cid}: {:?}",
+                pins.pins
+            )));
+        }
+        tracing::info!("Put `{name}` to IPFS with CID {cid}");
+        Ok(cid)
+    }
+
+    async fn get(&self, cid: &str) -> Result<Vec<u8>, IpfsStoreError> {
+        let response = match self.call("cat", &[("arg", cid)], None).await {
+            Ok(response) => response,
+            Err(IpfsStoreError::Other { source, .. })
+                if source.to_string().contains("not found") =>
+            {
+                return Err(IpfsStoreError::NotFound(cid.to_owned()));
+            }
+            Err(err) => return Err(err),
+        };
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(IpfsStoreError::from_reqwest)?;
+        verify_cid(cid, &bytes)?;
+        Ok(bytes.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    #[test]
+    fn parsing_add_response() {
+        let body = r#"{"Name":"batch_1","Hash":"bafkreigh2akiscaildcqabsyg3dfr6chu3fgpregiymsck7e7aqa4s52zy","Size":"42"}"#;
+        assert_eq!(
+            parse_add_response(body).unwrap(),
+            "bafkreigh2akiscaildcqabsyg3dfr6chu3fgpregiymsck7e7aqa4s52zy"
+        );
+
+        let body = format!("{{\"Name\":\"\",\"Hash\":\"\",\"Bytes\":42}}\n{body}\n");
+        assert_eq!(
+            parse_add_response(&body).unwrap(),
+            "bafkreigh2akiscaildcqabsyg3dfr6chu3fgpregiymsck7e7aqa4s52zy"
+        );
+
+        assert_matches!(
+            parse_add_response(""),
+            Err(IpfsStoreError::InvalidResponse(_))
+        );
+    }
+
+    #[test]
+    fn mapping_error_responses() {
+        let body = r#"{"Message":"invalid path \"foo\": invalid cid","Code":0,"Type":"error"}"#;
+        let err = map_error_response("cat", StatusCode::INTERNAL_SERVER_ERROR, body);
+        assert!(!err.is_transient(), "{err}");
+        assert!(err.to_string().contains("invalid cid"), "{err}");
+
+        let err = map_error_response("add", StatusCode::SERVICE_UNAVAILABLE, "overloaded");
+        assert!(err.is_transient(), "{err}");
+        let err = map_error_response("add", StatusCode::BAD_REQUEST, "bad request");
+        assert!(!err.is_transient(), "{err}");
+    }
+}
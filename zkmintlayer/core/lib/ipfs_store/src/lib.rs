@@ -0,0 +1,110 @@
+//! Storage of documents on IPFS used to anchor L1 operations on Mintlayer.
+//!
+//! The following [stores](IpfsStore) are available:
+//!
+//! - [S3-compatible gateway](S3IpfsStore) pinning uploaded objects to IPFS, such as 4EVERLAND
+//! - [Kubo node](KuboIpfsStore) accessed via its RPC API, e.g. a local node started by docker-compose
+//! - [Read-only HTTP gateway](GatewayIpfsStore) for components that only fetch documents
+//! - [Mock in-memory store](MockIpfsStore) for tests
+//!
+//! Normally, a store is created from the configuration using [`create_ipfs_store()`], or [`create_ipfs_reader()`]
+//! if the store is only used to fetch documents.
+//!
+//! Stores don't trust CIDs reported by IPFS backends: the [CID](Cid) of each uploaded document is computed locally
+//! and compared with the CID returned by the backend, and fetched documents are checked against the requested CID.
+
+use std::{fmt, sync::Arc};
+
+use async_trait::async_trait;
+use zksync_config::configs::{mintlayer::IpfsBackend, MintlayerConfig, MintlayerSecrets};
+
+pub use crate::{
+    cid::Cid, gateway::GatewayIpfsStore, kubo::KuboIpfsStore, mock::MockIpfsStore, s3::S3IpfsStore,
+};
+
+mod cid;
+mod gateway;
+mod kubo;
+mod mock;
+mod s3;
+
+/// Thread-safe boxed error.
+pub type BoxedError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Errors during [`IpfsStore`] operations.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum IpfsStoreError {
+    /// A document with the specified CID is not found.
+    #[error("document {0} is not found on IPFS")]
+    NotFound(String),
+    /// The IPFS backend has returned a response that cannot be interpreted.
+    #[error("invalid response from IPFS backend: {0}")]
+    InvalidResponse(String),
+    /// The CID of a document reported by the backend doesn't match the document contents.
+    #[error("CID mismatch: expected {expected}, got {actual} from IPFS backend")]
+    CidMismatch { expected: String, actual: String },
+    /// Other error has occurred when accessing the backend (e.g., a network error).
+    #[error("{} error accessing IPFS backend: {source}", if *is_transient { "transient" } else { "fatal" })]
+    Other {
+        #[source]
+        source: BoxedError,
+        is_transient: bool,
+    },
+}
+
+impl IpfsStoreError {
+    /// Gives a best-effort estimate whether this error is transient.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Other { is_transient, .. } => *is_transient,
+            Self::NotFound(_) | Self::InvalidResponse(_) | Self::CidMismatch { .. } => false,
+        }
+    }
+
+    pub(crate) fn from_reqwest(err: reqwest::Error) -> Self {
+        let is_transient =
+            err.is_timeout() || err.is_connect() || err.status().map_or(false, is_transient_status);
+        Self::Other {
+            source: err.into(),
+            is_transient,
+        }
+    }
+}
+
+pub(crate) fn is_transient_status(status: reqwest::StatusCode) -> bool {
+    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Storage of immutable documents on IPFS. Documents are addressed by their CIDs.
+#[async_trait]
+pub trait IpfsStore: 'static + fmt::Debug + Send + Sync {
+    /// Uploads a document to IPFS, pins it and returns its CID. `name` is a human-readable document name;
+    /// some backends use it as an object key.
+    ///
+    /// Implementations must check that the returned CID addresses `contents`.
+    async fn put(&self, name: &str, contents: Vec<u8>) -> Result<String, IpfsStoreError>;
+
+    /// Fetches a document by its CID. Implementations must check that the fetched document matches the CID.
+    async fn get(&self, cid: &str) -> Result<Vec<u8>, IpfsStoreError>;
+}
+
+/// Creates an IPFS store for the backend specified in the config.
+pub fn create_ipfs_store(
+    config: &MintlayerConfig,
+    secrets: &MintlayerSecrets,
+) -> anyhow::Result<Arc<dyn IpfsStore>> {
+    Ok(match config.ipfs_backend() {
+        IpfsBackend::S3 => Arc::new(S3IpfsStore::new(config, secrets)?),
+        IpfsBackend::Kubo => Arc::new(KuboIpfsStore::new(config.ipfs_api_url())?),
+    })
+}
+
+/// Creates a read-only IPFS store for the backend specified in the config. Unlike [`create_ipfs_store()`],
+/// this doesn't require upload credentials: with the S3 backend, documents are fetched through the HTTP gateway.
+pub fn create_ipfs_reader(config: &MintlayerConfig) -> anyhow::Result<Arc<dyn IpfsStore>> {
+    Ok(match config.ipfs_backend() {
+        IpfsBackend::S3 => Arc::new(GatewayIpfsStore::new(config.ipfs_gateway_url())?),
+        IpfsBackend::Kubo => Arc::new(KuboIpfsStore::new(config.ipfs_api_url())?),
+    })
+}
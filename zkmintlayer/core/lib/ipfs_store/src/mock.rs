@@ -0,0 +1,46 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+
+use crate::{Cid, IpfsStore, IpfsStoreError};
+
+/// In-memory [`IpfsStore`] for tests. Documents are addressed by their locally computed CIDs.
+/// Cloned stores share the stored documents.
+#[derive(Debug, Clone, Default)]
+pub struct MockIpfsStore {
+    documents: Arc<Mutex<HashMap<String, (String, Vec<u8>)>>>,
+}
+
+impl MockIpfsStore {
+    /// Returns names of all stored documents together with their CIDs.
+    pub fn documents(&self) -> Vec<(String, String)> {
+        let documents = self.documents.lock().unwrap();
+        let mut documents: Vec<_> = documents
+            .iter()
+            .map(|(cid, (name, _))| (name.clone(), cid.clone()))
+            .collect();
+        documents.sort_unstable();
+        documents
+    }
+}
+
+#[async_trait]
+impl IpfsStore for MockIpfsStore {
+    async fn put(&self, name: &str, contents: Vec<u8>) -> Result<String, IpfsStoreError> {
+        let mut documents = self.documents.lock().unwrap();
+        let cid = Cid::compute(&contents).to_string();
+        documents.insert(cid.clone(), (name.to_owned(), contents));
+        Ok(cid)
+    }
+
+    async fn get(&self, cid: &str) -> Result<Vec<u8>, IpfsStoreError> {
+        let documents = self.documents.lock().unwrap();
+        documents
+            .get(cid)
+            .map(|(_, contents)| contents.clone())
+            .ok_or_else(|| IpfsStoreError::NotFound(cid.to_owned()))
+    }
+}
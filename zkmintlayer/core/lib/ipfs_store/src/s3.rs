@@ -0,0 +1,143 @@
+use std::{io::Cursor, net::IpAddr};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use s3::{creds::Credentials, error::S3Error, region::Region, Bucket};
+use zksync_config::configs::{MintlayerConfig, MintlayerSecrets};
+
+use crate::{
+    cid::{verify_cid, Cid},
+    GatewayIpfsStore, IpfsStore, IpfsStoreError,
+};
+
+/// Name of the object metadata field containing the CID of the object.
+const CID_METADATA_KEY: &str = "ipfs-hash";
+
+/// IPFS store backed by an S3-compatible gateway that pins uploaded objects to IPFS (e.g., 4EVERLAND).
+///
+/// Documents are uploaded to a bucket; the gateway reports the CID of an uploaded object in its metadata.
+/// Documents are read by CID through a public IPFS HTTP gateway.
+#[derive(Debug)]
+pub struct S3IpfsStore {
+    bucket: Box<Bucket>,
+    gateway: GatewayIpfsStore,
+}
+
+impl S3IpfsStore {
+    pub fn new(config: &MintlayerConfig, secrets: &MintlayerSecrets) -> anyhow::Result<Self> {
+        let bucket_name = config
+            .ipfs_bucket_name
+            .as_deref()
+            .context("IPFS bucket name is required for the S3 backend")?;
+        let credentials = Credentials::new(
+            Some(secrets.ipfs_api_key()?),
+            Some(secrets.ipfs_secret_key()?),
+            None,
+            None,
+            None,
+        )
+        .context("invalid IPFS gateway credentials")?;
+        let bucket = Bucket::new(
+            bucket_name,
+            Region::Custom {
+                region: config.ipfs_region().to_owned(),
+                endpoint: config.ipfs_endpoint().to_owned(),
+            },
+            credentials,
+        )
+        .context("failed creating IPFS gateway bucket")?;
+        let bucket: Box<Bucket> = bucket.into();
+        // Local S3-compatible endpoints (e.g., stand-ins used in tests) cannot resolve virtual-hosted buckets.
+        let bucket = if is_local_endpoint(config.ipfs_endpoint()) {
+            bucket.with_path_style()
+        } else {
+            bucket
+        };
+
+        Ok(Self {
+            bucket,
+            gateway: GatewayIpfsStore::new(config.ipfs_gateway_url())?,
+        })
+    }
+}
+
+/// Checks whether the endpoint host is `localhost` or an IP address.
+fn is_local_endpoint(endpoint: &str) -> bool {
+    let Ok(url) = reqwest::Url::parse(endpoint) else {
+        return false;
+    };
+    match url.host_str() {
+        Some("localhost") => true,
+        Some(host) => host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .is_ok(),
+        None => false,
+    }
+}
+
+fn map_s3_error(err: S3Error) -> IpfsStoreError {
+    let is_transient = match &err {
+        S3Error::HttpFailWithBody(status, _) => {
+            *status >= 500 || *status == reqwest::StatusCode::TOO_MANY_REQUESTS.as_u16()
+        }
+        S3Error::Credentials(_) | S3Error::Region(_) | S3Error::UrlParse(_) => false,
+        _ => true,
+    };
+    IpfsStoreError::Other {
+        source: err.into(),
+        is_transient,
+    }
+}
+
+#[async_trait]
+impl IpfsStore for S3IpfsStore {
+    async fn put(&self, name: &str, contents: Vec<u8>) -> Result<String, IpfsStoreError> {
+        let local_cid = Cid::compute(&contents);
+        let response = self
+            .bucket
+            .put_object_stream(&mut Cursor::new(&contents), name)
+            .await
+            .map_err(map_s3_error)?;
+        tracing::debug!(
+            "Put `{name}` to S3 IPFS gateway, response code: {}",
+            response.status_code()
+        );
+
+        // The gateway pins the object asynchronously; its CID is available in the object metadata.
+        let (head, _) = self.bucket.head_object(name).await.map_err(map_s3_error)?;
+        let cid = head
+            .metadata
+            .and_then(|metadata| metadata.get(CID_METADATA_KEY).cloned())
+            .ok_or_else(|| {
+                IpfsStoreError::InvalidResponse(format!(
+                    "no `{CID_METADATA_KEY}` in metadata of `{name}`"
+                ))
+            })?;
+        if cid != local_cid.to_string() {
+            // The gateway may use another CID version or DAG layout.
+            verify_cid(&cid, &contents)?;
+        }
+        tracing::info!("Put `{name}` to IPFS with CID {cid}");
+        Ok(cid)
+    }
+
+    async fn get(&self, cid: &str) -> Result<Vec<u8>, IpfsStoreError> {
+        self.gateway.get(cid).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detecting_local_endpoints() {
+        assert!(is_local_endpoint("http://127.0.0.1:9000"));
+        assert!(is_local_endpoint("http://[::1]:9000/"));
+        assert!(is_local_endpoint("http://localhost:9000"));
+        assert!(!is_local_endpoint("https://endpoint.4everland.co"));
+        assert!(!is_local_endpoint("not a URL"));
+    }
+}
@@ -0,0 +1,447 @@
+use std::{future::Future, time::Duration};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use jsonrpsee::{
+    core::ClientError,
+    http_client::{HttpClient, HttpClientBuilder},
+    proc_macros::rpc,
+};
+use serde::{Deserialize, Serialize};
+use zksync_config::configs::{MintlayerConfig, MintlayerSecrets};
+
+use crate::{
+    metrics::METRICS, types::TxState, AccountBalances, BlockInfo, ConfirmedTransaction,
+    DepositOptions, MintlayerClientError, MintlayerClientResult, MintlayerInterface,
+    NewTransaction, TransactionInfo,
+};
+
+/// Default number of retries for idempotent requests failing with a transient error.
+const DEFAULT_MAX_RETRIES: usize = 3;
+/// Initial backoff between retries; doubled after each retry.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(200);
+
+/// Options for transactions created by the wallet. Unset options make the wallet use its defaults
+/// (e.g., the current fee rate).
+#[derive(Debug, Default, Serialize)]
+struct TransactionOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    in_top_x_mb: Option<u32>,
+}
+
+impl From<DepositOptions> for TransactionOptions {
+    fn from(options: DepositOptions) -> Self {
+        Self {
+            in_top_x_mb: options.in_top_x_mb,
+        }
+    }
+}
+
+/// Confirmed transaction as returned by `transaction_list_by_address`.
+#[derive(Debug, Deserialize)]
+struct WalletTxInfo {
+    id: String,
+    height: u64,
+}
+
+impl From<WalletTxInfo> for ConfirmedTransaction {
+    fn from(info: WalletTxInfo) -> Self {
+        Self {
+            tx_id: info.id,
+            block_height: info.height,
+        }
+    }
+}
+
+/// Subset of the Mintlayer wallet RPC used by the client. The wallet RPC uses named params.
+#[rpc(client, param_kind = map)]
+trait MintlayerWalletRpc {
+    #[method(name = "address_deposit_data")]
+    async fn address_deposit_data(
+        &self,
+        account: u32,
+        data: String,
+        options: TransactionOptions,
+    ) -> RpcResult<NewTransaction>;
+
+    #[method(name = "transaction_get")]
+    async fn transaction_get(
+        &self,
+        account: u32,
+        transaction_id: String,
+    ) -> RpcResult<serde_json::Value>;
+
+    #[method(name = "transaction_list_by_address")]
+    async fn transaction_list_by_address(
+        &self,
+        account: u32,
+        address: Option<String>,
+        limit: usize,
+    ) -> RpcResult<Vec<WalletTxInfo>>;
+
+    #[method(name = "transaction_get_signed_raw")]
+    async fn transaction_get_signed_raw(
+        &self,
+        account: u32,
+        transaction_id: String,
+    ) -> RpcResult<String>;
+
+    #[method(name = "node_submit_transaction")]
+    async fn node_submit_transaction(
+        &self,
+        tx: String,
+        do_not_store: bool,
+        options: TransactionOptions,
+    ) -> RpcResult<NewTransaction>;
+
+    #[method(name = "wallet_best_block")]
+    async fn wallet_best_block(&self) -> RpcResult<BlockInfo>;
+
+    #[method(name = "account_balance")]
+    async fn account_balance(
+        &self,
+        account: u32,
+        utxo_states: Vec<String>,
+        with_locked: String,
+    ) -> RpcResult<AccountBalances>;
+}
+
+/// HTTP client for the Mintlayer wallet RPC bound to a single wallet account.
+///
+/// Idempotent requests failing with a transient error are retried with exponential backoff;
+/// deposits are never retried since this could lead to duplicate transactions.
+#[derive(Debug)]
+pub struct HttpMintlayerClient {
+    client: HttpClient,
+    account_index: u32,
+    max_retries: usize,
+}
+
+impl HttpMintlayerClient {
+    /// Creates a client based on the provided config. If the config specifies an RPC username,
+    /// requests are authenticated using basic auth.
+    pub fn new(config: &MintlayerConfig, secrets: &MintlayerSecrets) -> anyhow::Result<Self> {
+        let mut url: url::Url = config
+            .rpc_url
+            .parse()
+            .context("invalid Mintlayer wallet RPC URL")?;
+        if let Some(username) = &config.rpc_username {
+            // `jsonrpsee` converts credentials embedded into the URL to the `Authorization` header.
+            url.set_username(username)
+                .ok()
+                .context("cannot set username for Mintlayer wallet RPC URL")?;
+            url.set_password(secrets.rpc_password())
+                .ok()
+                .context("cannot set password for Mintlayer wallet RPC URL")?;
+        }
+
+        let client = HttpClientBuilder::default()
+            .build(url.as_str())
+            .context("failed building Mintlayer wallet RPC client")?;
+        Ok(Self {
+            client,
+            account_index: config.account_index(),
+            max_retries: DEFAULT_MAX_RETRIES,
+        })
+    }
+
+    /// Sets the maximum number of retries for idempotent requests.
+    pub fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    async fn with_retries<T, Fut>(
+        &self,
+        method: &'static str,
+        mut call: impl FnMut() -> Fut,
+    ) -> MintlayerClientResult<T>
+    where
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        let mut backoff = INITIAL_BACKOFF;
+        let mut retries = 0;
+        loop {
+            let err = match call().await {
+                Ok(value) => return Ok(value),
+                Err(source) => MintlayerClientError::Rpc { method, source },
+            };
+            METRICS.observe_error(&err);
+            if retries >= self.max_retries || !err.is_transient() {
+                return Err(err);
+            }
+            retries += 1;
+            tracing::warn!("{err}; retrying in {backoff:?} (retry #{retries})");
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+    }
+}
+
+#[async_trait]
+impl MintlayerInterface for HttpMintlayerClient {
+    async fn address_deposit_data(
+        &self,
+        data: &[u8],
+        options: DepositOptions,
+    ) -> MintlayerClientResult<NewTransaction> {
+        let result = MintlayerWalletRpcClient::address_deposit_data(
+            &self.client,
+            self.account_index,
+            hex::encode(data),
+            options.into(),
+        )
+        .await
+        .map_err(|source| MintlayerClientError::Rpc {
+            method: "address_deposit_data",
+            source,
+        })
+        .and_then(|tx| {
+            if tx.broadcasted {
+                Ok(tx)
+            } else {
+                Err(MintlayerClientError::InvalidResponse {
+                    method: "address_deposit_data",
+                    message: format!("transaction {} was not broadcast", tx.tx_id),
+                })
+            }
+        });
+        if let Err(err) = &result {
+            METRICS.observe_error(err);
+        }
+        result
+    }
+
+    async fn transaction_get(&self, tx_id: &str) -> MintlayerClientResult<Option<TransactionInfo>> {
+        const METHOD: &str = "transaction_get";
+
+        let result = self
+            .with_retries(METHOD, || {
+                MintlayerWalletRpcClient::transaction_get(
+                    &self.client,
+                    self.account_index,
+                    tx_id.to_owned(),
+                )
+            })
+            .await;
+        let raw_tx = match result {
+            Ok(serde_json::Value::Null) => return Ok(None),
+            Ok(raw_tx) => raw_tx,
+            Err(MintlayerClientError::Rpc {
+                source: ClientError::Call(err),
+                ..
+            }) if is_not_found_message(err.message()) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        let state =
+            find_tx_state(&raw_tx).ok_or_else(|| MintlayerClientError::InvalidResponse {
+                method: METHOD,
+                message: format!("no transaction state in {raw_tx}"),
+            })?;
+        let deposited_data = find_deposited_data(&raw_tx).map_err(|message| {
+            MintlayerClientError::InvalidResponse {
+                method: METHOD,
+                message,
+            }
+        })?;
+        Ok(Some(TransactionInfo {
+            tx_id: tx_id.to_owned(),
+            state,
+            deposited_data,
+        }))
+    }
+
+    async fn transaction_list_by_address(
+        &self,
+        address: Option<&str>,
+        limit: usize,
+    ) -> MintlayerClientResult<Vec<ConfirmedTransaction>> {
+        let txs = self
+            .with_retries("transaction_list_by_address", || {
+                MintlayerWalletRpcClient::transaction_list_by_address(
+                    &self.client,
+                    self.account_index,
+                    address.map(str::to_owned),
+                    limit,
+                )
+            })
+            .await?;
+        Ok(txs.into_iter().map(ConfirmedTransaction::from).collect())
+    }
+
+    async fn transaction_rebroadcast(&self, tx_id: &str) -> MintlayerClientResult<()> {
+        let signed_tx = self
+            .with_retries("transaction_get_signed_raw", || {
+                MintlayerWalletRpcClient::transaction_get_signed_raw(
+                    &self.client,
+                    self.account_index,
+                    tx_id.to_owned(),
+                )
+            })
+            .await?;
+        // Submitting the same signed transaction is idempotent, so it's safe to retry. The wallet already stores
+        // the transaction, hence `do_not_store`.
+        self.with_retries("node_submit_transaction", || {
+            MintlayerWalletRpcClient::node_submit_transaction(
+                &self.client,
+                signed_tx.clone(),
+                true,
+                TransactionOptions::default(),
+            )
+        })
+        .await?;
+        Ok(())
+    }
+
+    async fn wallet_best_block(&self) -> MintlayerClientResult<BlockInfo> {
+        self.with_retries("wallet_best_block", || {
+            MintlayerWalletRpcClient::wallet_best_block(&self.client)
+        })
+        .await
+    }
+
+    async fn account_balance(&self) -> MintlayerClientResult<AccountBalances> {
+        self.with_retries("account_balance", || {
+            MintlayerWalletRpcClient::account_balance(
+                &self.client,
+                self.account_index,
+                vec!["Confirmed".to_owned()],
+                "Unlocked".to_owned(),
+            )
+        })
+        .await
+    }
+}
+
+fn is_not_found_message(message: &str) -> bool {
+    let message = message.to_ascii_lowercase();
+    message.contains("not found") || message.contains("cannot find")
+}
+
+/// Finds the transaction state in the JSON returned by `transaction_get`. The state is either a top-level field
+/// of the returned object, or a field of the single wrapped object (e.g., `{"Tx": {"tx": .., "state": ..}}`).
+fn find_tx_state(raw_tx: &serde_json::Value) -> Option<TxState> {
+    let object = raw_tx.as_object()?;
+    if let Some(state) = object.get("state") {
+        return TxState::from_json(state);
+    }
+    if object.len() == 1 {
+        return find_tx_state(object.values().next()?);
+    }
+    None
+}
+
+/// Finds data deposited by the transaction in the JSON returned by `transaction_get`. The data is the value
+/// of the first `DataDeposit` output; it's either a hex string (optionally `0x`-prefixed) or an array of bytes.
+fn find_deposited_data(raw_tx: &serde_json::Value) -> Result<Option<Vec<u8>>, String> {
+    match raw_tx {
+        serde_json::Value::Object(map) => {
+            if let Some(data) = map.get("DataDeposit") {
+                return parse_bytes(data).map(Some);
+            }
+            for value in map.values() {
+                if let Some(data) = find_deposited_data(value)? {
+                    return Ok(Some(data));
+                }
+            }
+            Ok(None)
+        }
+        serde_json::Value::Array(values) => {
+            for value in values {
+                if let Some(data) = find_deposited_data(value)? {
+                    return Ok(Some(data));
+                }
+            }
+            Ok(None)
+        }
+        _ => Ok(None),
+    }
+}
+
+fn parse_bytes(value: &serde_json::Value) -> Result<Vec<u8>, String> {
+    match value {
+        serde_json::Value::String(hex_str) => {
+            let hex_str = hex_str.strip_prefix("0x").unwrap_or(hex_str);
+            hex::decode(hex_str).map_err(|err| format!("invalid hex in deposited data: {err}"))
+        }
+        serde_json::Value::Array(bytes) => bytes
+            .iter()
+            .map(|byte| {
+                byte.as_u64()
+                    .and_then(|byte| u8::try_from(byte).ok())
+                    .ok_or_else(|| format!("invalid byte in deposited data: {byte}"))
+            })
+            .collect(),
+        _ => Err(format!("unexpected deposited data: {value}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn finding_tx_state() {
+        let raw_tx = json!({
+            "Tx": {
+                "tx": { "version": 1 },
+                "state": { "Confirmed": [42, 1_700_000_000, 1] },
+            },
+        });
+        assert_eq!(
+            find_tx_state(&raw_tx),
+            Some(TxState::Confirmed { block_height: 42 })
+        );
+
+        let raw_tx = json!({ "tx": {}, "state": { "InMempool": 3 } });
+        assert_eq!(find_tx_state(&raw_tx), Some(TxState::InMempool));
+        assert_eq!(find_tx_state(&json!({ "tx": {} })), None);
+    }
+
+    #[test]
+    fn finding_deposited_data() {
+        let raw_tx = json!({
+            "Tx": {
+                "tx": {
+                    "V1": {
+                        "outputs": [
+                            { "Transfer": [{ "Coin": { "atoms": "100" } }, "tmt1q..."] },
+                            { "DataDeposit": "0x01ff" },
+                        ],
+                    },
+                },
+                "state": { "Confirmed": [42, 1_700_000_000, 1] },
+            },
+        });
+        assert_eq!(find_deposited_data(&raw_tx).unwrap(), Some(vec![1, 255]));
+
+        let raw_tx = json!({ "tx": { "outputs": [{ "DataDeposit": [1, 2, 3] }] } });
+        assert_eq!(find_deposited_data(&raw_tx).unwrap(), Some(vec![1, 2, 3]));
+        assert_eq!(find_deposited_data(&json!({ "tx": {} })).unwrap(), None);
+        find_deposited_data(&json!({ "DataDeposit": "not hex" })).unwrap_err();
+        find_deposited_data(&json!({ "DataDeposit": [256] })).unwrap_err();
+    }
+
+    #[test]
+    fn creating_client_with_basic_auth() {
+        let config = MintlayerConfig {
+            rpc_username: Some("user".to_owned()),
+            ..MintlayerConfig::for_tests()
+        };
+        let secrets = MintlayerSecrets {
+            rpc_password: Some("password".to_owned().into()),
+            ipfs_api_key: None,
+            ipfs_secret_key: None,
+        };
+        HttpMintlayerClient::new(&config, &secrets).unwrap();
+
+        let config = MintlayerConfig {
+            rpc_url: "not a URL".to_owned(),
+            ..MintlayerConfig::for_tests()
+        };
+        HttpMintlayerClient::new(&config, &secrets).unwrap_err();
+    }
+}
@@ -0,0 +1,297 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, RwLock, RwLockWriteGuard},
+};
+
+use async_trait::async_trait;
+
+use crate::{
+    AccountBalances, Amount, BlockInfo, ConfirmedTransaction, DepositOptions, MintlayerClientError,
+    MintlayerClientResult, MintlayerInterface, NewTransaction, TransactionInfo, TxState,
+};
+
+/// Mutable part of [`MockMintlayerClient`] that needs to be synchronized via an `RwLock`.
+#[derive(Debug, Default)]
+struct MockMintlayerInner {
+    best_block_height: u64,
+    balance_atoms: u128,
+    deposits: Vec<(String, Vec<u8>)>,
+    deposit_options: Vec<DepositOptions>,
+    tx_states: HashMap<String, TxState>,
+    rebroadcasts: Vec<String>,
+    /// Errors returned by the following calls (one error per call) before the mock falls back to normal processing.
+    errors: VecDeque<MintlayerClientError>,
+}
+
+impl MockMintlayerInner {
+    fn pop_error(&mut self) -> MintlayerClientResult<()> {
+        match self.errors.pop_front() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Mock Mintlayer wallet RPC client that keeps transactions in memory.
+///
+/// Deposited transactions are put into the mempool; they can be confirmed, dropped or moved to another state using
+/// the corresponding methods. Rebroadcasting an inactive transaction puts it back into the mempool.
+/// The mock is cheaply cloneable; all clones share the same state.
+#[derive(Debug, Clone, Default)]
+pub struct MockMintlayerClient {
+    inner: Arc<RwLock<MockMintlayerInner>>,
+}
+
+impl MockMintlayerClient {
+    fn lock(&self) -> RwLockWriteGuard<'_, MockMintlayerInner> {
+        self.inner
+            .write()
+            .expect("mock Mintlayer client is poisoned")
+    }
+
+    /// Returns all data deposited using this client in the order of deposits, together with the transaction IDs.
+    pub fn deposited_data(&self) -> Vec<(String, Vec<u8>)> {
+        self.lock().deposits.clone()
+    }
+
+    /// Returns IDs of all transactions rebroadcast using this client in the order of rebroadcasts.
+    pub fn rebroadcasts(&self) -> Vec<String> {
+        self.lock().rebroadcasts.clone()
+    }
+
+    /// Returns options of all deposits made using this client in the order of deposits.
+    pub fn deposit_options(&self) -> Vec<DepositOptions> {
+        self.lock().deposit_options.clone()
+    }
+
+    /// Sets the height of the best block reported by the wallet.
+    pub fn set_best_block_height(&self, height: u64) {
+        self.lock().best_block_height = height;
+    }
+
+    /// Sets the coin balance of the account.
+    pub fn set_balance(&self, atoms: u128) {
+        self.lock().balance_atoms = atoms;
+    }
+
+    /// Marks the transaction as included into a block at the specified height.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the transaction is unknown.
+    pub fn confirm_tx(&self, tx_id: &str, block_height: u64) {
+        let mut inner = self.lock();
+        let state = inner
+            .tx_states
+            .get_mut(tx_id)
+            .unwrap_or_else(|| panic!("transaction {tx_id} is unknown"));
+        *state = TxState::Confirmed { block_height };
+        inner.best_block_height = inner.best_block_height.max(block_height);
+    }
+
+    /// Drops the transaction, so that the wallet no longer knows about it.
+    pub fn drop_tx(&self, tx_id: &str) {
+        self.lock().tx_states.remove(tx_id);
+    }
+
+    /// Sets the state of the transaction. If the transaction was dropped, the wallet will know about it again.
+    pub fn set_tx_state(&self, tx_id: &str, state: TxState) {
+        self.lock().tx_states.insert(tx_id.to_owned(), state);
+    }
+
+    /// Makes the next call to the client fail with the specified error.
+    pub fn push_error(&self, err: MintlayerClientError) {
+        self.lock().errors.push_back(err);
+    }
+}
+
+#[async_trait]
+impl MintlayerInterface for MockMintlayerClient {
+    async fn address_deposit_data(
+        &self,
+        data: &[u8],
+        options: DepositOptions,
+    ) -> MintlayerClientResult<NewTransaction> {
+        let mut inner = self.lock();
+        inner.pop_error()?;
+        let tx_id = format!("{:064x}", inner.deposits.len() + 1);
+        inner.deposits.push((tx_id.clone(), data.to_vec()));
+        inner.deposit_options.push(options);
+        inner.tx_states.insert(tx_id.clone(), TxState::InMempool);
+        Ok(NewTransaction {
+            tx_id,
+            broadcasted: true,
+        })
+    }
+
+    async fn transaction_get(&self, tx_id: &str) -> MintlayerClientResult<Option<TransactionInfo>> {
+        let mut inner = self.lock();
+        inner.pop_error()?;
+        let Some(&state) = inner.tx_states.get(tx_id) else {
+            return Ok(None);
+        };
+        let deposited_data = inner
+            .deposits
+            .iter()
+            .find_map(|(id, data)| (id == tx_id).then(|| data.clone()));
+        Ok(Some(TransactionInfo {
+            tx_id: tx_id.to_owned(),
+            state,
+            deposited_data,
+        }))
+    }
+
+    /// The mock doesn't track addresses, so all confirmed deposits are returned regardless of `address`,
+    /// starting from the most recent deposit.
+    async fn transaction_list_by_address(
+        &self,
+        _address: Option<&str>,
+        limit: usize,
+    ) -> MintlayerClientResult<Vec<ConfirmedTransaction>> {
+        let mut inner = self.lock();
+        inner.pop_error()?;
+        let txs = inner
+            .deposits
+            .iter()
+            .rev()
+            .filter_map(|(tx_id, _)| match inner.tx_states.get(tx_id)? {
+                TxState::Confirmed { block_height } => Some(ConfirmedTransaction {
+                    tx_id: tx_id.clone(),
+                    block_height: *block_height,
+                }),
+                _ => None,
+            })
+            .take(limit)
+            .collect();
+        Ok(txs)
+    }
+
+    async fn transaction_rebroadcast(&self, tx_id: &str) -> MintlayerClientResult<()> {
+        let mut inner = self.lock();
+        inner.pop_error()?;
+        let state = inner.tx_states.get_mut(tx_id).ok_or_else(|| {
+            MintlayerClientError::InvalidResponse {
+                method: "transaction_get_signed_raw",
+                message: format!("transaction {tx_id} not found"),
+            }
+        })?;
+        if *state == TxState::Inactive {
+            *state = TxState::InMempool;
+        }
+        inner.rebroadcasts.push(tx_id.to_owned());
+        Ok(())
+    }
+
+    async fn wallet_best_block(&self) -> MintlayerClientResult<BlockInfo> {
+        let mut inner = self.lock();
+        inner.pop_error()?;
+        let height = inner.best_block_height;
+        Ok(BlockInfo {
+            id: format!("{height:064x}"),
+            height,
+        })
+    }
+
+    async fn account_balance(&self) -> MintlayerClientResult<AccountBalances> {
+        let mut inner = self.lock();
+        inner.pop_error()?;
+        let atoms = inner.balance_atoms;
+        Ok(AccountBalances {
+            coins: Amount {
+                atoms: atoms.to_string(),
+                // Mintlayer coins have 11 decimals.
+                decimal: format!(
+                    "{}.{:011}",
+                    atoms / 100_000_000_000,
+                    atoms % 100_000_000_000
+                ),
+            },
+            tokens: Default::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use jsonrpsee::core::ClientError;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn mock_client_basics() {
+        let client = MockMintlayerClient::default();
+        let options = DepositOptions {
+            in_top_x_mb: Some(3),
+        };
+        let tx = client.address_deposit_data(b"root", options).await.unwrap();
+        assert_eq!(
+            client.deposited_data(),
+            [(tx.tx_id.clone(), b"root".to_vec())]
+        );
+        assert_eq!(client.deposit_options(), [options]);
+
+        let info = client.transaction_get(&tx.tx_id).await.unwrap().unwrap();
+        assert_eq!(info.state, TxState::InMempool);
+        assert_eq!(info.deposited_data.as_deref(), Some(b"root".as_slice()));
+        client.confirm_tx(&tx.tx_id, 10);
+        let info = client.transaction_get(&tx.tx_id).await.unwrap().unwrap();
+        assert_eq!(info.state, TxState::Confirmed { block_height: 10 });
+        assert_eq!(client.wallet_best_block().await.unwrap().height, 10);
+
+        let pending_tx = client
+            .address_deposit_data(b"pending", options)
+            .await
+            .unwrap();
+        let txs = client.transaction_list_by_address(None, 10).await.unwrap();
+        assert_eq!(
+            txs,
+            [ConfirmedTransaction {
+                tx_id: tx.tx_id.clone(),
+                block_height: 10,
+            }]
+        );
+        client.confirm_tx(&pending_tx.tx_id, 11);
+        let txs = client.transaction_list_by_address(None, 1).await.unwrap();
+        assert_eq!(txs.len(), 1);
+        assert_eq!(txs[0].tx_id, pending_tx.tx_id);
+
+        client.set_tx_state(&tx.tx_id, TxState::Inactive);
+        client.transaction_rebroadcast(&tx.tx_id).await.unwrap();
+        assert_eq!(client.rebroadcasts(), [tx.tx_id.clone()]);
+        let info = client.transaction_get(&tx.tx_id).await.unwrap().unwrap();
+        assert_eq!(info.state, TxState::InMempool);
+
+        client.drop_tx(&tx.tx_id);
+        assert_eq!(client.transaction_get(&tx.tx_id).await.unwrap(), None);
+        let txs = client.transaction_list_by_address(None, 10).await.unwrap();
+        assert_eq!(txs.len(), 1);
+        client.transaction_rebroadcast(&tx.tx_id).await.unwrap_err();
+
+        client.set_balance(150_000_000_000);
+        let balances = client.account_balance().await.unwrap();
+        assert_eq!(balances.coins.atoms().unwrap(), 150_000_000_000);
+        assert_eq!(balances.coins.decimal, "1.50000000000");
+    }
+
+    #[tokio::test]
+    async fn mock_client_errors() {
+        let client = MockMintlayerClient::default();
+        client.push_error(MintlayerClientError::Rpc {
+            method: "address_deposit_data",
+            source: ClientError::RequestTimeout,
+        });
+
+        let err = client
+            .address_deposit_data(b"root", DepositOptions::default())
+            .await
+            .unwrap_err();
+        assert_matches!(err, MintlayerClientError::Rpc { .. });
+        assert!(client.deposited_data().is_empty());
+        client
+            .address_deposit_data(b"root", DepositOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(client.deposited_data().len(), 1);
+    }
+}
@@ -0,0 +1,6 @@
+//! Various Mintlayer wallet RPC client implementations.
+
+mod http;
+mod mock;
+
+pub use self::{http::HttpMintlayerClient, mock::MockMintlayerClient};
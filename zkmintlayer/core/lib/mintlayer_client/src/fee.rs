@@ -0,0 +1,81 @@
+//! Fee policy for deposit transactions.
+
+use zksync_config::configs::MintlayerConfig;
+
+use crate::DepositOptions;
+
+/// Fee policy for deposit transactions based on the wallet fee rate target (top X MB of the mempool).
+///
+/// Each re-deposit of a dropped transaction divides the target by a constant divisor, so that the re-deposited
+/// transaction pays a higher fee.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DepositFeePolicy {
+    in_top_x_mb: u32,
+    bump_divisor: u32,
+}
+
+impl DepositFeePolicy {
+    pub fn new(config: &MintlayerConfig) -> anyhow::Result<Self> {
+        let in_top_x_mb = config.fee_in_top_x_mb();
+        anyhow::ensure!(in_top_x_mb > 0, "`fee_in_top_x_mb` must be positive");
+        let bump_divisor = config.fee_bump_divisor();
+        anyhow::ensure!(bump_divisor > 0, "`fee_bump_divisor` must be positive");
+        Ok(Self {
+            in_top_x_mb,
+            bump_divisor,
+        })
+    }
+
+    /// Returns options for a deposit that was previously re-deposited the specified number of times
+    /// (0 for the initial deposit).
+    pub fn options(&self, resubmissions: u32) -> DepositOptions {
+        let in_top_x_mb = self
+            .bump_divisor
+            .checked_pow(resubmissions)
+            .map_or(1, |divisor| self.in_top_x_mb / divisor)
+            .max(1);
+        DepositOptions {
+            in_top_x_mb: Some(in_top_x_mb),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(in_top_x_mb: u32, bump_divisor: u32) -> DepositFeePolicy {
+        let config = MintlayerConfig {
+            fee_in_top_x_mb: Some(in_top_x_mb),
+            fee_bump_divisor: Some(bump_divisor),
+            ..MintlayerConfig::for_tests()
+        };
+        DepositFeePolicy::new(&config).unwrap()
+    }
+
+    #[test]
+    fn bumping_fees() {
+        let policy = policy(10, 2);
+        let targets: Vec<_> = (0..5)
+            .map(|resubmissions| policy.options(resubmissions).in_top_x_mb.unwrap())
+            .collect();
+        assert_eq!(targets, [10, 5, 2, 1, 1]);
+        assert_eq!(policy.options(u32::MAX).in_top_x_mb, Some(1));
+    }
+
+    #[test]
+    fn disabled_fee_bumps() {
+        let policy = policy(5, 1);
+        assert_eq!(policy.options(0).in_top_x_mb, Some(5));
+        assert_eq!(policy.options(10).in_top_x_mb, Some(5));
+    }
+
+    #[test]
+    fn invalid_policy() {
+        let config = MintlayerConfig {
+            fee_bump_divisor: Some(0),
+            ..MintlayerConfig::for_tests()
+        };
+        DepositFeePolicy::new(&config).unwrap_err();
+    }
+}
@@ -0,0 +1,58 @@
+//! Client for the Mintlayer wallet RPC used to anchor L1 operations on Mintlayer.
+
+use std::fmt;
+
+use async_trait::async_trait;
+
+pub use crate::{
+    fee::DepositFeePolicy,
+    types::{
+        AccountBalances, Amount, BlockInfo, ConfirmedTransaction, DepositOptions,
+        MintlayerClientError, MintlayerClientResult, NewTransaction, TransactionInfo, TxState,
+    },
+};
+
+pub mod clients;
+mod fee;
+mod metrics;
+mod types;
+
+/// Interface of the Mintlayer wallet RPC, as seen by the core applications.
+///
+/// All methods operate on a single wallet account that the client is bound to (see
+/// [`MintlayerConfig::account_index`](zksync_config::configs::MintlayerConfig::account_index())).
+#[async_trait]
+pub trait MintlayerInterface: 'static + Sync + Send + fmt::Debug {
+    /// Creates and broadcasts a transaction depositing arbitrary `data` on chain.
+    ///
+    /// This method is not idempotent: every successful call creates a new transaction.
+    async fn address_deposit_data(
+        &self,
+        data: &[u8],
+        options: DepositOptions,
+    ) -> MintlayerClientResult<NewTransaction>;
+
+    /// Returns information about a wallet transaction with the specified ID.
+    ///
+    /// Returns `Ok(None)` if the wallet doesn't know the transaction (e.g., because it was dropped).
+    async fn transaction_get(&self, tx_id: &str) -> MintlayerClientResult<Option<TransactionInfo>>;
+
+    /// Returns up to `limit` confirmed wallet transactions involving the specified address, or any address
+    /// of the account if `address` is `None`. The order of returned transactions is unspecified.
+    async fn transaction_list_by_address(
+        &self,
+        address: Option<&str>,
+        limit: usize,
+    ) -> MintlayerClientResult<Vec<ConfirmedTransaction>>;
+
+    /// Broadcasts a wallet transaction that is not in the mempool (i.e., [`TxState::Inactive`]) to the network again.
+    ///
+    /// Unlike [`Self::address_deposit_data()`], this method doesn't create a new transaction, so it's safe to repeat.
+    async fn transaction_rebroadcast(&self, tx_id: &str) -> MintlayerClientResult<()>;
+
+    /// Returns the best block known to the wallet.
+    async fn wallet_best_block(&self) -> MintlayerClientResult<BlockInfo>;
+
+    /// Returns balances of the account, counting only confirmed and unlocked UTXOs.
+    async fn account_balance(&self) -> MintlayerClientResult<AccountBalances>;
+}
@@ -0,0 +1,22 @@
+//! Metrics for the Mintlayer wallet RPC client.
+
+use vise::{Counter, LabeledFamily, Metrics};
+
+use crate::MintlayerClientError;
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "mintlayer_client")]
+pub(crate) struct MintlayerClientMetrics {
+    /// Number of failed requests to the Mintlayer wallet RPC (including retried ones).
+    #[metrics(labels = ["method", "kind"])]
+    rpc_errors: LabeledFamily<(&'static str, &'static str), Counter, 2>,
+}
+
+impl MintlayerClientMetrics {
+    pub fn observe_error(&self, err: &MintlayerClientError) {
+        self.rpc_errors[&(err.method(), err.kind())].inc();
+    }
+}
+
+#[vise::register]
+pub(crate) static METRICS: vise::Global<MintlayerClientMetrics> = vise::Global::new();
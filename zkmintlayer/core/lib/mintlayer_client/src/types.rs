@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+
+use jsonrpsee::{core::ClientError, types::error::ErrorCode};
+use serde::{Deserialize, Serialize};
+
+/// Transaction created by the wallet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewTransaction {
+    /// Hex-encoded transaction ID.
+    pub tx_id: String,
+    /// Whether the transaction was broadcast to the network.
+    #[serde(default = "NewTransaction::default_broadcasted")]
+    pub broadcasted: bool,
+}
+
+impl NewTransaction {
+    fn default_broadcasted() -> bool {
+        true
+    }
+}
+
+/// Options for deposit transactions created by the wallet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DepositOptions {
+    /// Fee rate target: the wallet chooses the fee rate so that the transaction gets into the top X MB
+    /// of the mempool. If not set, the wallet uses its default fee rate.
+    pub in_top_x_mb: Option<u32>,
+}
+
+/// Reference to a Mintlayer block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockInfo {
+    /// Hex-encoded block ID.
+    pub id: String,
+    pub height: u64,
+}
+
+/// State of a wallet transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxState {
+    /// Transaction is included into a block at the specified height.
+    Confirmed { block_height: u64 },
+    /// Transaction is in the mempool.
+    InMempool,
+    /// Transaction conflicts with another transaction included into a block.
+    Conflicted,
+    /// Transaction is not in the mempool or a block, but may be rebroadcast.
+    Inactive,
+    /// Transaction was abandoned by the wallet and will never be included.
+    Abandoned,
+}
+
+impl TxState {
+    /// Parses the state from its JSON representation used by the wallet, e.g. `{"Confirmed": [100, 1700000000, 0]}`
+    /// or `"Abandoned"`.
+    pub(crate) fn from_json(value: &serde_json::Value) -> Option<Self> {
+        let (tag, payload) = match value {
+            serde_json::Value::String(tag) => (tag.as_str(), None),
+            serde_json::Value::Object(map) if map.len() == 1 => {
+                let (tag, payload) = map.iter().next()?;
+                (tag.as_str(), Some(payload))
+            }
+            _ => return None,
+        };
+
+        Some(match tag {
+            "Confirmed" => {
+                let block_height = match payload? {
+                    serde_json::Value::Array(fields) => fields.first()?.as_u64()?,
+                    other => other.as_u64()?,
+                };
+                Self::Confirmed { block_height }
+            }
+            "InMempool" => Self::InMempool,
+            "Conflicted" => Self::Conflicted,
+            "Inactive" => Self::Inactive,
+            "Abandoned" => Self::Abandoned,
+            _ => return None,
+        })
+    }
+}
+
+/// Confirmed transaction as listed by the wallet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfirmedTransaction {
+    /// Hex-encoded transaction ID.
+    pub tx_id: String,
+    /// Height of the block including the transaction.
+    pub block_height: u64,
+}
+
+/// Information about a wallet transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionInfo {
+    pub tx_id: String,
+    pub state: TxState,
+    /// Data deposited by the transaction, or `None` if the transaction has no data deposit outputs.
+    pub deposited_data: Option<Vec<u8>>,
+}
+
+/// Amount of coins or tokens as returned by the wallet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Amount {
+    /// Amount in atoms (the smallest indivisible units).
+    pub atoms: String,
+    /// Human-readable decimal amount.
+    pub decimal: String,
+}
+
+impl Amount {
+    /// Returns the amount in atoms.
+    pub fn atoms(&self) -> Result<u128, MintlayerClientError> {
+        self.atoms
+            .parse()
+            .map_err(|err| MintlayerClientError::InvalidResponse {
+                method: "account_balance",
+                message: format!("invalid amount `{}`: {err}", self.atoms),
+            })
+    }
+}
+
+/// Balances of a wallet account.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountBalances {
+    pub coins: Amount,
+    /// Token balances keyed by the token ID.
+    #[serde(default)]
+    pub tokens: BTreeMap<String, Amount>,
+}
+
+/// Errors returned by Mintlayer wallet RPC clients.
+#[derive(Debug, thiserror::Error)]
+pub enum MintlayerClientError {
+    /// Failed sending the request or the wallet returned a JSON-RPC error.
+    #[error("request to Mintlayer wallet RPC `{method}` failed: {source}")]
+    Rpc {
+        method: &'static str,
+        #[source]
+        source: ClientError,
+    },
+    /// The wallet returned a response that cannot be interpreted.
+    #[error("unexpected response from Mintlayer wallet RPC `{method}`: {message}")]
+    InvalidResponse {
+        method: &'static str,
+        message: String,
+    },
+}
+
+impl MintlayerClientError {
+    /// Gives a best-effort estimate whether this error is transient, i.e. whether the failed request
+    /// may succeed if retried.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Rpc { source, .. } => match source {
+                ClientError::Transport(_)
+                | ClientError::RequestTimeout
+                | ClientError::RestartNeeded(_) => true,
+                ClientError::Call(err) => err.code() == ErrorCode::ServerIsBusy.code(),
+                _ => false,
+            },
+            Self::InvalidResponse { .. } => false,
+        }
+    }
+
+    /// Returns a short label describing the kind of this error, e.g. for metrics.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Rpc { source, .. } => match source {
+                ClientError::Transport(_) | ClientError::RestartNeeded(_) => "transport",
+                ClientError::RequestTimeout => "timeout",
+                ClientError::Call(_) => "call",
+                ClientError::ParseError(_) => "parse",
+                _ => "other",
+            },
+            Self::InvalidResponse { .. } => "invalid_response",
+        }
+    }
+
+    /// Returns the RPC method that failed.
+    pub fn method(&self) -> &'static str {
+        match self {
+            Self::Rpc { method, .. } | Self::InvalidResponse { method, .. } => method,
+        }
+    }
+}
+
+pub type MintlayerClientResult<T> = Result<T, MintlayerClientError>;
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parsing_tx_state() {
+        assert_eq!(
+            TxState::from_json(&json!({ "Confirmed": [100, 1_700_000_000, 0] })),
+            Some(TxState::Confirmed { block_height: 100 })
+        );
+        assert_eq!(
+            TxState::from_json(&json!({ "InMempool": 0 })),
+            Some(TxState::InMempool)
+        );
+        assert_eq!(
+            TxState::from_json(&json!("Abandoned")),
+            Some(TxState::Abandoned)
+        );
+        assert_eq!(TxState::from_json(&json!({ "Unknown": 0 })), None);
+        assert_eq!(TxState::from_json(&json!(42)), None);
+    }
+
+    #[test]
+    fn classifying_errors() {
+        let err = MintlayerClientError::Rpc {
+            method: "wallet_best_block",
+            source: ClientError::RequestTimeout,
+        };
+        assert!(err.is_transient());
+        assert_eq!(err.kind(), "timeout");
+
+        let err = MintlayerClientError::Rpc {
+            method: "address_deposit_data",
+            source: ClientError::Call(ErrorCode::InvalidParams.into()),
+        };
+        assert!(!err.is_transient());
+        assert_eq!(err.kind(), "call");
+
+        let err = MintlayerClientError::InvalidResponse {
+            method: "transaction_get",
+            message: "test".to_owned(),
+        };
+        assert!(!err.is_transient());
+    }
+}
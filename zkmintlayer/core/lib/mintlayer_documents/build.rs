@@ -0,0 +1,12 @@
+//! Generates rust code from protobufs.
+fn main() {
+    zksync_protobuf_build::Config {
+        input_root: "src/proto".into(),
+        proto_root: "zksync/mintlayer".into(),
+        dependencies: vec![],
+        protobuf_crate: "::zksync_protobuf".parse().unwrap(),
+        is_public: true,
+    }
+    .generate()
+    .expect("generate()");
+}
@@ -0,0 +1,459 @@
+//! Conversions between protobuf messages and Rust types.
+
+use anyhow::Context as _;
+use zksync_contracts::BaseSystemContractsHashes;
+use zksync_protobuf::{
+    repr::{read_required_repr, ProtoRepr},
+    required,
+};
+use zksync_prover_interface::outputs::L1BatchProofForL1;
+use zksync_types::{
+    block::L1BatchHeader,
+    commitment::{L1BatchMetaParameters, L1BatchMetadata, L1BatchWithMetadata},
+    l2_to_l1_log::{L2ToL1Log, SystemL2ToL1Log, UserL2ToL1Log},
+    priority_op_onchain_data::PriorityOpOnchainData,
+    protocol_version::{ProtocolSemanticVersion, VersionPatch},
+    pubdata_da::PubdataDA,
+    L1BatchNumber, ProtocolVersionId, H160, H2048, H256, U256,
+};
+
+use crate::{proto, CommitOperation, ExecuteOperation, OperationDocument, ProveOperation};
+
+fn parse_h256(bytes: &[u8]) -> anyhow::Result<H256> {
+    Ok(<[u8; 32]>::try_from(bytes).context("invalid size")?.into())
+}
+
+fn parse_h160(bytes: &[u8]) -> anyhow::Result<H160> {
+    Ok(<[u8; 20]>::try_from(bytes).context("invalid size")?.into())
+}
+
+fn parse_u256(bytes: &[u8]) -> anyhow::Result<U256> {
+    anyhow::ensure!(bytes.len() == 32, "invalid size");
+    Ok(U256::from_big_endian(bytes))
+}
+
+fn u256_to_bytes(value: &U256) -> Vec<u8> {
+    let mut bytes = vec![0_u8; 32];
+    value.to_big_endian(&mut bytes);
+    bytes
+}
+
+fn parse_protocol_version(raw: u32) -> anyhow::Result<ProtocolVersionId> {
+    Ok(ProtocolVersionId::try_from(u16::try_from(raw)?)?)
+}
+
+fn read_l1_batches(l1_batches: &[proto::L1Batch]) -> anyhow::Result<Vec<L1BatchWithMetadata>> {
+    l1_batches
+        .iter()
+        .enumerate()
+        .map(|(i, l1_batch)| l1_batch.read().with_context(|| format!("l1_batches[{i}]")))
+        .collect()
+}
+
+fn read_l2_to_l1_logs(logs: &[proto::L2ToL1Log]) -> anyhow::Result<Vec<L2ToL1Log>> {
+    logs.iter()
+        .enumerate()
+        .map(|(i, log)| log.read().with_context(|| format!("[{i}]")))
+        .collect()
+}
+
+impl proto::PubdataDa {
+    fn new(x: PubdataDA) -> Self {
+        match x {
+            PubdataDA::Calldata => Self::Calldata,
+            PubdataDA::Blobs => Self::Blobs,
+            PubdataDA::Custom => Self::Custom,
+        }
+    }
+
+    fn parse(self) -> PubdataDA {
+        match self {
+            Self::Calldata => PubdataDA::Calldata,
+            Self::Blobs => PubdataDA::Blobs,
+            Self::Custom => PubdataDA::Custom,
+        }
+    }
+}
+
+impl ProtoRepr for proto::OperationDocument {
+    type Type = OperationDocument;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        use proto::operation_document::Operation;
+
+        Ok(match required(&self.operation).context("operation")? {
+            Operation::Commit(op) => OperationDocument::Commit(CommitOperation {
+                prev_l1_batch: read_required_repr(&op.prev_l1_batch).context("prev_l1_batch")?,
+                l1_batches: read_l1_batches(&op.l1_batches)?,
+                pubdata_da: required(&op.pubdata_da)
+                    .and_then(|x| Ok(proto::PubdataDa::try_from(*x)?))
+                    .context("pubdata_da")?
+                    .parse(),
+            }),
+            Operation::Prove(op) => OperationDocument::Prove(ProveOperation {
+                prev_l1_batch: read_required_repr(&op.prev_l1_batch).context("prev_l1_batch")?,
+                l1_batches: read_l1_batches(&op.l1_batches)?,
+                proofs: op
+                    .proofs
+                    .iter()
+                    .enumerate()
+                    .map(|(i, proof)| proof.read().with_context(|| format!("proofs[{i}]")))
+                    .collect::<anyhow::Result<_>>()?,
+            }),
+            Operation::Execute(op) => OperationDocument::Execute(ExecuteOperation {
+                l1_batches: read_l1_batches(&op.l1_batches)?,
+            }),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        use proto::operation_document::Operation;
+
+        let operation = match this {
+            OperationDocument::Commit(op) => Operation::Commit(proto::CommitOperation {
+                prev_l1_batch: Some(ProtoRepr::build(&op.prev_l1_batch)),
+                l1_batches: op.l1_batches.iter().map(ProtoRepr::build).collect(),
+                pubdata_da: Some(proto::PubdataDa::new(op.pubdata_da).into()),
+            }),
+            OperationDocument::Prove(op) => Operation::Prove(proto::ProveOperation {
+                prev_l1_batch: Some(ProtoRepr::build(&op.prev_l1_batch)),
+                l1_batches: op.l1_batches.iter().map(ProtoRepr::build).collect(),
+                proofs: op.proofs.iter().map(ProtoRepr::build).collect(),
+            }),
+            OperationDocument::Execute(op) => Operation::Execute(proto::ExecuteOperation {
+                l1_batches: op.l1_batches.iter().map(ProtoRepr::build).collect(),
+            }),
+        };
+        Self {
+            operation: Some(operation),
+        }
+    }
+}
+
+impl ProtoRepr for proto::L1Batch {
+    type Type = L1BatchWithMetadata;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            header: read_required_repr(&self.header).context("header")?,
+            metadata: read_required_repr(&self.metadata).context("metadata")?,
+            raw_published_factory_deps: self.raw_published_factory_deps.clone(),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            header: Some(ProtoRepr::build(&this.header)),
+            metadata: Some(ProtoRepr::build(&this.metadata)),
+            raw_published_factory_deps: this.raw_published_factory_deps.clone(),
+        }
+    }
+}
+
+impl ProtoRepr for proto::L1BatchHeader {
+    type Type = L1BatchHeader;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            number: L1BatchNumber(*required(&self.number).context("number")?),
+            timestamp: *required(&self.timestamp).context("timestamp")?,
+            l1_tx_count: required(&self.l1_tx_count)
+                .and_then(|x| Ok((*x).try_into()?))
+                .context("l1_tx_count")?,
+            l2_tx_count: required(&self.l2_tx_count)
+                .and_then(|x| Ok((*x).try_into()?))
+                .context("l2_tx_count")?,
+            priority_ops_onchain_data: self
+                .priority_ops_onchain_data
+                .iter()
+                .enumerate()
+                .map(|(i, data)| {
+                    data.read()
+                        .with_context(|| format!("priority_ops_onchain_data[{i}]"))
+                })
+                .collect::<anyhow::Result<_>>()?,
+            l2_to_l1_logs: read_l2_to_l1_logs(&self.l2_to_l1_logs)
+                .context("l2_to_l1_logs")?
+                .into_iter()
+                .map(UserL2ToL1Log)
+                .collect(),
+            l2_to_l1_messages: self.l2_to_l1_messages.clone(),
+            bloom: required(&self.bloom)
+                .and_then(|bloom| {
+                    anyhow::ensure!(bloom.len() == H2048::len_bytes(), "invalid size");
+                    Ok(H2048::from_slice(bloom))
+                })
+                .context("bloom")?,
+            used_contract_hashes: self
+                .used_contract_hashes
+                .iter()
+                .enumerate()
+                .map(|(i, hash)| {
+                    parse_u256(hash).with_context(|| format!("used_contract_hashes[{i}]"))
+                })
+                .collect::<anyhow::Result<_>>()?,
+            base_system_contracts_hashes: BaseSystemContractsHashes {
+                bootloader: required(&self.bootloader_code_hash)
+                    .and_then(|x| parse_h256(x))
+                    .context("bootloader_code_hash")?,
+                default_aa: required(&self.default_aa_code_hash)
+                    .and_then(|x| parse_h256(x))
+                    .context("default_aa_code_hash")?,
+            },
+            system_logs: read_l2_to_l1_logs(&self.system_logs)
+                .context("system_logs")?
+                .into_iter()
+                .map(SystemL2ToL1Log)
+                .collect(),
+            protocol_version: self
+                .protocol_version
+                .map(parse_protocol_version)
+                .transpose()
+                .context("protocol_version")?,
+            pubdata_input: self.pubdata_input.clone(),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            number: Some(this.number.0),
+            timestamp: Some(this.timestamp),
+            l1_tx_count: Some(this.l1_tx_count.into()),
+            l2_tx_count: Some(this.l2_tx_count.into()),
+            priority_ops_onchain_data: this
+                .priority_ops_onchain_data
+                .iter()
+                .map(ProtoRepr::build)
+                .collect(),
+            l2_to_l1_logs: this
+                .l2_to_l1_logs
+                .iter()
+                .map(|log| ProtoRepr::build(&log.0))
+                .collect(),
+            l2_to_l1_messages: this.l2_to_l1_messages.clone(),
+            bloom: Some(this.bloom.as_bytes().to_vec()),
+            used_contract_hashes: this
+                .used_contract_hashes
+                .iter()
+                .map(u256_to_bytes)
+                .collect(),
+            bootloader_code_hash: Some(
+                this.base_system_contracts_hashes
+                    .bootloader
+                    .as_bytes()
+                    .to_vec(),
+            ),
+            default_aa_code_hash: Some(
+                this.base_system_contracts_hashes
+                    .default_aa
+                    .as_bytes()
+                    .to_vec(),
+            ),
+            system_logs: this
+                .system_logs
+                .iter()
+                .map(|log| ProtoRepr::build(&log.0))
+                .collect(),
+            protocol_version: this.protocol_version.map(|v| (v as u16).into()),
+            pubdata_input: this.pubdata_input.clone(),
+        }
+    }
+}
+
+impl ProtoRepr for proto::PriorityOpOnchainData {
+    type Type = PriorityOpOnchainData;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            layer_2_tip_fee: required(&self.layer_2_tip_fee)
+                .and_then(|x| parse_u256(x))
+                .context("layer_2_tip_fee")?,
+            onchain_data_hash: required(&self.onchain_data_hash)
+                .and_then(|x| parse_h256(x))
+                .context("onchain_data_hash")?,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            layer_2_tip_fee: Some(u256_to_bytes(&this.layer_2_tip_fee)),
+            onchain_data_hash: Some(this.onchain_data_hash.as_bytes().to_vec()),
+        }
+    }
+}
+
+impl ProtoRepr for proto::L2ToL1Log {
+    type Type = L2ToL1Log;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            shard_id: required(&self.shard_id)
+                .and_then(|x| Ok((*x).try_into()?))
+                .context("shard_id")?,
+            is_service: *required(&self.is_service).context("is_service")?,
+            tx_number_in_block: required(&self.tx_number_in_block)
+                .and_then(|x| Ok((*x).try_into()?))
+                .context("tx_number_in_block")?,
+            sender: required(&self.sender)
+                .and_then(|x| parse_h160(x))
+                .context("sender")?,
+            key: required(&self.key)
+                .and_then(|x| parse_h256(x))
+                .context("key")?,
+            value: required(&self.value)
+                .and_then(|x| parse_h256(x))
+                .context("value")?,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            shard_id: Some(this.shard_id.into()),
+            is_service: Some(this.is_service),
+            tx_number_in_block: Some(this.tx_number_in_block.into()),
+            sender: Some(this.sender.as_bytes().to_vec()),
+            key: Some(this.key.as_bytes().to_vec()),
+            value: Some(this.value.as_bytes().to_vec()),
+        }
+    }
+}
+
+impl ProtoRepr for proto::L1BatchMetadata {
+    type Type = L1BatchMetadata;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        let optional_h256 = |field: &Option<Vec<u8>>, name: &'static str| {
+            field.as_deref().map(parse_h256).transpose().context(name)
+        };
+
+        Ok(Self::Type {
+            root_hash: required(&self.root_hash)
+                .and_then(|x| parse_h256(x))
+                .context("root_hash")?,
+            rollup_last_leaf_index: *required(&self.rollup_last_leaf_index)
+                .context("rollup_last_leaf_index")?,
+            initial_writes_compressed: self.initial_writes_compressed.clone(),
+            repeated_writes_compressed: self.repeated_writes_compressed.clone(),
+            commitment: required(&self.commitment)
+                .and_then(|x| parse_h256(x))
+                .context("commitment")?,
+            l2_l1_merkle_root: required(&self.l2_l1_merkle_root)
+                .and_then(|x| parse_h256(x))
+                .context("l2_l1_merkle_root")?,
+            block_meta_params: L1BatchMetaParameters {
+                zkporter_is_available: *required(&self.zkporter_is_available)
+                    .context("zkporter_is_available")?,
+                bootloader_code_hash: required(&self.bootloader_code_hash)
+                    .and_then(|x| parse_h256(x))
+                    .context("bootloader_code_hash")?,
+                default_aa_code_hash: required(&self.default_aa_code_hash)
+                    .and_then(|x| parse_h256(x))
+                    .context("default_aa_code_hash")?,
+                protocol_version: self
+                    .meta_protocol_version
+                    .map(parse_protocol_version)
+                    .transpose()
+                    .context("meta_protocol_version")?,
+            },
+            aux_data_hash: required(&self.aux_data_hash)
+                .and_then(|x| parse_h256(x))
+                .context("aux_data_hash")?,
+            meta_parameters_hash: required(&self.meta_parameters_hash)
+                .and_then(|x| parse_h256(x))
+                .context("meta_parameters_hash")?,
+            pass_through_data_hash: required(&self.pass_through_data_hash)
+                .and_then(|x| parse_h256(x))
+                .context("pass_through_data_hash")?,
+            events_queue_commitment: optional_h256(
+                &self.events_queue_commitment,
+                "events_queue_commitment",
+            )?,
+            bootloader_initial_content_commitment: optional_h256(
+                &self.bootloader_initial_content_commitment,
+                "bootloader_initial_content_commitment",
+            )?,
+            state_diffs_compressed: required(&self.state_diffs_compressed)
+                .context("state_diffs_compressed")?
+                .clone(),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        let params = &this.block_meta_params;
+        Self {
+            root_hash: Some(this.root_hash.as_bytes().to_vec()),
+            rollup_last_leaf_index: Some(this.rollup_last_leaf_index),
+            initial_writes_compressed: this.initial_writes_compressed.clone(),
+            repeated_writes_compressed: this.repeated_writes_compressed.clone(),
+            commitment: Some(this.commitment.as_bytes().to_vec()),
+            l2_l1_merkle_root: Some(this.l2_l1_merkle_root.as_bytes().to_vec()),
+            zkporter_is_available: Some(params.zkporter_is_available),
+            bootloader_code_hash: Some(params.bootloader_code_hash.as_bytes().to_vec()),
+            default_aa_code_hash: Some(params.default_aa_code_hash.as_bytes().to_vec()),
+            meta_protocol_version: params.protocol_version.map(|v| (v as u16).into()),
+            aux_data_hash: Some(this.aux_data_hash.as_bytes().to_vec()),
+            meta_parameters_hash: Some(this.meta_parameters_hash.as_bytes().to_vec()),
+            pass_through_data_hash: Some(this.pass_through_data_hash.as_bytes().to_vec()),
+            events_queue_commitment: this
+                .events_queue_commitment
+                .map(|hash| hash.as_bytes().to_vec()),
+            bootloader_initial_content_commitment: this
+                .bootloader_initial_content_commitment
+                .map(|hash| hash.as_bytes().to_vec()),
+            state_diffs_compressed: Some(this.state_diffs_compressed.clone()),
+        }
+    }
+}
+
+impl ProtoRepr for proto::L1BatchProof {
+    type Type = L1BatchProofForL1;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        anyhow::ensure!(
+            self.aggregation_result_coords.len() == 4,
+            "aggregation_result_coords: expected 4 items, got {}",
+            self.aggregation_result_coords.len()
+        );
+        let mut aggregation_result_coords = [[0_u8; 32]; 4];
+        for (i, (coord, raw)) in aggregation_result_coords
+            .iter_mut()
+            .zip(&self.aggregation_result_coords)
+            .enumerate()
+        {
+            *coord = raw
+                .as_slice()
+                .try_into()
+                .with_context(|| format!("aggregation_result_coords[{i}]: invalid size"))?;
+        }
+
+        Ok(Self::Type {
+            aggregation_result_coords,
+            scheduler_proof: required(&self.scheduler_proof)
+                .and_then(|x| Ok(bincode::deserialize(x)?))
+                .context("scheduler_proof")?,
+            protocol_version: ProtocolSemanticVersion {
+                minor: required(&self.protocol_version)
+                    .and_then(|x| parse_protocol_version(*x))
+                    .context("protocol_version")?,
+                patch: VersionPatch(
+                    *required(&self.protocol_version_patch).context("protocol_version_patch")?,
+                ),
+            },
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            aggregation_result_coords: this
+                .aggregation_result_coords
+                .iter()
+                .map(|coord| coord.to_vec())
+                .collect(),
+            scheduler_proof: Some(
+                bincode::serialize(&this.scheduler_proof).expect("failed serializing proof"),
+            ),
+            protocol_version: Some((this.protocol_version.minor as u16).into()),
+            protocol_version_patch: Some(this.protocol_version.patch.0),
+        }
+    }
+}
@@ -0,0 +1,210 @@
+//! Encoding of documents with aggregated L1 operations that are uploaded to IPFS when anchoring on Mintlayer.
+//!
+//! Each document contains the data of a single commit / prove / execute operation, i.e. the data necessary
+//! to reconstruct the corresponding L1 transaction. The crate can decode documents in all formats ever
+//! uploaded by the anchoring pipeline:
+//!
+//! - **Version 0** (legacy): JSON-serialized tuples without a header. Commit documents are
+//!   `(prev_l1_batch, l1_batches, pubdata_da)`, prove documents are `(prev_l1_batch, l1_batches, proofs)`
+//!   and execute documents are `l1_batches`. The action type of a legacy document cannot be inferred
+//!   from its contents and must be known from elsewhere (e.g., the anchor commitment).
+//! - **Version 1**: a 6-byte header followed by the protobuf-encoded `OperationDocument` message
+//!   (see `src/proto/mod.proto`), optionally compressed with zstd. The header consists of
+//!   the [`MAGIC`] bytes, the format version byte and the [`Compression`] byte.
+//!
+//! New format versions must only be added, never changed, so that documents already pinned on IPFS remain readable.
+
+use anyhow::Context as _;
+use zksync_prover_interface::outputs::L1BatchProofForL1;
+use zksync_types::{
+    aggregated_operations::AggregatedActionType, commitment::L1BatchWithMetadata,
+    pubdata_da::PubdataDA,
+};
+
+mod conversions;
+pub mod proto;
+#[cfg(test)]
+mod tests;
+
+/// Magic bytes starting every non-legacy document.
+pub const MAGIC: [u8; 4] = *b"ZKML";
+/// Length of the document header.
+const HEADER_LEN: usize = MAGIC.len() + 2;
+/// zstd compression level used for documents.
+const ZSTD_LEVEL: i32 = 3;
+
+/// Version of the document format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum DocumentVersion {
+    /// Legacy JSON format.
+    Json = 0,
+    /// Protobuf format.
+    Protobuf = 1,
+}
+
+impl DocumentVersion {
+    /// Format version used for new documents.
+    pub const LATEST: Self = Self::Protobuf;
+
+    /// Detects the format version of an encoded document.
+    pub fn detect(bytes: &[u8]) -> anyhow::Result<Self> {
+        if !bytes.starts_with(&MAGIC) {
+            return Ok(Self::Json);
+        }
+        match bytes.get(MAGIC.len()) {
+            Some(1) => Ok(Self::Protobuf),
+            Some(version) => anyhow::bail!("unsupported document format version: {version}"),
+            None => anyhow::bail!("document header is truncated"),
+        }
+    }
+}
+
+/// Compression applied to the protobuf payload of a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Compression {
+    None = 0,
+    Zstd = 1,
+}
+
+impl TryFrom<u8> for Compression {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::Zstd),
+            _ => anyhow::bail!("unsupported document compression: {value}"),
+        }
+    }
+}
+
+/// Data of the `commitBatches` L1 transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitOperation {
+    /// Last L1 batch committed before the operation.
+    pub prev_l1_batch: L1BatchWithMetadata,
+    pub l1_batches: Vec<L1BatchWithMetadata>,
+    pub pubdata_da: PubdataDA,
+}
+
+/// Data of the `proveBatches` L1 transaction.
+#[derive(Debug, Clone)]
+pub struct ProveOperation {
+    /// Last L1 batch proven before the operation.
+    pub prev_l1_batch: L1BatchWithMetadata,
+    pub l1_batches: Vec<L1BatchWithMetadata>,
+    /// Proofs sent to L1; empty if the operation was sent without proofs.
+    pub proofs: Vec<L1BatchProofForL1>,
+}
+
+/// Data of the `executeBatches` L1 transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecuteOperation {
+    pub l1_batches: Vec<L1BatchWithMetadata>,
+}
+
+/// Document describing a single aggregated L1 operation.
+#[derive(Debug, Clone)]
+pub enum OperationDocument {
+    Commit(CommitOperation),
+    Prove(ProveOperation),
+    Execute(ExecuteOperation),
+}
+
+impl OperationDocument {
+    pub fn action_type(&self) -> AggregatedActionType {
+        match self {
+            Self::Commit(_) => AggregatedActionType::Commit,
+            Self::Prove(_) => AggregatedActionType::PublishProofOnchain,
+            Self::Execute(_) => AggregatedActionType::Execute,
+        }
+    }
+
+    /// Returns L1 batches the operation is applied to.
+    pub fn l1_batches(&self) -> &[L1BatchWithMetadata] {
+        match self {
+            Self::Commit(op) => &op.l1_batches,
+            Self::Prove(op) => &op.l1_batches,
+            Self::Execute(op) => &op.l1_batches,
+        }
+    }
+
+    /// Encodes this document using the [latest format](DocumentVersion::LATEST).
+    pub fn encode(&self, compression: Compression) -> anyhow::Result<Vec<u8>> {
+        let payload = zksync_protobuf::repr::encode::<proto::OperationDocument>(self);
+        let mut bytes = Vec::with_capacity(HEADER_LEN + payload.len());
+        bytes.extend_from_slice(&MAGIC);
+        bytes.push(DocumentVersion::LATEST as u8);
+        bytes.push(compression as u8);
+        match compression {
+            Compression::None => bytes.extend_from_slice(&payload),
+            Compression::Zstd => {
+                zstd::stream::copy_encode(payload.as_slice(), &mut bytes, ZSTD_LEVEL)
+                    .context("failed compressing document")?;
+            }
+        }
+        Ok(bytes)
+    }
+
+    /// Decodes a document in any supported format. `action_type` is the expected type of the operation;
+    /// it is required to decode legacy documents and is checked for other ones.
+    pub fn decode(bytes: &[u8], action_type: AggregatedActionType) -> anyhow::Result<Self> {
+        let document = match DocumentVersion::detect(bytes)? {
+            DocumentVersion::Json => Self::decode_json(bytes, action_type)?,
+            DocumentVersion::Protobuf => Self::decode_protobuf(&bytes[MAGIC.len() + 1..])?,
+        };
+        anyhow::ensure!(
+            document.action_type() == action_type,
+            "unexpected document type: expected {action_type}, got {}",
+            document.action_type()
+        );
+        Ok(document)
+    }
+
+    fn decode_json(bytes: &[u8], action_type: AggregatedActionType) -> anyhow::Result<Self> {
+        Ok(match action_type {
+            AggregatedActionType::Commit => {
+                let (prev_l1_batch, l1_batches, pubdata_da) = serde_json::from_slice(bytes)
+                    .context("failed decoding legacy commit document")?;
+                Self::Commit(CommitOperation {
+                    prev_l1_batch,
+                    l1_batches,
+                    pubdata_da,
+                })
+            }
+            AggregatedActionType::PublishProofOnchain => {
+                let (prev_l1_batch, l1_batches, proofs) = serde_json::from_slice(bytes)
+                    .context("failed decoding legacy prove document")?;
+                Self::Prove(ProveOperation {
+                    prev_l1_batch,
+                    l1_batches,
+                    proofs,
+                })
+            }
+            AggregatedActionType::Execute => {
+                let l1_batches = serde_json::from_slice(bytes)
+                    .context("failed decoding legacy execute document")?;
+                Self::Execute(ExecuteOperation { l1_batches })
+            }
+        })
+    }
+
+    fn decode_protobuf(bytes: &[u8]) -> anyhow::Result<Self> {
+        let (&compression, payload) = bytes
+            .split_first()
+            .context("document header is truncated")?;
+        let decompressed;
+        let payload = match Compression::try_from(compression)? {
+            Compression::None => payload,
+            Compression::Zstd => {
+                decompressed =
+                    zstd::stream::decode_all(payload).context("failed decompressing document")?;
+                &decompressed
+            }
+        };
+        zksync_protobuf::repr::decode::<proto::OperationDocument>(payload)
+            .context("failed decoding protobuf document")
+    }
+}
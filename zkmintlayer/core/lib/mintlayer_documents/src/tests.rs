@@ -0,0 +1,185 @@
+use assert_matches::assert_matches;
+use circuit_sequencer_api_1_5_0::proof::FinalProof;
+use zksync_contracts::BaseSystemContractsHashes;
+use zksync_types::{
+    block::L1BatchHeader,
+    commitment::{L1BatchMetaParameters, L1BatchMetadata},
+    l2_to_l1_log::{L2ToL1Log, SystemL2ToL1Log, UserL2ToL1Log},
+    priority_op_onchain_data::PriorityOpOnchainData,
+    protocol_version::ProtocolSemanticVersion,
+    L1BatchNumber, ProtocolVersionId, H2048, H256, U256,
+};
+
+use super::*;
+
+fn l1_batch(number: u32) -> L1BatchWithMetadata {
+    let mut header = L1BatchHeader::new(
+        L1BatchNumber(number),
+        number.into(),
+        BaseSystemContractsHashes {
+            bootloader: H256::repeat_byte(1),
+            default_aa: H256::repeat_byte(2),
+        },
+        ProtocolVersionId::latest(),
+    );
+    header.l1_tx_count = 1;
+    header.l2_tx_count = 5;
+    header.priority_ops_onchain_data = vec![PriorityOpOnchainData {
+        layer_2_tip_fee: U256::from(100),
+        onchain_data_hash: H256::repeat_byte(3),
+    }];
+    let log = L2ToL1Log {
+        shard_id: 0,
+        is_service: true,
+        tx_number_in_block: 2,
+        sender: [4; 20].into(),
+        key: H256::repeat_byte(5),
+        value: H256::repeat_byte(6),
+    };
+    header.l2_to_l1_logs = vec![UserL2ToL1Log(log.clone())];
+    header.system_logs = vec![SystemL2ToL1Log(log)];
+    header.l2_to_l1_messages = vec![b"message".to_vec()];
+    header.bloom = H2048::repeat_byte(7);
+    header.used_contract_hashes = vec![U256::from(8), U256::MAX];
+    header.pubdata_input = Some(vec![9; 64]);
+
+    let metadata = L1BatchMetadata {
+        root_hash: H256::from_low_u64_be(number.into()),
+        rollup_last_leaf_index: u64::from(number) + 20,
+        initial_writes_compressed: Some(vec![10; 32]),
+        repeated_writes_compressed: None,
+        commitment: H256::repeat_byte(11),
+        l2_l1_merkle_root: H256::repeat_byte(12),
+        block_meta_params: L1BatchMetaParameters {
+            zkporter_is_available: false,
+            bootloader_code_hash: H256::repeat_byte(1),
+            default_aa_code_hash: H256::repeat_byte(2),
+            protocol_version: Some(ProtocolVersionId::latest()),
+        },
+        aux_data_hash: H256::repeat_byte(13),
+        meta_parameters_hash: H256::repeat_byte(14),
+        pass_through_data_hash: H256::repeat_byte(15),
+        events_queue_commitment: Some(H256::repeat_byte(16)),
+        bootloader_initial_content_commitment: None,
+        state_diffs_compressed: vec![17; 100],
+    };
+
+    L1BatchWithMetadata {
+        header,
+        metadata,
+        raw_published_factory_deps: vec![vec![18; 32]],
+    }
+}
+
+fn commit_operation() -> CommitOperation {
+    CommitOperation {
+        prev_l1_batch: l1_batch(1),
+        l1_batches: vec![l1_batch(2), l1_batch(3)],
+        pubdata_da: PubdataDA::Blobs,
+    }
+}
+
+#[test]
+fn encoding_commit_document() {
+    let document = OperationDocument::Commit(commit_operation());
+    for compression in [Compression::None, Compression::Zstd] {
+        let bytes = document.encode(compression).unwrap();
+        assert!(bytes.starts_with(&MAGIC));
+        assert_eq!(
+            DocumentVersion::detect(&bytes).unwrap(),
+            DocumentVersion::Protobuf
+        );
+        assert_eq!(bytes[MAGIC.len() + 1], compression as u8);
+
+        let decoded = OperationDocument::decode(&bytes, AggregatedActionType::Commit).unwrap();
+        assert_matches!(decoded, OperationDocument::Commit(op) if op == commit_operation());
+    }
+}
+
+#[test]
+fn compressed_documents_are_smaller() {
+    let document = OperationDocument::Execute(ExecuteOperation {
+        l1_batches: (1..=10).map(l1_batch).collect(),
+    });
+    let json_len = serde_json::to_vec(document.l1_batches()).unwrap().len();
+    let uncompressed = document.encode(Compression::None).unwrap();
+    let compressed = document.encode(Compression::Zstd).unwrap();
+    assert!(uncompressed.len() < json_len);
+    assert!(compressed.len() < uncompressed.len());
+}
+
+#[test]
+fn encoding_prove_document() {
+    let proof = L1BatchProofForL1 {
+        aggregation_result_coords: [[1; 32], [2; 32], [3; 32], [4; 32]],
+        scheduler_proof: FinalProof::empty(),
+        protocol_version: ProtocolSemanticVersion {
+            minor: ProtocolVersionId::latest(),
+            patch: 3.into(),
+        },
+    };
+    let document = OperationDocument::Prove(ProveOperation {
+        prev_l1_batch: l1_batch(1),
+        l1_batches: vec![l1_batch(2)],
+        proofs: vec![proof.clone()],
+    });
+
+    let bytes = document.encode(Compression::Zstd).unwrap();
+    let decoded =
+        OperationDocument::decode(&bytes, AggregatedActionType::PublishProofOnchain).unwrap();
+    let OperationDocument::Prove(decoded) = decoded else {
+        panic!("unexpected document: {decoded:?}");
+    };
+    assert_eq!(decoded.prev_l1_batch, l1_batch(1));
+    assert_eq!(decoded.l1_batches, [l1_batch(2)]);
+    assert_eq!(decoded.proofs.len(), 1);
+    assert_eq!(
+        decoded.proofs[0].aggregation_result_coords,
+        proof.aggregation_result_coords
+    );
+    assert_eq!(decoded.proofs[0].protocol_version, proof.protocol_version);
+    assert_eq!(
+        bincode::serialize(&decoded.proofs[0].scheduler_proof).unwrap(),
+        bincode::serialize(&proof.scheduler_proof).unwrap()
+    );
+}
+
+#[test]
+fn decoding_legacy_documents() {
+    let op = commit_operation();
+    let bytes = serde_json::to_vec(&(&op.prev_l1_batch, &op.l1_batches, op.pubdata_da)).unwrap();
+    assert_eq!(
+        DocumentVersion::detect(&bytes).unwrap(),
+        DocumentVersion::Json
+    );
+    let decoded = OperationDocument::decode(&bytes, AggregatedActionType::Commit).unwrap();
+    assert_matches!(decoded, OperationDocument::Commit(decoded) if decoded == op);
+
+    let bytes = serde_json::to_vec(&op.l1_batches).unwrap();
+    let decoded = OperationDocument::decode(&bytes, AggregatedActionType::Execute).unwrap();
+    assert_eq!(decoded.l1_batches(), op.l1_batches);
+
+    let bytes = serde_json::to_vec(&(&op.prev_l1_batch, &op.l1_batches, Vec::<()>::new())).unwrap();
+    let decoded =
+        OperationDocument::decode(&bytes, AggregatedActionType::PublishProofOnchain).unwrap();
+    assert_matches!(decoded, OperationDocument::Prove(decoded) if decoded.proofs.is_empty());
+}
+
+#[test]
+fn decoding_errors() {
+    let bytes = OperationDocument::Commit(commit_operation())
+        .encode(Compression::None)
+        .unwrap();
+    let err = OperationDocument::decode(&bytes, AggregatedActionType::Execute).unwrap_err();
+    assert!(
+        err.to_string().contains("unexpected document type"),
+        "{err}"
+    );
+
+    let mut bytes = bytes;
+    bytes[MAGIC.len()] = 100;
+    let err = DocumentVersion::detect(&bytes).unwrap_err();
+    assert!(err.to_string().contains("unsupported"), "{err}");
+
+    OperationDocument::decode(&MAGIC, AggregatedActionType::Commit).unwrap_err();
+}
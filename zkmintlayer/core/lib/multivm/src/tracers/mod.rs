@@ -1,11 +1,21 @@
+use zksync_types::Address;
+
 pub mod call_tracer;
 mod multivm_dispatcher;
 pub mod old_tracers;
 pub mod prestate_tracer;
+pub mod storage_access;
 pub mod storage_invocation;
 pub mod validator;
 
 pub use call_tracer::CallTracer;
 pub use multivm_dispatcher::TracerDispatcher;
 pub use prestate_tracer::PrestateTracer;
+pub use storage_access::StorageAccessTracer;
 pub use storage_invocation::StorageInvocations;
+
+/// Checks whether the address belongs to the kernel space (i.e., is below 2^16), which hosts system contracts
+/// and precompiles.
+pub(crate) fn is_kernel_space_address(address: &Address) -> bool {
+    address.as_bytes()[..18].iter().all(|&byte| byte == 0)
+}
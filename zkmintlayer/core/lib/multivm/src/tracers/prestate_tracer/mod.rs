@@ -3,18 +3,21 @@ use std::{collections::HashMap, fmt, sync::Arc};
 use once_cell::sync::OnceCell;
 use zksync_state::{StoragePtr, WriteStorage};
 use zksync_types::{
-    get_code_key, get_nonce_key, web3::keccak256, AccountTreeId, Address, StorageKey, StorageValue,
-    H160, H256, L2_BASE_TOKEN_ADDRESS, U256,
+    get_code_key, get_nonce_key, web3::keccak256, AccountTreeId, Address, StorageKey, H256,
+    L2_BASE_TOKEN_ADDRESS, U256,
 };
-use zksync_utils::{address_to_h256, h256_to_u256};
+use zksync_utils::{address_to_h256, h256_to_u256, u256_to_h256};
+
+use crate::{glue::tracers::IntoOldVmTracer, tracers::is_kernel_space_address};
 
 pub mod vm_1_4_1;
 pub mod vm_1_4_2;
+pub mod vm_boojum_integration;
 pub mod vm_latest;
 pub mod vm_refunds_enhancement;
 pub mod vm_virtual_blocks;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct Account {
     pub balance: Option<U256>,
     pub code: Option<U256>,
@@ -22,6 +25,15 @@ pub struct Account {
     pub storage: Option<HashMap<H256, H256>>,
 }
 
+impl Account {
+    /// Checks whether the account has no balance, code or nonce, i.e. whether it "exists" in Ethereum terms.
+    fn is_empty(&self) -> bool {
+        [self.balance, self.code, self.nonce]
+            .iter()
+            .all(|value| value.unwrap_or_default().is_zero())
+    }
+}
+
 impl fmt::Display for Account {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "{{")?;
@@ -45,14 +57,29 @@ impl fmt::Display for Account {
     }
 }
 
-type State = HashMap<Address, Account>;
+pub type State = HashMap<Address, Account>;
 
+/// Tracer collecting the state of accounts touched by the execution, akin to the `prestateTracer` in Geth.
+///
+/// Accounts are considered touched if they are called or if their storage is accessed. Their balance, nonce,
+/// code hash and accessed storage slots are recorded when they are touched for the first time, so the tracer
+/// must be attached only to the execution it is supposed to trace. System contracts and precompiles
+/// (i.e., contracts in the kernel address space) are not recorded; still, the initial values of their
+/// accessed storage slots are tracked since account balances, nonces and code hashes are stored there
+/// and can be modified before the account is touched (e.g., on a transfer).
+///
+/// The result is a `(pre, post)` tuple. In the default mode, `pre` contains the state of all touched accounts
+/// before the execution, and `post` is empty. In the diff mode, `pre` contains the pre-execution state of
+/// the modified accounts existing before the execution, and `post` contains the modified fields and storage slots
+/// of all modified accounts.
 #[derive(Debug, Clone)]
 pub struct PrestateTracer {
     pub pre: State,
     pub post: State,
     pub config: PrestateTracerConfig,
     pub result: Arc<OnceCell<(State, State)>>,
+    /// Initial values of the accessed storage slots of system contracts.
+    system_storage: HashMap<StorageKey, H256>,
 }
 
 impl PrestateTracer {
@@ -62,104 +89,177 @@ impl PrestateTracer {
             post: Default::default(),
             config: PrestateTracerConfig { diff_mode },
             result,
+            system_storage: HashMap::new(),
+        }
+    }
+
+    /// Returns the value of a system storage slot before the traced execution.
+    fn read_initial_system_value<S: WriteStorage>(
+        system_storage: &HashMap<StorageKey, H256>,
+        key: &StorageKey,
+        storage: &StoragePtr<S>,
+    ) -> U256 {
+        let value = system_storage
+            .get(key)
+            .copied()
+            .unwrap_or_else(|| storage.borrow_mut().read_value(key));
+        h256_to_u256(value)
+    }
+
+    /// Records the state of an account unless it is already recorded or is a system one.
+    fn touch_account<S: WriteStorage>(
+        &mut self,
+        address: Address,
+        storage: &StoragePtr<S>,
+    ) -> Option<&mut Account> {
+        if is_kernel_space_address(&address) {
+            return None;
+        }
+        let system_storage = &self.system_storage;
+        Some(self.pre.entry(address).or_insert_with(|| {
+            let read = |key| {
+                Some(Self::read_initial_system_value(
+                    system_storage,
+                    &key,
+                    storage,
+                ))
+            };
+            Account {
+                balance: read(get_balance_key(&address)),
+                code: read(get_code_key(&address)),
+                nonce: read(get_nonce_key(&address)),
+                storage: Some(HashMap::new()),
+            }
+        }))
+    }
+
+    /// Records the current value of a storage slot unless it is already recorded.
+    fn touch_storage_slot<S: WriteStorage>(
+        &mut self,
+        address: Address,
+        key: U256,
+        storage: &StoragePtr<S>,
+    ) {
+        let key = u256_to_h256(key);
+        if is_kernel_space_address(&address) {
+            let storage_key = StorageKey::new(AccountTreeId::new(address), key);
+            self.system_storage
+                .entry(storage_key)
+                .or_insert_with(|| storage.borrow_mut().read_value(&storage_key));
+            return;
         }
+
+        let Some(account) = self.touch_account(address, storage) else {
+            return;
+        };
+        account
+            .storage
+            .get_or_insert_with(HashMap::new)
+            .entry(key)
+            .or_insert_with(|| {
+                let storage_key = StorageKey::new(AccountTreeId::new(address), key);
+                storage.borrow_mut().read_value(&storage_key)
+            });
+    }
+
+    fn process_result<T: StorageAccess>(&mut self, state: &T) {
+        self.post = self
+            .pre
+            .iter()
+            .map(|(address, account)| (*address, get_account_data(address, account, state)))
+            .collect();
+
+        let result = if self.config.diff_mode {
+            diff_states(self.pre.clone(), &self.post)
+        } else {
+            (self.pre.clone(), State::new())
+        };
+        self.result.set(result).unwrap();
     }
 }
 
+impl IntoOldVmTracer for PrestateTracer {}
+
 #[derive(Debug, Clone)]
 pub struct PrestateTracerConfig {
     diff_mode: bool,
 }
 
-pub fn process_modified_storage_keys<S>(
-    prestate: State,
-    storage: &StoragePtr<S>,
-) -> HashMap<H160, Account>
-where
-    S: WriteStorage,
-{
-    let cloned_storage = &storage.clone();
-    let mut initial_storage_ref = cloned_storage.as_ref().borrow_mut();
-
-    initial_storage_ref
-        .modified_storage_keys()
-        .clone()
-        .iter()
-        .filter(|k| !prestate.contains_key(k.0.account().address()))
-        .map(|k| {
-            (
-                *(k.0.account().address()),
-                Account {
-                    balance: Some(h256_to_u256(
-                        initial_storage_ref.read_value(&get_balance_key(k.0.account())),
-                    )),
-                    code: Some(h256_to_u256(
-                        initial_storage_ref.read_value(&get_code_key(k.0.account().address())),
-                    )),
-                    nonce: Some(h256_to_u256(
-                        initial_storage_ref.read_value(&get_nonce_key(k.0.account().address())),
-                    )),
-                    storage: Some(get_storage_if_present(
-                        k.0.account(),
-                        initial_storage_ref.modified_storage_keys(),
-                    )),
-                },
-            )
-        })
-        .collect::<State>()
-}
-
-fn get_balance_key(account: &AccountTreeId) -> StorageKey {
-    let address_h256 = address_to_h256(account.address());
+fn get_balance_key(address: &Address) -> StorageKey {
+    let address_h256 = address_to_h256(address);
     let bytes = [address_h256.as_bytes(), &[0; 32]].concat();
     let balance_key: H256 = keccak256(&bytes).into();
     StorageKey::new(AccountTreeId::new(L2_BASE_TOKEN_ADDRESS), balance_key)
 }
 
-fn get_storage_if_present(
-    account: &AccountTreeId,
-    modified_storage_keys: &HashMap<StorageKey, StorageValue>,
-) -> HashMap<H256, H256> {
-    //check if there is a Storage Key struct with an account field that matches the account and return the key as the key and the Storage Value as the value
-    modified_storage_keys
-        .iter()
-        .filter(|(k, _)| k.account() == account)
-        .map(|(k, v)| (*k.key(), *v))
-        .collect()
+/// Reads the current state of the account fields and storage slots recorded in `account`.
+fn get_account_data<T: StorageAccess>(address: &Address, account: &Account, state: &T) -> Account {
+    let storage = account.storage.as_ref().map(|slots| {
+        slots
+            .keys()
+            .map(|key| {
+                let storage_key = StorageKey::new(AccountTreeId::new(*address), *key);
+                (*key, u256_to_h256(state.read_from_storage(&storage_key)))
+            })
+            .collect()
+    });
+    Account {
+        balance: Some(state.read_from_storage(&get_balance_key(address))),
+        code: Some(state.read_from_storage(&get_code_key(address))),
+        nonce: Some(state.read_from_storage(&get_nonce_key(address))),
+        storage,
+    }
 }
 
-fn process_result(result: &Arc<OnceCell<(State, State)>>, mut pre: State, post: State) {
-    pre.retain(|k, v| {
-        if let Some(post_v) = post.get(k) {
-            if v != post_v {
-                return true;
-            }
+/// Computes the diff-mode output from the full pre- and post-execution states, following Geth semantics.
+fn diff_states(mut pre: State, post: &State) -> (State, State) {
+    let mut modified_accounts = State::new();
+    pre.retain(|address, pre_account| {
+        let post_account = &post[address];
+        let mut modified_account = Account::default();
+        if pre_account.balance != post_account.balance {
+            modified_account.balance = post_account.balance;
+        }
+        if pre_account.code != post_account.code {
+            modified_account.code = post_account.code;
+        }
+        if pre_account.nonce != post_account.nonce {
+            modified_account.nonce = post_account.nonce;
+        }
+
+        let mut modified_slots = HashMap::new();
+        let post_slots = post_account.storage.as_ref();
+        if let Some(pre_slots) = &mut pre_account.storage {
+            pre_slots.retain(|key, pre_value| {
+                let post_value = post_slots.and_then(|slots| slots.get(key));
+                match post_value {
+                    Some(post_value) if *post_value != *pre_value => {
+                        if !post_value.is_zero() {
+                            modified_slots.insert(*key, *post_value);
+                        }
+                        true
+                    }
+                    _ => false,
+                }
+            });
         }
-        false
-    });
-    result.set((pre, post)).unwrap();
-}
 
-fn get_account_data<T: StorageAccess>(
-    account_key: &StorageKey,
-    state: &T,
-    storage: &HashMap<StorageKey, StorageValue>,
-) -> (Address, Account) {
-    let address = *(account_key.account().address());
-    let balance = state.read_from_storage(&get_balance_key(account_key.account()));
-    let code = state.read_from_storage(&get_code_key(account_key.account().address()));
-    let nonce = state.read_from_storage(&get_nonce_key(account_key.account().address()));
-    let storage = get_storage_if_present(account_key.account(), storage);
-
-    (
-        address,
-        Account {
-            balance: Some(balance),
-            code: Some(code),
-            nonce: Some(nonce),
-            storage: Some(storage),
-        },
-    )
+        let is_modified = modified_account != Account::default()
+            || pre_account
+                .storage
+                .as_ref()
+                .is_some_and(|slots| !slots.is_empty());
+        if !is_modified {
+            return false;
+        }
+        if !modified_slots.is_empty() {
+            modified_account.storage = Some(modified_slots);
+        }
+        modified_accounts.insert(*address, modified_account);
+        // Accounts created by the execution are only present in the post-state.
+        !pre_account.is_empty()
+    });
+    (pre, modified_accounts)
 }
 
 // Define a trait that abstracts storage access
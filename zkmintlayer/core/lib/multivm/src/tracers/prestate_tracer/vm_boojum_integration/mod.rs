@@ -0,0 +1,54 @@
+use zk_evm_1_4_0::{
+    tracing::{BeforeExecutionData, VmLocalStateData},
+    zkevm_opcode_defs::{LogOpcode, Opcode},
+};
+use zksync_state::{StoragePtr, WriteStorage};
+use zksync_types::StorageKey;
+use zksync_utils::u256_to_account_address;
+
+use super::{PrestateTracer, StorageAccess};
+use crate::{
+    interface::dyn_tracers::vm_1_4_0::DynTracer,
+    tracers::prestate_tracer::U256,
+    vm_boojum_integration::{BootloaderState, HistoryMode, SimpleMemory, VmTracer, ZkSyncVmState},
+};
+
+impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for PrestateTracer {
+    fn before_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        data: BeforeExecutionData,
+        _memory: &SimpleMemory<H>,
+        storage: StoragePtr<S>,
+    ) {
+        let this_address = state.vm_local_state.callstack.current.this_address;
+        match data.opcode.variant.opcode {
+            Opcode::FarCall(_) => {
+                let called_address = u256_to_account_address(&data.src1_value.value);
+                self.touch_account(this_address, &storage);
+                self.touch_account(called_address, &storage);
+            }
+            Opcode::Log(LogOpcode::StorageRead | LogOpcode::StorageWrite) => {
+                self.touch_storage_slot(this_address, data.src0_value.value, &storage);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for PrestateTracer {
+    fn after_vm_execution(
+        &mut self,
+        state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: crate::interface::tracer::VmExecutionStopReason,
+    ) {
+        self.process_result(state);
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> StorageAccess for ZkSyncVmState<S, H> {
+    fn read_from_storage(&self, key: &StorageKey) -> U256 {
+        self.storage.storage.read_from_storage(key)
+    }
+}
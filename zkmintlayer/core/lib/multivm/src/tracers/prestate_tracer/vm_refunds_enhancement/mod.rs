@@ -1,13 +1,12 @@
-use std::collections::HashMap;
-
-use zk_evm_1_3_3::tracing::{BeforeExecutionData, VmLocalStateData};
+use zk_evm_1_3_3::{
+    tracing::{BeforeExecutionData, VmLocalStateData},
+    zkevm_opcode_defs::{LogOpcode, Opcode},
+};
 use zksync_state::{StoragePtr, WriteStorage};
-use zksync_types::{StorageKey, H256};
+use zksync_types::StorageKey;
+use zksync_utils::u256_to_account_address;
 
-use super::{
-    get_account_data, process_modified_storage_keys, process_result, PrestateTracer, State,
-    StorageAccess,
-};
+use super::{PrestateTracer, StorageAccess};
 use crate::{
     interface::dyn_tracers::vm_1_3_3::DynTracer,
     tracers::prestate_tracer::U256,
@@ -17,14 +16,22 @@ use crate::{
 impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for PrestateTracer {
     fn before_execution(
         &mut self,
-        _state: VmLocalStateData<'_>,
-        _data: BeforeExecutionData,
+        state: VmLocalStateData<'_>,
+        data: BeforeExecutionData,
         _memory: &SimpleMemory<H>,
         storage: StoragePtr<S>,
     ) {
-        if self.config.diff_mode {
-            self.pre
-                .extend(process_modified_storage_keys(self.pre.clone(), &storage));
+        let this_address = state.vm_local_state.callstack.current.this_address;
+        match data.opcode.variant.opcode {
+            Opcode::FarCall(_) => {
+                let called_address = u256_to_account_address(&data.src1_value.value);
+                self.touch_account(this_address, &storage);
+                self.touch_account(called_address, &storage);
+            }
+            Opcode::Log(LogOpcode::StorageRead | LogOpcode::StorageWrite) => {
+                self.touch_storage_slot(this_address, data.src0_value.value, &storage);
+            }
+            _ => {}
         }
     }
 }
@@ -36,34 +43,11 @@ impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for PrestateTracer {
         _bootloader_state: &BootloaderState,
         _stop_reason: crate::interface::tracer::VmExecutionStopReason,
     ) {
-        let modified_storage_keys = state.storage.storage.inner().get_modified_storage_keys();
-        if self.config.diff_mode {
-            self.post = modified_storage_keys
-                .iter()
-                .map(|k| get_account_data(k.0, state, &modified_storage_keys))
-                .collect::<State>();
-        } else {
-            let read_keys: &HashMap<StorageKey, H256> = &state
-                .storage
-                .storage
-                .inner()
-                .get_ptr()
-                .borrow()
-                .read_storage_keys()
-                .iter()
-                .map(|(k, v)| (*k, *v))
-                .collect();
-            let res = read_keys
-                .iter()
-                .map(|k| get_account_data(k.0, state, &modified_storage_keys))
-                .collect::<State>();
-            self.post = res;
-        }
-        process_result(&self.result, self.pre.clone(), self.post.clone());
+        self.process_result(state);
     }
 }
 
-impl<S: zksync_state::WriteStorage, H: HistoryMode> StorageAccess for ZkSyncVmState<S, H> {
+impl<S: WriteStorage, H: HistoryMode> StorageAccess for ZkSyncVmState<S, H> {
     fn read_from_storage(&self, key: &StorageKey) -> U256 {
         self.storage.storage.read_from_storage(key)
     }
@@ -0,0 +1,52 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+
+use once_cell::sync::OnceCell;
+use zksync_types::{Address, H256, U256};
+use zksync_utils::u256_to_h256;
+
+use crate::{glue::tracers::IntoOldVmTracer, tracers::is_kernel_space_address};
+
+pub mod vm_1_4_1;
+pub mod vm_1_4_2;
+pub mod vm_boojum_integration;
+pub mod vm_latest;
+pub mod vm_refunds_enhancement;
+pub mod vm_virtual_blocks;
+
+/// Storage slots accessed during execution, grouped by the contract address.
+pub type StorageAccesses = BTreeMap<Address, BTreeSet<H256>>;
+
+/// Tracer collecting storage slots read or written during execution. Accesses to the storage
+/// of system contracts (i.e., contracts in the kernel address space) are not recorded.
+#[derive(Debug, Clone)]
+pub struct StorageAccessTracer {
+    accesses: StorageAccesses,
+    result: Arc<OnceCell<StorageAccesses>>,
+}
+
+impl StorageAccessTracer {
+    pub fn new(result: Arc<OnceCell<StorageAccesses>>) -> Self {
+        Self {
+            accesses: StorageAccesses::new(),
+            result,
+        }
+    }
+
+    fn record_access(&mut self, address: Address, key: U256) {
+        if !is_kernel_space_address(&address) {
+            self.accesses
+                .entry(address)
+                .or_default()
+                .insert(u256_to_h256(key));
+        }
+    }
+
+    fn save_result(&mut self) {
+        self.result.set(self.accesses.clone()).unwrap();
+    }
+}
+
+impl IntoOldVmTracer for StorageAccessTracer {}
@@ -0,0 +1,39 @@
+use zk_evm_1_4_1::{
+    tracing::{BeforeExecutionData, VmLocalStateData},
+    zkevm_opcode_defs::{LogOpcode, Opcode},
+};
+use zksync_state::{StoragePtr, WriteStorage};
+
+use super::StorageAccessTracer;
+use crate::{
+    interface::{dyn_tracers::vm_1_4_1::DynTracer, tracer::VmExecutionStopReason},
+    vm_1_4_1::{BootloaderState, HistoryMode, SimpleMemory, VmTracer, ZkSyncVmState},
+};
+
+impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for StorageAccessTracer {
+    fn before_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        data: BeforeExecutionData,
+        _memory: &SimpleMemory<H>,
+        _storage: StoragePtr<S>,
+    ) {
+        if let Opcode::Log(LogOpcode::StorageRead | LogOpcode::StorageWrite) =
+            data.opcode.variant.opcode
+        {
+            let this_address = state.vm_local_state.callstack.current.this_address;
+            self.record_access(this_address, data.src0_value.value);
+        }
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for StorageAccessTracer {
+    fn after_vm_execution(
+        &mut self,
+        _state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        self.save_result();
+    }
+}
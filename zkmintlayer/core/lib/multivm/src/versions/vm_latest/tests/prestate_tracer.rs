@@ -47,7 +47,8 @@ fn test_prestate_tracer() {
         .take()
         .unwrap_or_default();
 
-    assert!(prestate_result.1.contains_key(&contract_address));
+    assert!(prestate_result.0.contains_key(&contract_address));
+    assert!(prestate_result.1.is_empty());
 }
 
 #[test]
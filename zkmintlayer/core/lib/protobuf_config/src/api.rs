@@ -128,6 +128,11 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .transpose()
                 .context("latest_values_cache_size_mb")?,
             fee_history_limit: self.fee_history_limit,
+            simulate_calls_limit: self
+                .simulate_calls_limit
+                .map(|x| x.try_into())
+                .transpose()
+                .context("simulate_calls_limit")?,
             max_batch_request_size: self
                 .max_batch_request_size
                 .map(|x| x.try_into())
@@ -203,6 +208,7 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .latest_values_cache_size_mb
                 .map(|x| x.try_into().unwrap()),
             fee_history_limit: this.fee_history_limit,
+            simulate_calls_limit: this.simulate_calls_limit.map(|x| x.try_into().unwrap()),
             max_batch_request_size: this.max_batch_request_size.map(|x| x.try_into().unwrap()),
             max_response_body_size_mb: this
                 .max_response_body_size_mb
@@ -1,8 +1,27 @@
-use zksync_config::configs::{self};
+use anyhow::Context as _;
+use zksync_config::configs::{self, da_dispatcher::DAClient};
 use zksync_protobuf::ProtoRepr;
 
 use crate::proto::da_dispatcher as proto;
 
+impl proto::DaClient {
+    fn new(x: &DAClient) -> Self {
+        match x {
+            DAClient::NoDA => Self::NoDa,
+            DAClient::ObjectStore => Self::ObjectStore,
+            DAClient::Mintlayer => Self::Mintlayer,
+        }
+    }
+
+    fn parse(&self) -> DAClient {
+        match self {
+            Self::NoDa => DAClient::NoDA,
+            Self::ObjectStore => DAClient::ObjectStore,
+            Self::Mintlayer => DAClient::Mintlayer,
+        }
+    }
+}
+
 impl ProtoRepr for proto::DataAvailabilityDispatcher {
     type Type = configs::da_dispatcher::DADispatcherConfig;
 
@@ -11,6 +30,12 @@ impl ProtoRepr for proto::DataAvailabilityDispatcher {
             polling_interval_ms: self.polling_interval_ms,
             max_rows_to_dispatch: self.max_rows_to_dispatch,
             max_retries: self.max_retries.map(|x| x as u16),
+            client: self
+                .client
+                .map(proto::DaClient::try_from)
+                .transpose()
+                .context("client")?
+                .map(|client| client.parse()),
         })
     }
 
@@ -19,6 +44,10 @@ impl ProtoRepr for proto::DataAvailabilityDispatcher {
             polling_interval_ms: this.polling_interval_ms,
             max_rows_to_dispatch: this.max_rows_to_dispatch,
             max_retries: this.max_retries.map(Into::into),
+            client: this
+                .client
+                .as_ref()
+                .map(|client| proto::DaClient::new(client).into()),
         }
     }
 }
@@ -20,6 +20,7 @@ mod experimental;
 mod general;
 mod genesis;
 mod house_keeper;
+mod mintlayer;
 mod object_store;
 mod observability;
 mod proof_data_handler;
@@ -0,0 +1,85 @@
+use anyhow::Context as _;
+use zksync_config::configs::mintlayer::{IpfsBackend, MintlayerConfig};
+use zksync_protobuf::{required, ProtoRepr};
+
+use crate::proto::mintlayer as proto;
+
+impl proto::IpfsBackend {
+    fn new(x: &IpfsBackend) -> Self {
+        match x {
+            IpfsBackend::S3 => Self::S3,
+            IpfsBackend::Kubo => Self::Kubo,
+        }
+    }
+
+    fn parse(&self) -> IpfsBackend {
+        match self {
+            Self::S3 => IpfsBackend::S3,
+            Self::Kubo => IpfsBackend::Kubo,
+        }
+    }
+}
+
+impl ProtoRepr for proto::Mintlayer {
+    type Type = MintlayerConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            rpc_url: required(&self.rpc_url).context("rpc_url")?.clone(),
+            rpc_username: self.rpc_username.clone(),
+            account_index: self.account_index,
+            batch_size: self.batch_size,
+            max_operations_per_anchor: self.max_operations_per_anchor,
+            anchor_deadline_sec: self.anchor_deadline_sec,
+            flush_on_shutdown: self.flush_on_shutdown,
+            first_anchored_l1_batch: self.first_anchored_l1_batch,
+            confirmations: self.confirmations,
+            resubmission_grace_blocks: self.resubmission_grace_blocks,
+            compress_documents: self.compress_documents,
+            fee_in_top_x_mb: self.fee_in_top_x_mb,
+            fee_bump_divisor: self.fee_bump_divisor,
+            balance_check_interval_sec: self.balance_check_interval_sec,
+            min_balance_atoms: self.min_balance_atoms,
+            ipfs_backend: self
+                .ipfs_backend
+                .map(proto::IpfsBackend::try_from)
+                .transpose()
+                .context("ipfs_backend")?
+                .map(|backend| backend.parse()),
+            ipfs_endpoint: self.ipfs_endpoint.clone(),
+            ipfs_region: self.ipfs_region.clone(),
+            ipfs_bucket_name: self.ipfs_bucket_name.clone(),
+            ipfs_gateway_url: self.ipfs_gateway_url.clone(),
+            ipfs_api_url: self.ipfs_api_url.clone(),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            rpc_url: Some(this.rpc_url.clone()),
+            rpc_username: this.rpc_username.clone(),
+            account_index: this.account_index,
+            batch_size: this.batch_size,
+            max_operations_per_anchor: this.max_operations_per_anchor,
+            anchor_deadline_sec: this.anchor_deadline_sec,
+            flush_on_shutdown: this.flush_on_shutdown,
+            first_anchored_l1_batch: this.first_anchored_l1_batch,
+            confirmations: this.confirmations,
+            resubmission_grace_blocks: this.resubmission_grace_blocks,
+            compress_documents: this.compress_documents,
+            fee_in_top_x_mb: this.fee_in_top_x_mb,
+            fee_bump_divisor: this.fee_bump_divisor,
+            balance_check_interval_sec: this.balance_check_interval_sec,
+            min_balance_atoms: this.min_balance_atoms,
+            ipfs_backend: this
+                .ipfs_backend
+                .as_ref()
+                .map(|backend| proto::IpfsBackend::new(backend).into()),
+            ipfs_endpoint: this.ipfs_endpoint.clone(),
+            ipfs_region: this.ipfs_region.clone(),
+            ipfs_bucket_name: this.ipfs_bucket_name.clone(),
+            ipfs_gateway_url: this.ipfs_gateway_url.clone(),
+            ipfs_api_url: this.ipfs_api_url.clone(),
+        }
+    }
+}
@@ -6,7 +6,7 @@ use zksync_basic_types::url::SensitiveUrl;
 use zksync_config::configs::{
     consensus::{ConsensusSecrets, NodeSecretKey, ValidatorSecretKey},
     secrets::Secrets,
-    DatabaseSecrets, L1Secrets,
+    DatabaseSecrets, L1Secrets, MintlayerSecrets,
 };
 use zksync_protobuf::{required, ProtoRepr};
 
@@ -20,6 +20,7 @@ impl ProtoRepr for proto::Secrets {
             consensus: read_optional_repr(&self.consensus).context("consensus")?,
             database: read_optional_repr(&self.database).context("database")?,
             l1: read_optional_repr(&self.l1).context("l1")?,
+            mintlayer: read_optional_repr(&self.mintlayer).context("mintlayer")?,
         })
     }
 
@@ -28,6 +29,7 @@ impl ProtoRepr for proto::Secrets {
             database: this.database.as_ref().map(ProtoRepr::build),
             l1: this.l1.as_ref().map(ProtoRepr::build),
             consensus: this.consensus.as_ref().map(ProtoRepr::build),
+            mintlayer: this.mintlayer.as_ref().map(ProtoRepr::build),
         }
     }
 }
@@ -115,3 +117,31 @@ impl ProtoRepr for proto::ConsensusSecrets {
         }
     }
 }
+
+impl ProtoRepr for proto::MintlayerSecrets {
+    type Type = MintlayerSecrets;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            rpc_password: self.rpc_password.clone().map(Into::into),
+            ipfs_api_key: self.ipfs_api_key.clone().map(Into::into),
+            ipfs_secret_key: self.ipfs_secret_key.clone().map(Into::into),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            rpc_password: this
+                .rpc_password
+                .as_ref()
+                .map(|x| x.expose_secret().clone()),
+            ipfs_api_key: this
+                .ipfs_api_key
+                .as_ref()
+                .map(|x| x.expose_secret().clone()),
+            ipfs_secret_key: this
+                .ipfs_secret_key
+                .as_ref()
+                .map(|x| x.expose_secret().clone()),
+        }
+    }
+}
@@ -35,6 +35,7 @@ fn test_encoding() {
     test_encode_all_formats::<ReprConv<proto::prover::WitnessGenerator>>(rng);
     test_encode_all_formats::<ReprConv<proto::prover::WitnessVectorGenerator>>(rng);
     test_encode_all_formats::<ReprConv<proto::house_keeper::HouseKeeper>>(rng);
+    test_encode_all_formats::<ReprConv<proto::mintlayer::Mintlayer>>(rng);
     test_encode_all_formats::<ReprConv<proto::object_store::ObjectStore>>(rng);
     test_encode_all_formats::<ReprConv<proto::prover::ProofDataHandler>>(rng);
     test_encode_all_formats::<ReprConv<proto::snapshot_creator::SnapshotsCreator>>(rng);
@@ -222,6 +222,7 @@ fn l1_batch_details(number: L1BatchNumber, root_hash: H256) -> api::L1BatchDetai
     api::L1BatchDetails {
         number,
         base: block_details_base(root_hash),
+        mintlayer_anchor: None,
     }
 }
 
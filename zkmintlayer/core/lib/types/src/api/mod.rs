@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
@@ -8,16 +10,20 @@ use zksync_basic_types::{
 };
 use zksync_contracts::BaseSystemContractsHashes;
 
+use self::state_override::StateOverride;
 pub use crate::transaction_request::{
     Eip712Meta, SerializationTransactionError, TransactionRequest,
 };
 use crate::{
+    mintlayer::{MintlayerAnchorLeaf, MintlayerAnchorProof},
     protocol_version::L1VerifierConfig,
+    transaction_request::CallRequest,
     vm_trace::{Call, CallType},
     Address, L2BlockNumber, ProtocolVersionId,
 };
 
 pub mod en;
+pub mod state_override;
 
 /// Block Number
 #[derive(Copy, Clone, Debug, PartialEq, Display)]
@@ -648,6 +654,53 @@ impl From<Call> for DebugCall {
     }
 }
 
+/// Account state returned by the `prestateTracer`.
+///
+/// Unlike in Geth, the returned `nonce` is the transaction nonce of the account (i.e., it doesn't include
+/// the deployment nonce).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PrestateAccount {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<H256, H256>,
+}
+
+/// Output of the `prestateTracer`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PrestateTrace {
+    /// Output in the diff mode.
+    Diff {
+        pre: BTreeMap<Address, PrestateAccount>,
+        post: BTreeMap<Address, PrestateAccount>,
+    },
+    /// Output in the default mode: state of all touched accounts before the execution.
+    Prestate(BTreeMap<Address, PrestateAccount>),
+}
+
+/// Output of `debug_traceTransaction` / `debug_traceCall`, depending on the requested tracer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DebugTrace {
+    Call(DebugCall),
+    Prestate(PrestateTrace),
+}
+
+impl DebugTrace {
+    /// Returns the call trace, or `None` if the trace was produced by another tracer.
+    pub fn into_call(self) -> Option<DebugCall> {
+        match self {
+            Self::Call(call) => Some(call),
+            Self::Prestate(_) => None,
+        }
+    }
+}
+
 // TODO (PLA-965): remove deprecated fields from the struct. It is currently in a "migration" phase
 // to keep compatibility between old and new versions.
 #[derive(Default, Serialize, Deserialize, Clone, Debug)]
@@ -729,20 +782,38 @@ impl ProtocolVersion {
 #[serde(rename_all = "camelCase")]
 pub enum SupportedTracers {
     CallTracer,
+    PrestateTracer,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct CallTracerConfig {
+    #[serde(default)]
     pub only_top_call: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PrestateTracerConfig {
+    #[serde(default)]
+    pub diff_mode: bool,
+}
+
+/// Options for all supported tracers. Options not applicable to the selected tracer are ignored.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TracerOptions {
+    #[serde(flatten)]
+    pub call_tracer: CallTracerConfig,
+    #[serde(flatten)]
+    pub prestate_tracer: PrestateTracerConfig,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TracerConfig {
     pub tracer: SupportedTracers,
     #[serde(default)]
-    pub tracer_config: CallTracerConfig,
+    pub tracer_config: TracerOptions,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -791,6 +862,57 @@ pub struct L1BatchDetails {
     pub number: L1BatchNumber,
     #[serde(flatten)]
     pub base: BlockDetailsBase,
+    /// Anchoring status of the batch operations on Mintlayer. `None` if no operations for the batch
+    /// were uploaded to IPFS.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mintlayer_anchor: Option<MintlayerBatchAnchor>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MintlayerAnchorStatus {
+    /// Anchor root is saved, but not deposited on Mintlayer yet.
+    Pending,
+    /// Anchor root is deposited, but the Mintlayer transaction is not included into a block yet.
+    Sent,
+    /// Mintlayer transaction is included into a block, but doesn't have enough confirmations yet.
+    Included,
+    Confirmed,
+    /// Anchor covers L1 batches that were reverted, so it must not be trusted.
+    Revoked,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MintlayerAnchorInfo {
+    pub root_cid: String,
+    /// `None` for legacy anchors that committed to the root document only.
+    pub merkle_root: Option<H256>,
+    pub mintlayer_tx_id: Option<String>,
+    pub status: MintlayerAnchorStatus,
+    pub included_at_block: Option<u64>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    /// ID of the Mintlayer transaction that deposited the revocation record for a revoked anchor.
+    pub revocation_tx_id: Option<String>,
+    /// Inclusion proof for the operation against `merkle_root`.
+    pub proof: Option<MintlayerAnchorProof>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MintlayerOperationInfo {
+    #[serde(flatten)]
+    pub operation: MintlayerAnchorLeaf,
+    /// Anchor covering the operation; `None` if the operation is queued for anchoring.
+    pub anchor: Option<MintlayerAnchorInfo>,
+}
+
+/// Information about anchoring commit / prove / execute operations of an L1 batch on Mintlayer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MintlayerBatchAnchor {
+    pub l1_batch_number: L1BatchNumber,
+    pub operations: Vec<MintlayerOperationInfo>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -825,6 +947,92 @@ pub struct ApiStorageLog {
     pub written_value: U256,
 }
 
+/// Result of `eth_createAccessList`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListResult {
+    /// Storage slots accessed by the transaction, grouped by the contract address.
+    pub access_list: AccessList,
+    /// Gas used by the transaction.
+    pub gas_used: U256,
+    /// Error message if the transaction reverted or was halted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Payload of `eth_simulateV1`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+    /// Simulated L2 blocks, executed one after another on top of the base block.
+    pub block_state_calls: Vec<SimulatedBlockCalls>,
+}
+
+/// Calls executed in a simulated L2 block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlockCalls {
+    /// State overrides applied before executing the block calls.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateOverride>,
+    pub calls: Vec<SimulatedCall>,
+}
+
+/// Call executed in a simulated L2 block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedCall {
+    #[serde(flatten)]
+    pub request: CallRequest,
+    /// Per-call state overrides. Not supported by `eth_simulateV1`; overrides can only be specified for entire blocks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateOverride>,
+}
+
+impl From<CallRequest> for SimulatedCall {
+    fn from(request: CallRequest) -> Self {
+        Self {
+            request,
+            state_overrides: None,
+        }
+    }
+}
+
+/// L2 block returned by `eth_simulateV1`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlock {
+    pub number: U64,
+    pub timestamp: U64,
+    /// Total gas used by the block calls.
+    pub gas_used: U256,
+    pub calls: Vec<SimulatedCallResult>,
+}
+
+/// Result of a call executed by `eth_simulateV1`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedCallResult {
+    /// 1 if the call succeeded, 0 otherwise.
+    pub status: U64,
+    pub return_data: Bytes,
+    pub gas_used: U256,
+    /// Events emitted by the call. Empty if the call failed.
+    pub logs: Vec<Log>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<SimulatedCallError>,
+}
+
+/// Error of a failed call executed by `eth_simulateV1`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimulatedCallError {
+    /// Error code: 3 if the call reverted, -32015 if the VM halted.
+    pub code: i64,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Bytes>,
+}
+
 /// Raw transaction execution data.
 /// Data is taken from `TransactionExecutionMetrics`.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -866,4 +1074,79 @@ mod tests {
         serde_json::from_str::<OldProtocolVersion>(&serde_json::to_string(&new_version).unwrap())
             .unwrap();
     }
+    #[test]
+    fn deserializing_tracer_config() {
+        let config: TracerConfig = serde_json::from_value(serde_json::json!({
+            "tracer": "prestateTracer",
+            "tracerConfig": { "diffMode": true },
+        }))
+        .unwrap();
+        assert!(matches!(config.tracer, SupportedTracers::PrestateTracer));
+        assert!(config.tracer_config.prestate_tracer.diff_mode);
+        assert!(!config.tracer_config.call_tracer.only_top_call);
+
+        let config: TracerConfig = serde_json::from_value(serde_json::json!({
+            "tracer": "callTracer",
+            "tracerConfig": { "onlyTopCall": true },
+        }))
+        .unwrap();
+        assert!(matches!(config.tracer, SupportedTracers::CallTracer));
+        assert!(config.tracer_config.call_tracer.only_top_call);
+        assert!(!config.tracer_config.prestate_tracer.diff_mode);
+    }
+
+    #[test]
+    fn prestate_trace_serialization() {
+        let address = Address::repeat_byte(1);
+        let account = PrestateAccount {
+            balance: Some(100.into()),
+            nonce: Some(1),
+            code: None,
+            storage: BTreeMap::from([(H256::zero(), H256::repeat_byte(2))]),
+        };
+        let trace = DebugTrace::Prestate(PrestateTrace::Prestate(BTreeMap::from([(
+            address,
+            account.clone(),
+        )])));
+        let trace_json = serde_json::to_value(&trace).unwrap();
+        assert_eq!(
+            trace_json,
+            serde_json::json!({
+                "0x0101010101010101010101010101010101010101": {
+                    "balance": "0x64",
+                    "nonce": 1,
+                    "storage": {
+                        "0x0000000000000000000000000000000000000000000000000000000000000000":
+                            "0x0202020202020202020202020202020202020202020202020202020202020202",
+                    },
+                },
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<DebugTrace>(trace_json).unwrap(),
+            trace
+        );
+
+        let diff = DebugTrace::Prestate(PrestateTrace::Diff {
+            pre: BTreeMap::from([(address, account)]),
+            post: BTreeMap::from([(
+                address,
+                PrestateAccount {
+                    balance: Some(50.into()),
+                    ..PrestateAccount::default()
+                },
+            )]),
+        });
+        let diff_json = serde_json::to_value(&diff).unwrap();
+        assert_eq!(
+            diff_json["post"],
+            serde_json::json!({
+                "0x0101010101010101010101010101010101010101": { "balance": "0x32" },
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<DebugTrace>(diff_json).unwrap(),
+            diff
+        );
+    }
 }
@@ -0,0 +1,78 @@
+//! State overrides supported by the simulation methods of the Web3 API.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use zksync_basic_types::{web3::Bytes, Address, H256, U256};
+
+/// Collection of overridden accounts, keyed by the account address.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StateOverride(HashMap<Address, OverrideAccount>);
+
+impl StateOverride {
+    pub fn new(accounts: HashMap<Address, OverrideAccount>) -> Self {
+        Self(accounts)
+    }
+
+    pub fn get(&self, address: &Address) -> Option<&OverrideAccount> {
+        self.0.get(address)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Address, &OverrideAccount)> + '_ {
+        self.0.iter()
+    }
+}
+
+/// Overrides for a single account. All fields are optional; unspecified fields retain their values.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverrideAccount {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    /// Transaction nonce of the account. The deployment nonce is not affected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<U256>,
+    /// Deployed bytecode of the account. Must be a valid EraVM bytecode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// Replacement for the entire account storage; slots not mentioned here are considered zero.
+    /// Mutually exclusive with `state_diff`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<HashMap<H256, H256>>,
+    /// Overrides for individual storage slots. Mutually exclusive with `state`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_diff: Option<HashMap<H256, H256>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn deserializing_state_override() {
+        let address = Address::repeat_byte(1);
+        let state_override: StateOverride = serde_json::from_value(json!({
+            format!("{address:?}"): {
+                "balance": "0x100",
+                "nonce": "0x1",
+                "code": "0x0000",
+                "stateDiff": {
+                    format!("{:?}", H256::zero()): format!("{:?}", H256::repeat_byte(2)),
+                },
+            },
+        }))
+        .unwrap();
+
+        let account = state_override.get(&address).unwrap();
+        assert_eq!(account.balance, Some(0x100.into()));
+        assert_eq!(account.nonce, Some(1.into()));
+        assert_eq!(account.code, Some(Bytes(vec![0; 2])));
+        assert_eq!(account.state, None);
+        assert_eq!(
+            account.state_diff,
+            Some(HashMap::from([(H256::zero(), H256::repeat_byte(2))]))
+        );
+    }
+}
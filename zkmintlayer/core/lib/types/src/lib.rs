@@ -44,6 +44,7 @@ pub mod fee_model;
 pub mod l1;
 pub mod l2;
 pub mod l2_to_l1_log;
+pub mod mintlayer;
 pub mod priority_op_onchain_data;
 pub mod protocol_upgrade;
 pub mod pubdata_da;
@@ -0,0 +1,374 @@
+//! Merkle commitment to aggregated operations covered by a Mintlayer anchor.
+
+use std::ops;
+
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use zksync_basic_types::web3::keccak256;
+use zksync_crypto::hasher::{keccak::KeccakHasher, Hasher};
+use zksync_mini_merkle_tree::{HashEmptySubtree, MiniMerkleTree};
+
+use crate::{aggregated_operations::AggregatedActionType, L1BatchNumber, H256};
+
+/// Size of an encoded leaf: action type (1 byte), first and last L1 batch numbers (4 bytes each, big-endian),
+/// and keccak-256 hash of the CID (32 bytes).
+const LEAF_SIZE: usize = 41;
+/// Version of the [`MintlayerAnchorPayload`] encoding.
+const PAYLOAD_VERSION: u8 = 1;
+/// Tag of the [`MintlayerAnchorRevocation`] encoding.
+const REVOCATION_TAG: u8 = 2;
+
+/// Aggregated operation committed to by a Mintlayer anchor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MintlayerAnchorLeaf {
+    #[serde(with = "action_type_serde")]
+    pub action_type: AggregatedActionType,
+    pub l1_batch_range: ops::RangeInclusive<L1BatchNumber>,
+    /// IPFS CID of the operation document.
+    pub cid: String,
+}
+
+impl MintlayerAnchorLeaf {
+    fn encode(&self) -> EncodedLeaf {
+        let mut bytes = [0_u8; LEAF_SIZE];
+        bytes[0] = match self.action_type {
+            AggregatedActionType::Commit => 0,
+            AggregatedActionType::PublishProofOnchain => 1,
+            AggregatedActionType::Execute => 2,
+        };
+        bytes[1..5].copy_from_slice(&self.l1_batch_range.start().0.to_be_bytes());
+        bytes[5..9].copy_from_slice(&self.l1_batch_range.end().0.to_be_bytes());
+        bytes[9..].copy_from_slice(&keccak256(self.cid.as_bytes()));
+        EncodedLeaf(bytes)
+    }
+
+    /// Returns the hash of this leaf in the Merkle tree.
+    pub fn hash(&self) -> H256 {
+        KeccakHasher.hash_bytes(&self.encode().0)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct EncodedLeaf([u8; LEAF_SIZE]);
+
+impl AsRef<[u8]> for EncodedLeaf {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl HashEmptySubtree<EncodedLeaf> for KeccakHasher {
+    // The default implementation caches hashes in a static shared by all leaf types, so we need a separate cache.
+    fn empty_subtree_hash(&self, depth: usize) -> H256 {
+        static EMPTY_TREE_HASHES: OnceCell<Vec<H256>> = OnceCell::new();
+        let hashes = EMPTY_TREE_HASHES.get_or_init(|| {
+            let empty_leaf_hash = HashEmptySubtree::<EncodedLeaf>::empty_leaf_hash(self);
+            std::iter::successors(Some(empty_leaf_hash), |hash| {
+                Some(KeccakHasher.compress(hash, hash))
+            })
+            .take(MintlayerAnchorProof::MAX_DEPTH + 1)
+            .collect()
+        });
+        hashes[depth]
+    }
+
+    fn empty_leaf_hash(&self) -> H256 {
+        self.hash_bytes(&[0_u8; LEAF_SIZE])
+    }
+}
+
+/// Merkle inclusion proof for a single [`MintlayerAnchorLeaf`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MintlayerAnchorProof {
+    /// 0-based index of the leaf in the tree.
+    pub leaf_index: u32,
+    /// Hashes of sibling nodes starting from the bottom of the tree.
+    pub merkle_path: Vec<H256>,
+}
+
+impl MintlayerAnchorProof {
+    /// Maximum supported depth of the tree, matching the one of [`MiniMerkleTree`].
+    const MAX_DEPTH: usize = 32;
+
+    /// Computes the Merkle root for the specified leaf using this proof.
+    pub fn merkle_root(&self, leaf: &MintlayerAnchorLeaf) -> H256 {
+        let mut index = self.leaf_index;
+        let mut hash = leaf.hash();
+        for sibling in &self.merkle_path {
+            hash = if index % 2 == 0 {
+                KeccakHasher.compress(&hash, sibling)
+            } else {
+                KeccakHasher.compress(sibling, &hash)
+            };
+            index /= 2;
+        }
+        hash
+    }
+
+    /// Checks whether this proof proves inclusion of `leaf` into the tree with the specified root.
+    pub fn verify(&self, leaf: &MintlayerAnchorLeaf, merkle_root: H256) -> bool {
+        self.merkle_path.len() <= Self::MAX_DEPTH
+            && (u64::from(self.leaf_index) >> self.merkle_path.len()) == 0
+            && self.merkle_root(leaf) == merkle_root
+    }
+}
+
+/// Binary Merkle tree over operations covered by a Mintlayer anchor. The root of this tree is deposited on Mintlayer.
+#[derive(Debug, Clone)]
+pub struct MintlayerAnchorCommitment {
+    leaves: Vec<MintlayerAnchorLeaf>,
+    tree: MiniMerkleTree<EncodedLeaf>,
+}
+
+impl MintlayerAnchorCommitment {
+    /// Creates a commitment to the specified leaves. The order of leaves matters.
+    pub fn new(leaves: Vec<MintlayerAnchorLeaf>) -> Self {
+        let tree = MiniMerkleTree::new(leaves.iter().map(MintlayerAnchorLeaf::encode), None);
+        Self { leaves, tree }
+    }
+
+    /// Returns the leaves of the tree.
+    pub fn leaves(&self) -> &[MintlayerAnchorLeaf] {
+        &self.leaves
+    }
+
+    pub fn merkle_root(&self) -> H256 {
+        self.tree.merkle_root()
+    }
+
+    /// Returns an inclusion proof for a leaf with the specified index, or `None` if the index is out of bounds.
+    pub fn proof(&self, leaf_index: usize) -> Option<MintlayerAnchorProof> {
+        if leaf_index >= self.leaves.len() {
+            return None;
+        }
+        let (_, merkle_path) = self.tree.merkle_root_and_path(leaf_index);
+        Some(MintlayerAnchorProof {
+            leaf_index: leaf_index as u32,
+            merkle_path,
+        })
+    }
+
+    /// Finds the leaf for an operation of the specified type covering `l1_batch_number` and returns it together
+    /// with the inclusion proof.
+    pub fn find_operation(
+        &self,
+        action_type: AggregatedActionType,
+        l1_batch_number: L1BatchNumber,
+    ) -> Option<(&MintlayerAnchorLeaf, MintlayerAnchorProof)> {
+        let leaf_index = self.leaves.iter().position(|leaf| {
+            leaf.action_type == action_type && leaf.l1_batch_range.contains(&l1_batch_number)
+        })?;
+        Some((&self.leaves[leaf_index], self.proof(leaf_index)?))
+    }
+}
+
+/// Data deposited on Mintlayer for an anchor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MintlayerAnchorPayload {
+    /// Root of the [`MintlayerAnchorCommitment`]. `None` for legacy anchors that only deposited the root CID.
+    pub merkle_root: Option<H256>,
+    /// IPFS CID of the root document listing all leaves of the commitment.
+    pub root_cid: String,
+}
+
+impl MintlayerAnchorPayload {
+    /// Encodes this payload. Versioned payloads are encoded as the version byte, followed by the Merkle root
+    /// and the UTF-8 root CID; legacy payloads consist of the root CID only.
+    pub fn encode(&self) -> Vec<u8> {
+        match self.merkle_root {
+            Some(merkle_root) => {
+                let mut bytes = Vec::with_capacity(1 + 32 + self.root_cid.len());
+                bytes.push(PAYLOAD_VERSION);
+                bytes.extend_from_slice(merkle_root.as_bytes());
+                bytes.extend_from_slice(self.root_cid.as_bytes());
+                bytes
+            }
+            None => self.root_cid.as_bytes().to_vec(),
+        }
+    }
+
+    /// Decodes a payload encoded with [`Self::encode()`].
+    pub fn decode(bytes: &[u8]) -> anyhow::Result<Self> {
+        match bytes.first() {
+            None => anyhow::bail!("empty Mintlayer anchor payload"),
+            Some(&PAYLOAD_VERSION) => {
+                anyhow::ensure!(bytes.len() > 33, "Mintlayer anchor payload is too short");
+                let root_cid = std::str::from_utf8(&bytes[33..])?.to_owned();
+                Ok(Self {
+                    merkle_root: Some(H256::from_slice(&bytes[1..33])),
+                    root_cid,
+                })
+            }
+            Some(&REVOCATION_TAG) => anyhow::bail!("data is a Mintlayer anchor revocation record"),
+            // CIDs are ASCII strings, so they cannot start with the version byte.
+            Some(_) => Ok(Self {
+                merkle_root: None,
+                root_cid: std::str::from_utf8(bytes)?.to_owned(),
+            }),
+        }
+    }
+}
+
+/// Data deposited on Mintlayer to revoke an anchor covering L1 batches that were removed by the block reverter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MintlayerAnchorRevocation {
+    /// Payload of the revoked anchor.
+    pub revoked: MintlayerAnchorPayload,
+}
+
+impl MintlayerAnchorRevocation {
+    /// Encodes this record as the revocation tag byte, followed by the encoded payload of the revoked anchor.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = vec![REVOCATION_TAG];
+        bytes.extend_from_slice(&self.revoked.encode());
+        bytes
+    }
+
+    /// Decodes a record encoded with [`Self::encode()`].
+    pub fn decode(bytes: &[u8]) -> anyhow::Result<Self> {
+        match bytes.split_first() {
+            Some((&REVOCATION_TAG, payload)) => Ok(Self {
+                revoked: MintlayerAnchorPayload::decode(payload)?,
+            }),
+            _ => anyhow::bail!("data is not a Mintlayer anchor revocation record"),
+        }
+    }
+}
+
+mod action_type_serde {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    use crate::aggregated_operations::AggregatedActionType;
+
+    pub(super) fn serialize<S: Serializer>(
+        action_type: &AggregatedActionType,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(action_type.as_str())
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<AggregatedActionType, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(count: u32) -> Vec<MintlayerAnchorLeaf> {
+        let action_types = [
+            AggregatedActionType::Commit,
+            AggregatedActionType::PublishProofOnchain,
+            AggregatedActionType::Execute,
+        ];
+        (0..count)
+            .map(|i| MintlayerAnchorLeaf {
+                action_type: action_types[i as usize % 3],
+                l1_batch_range: L1BatchNumber(i / 3 + 1)..=L1BatchNumber(i / 3 + 1),
+                cid: format!("cid{i}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn proofs_are_verified() {
+        for count in [1, 2, 3, 5, 8, 30] {
+            let commitment = MintlayerAnchorCommitment::new(leaves(count));
+            let root = commitment.merkle_root();
+            for (i, leaf) in commitment.leaves().iter().enumerate() {
+                let proof = commitment.proof(i).unwrap();
+                assert!(proof.verify(leaf, root), "count={count}, i={i}");
+
+                let mut other_leaf = leaf.clone();
+                other_leaf.cid.push('!');
+                assert!(!proof.verify(&other_leaf, root));
+            }
+            assert!(commitment.proof(count as usize).is_none());
+        }
+    }
+
+    #[test]
+    fn root_depends_on_batch_ranges() {
+        let mut leaves = leaves(6);
+        let root = MintlayerAnchorCommitment::new(leaves.clone()).merkle_root();
+        leaves[0].l1_batch_range = L1BatchNumber(1)..=L1BatchNumber(2);
+        assert_ne!(MintlayerAnchorCommitment::new(leaves).merkle_root(), root);
+    }
+
+    #[test]
+    fn finding_operation() {
+        let commitment = MintlayerAnchorCommitment::new(leaves(6));
+        let (leaf, proof) = commitment
+            .find_operation(AggregatedActionType::Execute, L1BatchNumber(2))
+            .unwrap();
+        assert_eq!(leaf.cid, "cid5");
+        assert_eq!(proof.leaf_index, 5);
+        assert!(proof.verify(leaf, commitment.merkle_root()));
+        assert!(commitment
+            .find_operation(AggregatedActionType::Commit, L1BatchNumber(3))
+            .is_none());
+    }
+
+    #[test]
+    fn leaf_serialization() {
+        let leaf = &leaves(2)[1];
+        let json = serde_json::to_value(leaf).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "actionType": "PublishProofBlocksOnchain",
+                "l1BatchRange": { "start": 1, "end": 1 },
+                "cid": "cid1",
+            })
+        );
+        let restored: MintlayerAnchorLeaf = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, *leaf);
+    }
+
+    #[test]
+    fn payload_roundtrip() {
+        let payload = MintlayerAnchorPayload {
+            merkle_root: Some(H256::repeat_byte(0x23)),
+            root_cid: "bafkreiroot".to_owned(),
+        };
+        let encoded = payload.encode();
+        assert_eq!(encoded[0], PAYLOAD_VERSION);
+        assert_eq!(MintlayerAnchorPayload::decode(&encoded).unwrap(), payload);
+
+        let legacy_payload = MintlayerAnchorPayload {
+            merkle_root: None,
+            root_cid: "QmRoot".to_owned(),
+        };
+        assert_eq!(legacy_payload.encode(), b"QmRoot");
+        assert_eq!(
+            MintlayerAnchorPayload::decode(b"QmRoot").unwrap(),
+            legacy_payload
+        );
+        MintlayerAnchorPayload::decode(&[PAYLOAD_VERSION; 10]).unwrap_err();
+    }
+
+    #[test]
+    fn revocation_roundtrip() {
+        let revocation = MintlayerAnchorRevocation {
+            revoked: MintlayerAnchorPayload {
+                merkle_root: Some(H256::repeat_byte(0x23)),
+                root_cid: "bafkreiroot".to_owned(),
+            },
+        };
+        let encoded = revocation.encode();
+        assert_eq!(encoded[0], REVOCATION_TAG);
+        assert_eq!(
+            MintlayerAnchorRevocation::decode(&encoded).unwrap(),
+            revocation
+        );
+        // Revocation records must not be mistaken for anchors and vice versa.
+        MintlayerAnchorPayload::decode(&encoded).unwrap_err();
+        MintlayerAnchorRevocation::decode(&revocation.revoked.encode()).unwrap_err();
+    }
+}
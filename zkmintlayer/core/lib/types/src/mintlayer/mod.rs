@@ -0,0 +1,94 @@
+//! Types related to anchoring L1 batch data on the Mintlayer chain.
+
+use std::ops;
+
+use chrono::{DateTime, Utc};
+
+pub use self::commitment::{
+    MintlayerAnchorCommitment, MintlayerAnchorLeaf, MintlayerAnchorPayload, MintlayerAnchorProof,
+    MintlayerAnchorRevocation,
+};
+use crate::{aggregated_operations::AggregatedActionType, L1BatchNumber, H256};
+
+mod commitment;
+
+/// Aggregated operation (commit / prove / execute) that was uploaded to IPFS and is either
+/// queued for anchoring on Mintlayer, or already anchored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MintlayerAnchorOperation {
+    pub id: u32,
+    /// ID of the `eth_txs` entry the operation was produced for. `None` if the L1 transaction
+    /// was removed from the database (e.g., by the block reverter).
+    pub eth_tx_id: Option<u32>,
+    pub action_type: AggregatedActionType,
+    pub l1_batch_range: ops::RangeInclusive<L1BatchNumber>,
+    /// IPFS CID of the uploaded operation document.
+    pub cid: String,
+    /// ID of the anchor covering this operation, or `None` if the operation is still queued.
+    pub anchor_id: Option<u32>,
+    /// Time when the operation was added to the anchoring queue.
+    pub created_at: DateTime<Utc>,
+}
+
+impl MintlayerAnchorOperation {
+    /// Returns the leaf representing this operation in the [`MintlayerAnchorCommitment`].
+    pub fn to_leaf(&self) -> MintlayerAnchorLeaf {
+        MintlayerAnchorLeaf {
+            action_type: self.action_type,
+            l1_batch_range: self.l1_batch_range.clone(),
+            cid: self.cid.clone(),
+        }
+    }
+}
+
+/// Root document deposited on Mintlayer that covers a group of aggregated operations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MintlayerAnchor {
+    pub id: u32,
+    /// IPFS CID of the root document.
+    pub root_cid: String,
+    /// Root of the [`MintlayerAnchorCommitment`] to the covered operations, or `None` for legacy anchors
+    /// that committed to the root document only.
+    pub merkle_root: Option<H256>,
+    /// ID of the Mintlayer transaction that deposited the root, or `None` if the deposit
+    /// hasn't succeeded yet.
+    pub mintlayer_tx_id: Option<String>,
+    /// Height of the Mintlayer block including the deposit transaction, or `None` if the transaction
+    /// is not included yet.
+    pub included_at_block: Option<u64>,
+    /// Time when the anchor got enough confirmations on Mintlayer.
+    pub confirmed_at: Option<DateTime<Utc>>,
+    /// Number of times the root was re-deposited because the previous transaction was dropped.
+    pub resubmissions: u32,
+    /// Time when the anchor was revoked because it covers L1 batches removed by the block reverter.
+    pub revoked_at: Option<DateTime<Utc>>,
+    /// ID of the Mintlayer transaction that deposited the revocation record for the anchor, or `None`
+    /// if the revocation wasn't published.
+    pub revocation_tx_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl MintlayerAnchor {
+    /// Returns the payload deposited on Mintlayer for this anchor.
+    pub fn payload(&self) -> MintlayerAnchorPayload {
+        MintlayerAnchorPayload {
+            merkle_root: self.merkle_root,
+            root_cid: self.root_cid.clone(),
+        }
+    }
+
+    /// Returns the revocation record deposited on Mintlayer for this anchor.
+    pub fn revocation(&self) -> MintlayerAnchorRevocation {
+        MintlayerAnchorRevocation {
+            revoked: self.payload(),
+        }
+    }
+}
+
+/// L1 transaction saved by the eth sender whose aggregated operation is not uploaded to IPFS yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MintlayerOperationToUpload {
+    pub eth_tx_id: u32,
+    pub action_type: AggregatedActionType,
+    pub l1_batch_range: ops::RangeInclusive<L1BatchNumber>,
+}
@@ -39,6 +39,12 @@ pub enum Web3Error {
     LogsLimitExceeded(usize, u32, u32),
     #[error("invalid filter: if blockHash is supplied fromBlock and toBlock must not be")]
     InvalidFilterBlockHash,
+    #[error("{0} is not supported by this method")]
+    UnsupportedTracer(&'static str),
+    #[error("invalid state override: {0}")]
+    InvalidStateOverride(String),
+    #[error("invalid simulation payload: {0}")]
+    InvalidSimulationPayload(String),
     /// Weaker form of a "method not found" error; the method implementation is technically present,
     /// but the node configuration prevents the method from functioning.
     #[error("Method not implemented")]
@@ -2,7 +2,10 @@
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
-    api::{BlockId, BlockIdVariant, BlockNumber, Transaction, TransactionVariant},
+    api::{
+        state_override::StateOverride, AccessListResult, BlockId, BlockIdVariant, BlockNumber,
+        SimulatePayload, SimulatedBlock, Transaction, TransactionVariant,
+    },
     transaction_request::CallRequest,
     Address, H256,
 };
@@ -31,10 +34,34 @@ pub trait EthNamespace {
     async fn chain_id(&self) -> RpcResult<U64>;
 
     #[method(name = "call")]
-    async fn call(&self, req: CallRequest, block: Option<BlockIdVariant>) -> RpcResult<Bytes>;
+    async fn call(
+        &self,
+        req: CallRequest,
+        block: Option<BlockIdVariant>,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<Bytes>;
+
+    #[method(name = "createAccessList")]
+    async fn create_access_list(
+        &self,
+        req: CallRequest,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<AccessListResult>;
+
+    #[method(name = "simulateV1")]
+    async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<Vec<SimulatedBlock>>;
 
     #[method(name = "estimateGas")]
-    async fn estimate_gas(&self, req: CallRequest, _block: Option<BlockNumber>) -> RpcResult<U256>;
+    async fn estimate_gas(
+        &self,
+        req: CallRequest,
+        _block: Option<BlockNumber>,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<U256>;
 
     #[method(name = "gasPrice")]
     async fn gas_price(&self) -> RpcResult<U256>;
@@ -178,7 +205,7 @@ pub trait EthNamespace {
 mod pub_sub {
     use jsonrpsee::{core::SubscriptionResult, proc_macros::rpc};
 
-    use crate::types::PubSubFilter;
+    use crate::types::PubSubParams;
 
     #[rpc(server, namespace = "eth")]
     pub trait EthPubSub {
@@ -186,7 +213,7 @@ mod pub_sub {
         async fn subscribe(
             &self,
             sub_type: String,
-            filter: Option<PubSubFilter>,
+            params: Option<PubSubParams>,
         ) -> SubscriptionResult;
     }
 }
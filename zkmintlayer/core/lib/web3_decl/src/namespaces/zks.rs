@@ -5,8 +5,8 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion,
-        TransactionDetailedResult, TransactionDetails,
+        BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, MintlayerBatchAnchor, Proof,
+        ProtocolVersion, TransactionDetailedResult, TransactionDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -100,6 +100,12 @@ pub trait ZksNamespace {
     async fn get_l1_batch_details(&self, batch: L1BatchNumber)
         -> RpcResult<Option<L1BatchDetails>>;
 
+    #[method(name = "getMintlayerAnchor")]
+    async fn get_mintlayer_anchor(
+        &self,
+        batch: L1BatchNumber,
+    ) -> RpcResult<Option<MintlayerBatchAnchor>>;
+
     #[method(name = "getBytecodeByHash")]
     async fn get_bytecode_by_hash(&self, hash: H256) -> RpcResult<Option<Vec<u8>>>;
 
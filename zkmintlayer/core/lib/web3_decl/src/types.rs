@@ -334,6 +334,17 @@ fn topic_to_option<T>(topic: ethabi::Topic<T>) -> Option<Vec<T>> {
     }
 }
 
+/// Parameters of an `eth_subscribe` request; their meaning depends on the subscription type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PubSubParams {
+    /// Filter for `logs` subscriptions.
+    Filter(PubSubFilter),
+    /// For `newPendingTransactions` subscriptions, specifies whether full transactions should be returned
+    /// instead of their hashes.
+    FullTransactions(bool),
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -342,6 +353,8 @@ pub enum PubSubResult {
     Log(Log),
     TxHash(H256),
     Syncing(bool),
+    Transaction(zksync_types::api::Transaction),
+    L1Batch(zksync_types::api::L1BatchDetails),
 }
 
 #[cfg(test)]
@@ -438,4 +451,22 @@ mod tests {
         let restored_value: ValueOrArray<Address> = serde_json::from_value(json).unwrap();
         assert_eq!(restored_value, value);
     }
+
+    #[test]
+    fn deserializing_pub_sub_params() {
+        let params: PubSubParams = serde_json::from_str("true").unwrap();
+        assert_eq!(params, PubSubParams::FullTransactions(true));
+
+        let params: PubSubParams = serde_json::from_value(serde_json::json!({
+            "address": "0x1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f",
+        }))
+        .unwrap();
+        assert_eq!(
+            params,
+            PubSubParams::Filter(PubSubFilter {
+                address: Some(Address::repeat_byte(0x1f).into()),
+                topics: None,
+            })
+        );
+    }
 }
@@ -94,6 +94,8 @@ pub enum Component {
     BaseTokenRatioPersister,
     /// VM runner-based component that saves VM execution data for basic witness generation.
     VmRunnerBwip,
+    /// Component anchoring aggregated L1 operations on Mintlayer via IPFS.
+    MintlayerAnchor,
 }
 
 #[derive(Debug)]
@@ -138,6 +140,7 @@ impl FromStr for Components {
                 Ok(Components(vec![Component::BaseTokenRatioPersister]))
             }
             "vm_runner_bwip" => Ok(Components(vec![Component::VmRunnerBwip])),
+            "mintlayer_anchor" => Ok(Components(vec![Component::MintlayerAnchor])),
             other => Err(format!("{} is not a valid component name", other)),
         }
     }
@@ -14,8 +14,8 @@ use zksync_config::{
         wallets::{AddressWallet, EthSender, StateKeeper, Wallet, Wallets},
         CommitmentGeneratorConfig, FriProofCompressorConfig, FriProverConfig,
         FriProverGatewayConfig, FriWitnessGeneratorConfig, FriWitnessVectorGeneratorConfig,
-        GeneralConfig, ObservabilityConfig, PrometheusConfig, ProofDataHandlerConfig,
-        ProtectiveReadsWriterConfig, PruningConfig, SnapshotRecoveryConfig,
+        GeneralConfig, MintlayerConfig, ObservabilityConfig, PrometheusConfig,
+        ProofDataHandlerConfig, ProtectiveReadsWriterConfig, PruningConfig, SnapshotRecoveryConfig,
     },
     ApiConfig, BaseTokenAdjusterConfig, ContractVerifierConfig, DADispatcherConfig, DBConfig,
     EthConfig, EthWatchConfig, GasAdjusterConfig, ObjectStoreConfig, PostgresConfig,
@@ -73,6 +73,7 @@ pub struct TempConfigStore {
     pub commitment_generator: Option<CommitmentGeneratorConfig>,
     pub pruning: Option<PruningConfig>,
     pub snapshot_recovery: Option<SnapshotRecoveryConfig>,
+    pub mintlayer_config: Option<MintlayerConfig>,
 }
 
 impl TempConfigStore {
@@ -106,6 +107,7 @@ impl TempConfigStore {
             commitment_generator: self.commitment_generator.clone(),
             snapshot_recovery: self.snapshot_recovery.clone(),
             pruning: self.pruning.clone(),
+            mintlayer_config: self.mintlayer_config.clone(),
         }
     }
 
@@ -23,7 +23,7 @@ use zksync_system_constants::{
     SYSTEM_CONTEXT_CURRENT_TX_ROLLING_HASH_POSITION, ZKPORTER_IS_AVAILABLE,
 };
 use zksync_types::{
-    api,
+    api::{self, state_override::StateOverride},
     block::{pack_block_info, unpack_block_info, L2BlockHasher},
     fee_model::BatchFeeInput,
     get_nonce_key,
@@ -34,11 +34,13 @@ use zksync_types::{
 use zksync_utils::{h256_to_u256, time::seconds_since_epoch, u256_to_h256};
 
 use super::{
+    storage::StorageWithOverrides,
     vm_metrics::{self, SandboxStage, SANDBOX_METRICS},
     BlockArgs, TxExecutionArgs, TxSharedArgs, VmPermit,
 };
 
-type BoxedVm<'a> = Box<VmInstance<StorageView<PostgresStorage<'a>>, HistoryDisabled>>;
+pub(super) type SandboxStorage<'a> = StorageView<StorageWithOverrides<PostgresStorage<'a>>>;
+type BoxedVm<'a> = Box<VmInstance<SandboxStorage<'a>, HistoryDisabled>>;
 
 #[derive(Debug)]
 struct Sandbox<'a> {
@@ -46,7 +48,7 @@ struct Sandbox<'a> {
     l1_batch_env: L1BatchEnv,
     execution_args: &'a TxExecutionArgs,
     l2_block_info_to_reset: Option<StoredL2BlockInfo>,
-    storage_view: StorageView<PostgresStorage<'a>>,
+    storage_view: SandboxStorage<'a>,
 }
 
 impl<'a> Sandbox<'a> {
@@ -55,6 +57,8 @@ impl<'a> Sandbox<'a> {
         shared_args: TxSharedArgs,
         execution_args: &'a TxExecutionArgs,
         block_args: BlockArgs,
+        state_override: Option<&StateOverride>,
+        deferred_state_overrides: &[StateOverride],
     ) -> anyhow::Result<Sandbox<'a>> {
         let resolve_started_at = Instant::now();
         let resolved_block_info = block_args
@@ -90,6 +94,11 @@ impl<'a> Sandbox<'a> {
         .context("cannot create `PostgresStorage`")?
         .with_caches(shared_args.caches.clone());
 
+        let mut storage = StorageWithOverrides::new(storage);
+        if let Some(state_override) = state_override {
+            storage = storage.with_state_override(state_override);
+        }
+        let storage = storage.with_overridden_bytecodes(deferred_state_overrides);
         let storage_view = StorageView::new(storage);
         let (system_env, l1_batch_env) = Self::prepare_env(
             shared_args,
@@ -259,7 +268,7 @@ impl<'a> Sandbox<'a> {
         mut self,
         tx: &Transaction,
         adjust_pubdata_price: bool,
-    ) -> (BoxedVm<'a>, StoragePtr<StorageView<PostgresStorage<'a>>>) {
+    ) -> (BoxedVm<'a>, StoragePtr<SandboxStorage<'a>>) {
         self.setup_storage_view(tx);
         let protocol_version = self.system_env.version;
         if adjust_pubdata_price {
@@ -295,8 +304,47 @@ pub(super) fn apply_vm_in_sandbox<T>(
     connection_pool: &ConnectionPool<Core>,
     tx: Transaction,
     block_args: BlockArgs,
+    state_override: Option<&StateOverride>,
+    apply: impl FnOnce(
+        &mut VmInstance<SandboxStorage<'_>, HistoryDisabled>,
+        Transaction,
+        ProtocolVersionId,
+    ) -> T,
+) -> anyhow::Result<T> {
+    apply_vm_in_sandbox_with_storage(
+        vm_permit,
+        shared_args,
+        adjust_pubdata_price,
+        execution_args,
+        connection_pool,
+        tx,
+        block_args,
+        state_override,
+        &[],
+        |vm, _, _, tx, protocol_version| apply(vm, tx, protocol_version),
+    )
+}
+
+/// Same as [`apply_vm_in_sandbox()`], but additionally provides the `apply` closure with the VM storage
+/// and the environment of the first L2 block, so that the closure can modify the storage and start
+/// new L2 blocks between transactions. `state_override` is applied to the storage before the execution.
+/// `deferred_state_overrides` are expected to be applied by the closure itself; only their bytecodes
+/// are made known to the VM.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn apply_vm_in_sandbox_with_storage<T>(
+    vm_permit: VmPermit,
+    shared_args: TxSharedArgs,
+    adjust_pubdata_price: bool,
+    execution_args: &TxExecutionArgs,
+    connection_pool: &ConnectionPool<Core>,
+    tx: Transaction,
+    block_args: BlockArgs,
+    state_override: Option<&StateOverride>,
+    deferred_state_overrides: &[StateOverride],
     apply: impl FnOnce(
-        &mut VmInstance<StorageView<PostgresStorage<'_>>, HistoryDisabled>,
+        &mut VmInstance<SandboxStorage<'_>, HistoryDisabled>,
+        &StoragePtr<SandboxStorage<'_>>,
+        L2BlockEnv,
         Transaction,
         ProtocolVersionId,
     ) -> T,
@@ -319,8 +367,11 @@ pub(super) fn apply_vm_in_sandbox<T>(
         shared_args,
         execution_args,
         block_args,
+        state_override,
+        deferred_state_overrides,
     ))?;
     let protocol_version = sandbox.system_env.version;
+    let first_l2_block = sandbox.l1_batch_env.first_l2_block;
     let (mut vm, storage_view) = sandbox.into_vm(&tx, adjust_pubdata_price);
 
     SANDBOX_METRICS.sandbox[&SandboxStage::Initialization].observe(stage_started_at.elapsed());
@@ -332,7 +383,7 @@ pub(super) fn apply_vm_in_sandbox<T>(
         tx.nonce().unwrap_or(Nonce(0))
     );
     let execution_latency = SANDBOX_METRICS.sandbox[&SandboxStage::Execution].start();
-    let result = apply(&mut vm, tx, protocol_version);
+    let result = apply(&mut vm, &storage_view, first_l2_block, tx, protocol_version);
     let vm_execution_took = execution_latency.observe();
 
     let memory_metrics = vm.record_vm_memory_metrics();
@@ -4,18 +4,26 @@ use anyhow::Context as _;
 use tracing::{span, Level};
 use zksync_dal::{ConnectionPool, Core};
 use zksync_multivm::{
-    interface::{TxExecutionMode, VmExecutionResultAndLogs, VmInterface},
+    interface::{
+        L2BlockEnv, TxExecutionMode, VmExecutionMode, VmExecutionResultAndLogs, VmInterface,
+    },
     tracers::StorageInvocations,
     MultiVMTracer,
 };
+use zksync_state::{ReadStorage, StoragePtr};
+use zksync_system_constants::{
+    SYSTEM_CONTEXT_ADDRESS, SYSTEM_CONTEXT_CURRENT_TX_ROLLING_HASH_POSITION,
+};
 use zksync_types::{
-    fee::TransactionExecutionMetrics, l2::L2Tx, transaction_request::CallOverrides,
-    ExecuteTransactionCommon, Nonce, PackedEthSignature, Transaction, U256,
+    api::state_override::StateOverride, block::L2BlockHasher, fee::TransactionExecutionMetrics,
+    l2::L2Tx, transaction_request::CallOverrides, AccountTreeId, ExecuteTransactionCommon,
+    L2BlockNumber, Nonce, PackedEthSignature, ProtocolVersionId, StorageKey, Transaction, H256,
+    U256,
 };
 
 use super::{
-    apply, testonly::MockTransactionExecutor, vm_metrics, ApiTracer, BlockArgs, TxSharedArgs,
-    VmPermit,
+    apply, storage, testonly::MockTransactionExecutor, vm_metrics, ApiTracer, BlockArgs,
+    TxSharedArgs, VmPermit,
 };
 
 #[derive(Debug)]
@@ -38,6 +46,17 @@ impl TxExecutionArgs {
         }
     }
 
+    /// Arguments to re-execute a transaction included into an L2 block with the specified base fee.
+    fn for_replay(base_fee: u64) -> Self {
+        Self {
+            execution_mode: TxExecutionMode::VerifyExecute,
+            enforced_nonce: None,
+            added_balance: U256::zero(),
+            enforced_base_fee: Some(base_fee),
+            missed_storage_invocation_limit: usize::MAX,
+        }
+    }
+
     fn for_eth_call(
         enforced_base_fee: Option<u64>,
         vm_execution_cache_misses_limit: Option<usize>,
@@ -86,6 +105,23 @@ pub(crate) struct TransactionExecutionOutput {
     pub are_published_bytecodes_ok: bool,
 }
 
+/// Simulated L2 block to be executed by [`TransactionExecutor::simulate_blocks_in_sandbox()`].
+#[derive(Debug)]
+pub(crate) struct SimulatedBlockArgs {
+    /// State override applied before executing the block calls.
+    pub state_override: Option<StateOverride>,
+    pub calls: Vec<L2Tx>,
+}
+
+/// Output of a simulated L2 block.
+#[derive(Debug)]
+pub(crate) struct SimulatedBlockOutput {
+    pub number: L2BlockNumber,
+    pub timestamp: u64,
+    /// Execution results for each call in the block.
+    pub results: Vec<VmExecutionResultAndLogs>,
+}
+
 /// Executor of transactions.
 #[derive(Debug)]
 pub(crate) enum TransactionExecutor {
@@ -111,6 +147,7 @@ impl TransactionExecutor {
         connection_pool: ConnectionPool<Core>,
         tx: Transaction,
         block_args: BlockArgs,
+        state_override: Option<StateOverride>,
         custom_tracers: Vec<ApiTracer>,
     ) -> anyhow::Result<TransactionExecutionOutput> {
         if let Self::Mock(mock_executor) = self {
@@ -129,6 +166,7 @@ impl TransactionExecutor {
                 &connection_pool,
                 tx,
                 block_args,
+                state_override.as_ref(),
                 |vm, tx, _| {
                     let storage_invocation_tracer =
                         StorageInvocations::new(execution_args.missed_storage_invocation_limit);
@@ -169,6 +207,7 @@ impl TransactionExecutor {
         mut tx: L2Tx,
         block_args: BlockArgs,
         vm_execution_cache_misses_limit: Option<usize>,
+        state_override: Option<StateOverride>,
         custom_tracers: Vec<ApiTracer>,
     ) -> anyhow::Result<VmExecutionResultAndLogs> {
         let execution_args = TxExecutionArgs::for_eth_call(
@@ -189,9 +228,200 @@ impl TransactionExecutor {
                 connection_pool,
                 tx.into(),
                 block_args,
+                state_override,
                 custom_tracers,
             )
             .await?;
         Ok(output.vm)
     }
+
+    /// Re-executes a transaction included into an L2 block. `preceding_txs` are the transactions
+    /// preceding it in the block; they are executed without tracing before the traced transaction.
+    ///
+    /// `block_args` must point to the L2 block preceding the block of the transaction, so the transactions
+    /// are executed on top of its state and in its context.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn replay_tx_in_sandbox(
+        &self,
+        vm_permit: VmPermit,
+        shared_args: TxSharedArgs,
+        connection_pool: ConnectionPool<Core>,
+        preceding_txs: Vec<Transaction>,
+        tx: Transaction,
+        block_args: BlockArgs,
+        base_fee: u64,
+        custom_tracers: Vec<ApiTracer>,
+    ) -> anyhow::Result<VmExecutionResultAndLogs> {
+        if let Self::Mock(mock_executor) = self {
+            return Ok(mock_executor.execute_tx(&tx, &block_args)?.vm);
+        }
+
+        let execution_args = TxExecutionArgs::for_replay(base_fee);
+        tokio::task::spawn_blocking(move || {
+            let span = span!(Level::DEBUG, "replay_in_sandbox").entered();
+            let result = apply::apply_vm_in_sandbox(
+                vm_permit,
+                shared_args,
+                false,
+                &execution_args,
+                &connection_pool,
+                tx,
+                block_args,
+                None,
+                |vm, tx, _| {
+                    for preceding_tx in preceding_txs {
+                        vm.push_transaction(preceding_tx);
+                        vm.execute(VmExecutionMode::OneTx);
+                    }
+                    let custom_tracers: Vec<_> = custom_tracers
+                        .into_iter()
+                        .map(|tracer| tracer.into_boxed())
+                        .collect();
+                    vm.push_transaction(tx);
+                    vm.inspect(custom_tracers.into(), VmExecutionMode::OneTx)
+                },
+            );
+            span.exit();
+            result
+        })
+        .await
+        .context("transaction replay panicked")?
+    }
+
+    /// Executes calls in a sequence of simulated L2 blocks on top of the state at `block_args`.
+    /// Each block is started after the previous one and observes its state changes. State overrides
+    /// of a block are applied before executing its calls. Blocks must contain at least one call each.
+    pub async fn simulate_blocks_in_sandbox(
+        &self,
+        vm_permit: VmPermit,
+        shared_args: TxSharedArgs,
+        connection_pool: ConnectionPool<Core>,
+        mut blocks: Vec<SimulatedBlockArgs>,
+        block_args: BlockArgs,
+        vm_execution_cache_misses_limit: Option<usize>,
+    ) -> anyhow::Result<Vec<SimulatedBlockOutput>> {
+        anyhow::ensure!(
+            blocks.iter().all(|block| !block.calls.is_empty()),
+            "simulated L2 blocks must contain at least one call"
+        );
+        for tx in blocks.iter_mut().flat_map(|block| &mut block.calls) {
+            if tx.common_data.signature.is_empty() {
+                tx.common_data.signature = PackedEthSignature::default().serialize_packed().into();
+            }
+        }
+
+        if let Self::Mock(mock_executor) = self {
+            let first_timestamp = block_args.l1_batch_timestamp_s.unwrap_or_default();
+            return blocks
+                .into_iter()
+                .enumerate()
+                .map(|(i, block)| {
+                    let results = block
+                        .calls
+                        .into_iter()
+                        .map(|tx| Ok(mock_executor.execute_tx(&tx.into(), &block_args)?.vm))
+                        .collect::<anyhow::Result<_>>()?;
+                    Ok(SimulatedBlockOutput {
+                        number: block_args.resolved_block_number() + i as u32,
+                        timestamp: first_timestamp + i as u64,
+                        results,
+                    })
+                })
+                .collect();
+        }
+
+        let Some(first_tx) = blocks.first().map(|block| block.calls[0].clone()) else {
+            return Ok(vec![]);
+        };
+        let execution_args = TxExecutionArgs::for_eth_call(None, vm_execution_cache_misses_limit);
+        tokio::task::spawn_blocking(move || {
+            let span = span!(Level::DEBUG, "simulate_in_sandbox").entered();
+            let state_overrides: Vec<_> = blocks
+                .iter()
+                .filter_map(|block| block.state_override.clone())
+                .collect();
+            let result = apply::apply_vm_in_sandbox_with_storage(
+                vm_permit,
+                shared_args,
+                false,
+                &execution_args,
+                &connection_pool,
+                first_tx.into(),
+                block_args,
+                None,
+                &state_overrides,
+                |vm, storage_view, first_l2_block, _, protocol_version| {
+                    let mut l2_block = first_l2_block;
+                    let mut outputs = Vec::with_capacity(blocks.len());
+                    for (i, block) in blocks.into_iter().enumerate() {
+                        if i > 0 {
+                            let prev_block_hash =
+                                current_l2_block_hash(storage_view, &l2_block, protocol_version);
+                            l2_block = L2BlockEnv {
+                                number: l2_block.number + 1,
+                                timestamp: l2_block.timestamp + 1,
+                                prev_block_hash,
+                                max_virtual_blocks_to_create: 1,
+                            };
+                            vm.start_new_l2_block(l2_block);
+                        }
+                        if let Some(state_override) = &block.state_override {
+                            storage::apply_state_override(
+                                &mut *storage_view.borrow_mut(),
+                                state_override,
+                            );
+                        }
+
+                        let results = block
+                            .calls
+                            .into_iter()
+                            .map(|tx| {
+                                let storage_invocation_tracer = StorageInvocations::new(
+                                    execution_args.missed_storage_invocation_limit,
+                                );
+                                let tracers = vec![storage_invocation_tracer.into_tracer_pointer()];
+                                vm.inspect_transaction_with_bytecode_compression(
+                                    tracers.into(),
+                                    tx.into(),
+                                    true,
+                                )
+                                .1
+                            })
+                            .collect();
+                        outputs.push(SimulatedBlockOutput {
+                            number: L2BlockNumber(l2_block.number),
+                            timestamp: l2_block.timestamp,
+                            results,
+                        });
+                    }
+                    outputs
+                },
+            );
+            span.exit();
+            result
+        })
+        .await
+        .context("simulation panicked")?
+    }
+}
+
+/// Computes the hash of the L2 block currently executed by the VM based on its transactions rolling hash
+/// stored in the system context.
+fn current_l2_block_hash<S: ReadStorage>(
+    storage: &StoragePtr<S>,
+    l2_block: &L2BlockEnv,
+    protocol_version: ProtocolVersionId,
+) -> H256 {
+    let txs_rolling_hash_key = StorageKey::new(
+        AccountTreeId::new(SYSTEM_CONTEXT_ADDRESS),
+        SYSTEM_CONTEXT_CURRENT_TX_ROLLING_HASH_POSITION,
+    );
+    let txs_rolling_hash = storage.borrow_mut().read_value(&txs_rolling_hash_key);
+    L2BlockHasher::hash(
+        L2BlockNumber(l2_block.number),
+        l2_block.timestamp,
+        l2_block.prev_block_hash,
+        txs_rolling_hash,
+        protocol_version,
+    )
 }
@@ -15,7 +15,7 @@ use zksync_types::{
 use self::vm_metrics::SandboxStage;
 pub(super) use self::{
     error::SandboxExecutionError,
-    execute::{TransactionExecutor, TxExecutionArgs},
+    execute::{SimulatedBlockArgs, SimulatedBlockOutput, TransactionExecutor, TxExecutionArgs},
     tracers::ApiTracer,
     validate::ValidationError,
     vm_metrics::{SubmitTxStage, SANDBOX_METRICS},
@@ -26,6 +26,7 @@ use super::tx_sender::MultiVMBaseSystemContracts;
 mod apply;
 mod error;
 mod execute;
+mod storage;
 pub mod testonly;
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,279 @@
+//! Storage overlays used to apply state overrides in the sandbox.
+
+use std::collections::{HashMap, HashSet};
+
+use zksync_state::{ReadStorage, WriteStorage};
+use zksync_types::{
+    api::state_override::{OverrideAccount, StateOverride},
+    get_code_key, get_nonce_key,
+    utils::{decompose_full_nonce, nonces_to_full_nonce, storage_key_for_eth_balance},
+    AccountTreeId, Address, StorageKey, StorageValue, H256,
+};
+use zksync_utils::{bytecode::hash_bytecode, h256_to_u256, u256_to_h256};
+
+/// Read-only storage layer applying state overrides on top of the underlying storage.
+///
+/// Overridden values are returned instead of the stored ones, and accounts with replaced storage (`state` overrides)
+/// have all slots not mentioned in the override read as zero. Bytecodes supplied in overrides are not present
+/// in the underlying storage, so this layer also makes them known to the VM; otherwise, the VM would fail
+/// to decommit them.
+#[derive(Debug)]
+pub(super) struct StorageWithOverrides<S> {
+    storage_handle: S,
+    overridden_slots: HashMap<StorageKey, H256>,
+    overridden_factory_deps: HashMap<H256, Vec<u8>>,
+    accounts_with_replaced_storage: HashSet<AccountTreeId>,
+}
+
+impl<S: ReadStorage> StorageWithOverrides<S> {
+    pub fn new(storage_handle: S) -> Self {
+        Self {
+            storage_handle,
+            overridden_slots: HashMap::new(),
+            overridden_factory_deps: HashMap::new(),
+            accounts_with_replaced_storage: HashSet::new(),
+        }
+    }
+
+    /// Applies the provided override on top of the underlying storage.
+    pub fn with_state_override(mut self, state_override: &StateOverride) -> Self {
+        for (address, account) in state_override.iter() {
+            let values =
+                overridden_values(address, account, |key| self.storage_handle.read_value(key));
+            self.overridden_slots.extend(values);
+
+            if let Some(state) = &account.state {
+                let account_id = AccountTreeId::new(*address);
+                self.accounts_with_replaced_storage.insert(account_id);
+                self.overridden_slots.extend(
+                    state
+                        .iter()
+                        .map(|(&key, &value)| (StorageKey::new(account_id, key), value)),
+                );
+            }
+        }
+        self.with_overridden_bytecodes([state_override])
+    }
+
+    /// Makes the bytecodes from all provided overrides known to the VM.
+    pub fn with_overridden_bytecodes<'a>(
+        mut self,
+        state_overrides: impl IntoIterator<Item = &'a StateOverride>,
+    ) -> Self {
+        let bytecodes = state_overrides
+            .into_iter()
+            .flat_map(StateOverride::iter)
+            .filter_map(|(_, account)| account.code.as_ref());
+        for bytecode in bytecodes {
+            self.overridden_factory_deps
+                .insert(hash_bytecode(&bytecode.0), bytecode.0.clone());
+        }
+        self
+    }
+}
+
+impl<S: ReadStorage> ReadStorage for StorageWithOverrides<S> {
+    fn read_value(&mut self, key: &StorageKey) -> StorageValue {
+        if let Some(value) = self.overridden_slots.get(key) {
+            return *value;
+        }
+        if self.accounts_with_replaced_storage.contains(key.account()) {
+            return H256::zero();
+        }
+        self.storage_handle.read_value(key)
+    }
+
+    fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        self.storage_handle.is_write_initial(key)
+    }
+
+    fn load_factory_dep(&mut self, hash: H256) -> Option<Vec<u8>> {
+        self.overridden_factory_deps
+            .get(&hash)
+            .cloned()
+            .or_else(|| self.storage_handle.load_factory_dep(hash))
+    }
+
+    fn is_bytecode_known(&mut self, bytecode_hash: &H256) -> bool {
+        self.overridden_factory_deps.contains_key(bytecode_hash)
+            || self.storage_handle.is_bytecode_known(bytecode_hash)
+    }
+
+    fn get_enumeration_index(&mut self, key: &StorageKey) -> Option<u64> {
+        self.storage_handle.get_enumeration_index(key)
+    }
+}
+
+/// Returns storage values corresponding to the overridden balance, nonce, code hash and `stateDiff` slots
+/// of an account. `read_value` is used to read the current full nonce, so that the deployment nonce is retained.
+fn overridden_values(
+    address: &Address,
+    account: &OverrideAccount,
+    mut read_value: impl FnMut(&StorageKey) -> StorageValue,
+) -> Vec<(StorageKey, StorageValue)> {
+    let mut values = vec![];
+    if let Some(balance) = account.balance {
+        values.push((storage_key_for_eth_balance(address), u256_to_h256(balance)));
+    }
+
+    if let Some(nonce) = account.nonce {
+        let nonce_key = get_nonce_key(address);
+        let full_nonce = read_value(&nonce_key);
+        let (_, deployment_nonce) = decompose_full_nonce(h256_to_u256(full_nonce));
+        let new_full_nonce = nonces_to_full_nonce(nonce, deployment_nonce);
+        values.push((nonce_key, u256_to_h256(new_full_nonce)));
+    }
+
+    if let Some(code) = &account.code {
+        values.push((get_code_key(address), hash_bytecode(&code.0)));
+    }
+
+    for (&key, &value) in account.state_diff.iter().flatten() {
+        values.push((StorageKey::new(AccountTreeId::new(*address), key), value));
+    }
+    values
+}
+
+/// Writes overridden balances, nonces, code hashes and storage slots into the storage.
+///
+/// Overridden bytecodes must be made known to the VM separately (see [`StorageWithOverrides`]).
+/// Full storage replacement (`state`) cannot be expressed as a set of writes and must be rejected beforehand.
+pub(super) fn apply_state_override<S: WriteStorage>(
+    storage: &mut S,
+    state_override: &StateOverride,
+) {
+    for (address, account) in state_override.iter() {
+        for (key, value) in overridden_values(address, account, |key| storage.read_value(key)) {
+            storage.set_value(key, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use zksync_state::{InMemoryStorage, StorageView};
+    use zksync_types::{web3::Bytes, U256};
+
+    use super::*;
+
+    #[test]
+    fn applying_state_override() {
+        let address = Address::repeat_byte(1);
+        let slot = StorageKey::new(AccountTreeId::new(address), H256::repeat_byte(2));
+        let nonce_key = get_nonce_key(&address);
+        let mut storage = InMemoryStorage::default();
+        storage.set_value(slot, H256::repeat_byte(3));
+        // Deployment nonce 1, transaction nonce 2
+        let full_nonce = nonces_to_full_nonce(2.into(), 1.into());
+        storage.set_value(nonce_key, u256_to_h256(full_nonce));
+
+        let bytecode = vec![1_u8; 32];
+        let bytecode_hash = hash_bytecode(&bytecode);
+        let state_override = StateOverride::new(HashMap::from([(
+            address,
+            OverrideAccount {
+                balance: Some(100.into()),
+                nonce: Some(5.into()),
+                code: Some(Bytes(bytecode.clone())),
+                state: None,
+                state_diff: Some(HashMap::from([(
+                    H256::repeat_byte(4),
+                    H256::repeat_byte(5),
+                )])),
+            },
+        )]));
+
+        let storage =
+            StorageWithOverrides::new(storage).with_overridden_bytecodes([&state_override]);
+        let mut storage = StorageView::new(storage);
+        apply_state_override(&mut storage, &state_override);
+
+        let balance = storage.read_value(&storage_key_for_eth_balance(&address));
+        assert_eq!(h256_to_u256(balance), U256::from(100));
+        let full_nonce = h256_to_u256(storage.read_value(&nonce_key));
+        assert_eq!(decompose_full_nonce(full_nonce), (5.into(), 1.into()));
+        assert_eq!(storage.read_value(&get_code_key(&address)), bytecode_hash);
+        assert_eq!(storage.load_factory_dep(bytecode_hash), Some(bytecode));
+        assert!(storage.is_bytecode_known(&bytecode_hash));
+
+        // Slots not mentioned in `stateDiff` must retain their values.
+        assert_eq!(storage.read_value(&slot), H256::repeat_byte(3));
+        let overridden_slot = StorageKey::new(AccountTreeId::new(address), H256::repeat_byte(4));
+        assert_eq!(storage.read_value(&overridden_slot), H256::repeat_byte(5));
+    }
+
+    #[test]
+    fn storage_with_state_override() {
+        let address = Address::repeat_byte(1);
+        let other_address = Address::repeat_byte(2);
+        let slot = |address, key| StorageKey::new(AccountTreeId::new(address), key);
+        let nonce_key = get_nonce_key(&address);
+        let mut storage = InMemoryStorage::default();
+        storage.set_value(slot(address, H256::repeat_byte(1)), H256::repeat_byte(3));
+        storage.set_value(slot(address, H256::repeat_byte(2)), H256::repeat_byte(3));
+        storage.set_value(
+            slot(other_address, H256::repeat_byte(1)),
+            H256::repeat_byte(3),
+        );
+        let full_nonce = nonces_to_full_nonce(2.into(), 1.into());
+        storage.set_value(nonce_key, u256_to_h256(full_nonce));
+
+        let bytecode = vec![1_u8; 32];
+        let bytecode_hash = hash_bytecode(&bytecode);
+        let state_override = StateOverride::new(HashMap::from([
+            (
+                address,
+                OverrideAccount {
+                    nonce: Some(5.into()),
+                    code: Some(Bytes(bytecode.clone())),
+                    state: Some(HashMap::from([(
+                        H256::repeat_byte(2),
+                        H256::repeat_byte(4),
+                    )])),
+                    ..OverrideAccount::default()
+                },
+            ),
+            (
+                other_address,
+                OverrideAccount {
+                    balance: Some(100.into()),
+                    state_diff: Some(HashMap::from([(
+                        H256::repeat_byte(2),
+                        H256::repeat_byte(4),
+                    )])),
+                    ..OverrideAccount::default()
+                },
+            ),
+        ]));
+
+        let mut storage = StorageWithOverrides::new(storage).with_state_override(&state_override);
+        let full_nonce = h256_to_u256(storage.read_value(&nonce_key));
+        assert_eq!(decompose_full_nonce(full_nonce), (5.into(), 1.into()));
+        assert_eq!(storage.read_value(&get_code_key(&address)), bytecode_hash);
+        assert_eq!(storage.load_factory_dep(bytecode_hash), Some(bytecode));
+        assert!(storage.is_bytecode_known(&bytecode_hash));
+        let balance = storage.read_value(&storage_key_for_eth_balance(&other_address));
+        assert_eq!(h256_to_u256(balance), U256::from(100));
+
+        // Storage of `address` is replaced, so slots not mentioned in `state` must read as zero.
+        assert_eq!(
+            storage.read_value(&slot(address, H256::repeat_byte(1))),
+            H256::zero()
+        );
+        assert_eq!(
+            storage.read_value(&slot(address, H256::repeat_byte(2))),
+            H256::repeat_byte(4)
+        );
+        // ...while `stateDiff` only affects the mentioned slots.
+        assert_eq!(
+            storage.read_value(&slot(other_address, H256::repeat_byte(1))),
+            H256::repeat_byte(3)
+        );
+        assert_eq!(
+            storage.read_value(&slot(other_address, H256::repeat_byte(2))),
+            H256::repeat_byte(4)
+        );
+    }
+}
@@ -2,7 +2,12 @@ use std::sync::Arc;
 
 use once_cell::sync::OnceCell;
 use zksync_multivm::{
-    tracers::CallTracer, vm_latest::HistoryMode, MultiVMTracer, MultiVmTracerPointer,
+    tracers::{
+        prestate_tracer::State, storage_access::StorageAccesses, CallTracer, PrestateTracer,
+        StorageAccessTracer,
+    },
+    vm_latest::HistoryMode,
+    MultiVMTracer, MultiVmTracerPointer,
 };
 use zksync_state::WriteStorage;
 use zksync_types::vm_trace::Call;
@@ -11,6 +16,11 @@ use zksync_types::vm_trace::Call;
 #[derive(Debug)]
 pub(crate) enum ApiTracer {
     CallTracer(Arc<OnceCell<Vec<Call>>>),
+    PrestateTracer {
+        diff_mode: bool,
+        result: Arc<OnceCell<(State, State)>>,
+    },
+    StorageAccessTracer(Arc<OnceCell<StorageAccesses>>),
 }
 
 impl ApiTracer {
@@ -22,6 +32,12 @@ impl ApiTracer {
     ) -> MultiVmTracerPointer<S, H> {
         match self {
             ApiTracer::CallTracer(tracer) => CallTracer::new(tracer.clone()).into_tracer_pointer(),
+            ApiTracer::PrestateTracer { diff_mode, result } => {
+                PrestateTracer::new(diff_mode, result).into_tracer_pointer()
+            }
+            ApiTracer::StorageAccessTracer(result) => {
+                StorageAccessTracer::new(result).into_tracer_pointer()
+            }
         }
     }
 }
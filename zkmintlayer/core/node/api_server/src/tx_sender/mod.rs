@@ -3,6 +3,7 @@
 use std::{sync::Arc, time::Instant};
 
 use anyhow::Context as _;
+use once_cell::sync::OnceCell;
 use tokio::sync::RwLock;
 use zksync_config::configs::{api::Web3JsonRpcConfig, chain::StateKeeperConfig};
 use zksync_contracts::BaseSystemContracts;
@@ -11,6 +12,7 @@ use zksync_dal::{
 };
 use zksync_multivm::{
     interface::VmExecutionResultAndLogs,
+    tracers::storage_access::StorageAccesses,
     utils::{
         adjust_pubdata_price_for_tx, derive_base_fee_and_gas_per_pubdata, derive_overhead,
         get_eth_call_gas_limit, get_max_batch_gas_limit,
@@ -24,6 +26,7 @@ use zksync_state_keeper::{
     SequencerSealer,
 };
 use zksync_types::{
+    api::state_override::StateOverride,
     fee::{Fee, TransactionExecutionMetrics},
     fee_model::BatchFeeInput,
     get_code_key, get_intrinsic_constants,
@@ -34,14 +37,15 @@ use zksync_types::{
     ProtocolVersionId, Transaction, VmVersion, H160, H256, MAX_L2_TX_GAS_LIMIT,
     MAX_NEW_FACTORY_DEPS, U256,
 };
-use zksync_utils::h256_to_u256;
+use zksync_utils::{bytecode::hash_bytecode, h256_to_u256};
 
 pub(super) use self::result::SubmitTxError;
 use self::{master_pool_sink::MasterPoolSink, tx_sink::TxSink};
 use crate::{
     execution_sandbox::{
-        BlockArgs, SubmitTxStage, TransactionExecutor, TxExecutionArgs, TxSharedArgs,
-        VmConcurrencyBarrier, VmConcurrencyLimiter, VmPermit, SANDBOX_METRICS,
+        ApiTracer, BlockArgs, SimulatedBlockArgs, SimulatedBlockOutput, SubmitTxStage,
+        TransactionExecutor, TxExecutionArgs, TxSharedArgs, VmConcurrencyBarrier,
+        VmConcurrencyLimiter, VmPermit, SANDBOX_METRICS,
     },
     tx_sender::result::ApiCallResult,
 };
@@ -385,6 +389,7 @@ impl TxSender {
                 self.0.replica_connection_pool.clone(),
                 tx.clone().into(),
                 block_args,
+                None,
                 vec![],
             )
             .await?;
@@ -656,6 +661,7 @@ impl TxSender {
         block_args: BlockArgs,
         base_fee: u64,
         vm_version: VmVersion,
+        state_override: Option<&StateOverride>,
     ) -> anyhow::Result<(VmExecutionResultAndLogs, TransactionExecutionMetrics)> {
         let gas_limit_with_overhead = tx_gas_limit
             + derive_overhead(
@@ -703,6 +709,7 @@ impl TxSender {
                 self.0.replica_connection_pool.clone(),
                 tx.clone(),
                 block_args,
+                state_override.cloned(),
                 vec![],
             )
             .await?;
@@ -733,6 +740,7 @@ impl TxSender {
         mut tx: Transaction,
         estimated_fee_scale_factor: f64,
         acceptable_overestimation: u64,
+        state_override: Option<StateOverride>,
     ) -> Result<Fee, SubmitTxError> {
         let estimation_started_at = Instant::now();
 
@@ -770,33 +778,45 @@ impl TxSender {
             }
         }
 
-        let hashed_key = get_code_key(&tx.initiator_account());
+        let initiator_override = state_override
+            .as_ref()
+            .and_then(|state_override| state_override.get(&tx.initiator_account()));
+        let overridden_code_hash = initiator_override
+            .and_then(|account| account.code.as_ref())
+            .map(|code| hash_bytecode(&code.0));
+        let account_code_hash = if let Some(code_hash) = overridden_code_hash {
+            code_hash
+        } else {
+            let hashed_key = get_code_key(&tx.initiator_account());
+            self.acquire_replica_connection()
+                .await?
+                .storage_web3_dal()
+                .get_value(&hashed_key)
+                .await
+                .with_context(|| {
+                    format!(
+                        "failed getting code hash for account {:?}",
+                        tx.initiator_account()
+                    )
+                })?
+        };
+
         // If the default account does not have enough funds for transferring `tx.value`, without taking into account the fee,
         // there is no sense to estimate the fee.
-        let account_code_hash = self
-            .acquire_replica_connection()
-            .await?
-            .storage_web3_dal()
-            .get_value(&hashed_key)
-            .await
-            .with_context(|| {
-                format!(
-                    "failed getting code hash for account {:?}",
-                    tx.initiator_account()
-                )
-            })?;
-
-        if !tx.is_l1()
-            && account_code_hash == H256::zero()
-            && tx.execute.value > self.get_balance(&tx.initiator_account()).await?
-        {
-            tracing::info!(
-                "fee estimation failed on validation step.
+        if !tx.is_l1() && account_code_hash == H256::zero() {
+            let balance = match initiator_override.and_then(|account| account.balance) {
+                Some(balance) => balance,
+                None => self.get_balance(&tx.initiator_account()).await?,
+            };
+            if tx.execute.value > balance {
+                tracing::info!(
+                    "fee estimation failed on validation step.
                 account: {} does not have enough funds for for transferring tx.value: {}.",
-                &tx.initiator_account(),
-                tx.execute.value
-            );
-            return Err(SubmitTxError::InsufficientFundsForTransfer);
+                    &tx.initiator_account(),
+                    tx.execute.value
+                );
+                return Err(SubmitTxError::InsufficientFundsForTransfer);
+            }
         }
 
         // For L2 transactions we need a properly formatted signature
@@ -836,6 +856,7 @@ impl TxSender {
                     block_args,
                     base_fee,
                     protocol_version.into(),
+                    state_override.as_ref(),
                 )
                 .await
                 .context("estimate_gas step failed")?;
@@ -871,6 +892,7 @@ impl TxSender {
                     block_args,
                     base_fee,
                     protocol_version.into(),
+                    state_override.as_ref(),
                 )
                 .await
                 .context("estimate_gas step failed")?;
@@ -903,6 +925,7 @@ impl TxSender {
                 block_args,
                 base_fee,
                 protocol_version.into(),
+                state_override.as_ref(),
             )
             .await
             .context("final estimate_gas step failed")?;
@@ -973,6 +996,7 @@ impl TxSender {
         block_args: BlockArgs,
         call_overrides: CallOverrides,
         tx: L2Tx,
+        state_override: Option<StateOverride>,
     ) -> Result<Vec<u8>, SubmitTxError> {
         let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
         let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
@@ -988,12 +1012,73 @@ impl TxSender {
                 tx,
                 block_args,
                 vm_execution_cache_misses_limit,
+                state_override,
                 vec![],
             )
             .await?
             .into_api_call_result()
     }
 
+    /// Executes a call and collects the storage slots it accesses.
+    pub(super) async fn create_access_list(
+        &self,
+        block_args: BlockArgs,
+        call_overrides: CallOverrides,
+        tx: L2Tx,
+    ) -> Result<(VmExecutionResultAndLogs, StorageAccesses), SubmitTxError> {
+        let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
+        let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
+
+        let accesses = Arc::new(OnceCell::new());
+        let vm_execution_cache_misses_limit = self.0.sender_config.vm_execution_cache_misses_limit;
+        let result = self
+            .0
+            .executor
+            .execute_tx_eth_call(
+                vm_permit,
+                self.shared_args().await?,
+                self.0.replica_connection_pool.clone(),
+                call_overrides,
+                tx,
+                block_args,
+                vm_execution_cache_misses_limit,
+                None,
+                vec![ApiTracer::StorageAccessTracer(accesses.clone())],
+            )
+            .await?;
+        // The tracer is dropped after the execution, so we hold the only reference to the result.
+        let accesses = Arc::try_unwrap(accesses)
+            .unwrap()
+            .take()
+            .unwrap_or_default();
+        Ok((result, accesses))
+    }
+
+    /// Executes calls in a sequence of simulated L2 blocks.
+    pub(super) async fn simulate_blocks(
+        &self,
+        block_args: BlockArgs,
+        blocks: Vec<SimulatedBlockArgs>,
+    ) -> Result<Vec<SimulatedBlockOutput>, SubmitTxError> {
+        let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
+        let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
+
+        let vm_execution_cache_misses_limit = self.0.sender_config.vm_execution_cache_misses_limit;
+        let outputs = self
+            .0
+            .executor
+            .simulate_blocks_in_sandbox(
+                vm_permit,
+                self.shared_args().await?,
+                self.0.replica_connection_pool.clone(),
+                blocks,
+                block_args,
+                vm_execution_cache_misses_limit,
+            )
+            .await?;
+        Ok(outputs)
+    }
+
     pub async fn gas_price(&self) -> anyhow::Result<u64> {
         let mut connection = self.acquire_replica_connection().await?;
         let protocol_version = connection
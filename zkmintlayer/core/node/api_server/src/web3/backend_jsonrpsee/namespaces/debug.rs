@@ -1,5 +1,5 @@
 use zksync_types::{
-    api::{BlockId, BlockNumber, DebugCall, ResultDebugCall, TracerConfig},
+    api::{BlockId, BlockNumber, DebugTrace, ResultDebugCall, TracerConfig},
     debug_flat_call::DebugCallFlat,
     transaction_request::CallRequest,
     H256,
@@ -48,7 +48,7 @@ impl DebugNamespaceServer for DebugNamespace {
         request: CallRequest,
         block: Option<BlockId>,
         options: Option<TracerConfig>,
-    ) -> RpcResult<DebugCall> {
+    ) -> RpcResult<DebugTrace> {
         self.debug_trace_call_impl(request, block, options)
             .await
             .map_err(|err| self.current_method().map_err(err))
@@ -58,7 +58,7 @@ impl DebugNamespaceServer for DebugNamespace {
         &self,
         tx_hash: H256,
         options: Option<TracerConfig>,
-    ) -> RpcResult<Option<DebugCall>> {
+    ) -> RpcResult<Option<DebugTrace>> {
         self.debug_trace_transaction_impl(tx_hash, options)
             .await
             .map_err(|err| self.current_method().map_err(err))
@@ -1,6 +1,7 @@
 use zksync_types::{
     api::{
-        Block, BlockId, BlockIdVariant, BlockNumber, Log, Transaction, TransactionId,
+        state_override::StateOverride, AccessListResult, Block, BlockId, BlockIdVariant,
+        BlockNumber, Log, SimulatePayload, SimulatedBlock, Transaction, TransactionId,
         TransactionReceipt, TransactionVariant,
     },
     transaction_request::CallRequest,
@@ -27,14 +28,44 @@ impl EthNamespaceServer for EthNamespace {
         Ok(self.chain_id_impl())
     }
 
-    async fn call(&self, req: CallRequest, block: Option<BlockIdVariant>) -> RpcResult<Bytes> {
-        self.call_impl(req, block.map(Into::into))
+    async fn call(
+        &self,
+        req: CallRequest,
+        block: Option<BlockIdVariant>,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<Bytes> {
+        self.call_impl(req, block.map(Into::into), state_override)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn create_access_list(
+        &self,
+        req: CallRequest,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<AccessListResult> {
+        self.create_access_list_impl(req, block.map(Into::into))
             .await
             .map_err(|err| self.current_method().map_err(err))
     }
 
-    async fn estimate_gas(&self, req: CallRequest, block: Option<BlockNumber>) -> RpcResult<U256> {
-        self.estimate_gas_impl(req, block)
+    async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<Vec<SimulatedBlock>> {
+        self.simulate_v1_impl(payload, block.map(Into::into))
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn estimate_gas(
+        &self,
+        req: CallRequest,
+        block: Option<BlockNumber>,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<U256> {
+        self.estimate_gas_impl(req, block, state_override)
             .await
             .map_err(|err| self.current_method().map_err(err))
     }
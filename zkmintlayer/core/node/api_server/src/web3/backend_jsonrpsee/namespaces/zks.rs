@@ -3,8 +3,9 @@ use std::collections::HashMap;
 use itertools::Itertools;
 use zksync_types::{
     api::{
-        ApiStorageLog, BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Log, Proof,
-        ProtocolVersion, TransactionDetailedResult, TransactionDetails,
+        ApiStorageLog, BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Log,
+        MintlayerBatchAnchor, Proof, ProtocolVersion, TransactionDetailedResult,
+        TransactionDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -136,6 +137,15 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_mintlayer_anchor(
+        &self,
+        batch_number: L1BatchNumber,
+    ) -> RpcResult<Option<MintlayerBatchAnchor>> {
+        self.get_mintlayer_anchor_impl(batch_number)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_bytecode_by_hash(&self, hash: H256) -> RpcResult<Option<Vec<u8>>> {
         self.get_bytecode_by_hash_impl(hash)
             .await
@@ -170,6 +170,9 @@ enum Web3ErrorKind {
     FilterNotFound,
     LogsLimitExceeded,
     InvalidFilterBlockHash,
+    UnsupportedTracer,
+    InvalidStateOverride,
+    InvalidSimulationPayload,
     TreeApiUnavailable,
     Internal,
 }
@@ -186,6 +189,9 @@ impl Web3ErrorKind {
             Web3Error::FilterNotFound => Self::FilterNotFound,
             Web3Error::LogsLimitExceeded(..) => Self::LogsLimitExceeded,
             Web3Error::InvalidFilterBlockHash => Self::InvalidFilterBlockHash,
+            Web3Error::UnsupportedTracer(_) => Self::UnsupportedTracer,
+            Web3Error::InvalidStateOverride(_) => Self::InvalidStateOverride,
+            Web3Error::InvalidSimulationPayload(_) => Self::InvalidSimulationPayload,
             Web3Error::TreeApiUnavailable => Self::TreeApiUnavailable,
             Web3Error::InternalError(_) | Web3Error::MethodNotImplemented => Self::Internal,
         }
@@ -420,7 +426,10 @@ pub(crate) static API_METRICS: vise::Global<ApiMetrics> = vise::Global::new();
 pub enum SubscriptionType {
     Blocks,
     Txs,
+    FullTxs,
     Logs,
+    L1BatchesCommitted,
+    L1BatchesExecuted,
 }
 
 #[derive(Debug, Metrics)]
@@ -499,6 +499,7 @@ impl ApiServer {
             tasks.extend(pub_sub.spawn_notifiers(
                 self.pool.clone(),
                 self.polling_interval,
+                self.config.l2_chain_id,
                 stop_receiver.clone(),
             ));
             Some(pub_sub)
@@ -1,20 +1,33 @@
-use std::sync::Arc;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::Arc,
+};
 
 use anyhow::Context as _;
 use once_cell::sync::OnceCell;
 use zksync_dal::{CoreDal, DalError};
 use zksync_multivm::{
-    interface::ExecutionResult, vm_latest::constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
+    interface::ExecutionResult, tracers::prestate_tracer::State as PrestateState,
+    vm_latest::constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
 };
 use zksync_system_constants::MAX_ENCODED_TX_SIZE;
 use zksync_types::{
-    api::{BlockId, BlockNumber, DebugCall, ResultDebugCall, TracerConfig},
+    api::{
+        BlockId, BlockNumber, DebugCall, DebugTrace, PrestateAccount, PrestateTrace,
+        ResultDebugCall, SupportedTracers, TracerConfig,
+    },
     debug_flat_call::{flatten_debug_calls, DebugCallFlat},
     fee_model::BatchFeeInput,
     l2::L2Tx,
     transaction_request::CallRequest,
+    utils::decompose_full_nonce,
     vm_trace::Call,
-    AccountTreeId, H256,
+    web3::Bytes,
+    AccountTreeId, Address, L2BlockNumber, H256,
+};
+use zksync_utils::{
+    bytecode::{hash_bytecode, validate_bytecode},
+    u256_to_h256,
 };
 use zksync_web3_decl::error::Web3Error;
 
@@ -70,9 +83,14 @@ impl DebugNamespace {
             return Ok(vec![]);
         }
 
-        let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
-            .unwrap_or(false);
+        let only_top_call = match options {
+            Some(TracerConfig {
+                tracer: SupportedTracers::PrestateTracer,
+                ..
+            }) => return Err(Web3Error::UnsupportedTracer("prestateTracer")),
+            Some(options) => options.tracer_config.call_tracer.only_top_call,
+            None => false,
+        };
         let mut connection = self.state.acquire_connection().await?;
         let block_number = self.state.resolve_block(&mut connection, block_id).await?;
         self.current_method()
@@ -110,9 +128,19 @@ impl DebugNamespace {
         &self,
         tx_hash: H256,
         options: Option<TracerConfig>,
-    ) -> Result<Option<DebugCall>, Web3Error> {
+    ) -> Result<Option<DebugTrace>, Web3Error> {
+        if let Some(TracerConfig {
+            tracer: SupportedTracers::PrestateTracer,
+            tracer_config,
+        }) = &options
+        {
+            let diff_mode = tracer_config.prestate_tracer.diff_mode;
+            let trace = self.trace_transaction_prestate(tx_hash, diff_mode).await?;
+            return Ok(trace.map(DebugTrace::Prestate));
+        }
+
         let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
+            .map(|options| options.tracer_config.call_tracer.only_top_call)
             .unwrap_or(false);
         let mut connection = self.state.acquire_connection().await?;
         let call_trace = connection
@@ -125,21 +153,124 @@ impl DebugNamespace {
             if only_top_call {
                 result.calls = vec![];
             }
-            result
+            DebugTrace::Call(result)
         }))
     }
 
+    /// Re-executes the transaction with the specified hash in the sandbox to trace its pre-state.
+    /// Returns `None` if the transaction is not included into a sealed L2 block.
+    ///
+    /// The transaction is executed on top of the state of the preceding L2 block, after the transactions
+    /// preceding it in its block. Since it's executed in the context of the preceding L2 block,
+    /// the block-related values (e.g., the block number and timestamp) it observes may differ from the original ones.
+    async fn trace_transaction_prestate(
+        &self,
+        tx_hash: H256,
+        diff_mode: bool,
+    ) -> Result<Option<PrestateTrace>, Web3Error> {
+        let mut connection = self.state.acquire_connection().await?;
+        let receipt = connection
+            .transactions_web3_dal()
+            .get_transaction_receipts(&[tx_hash])
+            .await
+            .map_err(DalError::generalize)?
+            .pop();
+        let Some(receipt) = receipt else {
+            return Ok(None);
+        };
+        let block_number = L2BlockNumber(receipt.block_number.as_u32());
+        self.current_method()
+            .set_block_id(BlockId::Number(receipt.block_number.into()));
+        self.current_method()
+            .set_block_diff(self.state.last_sealed_l2_block.diff(block_number));
+        if block_number == L2BlockNumber(0) {
+            // Genesis transactions cannot be re-executed since there's no preceding state.
+            return Ok(None);
+        }
+
+        let block_header = connection
+            .blocks_dal()
+            .get_l2_block_header(block_number)
+            .await
+            .map_err(DalError::generalize)?
+            .ok_or(Web3Error::NoBlock)?;
+        let mut block_txs = connection
+            .transactions_web3_dal()
+            .get_raw_l2_block_transactions(block_number)
+            .await
+            .map_err(DalError::generalize)?;
+        let Some(tx_position) = block_txs.iter().position(|tx| tx.hash() == tx_hash) else {
+            return Ok(None);
+        };
+        block_txs.truncate(tx_position + 1);
+        let tx = block_txs.pop().unwrap();
+        // ^ `unwrap()` is safe since `block_txs` contains at least the traced transaction.
+
+        let prev_block_id = BlockId::Number((block_number.0 - 1).into());
+        let block_args = self
+            .state
+            .resolve_block_args(&mut connection, prev_block_id)
+            .await?;
+        drop(connection);
+
+        let shared_args = self.shared_args().await;
+        let vm_permit = self
+            .state
+            .tx_sender
+            .vm_concurrency_limiter()
+            .acquire()
+            .await;
+        let vm_permit = vm_permit.context("cannot acquire VM permit")?;
+
+        let prestate_tracer_result = Arc::new(OnceCell::default());
+        let custom_tracers = vec![ApiTracer::PrestateTracer {
+            diff_mode,
+            result: prestate_tracer_result.clone(),
+        }];
+        let factory_deps = tx.execute.factory_deps.clone();
+        let executor = &self.state.tx_sender.0.executor;
+        executor
+            .replay_tx_in_sandbox(
+                vm_permit,
+                shared_args,
+                self.state.connection_pool.clone(),
+                block_txs,
+                tx,
+                block_args,
+                block_header.base_fee_per_gas,
+                custom_tracers,
+            )
+            .await?;
+
+        // The tracer is dropped after execution, so it's safe to unwrap
+        let prestate = Arc::try_unwrap(prestate_tracer_result)
+            .unwrap()
+            .take()
+            .unwrap_or_default();
+        let trace = self
+            .prestate_trace(prestate, diff_mode, &factory_deps)
+            .await?;
+        Ok(Some(trace))
+    }
+
     pub async fn debug_trace_call_impl(
         &self,
         mut request: CallRequest,
         block_id: Option<BlockId>,
         options: Option<TracerConfig>,
-    ) -> Result<DebugCall, Web3Error> {
+    ) -> Result<DebugTrace, Web3Error> {
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
         self.current_method().set_block_id(block_id);
 
+        let prestate_diff_mode = match &options {
+            Some(TracerConfig {
+                tracer: SupportedTracers::PrestateTracer,
+                tracer_config,
+            }) => Some(tracer_config.prestate_tracer.diff_mode),
+            _ => None,
+        };
         let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
+            .map(|options| options.tracer_config.call_tracer.only_top_call)
             .unwrap_or(false);
 
         let mut connection = self.state.acquire_connection().await?;
@@ -180,7 +311,13 @@ impl DebugNamespace {
 
         // We don't need properly trace if we only need top call
         let call_tracer_result = Arc::new(OnceCell::default());
-        let custom_tracers = if only_top_call {
+        let prestate_tracer_result = Arc::new(OnceCell::default());
+        let custom_tracers = if let Some(diff_mode) = prestate_diff_mode {
+            vec![ApiTracer::PrestateTracer {
+                diff_mode,
+                result: prestate_tracer_result.clone(),
+            }]
+        } else if only_top_call {
             vec![]
         } else {
             vec![ApiTracer::CallTracer(call_tracer_result.clone())]
@@ -196,10 +333,23 @@ impl DebugNamespace {
                 tx.clone(),
                 block_args,
                 self.sender_config().vm_execution_cache_misses_limit,
+                None,
                 custom_tracers,
             )
             .await?;
 
+        if let Some(diff_mode) = prestate_diff_mode {
+            // We had only one copy of Arc this arc is already dropped it's safe to unwrap
+            let prestate = Arc::try_unwrap(prestate_tracer_result)
+                .unwrap()
+                .take()
+                .unwrap_or_default();
+            let trace = self
+                .prestate_trace(prestate, diff_mode, &tx.execute.factory_deps)
+                .await?;
+            return Ok(DebugTrace::Prestate(trace));
+        }
+
         let (output, revert_reason) = match result.result {
             ExecutionResult::Success { output, .. } => (output, None),
             ExecutionResult::Revert { output } => (vec![], Some(output.to_string())),
@@ -225,7 +375,70 @@ impl DebugNamespace {
             revert_reason,
             trace,
         );
-        Ok(call.into())
+        Ok(DebugTrace::Call(call.into()))
+    }
+
+    /// Converts the output of the prestate tracer to the Geth-compatible format. Bytecodes of the traced contracts
+    /// are taken from `factory_deps` of the traced transaction or loaded from Postgres.
+    async fn prestate_trace(
+        &self,
+        (pre, post): (PrestateState, PrestateState),
+        diff_mode: bool,
+        factory_deps: &[Vec<u8>],
+    ) -> Result<PrestateTrace, Web3Error> {
+        let mut bytecodes: HashMap<_, _> = factory_deps
+            .iter()
+            .filter(|bytecode| validate_bytecode(bytecode).is_ok())
+            .map(|bytecode| (hash_bytecode(bytecode), bytecode.clone()))
+            .collect();
+        let code_hashes: HashSet<_> = pre
+            .values()
+            .chain(post.values())
+            .filter_map(|account| account.code.map(u256_to_h256))
+            .filter(|hash| !hash.is_zero() && !bytecodes.contains_key(hash))
+            .collect();
+
+        let mut connection = self.state.acquire_connection().await?;
+        for hash in code_hashes {
+            let bytecode = connection
+                .factory_deps_dal()
+                .get_sealed_factory_dep(hash)
+                .await
+                .map_err(DalError::generalize)?;
+            if let Some(bytecode) = bytecode {
+                bytecodes.insert(hash, bytecode);
+            }
+        }
+        drop(connection);
+
+        let convert_state = |state: PrestateState| -> BTreeMap<Address, PrestateAccount> {
+            state
+                .into_iter()
+                .map(|(address, account)| {
+                    let code = account
+                        .code
+                        .and_then(|hash| bytecodes.get(&u256_to_h256(hash)))
+                        .map(|bytecode| Bytes(bytecode.clone()));
+                    let account = PrestateAccount {
+                        balance: account.balance,
+                        nonce: account
+                            .nonce
+                            .map(|nonce| decompose_full_nonce(nonce).0.low_u64()),
+                        code,
+                        storage: account.storage.unwrap_or_default().into_iter().collect(),
+                    };
+                    (address, account)
+                })
+                .collect()
+        };
+        Ok(if diff_mode {
+            PrestateTrace::Diff {
+                pre: convert_state(pre),
+                post: convert_state(post),
+            }
+        } else {
+            PrestateTrace::Prestate(convert_state(pre))
+        })
     }
 
     async fn shared_args(&self) -> TxSharedArgs {
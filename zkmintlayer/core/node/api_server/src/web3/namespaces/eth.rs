@@ -1,30 +1,39 @@
 use anyhow::Context as _;
 use zksync_dal::{CoreDal, DalError};
+use zksync_multivm::interface::{ExecutionResult, VmExecutionResultAndLogs};
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
-        BlockId, BlockNumber, GetLogsFilter, Transaction, TransactionId, TransactionReceipt,
-        TransactionVariant,
+        state_override::StateOverride, AccessListResult, BlockId, BlockNumber, GetLogsFilter,
+        SimulatePayload, SimulatedBlock, SimulatedCallError, SimulatedCallResult, Transaction,
+        TransactionId, TransactionReceipt, TransactionVariant,
     },
     l2::{L2Tx, TransactionType},
     transaction_request::CallRequest,
     utils::decompose_full_nonce,
-    web3::{self, Bytes, FeeHistory, SyncInfo, SyncState},
+    web3::{self, AccessListItem, Bytes, FeeHistory, SyncInfo, SyncState},
     AccountTreeId, L2BlockNumber, StorageKey, H256, L2_BASE_TOKEN_ADDRESS, U256,
 };
-use zksync_utils::u256_to_h256;
+use zksync_utils::{bytecode::validate_bytecode, u256_to_h256};
 use zksync_web3_decl::{
     error::Web3Error,
     types::{Address, Block, Filter, FilterChanges, Log, U64},
 };
 
 use crate::{
+    execution_sandbox::SimulatedBlockArgs,
     utils::open_readonly_transaction,
     web3::{backend_jsonrpsee::MethodTracer, metrics::API_METRICS, state::RpcState, TypedFilter},
 };
 
 pub const EVENT_TOPIC_NUMBER_LIMIT: usize = 4;
 pub const PROTOCOL_VERSION: &str = "zks/1";
+/// Maximum number of L2 blocks that can be simulated by `eth_simulateV1`.
+const MAX_SIMULATED_BLOCKS: usize = 256;
+/// JSON-RPC error code for reverted calls in `eth_simulateV1` results.
+const REVERTED_CALL_ERROR_CODE: i64 = 3;
+/// JSON-RPC error code for calls halted by the VM in `eth_simulateV1` results.
+const HALTED_CALL_ERROR_CODE: i64 = -32015;
 
 #[derive(Debug)]
 pub(crate) struct EthNamespace {
@@ -55,7 +64,11 @@ impl EthNamespace {
         &self,
         mut request: CallRequest,
         block_id: Option<BlockId>,
+        state_override: Option<StateOverride>,
     ) -> Result<Bytes, Web3Error> {
+        if let Some(state_override) = &state_override {
+            validate_state_override(state_override)?;
+        }
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
         self.current_method().set_block_id(block_id);
 
@@ -88,17 +101,216 @@ impl EthNamespace {
         let call_result: Vec<u8> = self
             .state
             .tx_sender
-            .eth_call(block_args, call_overrides, tx)
+            .eth_call(block_args, call_overrides, tx, state_override)
             .await?;
         Ok(call_result.into())
     }
 
+    pub async fn create_access_list_impl(
+        &self,
+        mut request: CallRequest,
+        block_id: Option<BlockId>,
+    ) -> Result<AccessListResult, Web3Error> {
+        let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
+        self.current_method().set_block_id(block_id);
+
+        let mut connection = self.state.acquire_connection().await?;
+        let block_args = self
+            .state
+            .resolve_block_args(&mut connection, block_id)
+            .await?;
+        self.current_method().set_block_diff(
+            self.state
+                .last_sealed_l2_block
+                .diff_with_block_args(&block_args),
+        );
+        drop(connection);
+
+        if request.gas.is_none() {
+            request.gas = Some(
+                self.state
+                    .tx_sender
+                    .get_default_eth_call_gas(block_args)
+                    .await
+                    .map_err(Web3Error::InternalError)?
+                    .into(),
+            )
+        }
+        let call_overrides = request.get_call_overrides()?;
+        let tx = L2Tx::from_request(request.into(), self.state.api_config.max_tx_size)?;
+
+        let (result, accesses) = self
+            .state
+            .tx_sender
+            .create_access_list(block_args, call_overrides, tx)
+            .await?;
+        let error = match result.result {
+            ExecutionResult::Success { .. } => None,
+            ExecutionResult::Revert { output } => Some(output.to_user_friendly_string()),
+            ExecutionResult::Halt { reason } => Some(reason.to_string()),
+        };
+        let access_list = accesses
+            .into_iter()
+            .map(|(address, keys)| AccessListItem {
+                address,
+                storage_keys: keys.into_iter().collect(),
+            })
+            .collect();
+        Ok(AccessListResult {
+            access_list,
+            gas_used: result.statistics.gas_used.into(),
+            error,
+        })
+    }
+
+    pub async fn simulate_v1_impl(
+        &self,
+        payload: SimulatePayload,
+        block_id: Option<BlockId>,
+    ) -> Result<Vec<SimulatedBlock>, Web3Error> {
+        let block_count = payload.block_state_calls.len();
+        if block_count == 0 || block_count > MAX_SIMULATED_BLOCKS {
+            return Err(Web3Error::InvalidSimulationPayload(format!(
+                "number of simulated blocks must be between 1 and {MAX_SIMULATED_BLOCKS}"
+            )));
+        }
+        let call_count: usize = payload
+            .block_state_calls
+            .iter()
+            .map(|block| block.calls.len())
+            .sum();
+        let calls_limit = self.state.api_config.simulate_calls_limit;
+        if call_count > calls_limit {
+            return Err(Web3Error::InvalidSimulationPayload(format!(
+                "total number of simulated calls ({call_count}) exceeds the limit of {calls_limit}"
+            )));
+        }
+
+        let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
+        self.current_method().set_block_id(block_id);
+
+        let mut connection = self.state.acquire_connection().await?;
+        let block_args = self
+            .state
+            .resolve_block_args(&mut connection, block_id)
+            .await?;
+        self.current_method().set_block_diff(
+            self.state
+                .last_sealed_l2_block
+                .diff_with_block_args(&block_args),
+        );
+        drop(connection);
+
+        let default_gas = self
+            .state
+            .tx_sender
+            .get_default_eth_call_gas(block_args)
+            .await
+            .map_err(Web3Error::InternalError)?;
+        let mut blocks = Vec::with_capacity(block_count);
+        for (i, block) in payload.block_state_calls.into_iter().enumerate() {
+            if block.calls.is_empty() {
+                return Err(Web3Error::InvalidSimulationPayload(format!(
+                    "simulated block #{i} contains no calls"
+                )));
+            }
+            if let Some(state_override) = &block.state_overrides {
+                validate_state_override(state_override)?;
+                let replaces_storage = state_override
+                    .iter()
+                    .any(|(_, account)| account.state.is_some());
+                if replaces_storage {
+                    return Err(Web3Error::InvalidStateOverride(
+                        "`state` is not supported in simulated blocks; use `stateDiff`".to_owned(),
+                    ));
+                }
+            }
+            // Block overrides are applied to the storage shared by all simulated calls, so there's no way
+            // to scope an override to a single call.
+            let call_with_overrides = block
+                .calls
+                .iter()
+                .position(|call| call.state_overrides.is_some());
+            if let Some(call_index) = call_with_overrides {
+                return Err(Web3Error::InvalidStateOverride(format!(
+                    "state overrides are not supported for individual calls (call #{call_index} in simulated block #{i}); \
+                     use `stateOverrides` of the block instead"
+                )));
+            }
+
+            let calls = block
+                .calls
+                .into_iter()
+                .map(|call| {
+                    let mut request = call.request;
+                    request.gas.get_or_insert(default_gas.into());
+                    L2Tx::from_request(request.into(), self.state.api_config.max_tx_size)
+                })
+                .collect::<Result<_, _>>()?;
+            blocks.push(SimulatedBlockArgs {
+                state_override: block.state_overrides,
+                calls,
+            });
+        }
+
+        let outputs = self
+            .state
+            .tx_sender
+            .simulate_blocks(block_args, blocks)
+            .await?;
+        let simulated_blocks = outputs
+            .into_iter()
+            .map(|block| {
+                let block_number = U64::from(block.number.0);
+                let mut calls: Vec<_> = block
+                    .results
+                    .into_iter()
+                    .map(simulated_call_result)
+                    .collect();
+
+                let mut log_index = 0_u64;
+                for (call_index, call) in calls.iter_mut().enumerate() {
+                    for log in &mut call.logs {
+                        log.block_number = Some(block_number);
+                        log.block_timestamp = Some(block.timestamp as i64);
+                        log.transaction_index = Some((call_index as u64).into());
+                        log.log_index = Some(log_index.into());
+                        log_index += 1;
+                    }
+                }
+
+                SimulatedBlock {
+                    number: block_number,
+                    timestamp: block.timestamp.into(),
+                    gas_used: calls
+                        .iter()
+                        .fold(U256::zero(), |acc, call| acc + call.gas_used),
+                    calls,
+                }
+            })
+            .collect();
+        Ok(simulated_blocks)
+    }
+
     pub async fn estimate_gas_impl(
         &self,
         request: CallRequest,
         _block: Option<BlockNumber>,
+        state_override: Option<StateOverride>,
     ) -> Result<U256, Web3Error> {
+        if let Some(state_override) = &state_override {
+            validate_state_override(state_override)?;
+        }
         let mut request_with_gas_per_pubdata_overridden = request;
+        // The overridden nonce of the sender takes precedence over the stored one.
+        if request_with_gas_per_pubdata_overridden.nonce.is_none() {
+            let from = request_with_gas_per_pubdata_overridden
+                .from
+                .unwrap_or_default();
+            request_with_gas_per_pubdata_overridden.nonce = state_override
+                .as_ref()
+                .and_then(|state_override| state_override.get(&from)?.nonce);
+        }
         self.state
             .set_nonce_for_call_request(&mut request_with_gas_per_pubdata_overridden)
             .await?;
@@ -138,7 +350,12 @@ impl EthNamespace {
         let fee = self
             .state
             .tx_sender
-            .get_txs_fee_in_wei(tx.into(), scale_factor, acceptable_overestimation as u64)
+            .get_txs_fee_in_wei(
+                tx.into(),
+                scale_factor,
+                acceptable_overestimation as u64,
+                state_override,
+            )
             .await?;
         Ok(fee.gas_limit)
     }
@@ -869,3 +1086,58 @@ impl EthNamespace {
     // - `compile_solidity`.
     // - `compile_serpent`.
 }
+
+/// Checks that the state override is well-formed.
+fn validate_state_override(state_override: &StateOverride) -> Result<(), Web3Error> {
+    for (address, account) in state_override.iter() {
+        if account.state.is_some() && account.state_diff.is_some() {
+            return Err(Web3Error::InvalidStateOverride(format!(
+                "both `state` and `stateDiff` are specified for account {address:?}"
+            )));
+        }
+        if let Some(code) = &account.code {
+            validate_bytecode(&code.0).map_err(|err| {
+                Web3Error::InvalidStateOverride(format!(
+                    "invalid bytecode for account {address:?}: {err}"
+                ))
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Converts the VM output of a simulated call. Logs are returned only for successful calls.
+fn simulated_call_result(result: VmExecutionResultAndLogs) -> SimulatedCallResult {
+    let (return_data, error) = match result.result {
+        ExecutionResult::Success { output } => (output, None),
+        ExecutionResult::Revert { output } => {
+            let error = SimulatedCallError {
+                code: REVERTED_CALL_ERROR_CODE,
+                message: output.to_user_friendly_string(),
+                data: Some(output.encoded_data().into()),
+            };
+            (output.encoded_data(), Some(error))
+        }
+        ExecutionResult::Halt { reason } => {
+            let error = SimulatedCallError {
+                code: HALTED_CALL_ERROR_CODE,
+                message: reason.to_string(),
+                data: None,
+            };
+            (vec![], Some(error))
+        }
+    };
+    let logs = if error.is_none() {
+        result.logs.events.iter().map(Log::from).collect()
+    } else {
+        vec![]
+    };
+
+    SimulatedCallResult {
+        status: U64::from(u64::from(error.is_none())),
+        return_data: return_data.into(),
+        gas_used: result.statistics.gas_used.into(),
+        logs,
+        error,
+    }
+}
@@ -8,14 +8,16 @@ use zksync_multivm::interface::VmExecutionResultAndLogs;
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, GetLogsFilter, L1BatchDetails, L2ToL1LogProof, Proof,
-        ProtocolVersion, StorageProof, TransactionDetails,
+        BlockDetails, BridgeAddresses, GetLogsFilter, L1BatchDetails, L2ToL1LogProof,
+        MintlayerAnchorInfo, MintlayerAnchorStatus, MintlayerBatchAnchor, MintlayerOperationInfo,
+        Proof, ProtocolVersion, StorageProof, TransactionDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
     l1::L1Tx,
     l2::L2Tx,
     l2_to_l1_log::{l2_to_l1_logs_tree_size, L2ToL1Log},
+    mintlayer::{MintlayerAnchor, MintlayerAnchorCommitment, MintlayerAnchorOperation},
     tokens::ETHEREUM_ADDRESS,
     transaction_request::CallRequest,
     utils::storage_key_for_standard_token_balance,
@@ -99,7 +101,7 @@ impl ZksNamespace {
         Ok(self
             .state
             .tx_sender
-            .get_txs_fee_in_wei(tx, scale_factor, acceptable_overestimation as u64)
+            .get_txs_fee_in_wei(tx, scale_factor, acceptable_overestimation as u64, None)
             .await?)
     }
 
@@ -430,13 +432,123 @@ impl ZksNamespace {
             .ensure_not_pruned(batch_number, &mut storage)
             .await?;
 
-        Ok(storage
+        let Some(mut details) = storage
             .blocks_web3_dal()
             .get_l1_batch_details(batch_number)
+            .await
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(None);
+        };
+        details.mintlayer_anchor = Self::load_mintlayer_anchor(&mut storage, batch_number)
+            .await
+            .map_err(DalError::generalize)?;
+        Ok(Some(details))
+    }
+
+    pub async fn get_mintlayer_anchor_impl(
+        &self,
+        batch_number: L1BatchNumber,
+    ) -> Result<Option<MintlayerBatchAnchor>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        self.state
+            .start_info
+            .ensure_not_pruned(batch_number, &mut storage)
+            .await?;
+
+        Ok(Self::load_mintlayer_anchor(&mut storage, batch_number)
             .await
             .map_err(DalError::generalize)?)
     }
 
+    /// Loads anchoring information for all operations covering the specified L1 batch. Returns `None`
+    /// if no operations for the batch were uploaded to IPFS.
+    async fn load_mintlayer_anchor(
+        storage: &mut Connection<'_, Core>,
+        batch_number: L1BatchNumber,
+    ) -> Result<Option<MintlayerBatchAnchor>, DalError> {
+        let operations = storage
+            .mintlayer_anchor_dal()
+            .get_l1_batch_operations(batch_number)
+            .await?;
+        if operations.is_empty() {
+            return Ok(None);
+        }
+
+        // Operations of a batch are usually covered by the same anchor, so we cache anchors and their commitments.
+        let mut anchors = HashMap::new();
+        let mut operation_infos = Vec::with_capacity(operations.len());
+        for operation in operations {
+            let anchor_info = if let Some(anchor_id) = operation.anchor_id {
+                if !anchors.contains_key(&anchor_id) {
+                    let mut dal = storage.mintlayer_anchor_dal();
+                    let anchor = dal.get_anchor(anchor_id).await?;
+                    let anchor_operations = dal.get_anchor_operations(anchor_id).await?;
+                    anchors.insert(anchor_id, anchor.map(|anchor| (anchor, anchor_operations)));
+                }
+                anchors[&anchor_id]
+                    .as_ref()
+                    .map(|(anchor, anchor_operations)| {
+                        Self::mintlayer_anchor_info(anchor, anchor_operations, operation.id)
+                    })
+            } else {
+                None
+            };
+            operation_infos.push(MintlayerOperationInfo {
+                operation: operation.to_leaf(),
+                anchor: anchor_info,
+            });
+        }
+
+        Ok(Some(MintlayerBatchAnchor {
+            l1_batch_number: batch_number,
+            operations: operation_infos,
+        }))
+    }
+
+    fn mintlayer_anchor_info(
+        anchor: &MintlayerAnchor,
+        anchor_operations: &[MintlayerAnchorOperation],
+        operation_id: u32,
+    ) -> MintlayerAnchorInfo {
+        let status = if anchor.revoked_at.is_some() {
+            MintlayerAnchorStatus::Revoked
+        } else if anchor.confirmed_at.is_some() {
+            MintlayerAnchorStatus::Confirmed
+        } else if anchor.included_at_block.is_some() {
+            MintlayerAnchorStatus::Included
+        } else if anchor.mintlayer_tx_id.is_some() {
+            MintlayerAnchorStatus::Sent
+        } else {
+            MintlayerAnchorStatus::Pending
+        };
+
+        // Legacy anchors don't commit to a Merkle tree, so there's nothing to prove against.
+        let proof = anchor.merkle_root.and_then(|_| {
+            let leaf_index = anchor_operations
+                .iter()
+                .position(|op| op.id == operation_id)?;
+            let commitment = MintlayerAnchorCommitment::new(
+                anchor_operations
+                    .iter()
+                    .map(MintlayerAnchorOperation::to_leaf)
+                    .collect(),
+            );
+            commitment.proof(leaf_index)
+        });
+
+        MintlayerAnchorInfo {
+            root_cid: anchor.root_cid.clone(),
+            merkle_root: anchor.merkle_root,
+            mintlayer_tx_id: anchor.mintlayer_tx_id.clone(),
+            status,
+            included_at_block: anchor.included_at_block,
+            confirmed_at: anchor.confirmed_at,
+            revocation_tx_id: anchor.revocation_tx_id.clone(),
+            proof,
+        }
+    }
+
     pub async fn get_bytecode_by_hash_impl(
         &self,
         hash: H256,
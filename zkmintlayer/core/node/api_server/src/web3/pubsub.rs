@@ -1,5 +1,7 @@
 //! (Largely) backend-agnostic logic for dealing with Web3 subscriptions.
 
+use std::{collections::HashMap, mem};
+
 use chrono::NaiveDateTime;
 use futures::FutureExt;
 use tokio::{
@@ -9,7 +11,7 @@ use tokio::{
 };
 use tracing::Instrument as _;
 use zksync_dal::{ConnectionPool, Core, CoreDal};
-use zksync_types::{L2BlockNumber, H128, H256};
+use zksync_types::{api, L1BatchNumber, L2BlockNumber, L2ChainId, H128, H256};
 use zksync_web3_decl::{
     jsonrpsee::{
         core::{server::SubscriptionMessage, SubscriptionResult},
@@ -18,7 +20,7 @@ use zksync_web3_decl::{
         PendingSubscriptionSink, SendTimeoutError, SubscriptionSink,
     },
     namespaces::EthPubSubServer,
-    types::{BlockHeader, Log, PubSubFilter, PubSubResult},
+    types::{BlockHeader, Log, PubSubFilter, PubSubParams, PubSubResult},
 };
 
 use super::{
@@ -47,6 +49,22 @@ pub enum PubSubEvent {
     L2BlockAdvanced(SubscriptionType, L2BlockNumber),
 }
 
+/// Stage of the L1 batch lifecycle tracked by L1 batch subscriptions.
+#[derive(Debug, Clone, Copy)]
+enum L1BatchStage {
+    Committed,
+    Executed,
+}
+
+impl L1BatchStage {
+    fn subscription_type(self) -> SubscriptionType {
+        match self {
+            Self::Committed => SubscriptionType::L1BatchesCommitted,
+            Self::Executed => SubscriptionType::L1BatchesExecuted,
+        }
+    }
+}
+
 /// Manager of notifications for a certain type of subscriptions.
 #[derive(Debug)]
 struct PubSubNotifier {
@@ -145,7 +163,14 @@ impl PubSubNotifier {
             .map_err(Into::into)
     }
 
-    async fn notify_txs(self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+    /// Notifies about new pending transactions. Full transactions are loaded and sent to `full_txs_sender`
+    /// only if it has subscribers.
+    async fn notify_txs(
+        self,
+        full_txs_sender: broadcast::Sender<Vec<PubSubResult>>,
+        chain_id: L2ChainId,
+        stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
         let mut last_time = chrono::Utc::now().naive_utc();
         let mut timer = interval(self.polling_interval);
         loop {
@@ -161,10 +186,23 @@ impl PubSubNotifier {
 
             if let Some((new_last_time, _)) = new_txs.last() {
                 last_time = *new_last_time;
-                let new_txs = new_txs
-                    .into_iter()
-                    .map(|(_, tx_hash)| PubSubResult::TxHash(tx_hash))
-                    .collect();
+                let tx_hashes: Vec<_> = new_txs.into_iter().map(|(_, tx_hash)| tx_hash).collect();
+                if full_txs_sender.receiver_count() > 0 {
+                    let db_latency =
+                        PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::FullTxs].start();
+                    let full_txs = self.full_txs(&tx_hashes, chain_id).await?;
+                    db_latency.observe();
+
+                    let full_txs = full_txs
+                        .into_iter()
+                        .map(PubSubResult::Transaction)
+                        .collect();
+                    full_txs_sender.send(full_txs).ok();
+                    PUB_SUB_METRICS.broadcast_channel_len[&SubscriptionType::FullTxs]
+                        .set(full_txs_sender.len());
+                }
+
+                let new_txs = tx_hashes.into_iter().map(PubSubResult::TxHash).collect();
                 self.send_pub_sub_results(new_txs, SubscriptionType::Txs);
             }
             self.emit_event(PubSubEvent::NotifyIterationFinished(SubscriptionType::Txs));
@@ -185,6 +223,28 @@ impl PubSubNotifier {
             .map_err(Into::into)
     }
 
+    /// Loads transactions with the specified hashes, preserving their order.
+    async fn full_txs(
+        &self,
+        tx_hashes: &[H256],
+        chain_id: L2ChainId,
+    ) -> anyhow::Result<Vec<api::Transaction>> {
+        let mut txs = self
+            .connection_pool
+            .connection_tagged("api")
+            .await?
+            .transactions_web3_dal()
+            .get_transactions(tx_hashes, chain_id)
+            .await?;
+        let positions: HashMap<_, _> = tx_hashes
+            .iter()
+            .enumerate()
+            .map(|(i, hash)| (*hash, i))
+            .collect();
+        txs.sort_unstable_by_key(|tx| positions.get(&tx.hash).copied());
+        Ok(txs)
+    }
+
     async fn notify_logs(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let Some(mut last_block_number) = self
             .get_starting_l2_block_number(&mut stop_receiver)
@@ -194,6 +254,8 @@ impl PubSubNotifier {
             return Ok(());
         };
 
+        let (mut last_rollback_id, mut pending_removed_logs) =
+            self.initial_removed_logs(last_block_number).await?;
         let mut timer = interval(self.polling_interval);
         loop {
             if *stop_receiver.borrow() {
@@ -203,9 +265,27 @@ impl PubSubNotifier {
             timer.tick().await;
 
             let db_latency = PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::Logs].start();
+            let mut removed_logs = self
+                .removed_logs(&mut last_rollback_id, &mut last_block_number)
+                .await?;
             let new_logs = self.new_logs(last_block_number).await?;
             db_latency.observe();
 
+            // Subscriptions don't survive server restarts, so we wait for new subscribers before reporting logs
+            // from the rollbacks performed while the server was stopped.
+            if !pending_removed_logs.is_empty() && self.sender.receiver_count() > 0 {
+                removed_logs.splice(0..0, mem::take(&mut pending_removed_logs));
+            }
+
+            if !removed_logs.is_empty() {
+                tracing::info!(
+                    "L2 blocks were rolled back; notifying about {} removed logs",
+                    removed_logs.len()
+                );
+                let removed_logs = removed_logs.into_iter().map(PubSubResult::Log).collect();
+                self.send_pub_sub_results(removed_logs, SubscriptionType::Logs);
+            }
+
             if let Some(last_log) = new_logs.last() {
                 last_block_number = L2BlockNumber(last_log.block_number.unwrap().as_u32());
                 let new_logs = new_logs.into_iter().map(PubSubResult::Log).collect();
@@ -229,13 +309,166 @@ impl PubSubNotifier {
             .await
             .map_err(Into::into)
     }
+
+    fn mark_removed(log: Log) -> Log {
+        api::Log {
+            removed: Some(true),
+            ..log
+        }
+    }
+
+    /// Loads logs from the L2 block rollbacks persisted before the notifier has started (e.g., by the block reverter
+    /// while the API server was stopped) whose blocks haven't been replaced yet. Returns the ID of the last persisted
+    /// rollback together with these logs marked as removed.
+    async fn initial_removed_logs(
+        &self,
+        last_block_number: L2BlockNumber,
+    ) -> anyhow::Result<(u64, Vec<Log>)> {
+        let mut storage = self.connection_pool.connection_tagged("api").await?;
+        let last_rollback_id = storage.events_dal().get_last_l2_block_rollback_id().await?;
+        let rollbacks = storage
+            .events_dal()
+            .get_l2_block_rollbacks_after_l2_block(last_block_number)
+            .await?;
+        let removed_logs = rollbacks
+            .into_iter()
+            .filter(|rollback| rollback.id <= last_rollback_id)
+            .flat_map(|rollback| rollback.logs)
+            .map(Self::mark_removed)
+            .collect();
+        Ok((last_rollback_id, removed_logs))
+    }
+
+    /// Loads L2 block rollbacks persisted after `last_rollback_id` and returns logs from the rolled back blocks
+    /// that were already processed by the notifier, marked as removed. `last_block_number` is moved back accordingly,
+    /// so that logs from the replacement blocks are emitted.
+    async fn removed_logs(
+        &self,
+        last_rollback_id: &mut u64,
+        last_block_number: &mut L2BlockNumber,
+    ) -> anyhow::Result<Vec<Log>> {
+        let mut storage = self.connection_pool.connection_tagged("api").await?;
+        let rollbacks = storage
+            .events_dal()
+            .get_l2_block_rollbacks_after(*last_rollback_id)
+            .await?;
+
+        let mut removed_logs = vec![];
+        for rollback in rollbacks {
+            *last_rollback_id = rollback.id;
+            let processed_block_number = *last_block_number;
+            let processed_logs = rollback.logs.into_iter().filter(|log| {
+                log.block_number
+                    .is_some_and(|number| number.as_u32() <= processed_block_number.0)
+            });
+            removed_logs.extend(processed_logs.map(Self::mark_removed));
+            let last_kept_block_number = rollback.first_l2_block.0.saturating_sub(1);
+            *last_block_number = (*last_block_number).min(L2BlockNumber(last_kept_block_number));
+        }
+
+        // L2 blocks may be removed without persisting a rollback (e.g., if Postgres is modified manually), so we check
+        // whether the sealed L2 block has moved back separately.
+        let sealed_block_number = storage.blocks_dal().get_sealed_l2_block_number().await?;
+        if let Some(sealed_block_number) = sealed_block_number {
+            *last_block_number = (*last_block_number).min(sealed_block_number);
+        }
+        Ok(removed_logs)
+    }
+
+    async fn notify_l1_batches(
+        self,
+        stage: L1BatchStage,
+        stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        let sub_type = stage.subscription_type();
+        let mut last_batch_number = self.last_l1_batch_number(stage).await?;
+        let mut timer = interval(self.polling_interval);
+        loop {
+            if *stop_receiver.borrow() {
+                tracing::info!(
+                    "Stop signal received, pubsub_l1_batches_notifier ({stage:?}) is shutting down"
+                );
+                break;
+            }
+            timer.tick().await;
+
+            let db_latency = PUB_SUB_METRICS.db_poll_latency[&sub_type].start();
+            let current_batch_number = self.last_l1_batch_number(stage).await?;
+            let new_batches = self
+                .new_l1_batches(last_batch_number, current_batch_number)
+                .await?;
+            db_latency.observe();
+
+            // The batch number can decrease if L1 batches are rolled back.
+            last_batch_number = current_batch_number;
+            if !new_batches.is_empty() {
+                let new_batches = new_batches.into_iter().map(PubSubResult::L1Batch).collect();
+                self.send_pub_sub_results(new_batches, sub_type);
+            }
+            self.emit_event(PubSubEvent::NotifyIterationFinished(sub_type));
+        }
+        Ok(())
+    }
+
+    async fn last_l1_batch_number(
+        &self,
+        stage: L1BatchStage,
+    ) -> anyhow::Result<Option<L1BatchNumber>> {
+        let mut storage = self.connection_pool.connection_tagged("api").await?;
+        let number = match stage {
+            L1BatchStage::Committed => {
+                storage
+                    .blocks_dal()
+                    .get_number_of_last_l1_batch_committed_on_eth()
+                    .await?
+            }
+            L1BatchStage::Executed => {
+                storage
+                    .blocks_dal()
+                    .get_number_of_last_l1_batch_executed_on_eth()
+                    .await?
+            }
+        };
+        Ok(number)
+    }
+
+    /// Loads details for L1 batches in the `(last_batch_number, current_batch_number]` range. If `last_batch_number`
+    /// is `None`, only the current batch is loaded.
+    async fn new_l1_batches(
+        &self,
+        last_batch_number: Option<L1BatchNumber>,
+        current_batch_number: Option<L1BatchNumber>,
+    ) -> anyhow::Result<Vec<api::L1BatchDetails>> {
+        let Some(current_batch_number) = current_batch_number else {
+            return Ok(vec![]);
+        };
+        let first_batch_number = match last_batch_number {
+            Some(number) if number >= current_batch_number => return Ok(vec![]),
+            Some(number) => number + 1,
+            None => current_batch_number,
+        };
+
+        let mut storage = self.connection_pool.connection_tagged("api").await?;
+        let mut batches = vec![];
+        for number in first_batch_number.0..=current_batch_number.0 {
+            let details = storage
+                .blocks_web3_dal()
+                .get_l1_batch_details(L1BatchNumber(number))
+                .await?;
+            batches.extend(details);
+        }
+        Ok(batches)
+    }
 }
 
 /// Subscription support for Web3 APIs.
 pub(super) struct EthSubscribe {
     blocks: broadcast::Sender<Vec<PubSubResult>>,
     transactions: broadcast::Sender<Vec<PubSubResult>>,
+    full_transactions: broadcast::Sender<Vec<PubSubResult>>,
     logs: broadcast::Sender<Vec<PubSubResult>>,
+    committed_l1_batches: broadcast::Sender<Vec<PubSubResult>>,
+    executed_l1_batches: broadcast::Sender<Vec<PubSubResult>>,
     events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
 }
 
@@ -243,12 +476,18 @@ impl EthSubscribe {
     pub fn new() -> Self {
         let (blocks, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
         let (transactions, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
+        let (full_transactions, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
         let (logs, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
+        let (committed_l1_batches, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
+        let (executed_l1_batches, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
 
         Self {
             blocks,
             transactions,
+            full_transactions,
             logs,
+            committed_l1_batches,
+            executed_l1_batches,
             events_sender: None,
         }
     }
@@ -350,7 +589,7 @@ impl EthSubscribe {
         &self,
         pending_sink: PendingSubscriptionSink,
         sub_type: String,
-        params: Option<PubSubFilter>,
+        params: Option<PubSubParams>,
     ) {
         let sub_type = match sub_type.as_str() {
             "newHeads" => {
@@ -369,18 +608,32 @@ impl EthSubscribe {
                 let Ok(sink) = pending_sink.accept().await else {
                     return;
                 };
-                let transactions_rx = self.transactions.subscribe();
+                let full_transactions =
+                    matches!(params, Some(PubSubParams::FullTransactions(true)));
+                let (sub_type, transactions_rx) = if full_transactions {
+                    (
+                        SubscriptionType::FullTxs,
+                        self.full_transactions.subscribe(),
+                    )
+                } else {
+                    (SubscriptionType::Txs, self.transactions.subscribe())
+                };
                 tokio::spawn(
-                    Self::run_subscriber(sink, SubscriptionType::Txs, transactions_rx, None)
-                        .in_current_span(),
+                    Self::run_subscriber(sink, sub_type, transactions_rx, None).in_current_span(),
                 );
-                Some(SubscriptionType::Txs)
+                Some(sub_type)
             }
             "logs" => {
-                let filter = params.unwrap_or_default();
-                let topic_count = filter.topics.as_ref().map_or(0, Vec::len);
+                let filter = match params {
+                    Some(PubSubParams::Filter(filter)) => Some(filter),
+                    Some(PubSubParams::FullTransactions(_)) => None,
+                    None => Some(PubSubFilter::default()),
+                };
+                let is_valid_filter = filter.as_ref().is_some_and(|filter| {
+                    filter.topics.as_ref().map_or(0, Vec::len) <= EVENT_TOPIC_NUMBER_LIMIT
+                });
 
-                if topic_count > EVENT_TOPIC_NUMBER_LIMIT {
+                if !is_valid_filter {
                     Self::reject(pending_sink).await;
                     None
                 } else {
@@ -389,12 +642,32 @@ impl EthSubscribe {
                     };
                     let logs_rx = self.logs.subscribe();
                     tokio::spawn(
-                        Self::run_subscriber(sink, SubscriptionType::Logs, logs_rx, Some(filter))
+                        Self::run_subscriber(sink, SubscriptionType::Logs, logs_rx, filter)
                             .in_current_span(),
                     );
                     Some(SubscriptionType::Logs)
                 }
             }
+            "zks_l1BatchCommitted" | "zks_l1BatchExecuted" => {
+                let Ok(sink) = pending_sink.accept().await else {
+                    return;
+                };
+                let (sub_type, l1_batches_rx) = if sub_type == "zks_l1BatchCommitted" {
+                    (
+                        SubscriptionType::L1BatchesCommitted,
+                        self.committed_l1_batches.subscribe(),
+                    )
+                } else {
+                    (
+                        SubscriptionType::L1BatchesExecuted,
+                        self.executed_l1_batches.subscribe(),
+                    )
+                };
+                tokio::spawn(
+                    Self::run_subscriber(sink, sub_type, l1_batches_rx, None).in_current_span(),
+                );
+                Some(sub_type)
+            }
             "syncing" => {
                 let Ok(sink) = pending_sink.accept().await else {
                     return;
@@ -427,9 +700,10 @@ impl EthSubscribe {
         &self,
         connection_pool: ConnectionPool<Core>,
         polling_interval: Duration,
+        chain_id: L2ChainId,
         stop_receiver: watch::Receiver<bool>,
     ) -> Vec<JoinHandle<anyhow::Result<()>>> {
-        let mut notifier_tasks = Vec::with_capacity(3);
+        let mut notifier_tasks = Vec::with_capacity(5);
 
         let notifier = PubSubNotifier {
             sender: self.blocks.clone(),
@@ -446,16 +720,41 @@ impl EthSubscribe {
             polling_interval,
             events_sender: self.events_sender.clone(),
         };
-        let notifier_task = tokio::spawn(notifier.notify_txs(stop_receiver.clone()));
+        let notifier_task = tokio::spawn(notifier.notify_txs(
+            self.full_transactions.clone(),
+            chain_id,
+            stop_receiver.clone(),
+        ));
         notifier_tasks.push(notifier_task);
 
         let notifier = PubSubNotifier {
             sender: self.logs.clone(),
+            connection_pool: connection_pool.clone(),
+            polling_interval,
+            events_sender: self.events_sender.clone(),
+        };
+        let notifier_task = tokio::spawn(notifier.notify_logs(stop_receiver.clone()));
+        notifier_tasks.push(notifier_task);
+
+        let notifier = PubSubNotifier {
+            sender: self.committed_l1_batches.clone(),
+            connection_pool: connection_pool.clone(),
+            polling_interval,
+            events_sender: self.events_sender.clone(),
+        };
+        let notifier_task = tokio::spawn(
+            notifier.notify_l1_batches(L1BatchStage::Committed, stop_receiver.clone()),
+        );
+        notifier_tasks.push(notifier_task);
+
+        let notifier = PubSubNotifier {
+            sender: self.executed_l1_batches.clone(),
             connection_pool,
             polling_interval,
             events_sender: self.events_sender.clone(),
         };
-        let notifier_task = tokio::spawn(notifier.notify_logs(stop_receiver));
+        let notifier_task =
+            tokio::spawn(notifier.notify_l1_batches(L1BatchStage::Executed, stop_receiver));
 
         notifier_tasks.push(notifier_task);
         notifier_tasks
@@ -468,9 +767,9 @@ impl EthPubSubServer for EthSubscribe {
         &self,
         pending: PendingSubscriptionSink,
         sub_type: String,
-        filter: Option<PubSubFilter>,
+        params: Option<PubSubParams>,
     ) -> SubscriptionResult {
-        self.sub(pending, sub_type, filter).await;
+        self.sub(pending, sub_type, params).await;
         Ok(())
     }
 }
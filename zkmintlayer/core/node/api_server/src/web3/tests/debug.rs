@@ -1,5 +1,8 @@
 //! Tests for the `debug` Web3 namespace.
 
+use std::collections::BTreeMap;
+
+use zksync_multivm::interface::ExecutionResult;
 use zksync_types::{tx::TransactionExecutionResult, vm_trace::Call, BOOTLOADER_ADDRESS};
 use zksync_web3_decl::{
     client::{DynClient, L2},
@@ -203,7 +206,9 @@ impl HttpTest for TraceTransactionTest {
         let result = client
             .trace_transaction(tx_results[0].hash, None)
             .await?
-            .context("no transaction traces")?;
+            .context("no transaction traces")?
+            .into_call()
+            .context("unexpected trace type")?;
         assert_eq!(result.from, Address::zero());
         assert_eq!(result.to, BOOTLOADER_ADDRESS);
         assert_eq!(result.gas, tx_results[0].transaction.gas_limit());
@@ -218,6 +223,72 @@ async fn tracing_transaction() {
     test_http_server(TraceTransactionTest).await;
 }
 
+#[derive(Debug)]
+struct TraceTransactionWithPrestateTracerTest;
+
+#[async_trait]
+impl HttpTest for TraceTransactionWithPrestateTracerTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        let mut tx_executor = MockTransactionExecutor::default();
+        tx_executor.set_tx_responses(|_, block_args| {
+            // Transactions must be replayed on top of the preceding L2 block.
+            assert_eq!(block_args.resolved_block_number(), L2BlockNumber(0));
+            ExecutionResult::Success { output: vec![] }
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let tx_results = [execute_l2_transaction_with_traces(0)];
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &tx_results).await?;
+        drop(storage);
+
+        let options = api::TracerConfig {
+            tracer: api::SupportedTracers::PrestateTracer,
+            tracer_config: api::TracerOptions {
+                prestate_tracer: api::PrestateTracerConfig { diff_mode: true },
+                ..api::TracerOptions::default()
+            },
+        };
+        let result = client
+            .trace_transaction(tx_results[0].hash, Some(options.clone()))
+            .await?
+            .context("no transaction traces")?;
+        // The mock executor doesn't run tracers, so the trace is empty.
+        let expected_trace = api::PrestateTrace::Diff {
+            pre: BTreeMap::new(),
+            post: BTreeMap::new(),
+        };
+        assert_eq!(result, api::DebugTrace::Prestate(expected_trace));
+
+        let result = client
+            .trace_transaction(H256::repeat_byte(0xff), Some(options.clone()))
+            .await?;
+        assert_eq!(result, None);
+
+        let error = client
+            .trace_block_by_number(1_u32.into(), Some(options))
+            .await
+            .unwrap_err();
+        if let ClientError::Call(error) = error {
+            assert_eq!(error.code(), ErrorCode::InvalidParams.code());
+        } else {
+            panic!("Unexpected error: {error:?}");
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn tracing_transaction_with_prestate_tracer() {
+    test_http_server(TraceTransactionWithPrestateTracerTest).await;
+}
+
 #[derive(Debug)]
 struct TraceBlockTestWithSnapshotRecovery;
 
@@ -24,11 +24,13 @@ use zksync_node_test_utils::{
     l1_batch_metadata_to_commitment_artifacts, prepare_recovery_snapshot,
 };
 use zksync_types::{
+    aggregated_operations::AggregatedActionType,
     api,
     block::L2BlockHeader,
     fee::TransactionExecutionMetrics,
     get_nonce_key,
     l2::L2Tx,
+    mintlayer::{MintlayerAnchorCommitment, MintlayerAnchorOperation},
     storage::get_code_key,
     tokens::{TokenInfo, TokenMetadata},
     tx::{
@@ -1066,3 +1068,109 @@ impl HttpTest for GenesisConfigTest {
 async fn tracing_genesis_config() {
     test_http_server(GenesisConfigTest).await;
 }
+
+#[derive(Debug)]
+struct MintlayerAnchorTest;
+
+#[async_trait]
+impl HttpTest for MintlayerAnchorTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let anchor = client.get_mintlayer_anchor(L1BatchNumber(0)).await?;
+        assert_eq!(anchor, None);
+
+        let mut storage = pool.connection().await?;
+        for (i, action_type) in [
+            AggregatedActionType::Commit,
+            AggregatedActionType::PublishProofOnchain,
+            AggregatedActionType::Execute,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let eth_tx_id = storage
+                .eth_sender_dal()
+                .save_eth_tx(
+                    i as u64,
+                    vec![],
+                    action_type,
+                    Address::default(),
+                    0,
+                    None,
+                    None,
+                )
+                .await?
+                .id;
+            storage
+                .mintlayer_anchor_dal()
+                .insert_operation(
+                    eth_tx_id,
+                    action_type,
+                    L1BatchNumber(0)..=L1BatchNumber(0),
+                    &format!("cid{i}"),
+                )
+                .await?;
+        }
+
+        let anchor = client
+            .get_mintlayer_anchor(L1BatchNumber(0))
+            .await?
+            .context("no anchor info")?;
+        assert_eq!(anchor.operations.len(), 3);
+        assert_eq!(anchor.operations[0].operation.cid, "cid0");
+        assert!(anchor.operations.iter().all(|op| op.anchor.is_none()));
+
+        let pending_operations = storage
+            .mintlayer_anchor_dal()
+            .get_pending_operations(3)
+            .await?;
+        let operation_ids: Vec<_> = pending_operations.iter().map(|op| op.id).collect();
+        let commitment = MintlayerAnchorCommitment::new(
+            pending_operations
+                .iter()
+                .map(MintlayerAnchorOperation::to_leaf)
+                .collect(),
+        );
+        let anchor_id = storage
+            .mintlayer_anchor_dal()
+            .insert_anchor("root_cid", commitment.merkle_root(), &operation_ids)
+            .await?;
+        storage
+            .mintlayer_anchor_dal()
+            .set_anchor_tx_id(anchor_id, "ml_tx")
+            .await?;
+        storage
+            .mintlayer_anchor_dal()
+            .confirm_anchor(anchor_id, 100)
+            .await?;
+
+        let anchor = client
+            .get_mintlayer_anchor(L1BatchNumber(0))
+            .await?
+            .context("no anchor info")?;
+        for operation in &anchor.operations {
+            let anchor_info = operation.anchor.as_ref().context("no anchor")?;
+            assert_eq!(anchor_info.root_cid, "root_cid");
+            assert_eq!(anchor_info.mintlayer_tx_id.as_deref(), Some("ml_tx"));
+            assert_eq!(anchor_info.status, api::MintlayerAnchorStatus::Confirmed);
+            assert_eq!(anchor_info.included_at_block, Some(100));
+            let proof = anchor_info.proof.as_ref().context("no proof")?;
+            assert!(proof.verify(&operation.operation, commitment.merkle_root()));
+        }
+
+        let details = client
+            .get_l1_batch_details(L1BatchNumber(0))
+            .await?
+            .context("no genesis L1 batch")?;
+        assert_eq!(details.mintlayer_anchor, Some(anchor));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_mintlayer_anchor() {
+    test_http_server(MintlayerAnchorTest).await;
+}
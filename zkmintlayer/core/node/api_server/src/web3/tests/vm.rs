@@ -8,7 +8,10 @@ use zksync_multivm::{
     vm_latest::{VmExecutionLogs, VmExecutionResultAndLogs},
 };
 use zksync_types::{
-    api::{ApiStorageLog, Log},
+    api::{
+        state_override::{OverrideAccount, StateOverride},
+        ApiStorageLog, Log,
+    },
     get_intrinsic_constants,
     transaction_request::CallRequest,
     K256PrivateKey, L2ChainId, PackedEthSignature, StorageLogKind, StorageLogWithPreviousValue,
@@ -63,7 +66,9 @@ impl HttpTest for CallTest {
         client: &DynClient<L2>,
         _pool: &ConnectionPool<Core>,
     ) -> anyhow::Result<()> {
-        let call_result = client.call(Self::call_request(b"pending"), None).await?;
+        let call_result = client
+            .call(Self::call_request(b"pending"), None, None)
+            .await?;
         assert_eq!(call_result.0, b"output");
 
         let valid_block_numbers_and_calldata = [
@@ -74,7 +79,7 @@ impl HttpTest for CallTest {
         for (number, calldata) in valid_block_numbers_and_calldata {
             let number = api::BlockIdVariant::BlockNumber(number);
             let call_result = client
-                .call(Self::call_request(calldata), Some(number))
+                .call(Self::call_request(calldata), Some(number), None)
                 .await?;
             assert_eq!(call_result.0, b"output");
         }
@@ -82,7 +87,43 @@ impl HttpTest for CallTest {
         let invalid_block_number = api::BlockNumber::from(100);
         let number = api::BlockIdVariant::BlockNumber(invalid_block_number);
         let error = client
-            .call(Self::call_request(b"100"), Some(number))
+            .call(Self::call_request(b"100"), Some(number), None)
+            .await
+            .unwrap_err();
+        if let ClientError::Call(error) = error {
+            assert_eq!(error.code(), ErrorCode::InvalidParams.code());
+        } else {
+            panic!("Unexpected error: {error:?}");
+        }
+
+        let state_override = StateOverride::new(HashMap::from([(
+            Address::repeat_byte(2),
+            OverrideAccount {
+                balance: Some(1.into()),
+                state_diff: Some(HashMap::from([(H256::zero(), H256::repeat_byte(1))])),
+                ..OverrideAccount::default()
+            },
+        )]));
+        let call_result = client
+            .call(Self::call_request(b"pending"), None, Some(state_override))
+            .await?;
+        assert_eq!(call_result.0, b"output");
+
+        // `state` and `stateDiff` are mutually exclusive.
+        let invalid_state_override = StateOverride::new(HashMap::from([(
+            Address::repeat_byte(2),
+            OverrideAccount {
+                state: Some(HashMap::new()),
+                state_diff: Some(HashMap::new()),
+                ..OverrideAccount::default()
+            },
+        )]));
+        let error = client
+            .call(
+                Self::call_request(b"pending"),
+                None,
+                Some(invalid_state_override),
+            )
             .await
             .unwrap_err();
         if let ClientError::Call(error) = error {
@@ -120,7 +161,7 @@ impl HttpTest for CallTestAfterSnapshotRecovery {
         _pool: &ConnectionPool<Core>,
     ) -> anyhow::Result<()> {
         let call_result = client
-            .call(CallTest::call_request(b"pending"), None)
+            .call(CallTest::call_request(b"pending"), None, None)
             .await?;
         assert_eq!(call_result.0, b"output");
         let pending_block_number = api::BlockIdVariant::BlockNumber(api::BlockNumber::Pending);
@@ -128,6 +169,7 @@ impl HttpTest for CallTestAfterSnapshotRecovery {
             .call(
                 CallTest::call_request(b"pending"),
                 Some(pending_block_number),
+                None,
             )
             .await?;
         assert_eq!(call_result.0, b"output");
@@ -137,7 +179,7 @@ impl HttpTest for CallTestAfterSnapshotRecovery {
         for number in pruned_block_numbers {
             let number = api::BlockIdVariant::BlockNumber(number.into());
             let error = client
-                .call(CallTest::call_request(b"pruned"), Some(number))
+                .call(CallTest::call_request(b"pruned"), Some(number), None)
                 .await
                 .unwrap_err();
             assert_pruned_block_error(&error, first_local_l2_block);
@@ -147,7 +189,7 @@ impl HttpTest for CallTestAfterSnapshotRecovery {
         for number in first_l2_block_numbers {
             let number = api::BlockIdVariant::BlockNumber(number);
             let call_result = client
-                .call(CallTest::call_request(b"first"), Some(number))
+                .call(CallTest::call_request(b"first"), Some(number), None)
                 .await?;
             assert_eq!(call_result.0, b"output");
         }
@@ -389,7 +431,10 @@ async fn send_raw_transaction_with_detailed_output() {
 struct TraceCallTest;
 
 impl TraceCallTest {
-    fn assert_debug_call(call_request: &CallRequest, call_result: &api::DebugCall) {
+    fn assert_debug_call(call_request: &CallRequest, call_result: &api::DebugTrace) {
+        let api::DebugTrace::Call(call_result) = call_result else {
+            panic!("Unexpected trace: {call_result:?}");
+        };
         assert_eq!(call_result.from, Address::zero());
         assert_eq!(call_result.gas, call_request.gas.unwrap());
         assert_eq!(call_result.value, call_request.value.unwrap());
@@ -466,6 +511,220 @@ async fn trace_call_basics() {
     test_http_server(TraceCallTest).await;
 }
 
+#[derive(Debug)]
+struct TraceCallWithPrestateTracerTest;
+
+#[async_trait]
+impl HttpTest for TraceCallWithPrestateTracerTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        CallTest::create_executor(L2BlockNumber(0))
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let options = api::TracerConfig {
+            tracer: api::SupportedTracers::PrestateTracer,
+            tracer_config: api::TracerOptions::default(),
+        };
+        let call_request = CallTest::call_request(b"pending");
+        let call_result = client.trace_call(call_request, None, Some(options)).await?;
+        // The mock executor doesn't run tracers, so the trace is empty.
+        assert_eq!(
+            call_result,
+            api::DebugTrace::Prestate(api::PrestateTrace::Prestate(Default::default()))
+        );
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn trace_call_with_prestate_tracer() {
+    test_http_server(TraceCallWithPrestateTracerTest).await;
+}
+
+#[derive(Debug)]
+struct CreateAccessListTest;
+
+#[async_trait]
+impl HttpTest for CreateAccessListTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        let mut tx_executor = MockTransactionExecutor::default();
+        tx_executor.set_call_responses(|tx, _| match tx.execute.calldata() {
+            b"success" => ExecutionResult::Success {
+                output: b"output".to_vec(),
+            },
+            b"revert" => ExecutionResult::Revert {
+                output: VmRevertReason::General {
+                    msg: "oops".to_owned(),
+                    data: vec![],
+                },
+            },
+            data => panic!("Unexpected calldata: {data:?}"),
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let result = client
+            .create_access_list(CallTest::call_request(b"success"), None)
+            .await?;
+        // The mock executor doesn't run tracers, so the access list is empty.
+        assert_eq!(
+            result,
+            api::AccessListResult {
+                access_list: vec![],
+                gas_used: 0.into(),
+                error: None,
+            }
+        );
+
+        let latest_block = api::BlockIdVariant::BlockNumber(api::BlockNumber::Latest);
+        let result = client
+            .create_access_list(CallTest::call_request(b"revert"), Some(latest_block))
+            .await?;
+        assert!(result.access_list.is_empty());
+        assert_eq!(result.error.as_deref(), Some("oops"));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn create_access_list_basics() {
+    test_http_server(CreateAccessListTest).await;
+}
+
+#[derive(Debug)]
+struct SimulateTest;
+
+impl SimulateTest {
+    fn payload(blocks: Vec<(Option<StateOverride>, Vec<&[u8]>)>) -> api::SimulatePayload {
+        let block_state_calls = blocks
+            .into_iter()
+            .map(|(state_overrides, calls)| api::SimulatedBlockCalls {
+                state_overrides,
+                calls: calls
+                    .into_iter()
+                    .map(|data| CallTest::call_request(data).into())
+                    .collect(),
+            })
+            .collect();
+        api::SimulatePayload { block_state_calls }
+    }
+
+    async fn assert_invalid_params(
+        client: &DynClient<L2>,
+        payload: api::SimulatePayload,
+    ) -> anyhow::Result<()> {
+        let error = client.simulate_v1(payload, None).await.unwrap_err();
+        if let ClientError::Call(error) = error {
+            assert_eq!(error.code(), ErrorCode::InvalidParams.code());
+        } else {
+            panic!("Unexpected error: {error:?}");
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HttpTest for SimulateTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        let mut tx_executor = MockTransactionExecutor::default();
+        tx_executor.set_call_responses(|tx, _| match tx.execute.calldata() {
+            b"success" => ExecutionResult::Success {
+                output: b"output".to_vec(),
+            },
+            b"revert" => ExecutionResult::Revert {
+                output: VmRevertReason::General {
+                    msg: "oops".to_owned(),
+                    data: vec![],
+                },
+            },
+            data => panic!("Unexpected calldata: {data:?}"),
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let state_override = StateOverride::new(HashMap::from([(
+            Address::repeat_byte(1),
+            OverrideAccount {
+                balance: Some(1_000.into()),
+                ..OverrideAccount::default()
+            },
+        )]));
+        let payload = Self::payload(vec![
+            (None, vec![b"success" as &[_], b"revert"]),
+            (Some(state_override), vec![b"success"]),
+        ]);
+        let blocks = client.simulate_v1(payload, None).await?;
+
+        assert_eq!(blocks.len(), 2);
+        // The pending block follows the genesis block.
+        assert_eq!(blocks[0].number, 1.into());
+        assert_eq!(blocks[1].number, 2.into());
+        assert_eq!(blocks[1].timestamp, blocks[0].timestamp + 1);
+
+        let calls = &blocks[0].calls;
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].status, 1.into());
+        assert_eq!(calls[0].return_data.0, b"output");
+        assert_eq!(calls[0].error, None);
+        assert_eq!(calls[1].status, 0.into());
+        let error = calls[1].error.as_ref().unwrap();
+        assert_eq!(error.code, 3);
+        assert_eq!(error.message, "oops");
+        assert_eq!(blocks[1].calls.len(), 1);
+        assert_eq!(blocks[1].calls[0].status, 1.into());
+
+        Self::assert_invalid_params(client, Self::payload(vec![])).await?;
+        Self::assert_invalid_params(client, Self::payload(vec![(None, vec![])])).await?;
+        let state_replacement = StateOverride::new(HashMap::from([(
+            Address::repeat_byte(1),
+            OverrideAccount {
+                state: Some(HashMap::new()),
+                ..OverrideAccount::default()
+            },
+        )]));
+        let payload = Self::payload(vec![(Some(state_replacement), vec![b"success"])]);
+        Self::assert_invalid_params(client, payload).await?;
+        let invalid_code = StateOverride::new(HashMap::from([(
+            Address::repeat_byte(1),
+            OverrideAccount {
+                code: Some(vec![1; 64].into()),
+                ..OverrideAccount::default()
+            },
+        )]));
+        let payload = Self::payload(vec![(Some(invalid_code), vec![b"success"])]);
+        Self::assert_invalid_params(client, payload).await?;
+        let mut payload = Self::payload(vec![(None, vec![b"success", b"success"])]);
+        payload.block_state_calls[0].calls[1].state_overrides = Some(StateOverride::default());
+        Self::assert_invalid_params(client, payload).await?;
+
+        // Each block is within the limit, but the total number of calls exceeds it.
+        let calls_limit = Web3JsonRpcConfig::for_tests().simulate_calls_limit();
+        let block_calls = vec![b"success" as &[_]; calls_limit / 2 + 1];
+        let payload = Self::payload(vec![(None, block_calls.clone()), (None, block_calls)]);
+        Self::assert_invalid_params(client, payload).await?;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn simulate_v1_basics() {
+    test_http_server(SimulateTest).await;
+}
+
 #[derive(Debug)]
 struct TraceCallTestAfterSnapshotRecovery;
 
@@ -499,7 +758,7 @@ impl HttpTest for TraceCallTestAfterSnapshotRecovery {
         for number in pruned_block_numbers {
             let number = api::BlockIdVariant::BlockNumber(number.into());
             let error = client
-                .call(CallTest::call_request(b"pruned"), Some(number))
+                .call(CallTest::call_request(b"pruned"), Some(number), None)
                 .await
                 .unwrap_err();
             assert_pruned_block_error(&error, first_local_l2_block);
@@ -579,7 +838,7 @@ impl HttpTest for EstimateGasTest {
         for threshold in [10_000, 50_000, 100_000, 1_000_000] {
             self.gas_limit_threshold.store(threshold, Ordering::Relaxed);
             let output = client
-                .estimate_gas(l2_transaction.clone().into(), None)
+                .estimate_gas(l2_transaction.clone().into(), None, None)
                 .await?;
             assert!(
                 output >= U256::from(threshold),
@@ -604,10 +863,15 @@ impl HttpTest for EstimateGasTest {
         let mut call_request = CallRequest::from(l2_transaction);
         call_request.from = Some(SendRawTransactionTest::private_key().address());
         call_request.value = Some(1_000_000.into());
-        client.estimate_gas(call_request.clone(), None).await?;
+        client
+            .estimate_gas(call_request.clone(), None, None)
+            .await?;
 
         call_request.value = Some(U256::max_value());
-        let error = client.estimate_gas(call_request, None).await.unwrap_err();
+        let error = client
+            .estimate_gas(call_request, None, None)
+            .await
+            .unwrap_err();
         if let ClientError::Call(error) = error {
             let error_msg = error.message();
             assert!(
@@ -617,6 +881,18 @@ impl HttpTest for EstimateGasTest {
         } else {
             panic!("Unexpected error: {error:?}");
         }
+
+        // Overriding the balance of the sender should make the transaction estimable.
+        let state_override = StateOverride::new(HashMap::from([(
+            call_request.from.unwrap(),
+            OverrideAccount {
+                balance: Some(U256::max_value()),
+                ..OverrideAccount::default()
+            },
+        )]));
+        client
+            .estimate_gas(call_request, None, Some(state_override))
+            .await?;
         Ok(())
     }
 }
@@ -8,7 +8,7 @@ use http::StatusCode;
 use tokio::sync::watch;
 use zksync_config::configs::chain::NetworkConfig;
 use zksync_dal::ConnectionPool;
-use zksync_types::{api, Address, L1BatchNumber, H160, H2048, H256, U64};
+use zksync_types::{api, Address, L1BatchNumber, L2ChainId, H160, H2048, H256, U64};
 use zksync_web3_decl::{
     client::{WsClient, L2},
     jsonrpsee::{
@@ -111,8 +111,12 @@ async fn notifiers_start_after_snapshot_recovery() {
     let (events_sender, mut events_receiver) = mpsc::unbounded_channel();
     let mut subscribe_logic = EthSubscribe::new();
     subscribe_logic.set_events_sender(events_sender);
-    let notifier_handles =
-        subscribe_logic.spawn_notifiers(pool.clone(), POLL_INTERVAL, stop_receiver);
+    let notifier_handles = subscribe_logic.spawn_notifiers(
+        pool.clone(),
+        POLL_INTERVAL,
+        L2ChainId::default(),
+        stop_receiver,
+    );
     assert!(!notifier_handles.is_empty());
 
     // Wait a little doing nothing and check that notifier tasks are still active (i.e., have not panicked).
@@ -150,6 +154,11 @@ trait WsTest: Send + Sync {
         StorageInitialization::Genesis
     }
 
+    /// Modifies the storage after its initialization, but before the server is started.
+    async fn prepare_storage(&self, _storage: &mut Connection<'_, Core>) -> anyhow::Result<()> {
+        Ok(())
+    }
+
     async fn test(
         &self,
         client: &WsClient<L2>,
@@ -174,6 +183,9 @@ async fn test_ws_server(test: impl WsTest) {
         .prepare_storage(&network_config, &mut storage)
         .await
         .expect("Failed preparing storage for test");
+    test.prepare_storage(&mut storage)
+        .await
+        .expect("Failed preparing storage for test");
     drop(storage);
 
     let (stop_sender, stop_receiver) = watch::channel(false);
@@ -268,6 +280,12 @@ impl WsTest for BasicSubscriptionsTest {
             .await?;
         wait_for_subscription(&mut pub_sub_events, SubscriptionType::Txs).await;
 
+        let params = rpc_params!["newPendingTransactions", true];
+        let mut full_txs_subscription = client
+            .subscribe::<api::Transaction, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::FullTxs).await;
+
         let mut storage = pool.connection().await?;
         let tx_result = execute_l2_transaction(create_l2_transaction(1, 2));
         let new_tx_hash = tx_result.hash;
@@ -284,6 +302,11 @@ impl WsTest for BasicSubscriptionsTest {
             .context("Timed out waiting for new tx hash")?
             .context("Pending txs subscription terminated")??;
         assert_eq!(received_tx_hash, new_tx_hash);
+        let received_tx = tokio::time::timeout(TEST_TIMEOUT, full_txs_subscription.next())
+            .await
+            .context("Timed out waiting for new tx")?
+            .context("Pending full txs subscription terminated")??;
+        assert_eq!(received_tx.hash, new_tx_hash);
         let received_block_header = tokio::time::timeout(TEST_TIMEOUT, blocks_subscription.next())
             .await
             .context("Timed out waiting for new block header")?
@@ -659,6 +682,245 @@ async fn log_subscriptions_with_delay() {
     test_ws_server(LogSubscriptionsWithDelayTest).await;
 }
 
+#[derive(Debug)]
+struct LogSubscriptionsWithRollbackTest;
+
+#[async_trait]
+impl WsTest for LogSubscriptionsWithRollbackTest {
+    async fn test(
+        &self,
+        client: &WsClient<L2>,
+        pool: &ConnectionPool<Core>,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        let LogSubscriptions {
+            mut all_logs_subscription,
+            mut address_subscription,
+            ..
+        } = LogSubscriptions::new(client, &mut pub_sub_events).await?;
+
+        let mut storage = pool.connection().await?;
+        let (_, events) = store_events(&mut storage, 1, 0).await?;
+        let events: Vec<_> = events.iter().collect();
+        let all_logs = collect_logs(&mut all_logs_subscription, 4).await?;
+        assert_logs_match(&all_logs, &events);
+        let (_, new_events) = store_events(&mut storage, 2, 4).await?;
+        let new_events: Vec<_> = new_events.iter().collect();
+        let all_new_logs = collect_logs(&mut all_logs_subscription, 4).await?;
+        assert_logs_match(&all_new_logs, &new_events);
+        let address_logs = collect_logs(&mut address_subscription, 4).await?;
+        assert!(address_logs.iter().all(|log| !log.is_removed()));
+
+        roll_back_l2_blocks(&mut storage, L2BlockNumber(1)).await?;
+
+        let removed_logs = collect_logs(&mut all_logs_subscription, 4).await?;
+        assert!(removed_logs.iter().all(api::Log::is_removed));
+        assert_logs_match(&removed_logs, &new_events);
+        let removed_address_logs = collect_logs(&mut address_subscription, 2).await?;
+        assert!(removed_address_logs.iter().all(api::Log::is_removed));
+        assert_logs_match(&removed_address_logs, &[new_events[0], new_events[3]]);
+
+        // Logs from the replacement block must be emitted as well.
+        let (_, replacement_events) = store_events(&mut storage, 2, 8).await?;
+        let replacement_events: Vec<_> = replacement_events.iter().collect();
+        let replacement_logs = collect_logs(&mut all_logs_subscription, 4).await?;
+        assert!(replacement_logs.iter().all(|log| !log.is_removed()));
+        assert_logs_match(&replacement_logs, &replacement_events);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn log_subscriptions_with_rollback() {
+    test_ws_server(LogSubscriptionsWithRollbackTest).await;
+}
+
+/// Rolls back L2 blocks after `last_l2_block_to_keep` in the same way as the block reverter does.
+async fn roll_back_l2_blocks(
+    storage: &mut Connection<'_, Core>,
+    last_l2_block_to_keep: L2BlockNumber,
+) -> anyhow::Result<()> {
+    storage
+        .events_dal()
+        .save_l2_block_rollback(last_l2_block_to_keep)
+        .await?;
+    storage
+        .events_dal()
+        .roll_back_events(last_l2_block_to_keep)
+        .await?;
+    storage
+        .blocks_dal()
+        .delete_l2_blocks(last_l2_block_to_keep)
+        .await?;
+    Ok(())
+}
+
+/// Checks that logs from L2 blocks rolled back while the server is stopped are reported as removed after a restart.
+#[derive(Debug)]
+struct LogSubscriptionsAfterOfflineRollbackTest;
+
+#[async_trait]
+impl WsTest for LogSubscriptionsAfterOfflineRollbackTest {
+    async fn prepare_storage(&self, storage: &mut Connection<'_, Core>) -> anyhow::Result<()> {
+        store_events(storage, 1, 0).await?;
+        store_events(storage, 2, 4).await?;
+        roll_back_l2_blocks(storage, L2BlockNumber(1)).await
+    }
+
+    async fn test(
+        &self,
+        client: &WsClient<L2>,
+        pool: &ConnectionPool<Core>,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        // Only the first subscription is guaranteed to receive removed logs; they are sent as soon as
+        // the notifier observes a subscriber.
+        let LogSubscriptions {
+            mut all_logs_subscription,
+            ..
+        } = LogSubscriptions::new(client, &mut pub_sub_events).await?;
+
+        // Store the replacement L2 block. `store_events()` is deterministic, so its events are the same
+        // as in the rolled back block.
+        let mut storage = pool.connection().await?;
+        let (_, rolled_back_events) = store_events(&mut storage, 2, 4).await?;
+        let rolled_back_events: Vec<_> = rolled_back_events.iter().collect();
+        let removed_logs = collect_logs(&mut all_logs_subscription, 4).await?;
+        assert!(removed_logs.iter().all(api::Log::is_removed));
+        assert_logs_match(&removed_logs, &rolled_back_events);
+
+        let replacement_logs = collect_logs(&mut all_logs_subscription, 4).await?;
+        assert!(replacement_logs.iter().all(|log| !log.is_removed()));
+        assert_logs_match(&replacement_logs, &rolled_back_events);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn log_subscriptions_after_offline_rollback() {
+    test_ws_server(LogSubscriptionsAfterOfflineRollbackTest).await;
+}
+
+#[derive(Debug)]
+struct L1BatchSubscriptionsTest;
+
+impl L1BatchSubscriptionsTest {
+    async fn confirm_l1_batch_operation(
+        storage: &mut Connection<'_, Core>,
+        number: L1BatchNumber,
+        action_type: AggregatedActionType,
+        nonce: u64,
+    ) -> anyhow::Result<()> {
+        let eth_tx_id = storage
+            .eth_sender_dal()
+            .save_eth_tx(
+                nonce,
+                vec![],
+                action_type,
+                Address::default(),
+                0,
+                None,
+                None,
+            )
+            .await?
+            .id;
+        let tx_hash = H256::from_low_u64_be(nonce);
+        storage
+            .eth_sender_dal()
+            .insert_tx_history(eth_tx_id, 0, 0, None, tx_hash, &[], 0)
+            .await?;
+        storage
+            .eth_sender_dal()
+            .confirm_tx(tx_hash, U256::zero())
+            .await?;
+        storage
+            .blocks_dal()
+            .set_eth_tx_id(number..=number, eth_tx_id, action_type)
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl WsTest for L1BatchSubscriptionsTest {
+    async fn test(
+        &self,
+        client: &WsClient<L2>,
+        pool: &ConnectionPool<Core>,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        wait_for_notifiers(
+            &mut pub_sub_events,
+            &[
+                SubscriptionType::L1BatchesCommitted,
+                SubscriptionType::L1BatchesExecuted,
+            ],
+        )
+        .await;
+
+        let params = rpc_params!["zks_l1BatchCommitted"];
+        let mut committed_subscription = client
+            .subscribe::<api::L1BatchDetails, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::L1BatchesCommitted).await;
+        let params = rpc_params!["zks_l1BatchExecuted"];
+        let mut executed_subscription = client
+            .subscribe::<api::L1BatchDetails, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::L1BatchesExecuted).await;
+
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &[]).await?;
+        seal_l1_batch(&mut storage, L1BatchNumber(1)).await?;
+        Self::confirm_l1_batch_operation(
+            &mut storage,
+            L1BatchNumber(1),
+            AggregatedActionType::Commit,
+            0,
+        )
+        .await?;
+
+        let committed_batch = tokio::time::timeout(TEST_TIMEOUT, committed_subscription.next())
+            .await
+            .context("Timed out waiting for committed L1 batch")?
+            .context("Committed L1 batches subscription terminated")??;
+        assert_eq!(committed_batch.number, L1BatchNumber(1));
+        assert_eq!(
+            committed_batch.base.commit_tx_hash,
+            Some(H256::from_low_u64_be(0))
+        );
+
+        Self::confirm_l1_batch_operation(
+            &mut storage,
+            L1BatchNumber(1),
+            AggregatedActionType::Execute,
+            1,
+        )
+        .await?;
+        let executed_batch = tokio::time::timeout(TEST_TIMEOUT, executed_subscription.next())
+            .await
+            .context("Timed out waiting for executed L1 batch")?
+            .context("Executed L1 batches subscription terminated")??;
+        assert_eq!(executed_batch.number, L1BatchNumber(1));
+        assert_eq!(
+            executed_batch.base.execute_tx_hash,
+            Some(H256::from_low_u64_be(1))
+        );
+
+        // The batch must not be reported as committed again.
+        wait_for_notifiers(&mut pub_sub_events, &[SubscriptionType::L1BatchesCommitted]).await;
+        tokio::time::timeout(POLL_INTERVAL, committed_subscription.next())
+            .await
+            .unwrap_err();
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn l1_batch_subscriptions() {
+    test_ws_server(L1BatchSubscriptionsTest).await;
+}
+
 #[derive(Debug)]
 struct RateLimitingTest;
 
@@ -13,6 +13,7 @@ use zksync_eth_client::{
     BoundEthInterface, CallFunctionArgs, EthInterface, Options,
 };
 use zksync_merkle_tree::domain::ZkSyncTree;
+use zksync_mintlayer_client::{DepositOptions, MintlayerInterface};
 use zksync_object_store::{ObjectStore, ObjectStoreError};
 use zksync_state::RocksdbStorage;
 use zksync_storage::RocksDB;
@@ -82,6 +83,9 @@ pub enum NodeRole {
 /// - State of the state keeper cache
 /// - Object store for protocol snapshots
 ///
+/// Rolling back Postgres also revokes Mintlayer anchors covering the rolled back L1 batches and purges the anchoring
+/// queue; revocation records for the revoked anchors can optionally be published on Mintlayer.
+///
 /// In addition, it can revert the state of the Ethereum contract (if the reverted L1 batches were committed).
 #[derive(Debug)]
 pub struct BlockReverter {
@@ -94,6 +98,7 @@ pub struct BlockReverter {
     state_keeper_cache_path: Option<String>,
     merkle_tree_path: Option<String>,
     snapshots_object_store: Option<Arc<dyn ObjectStore>>,
+    mintlayer_revocations: Option<(Box<dyn MintlayerInterface>, DepositOptions)>,
 }
 
 impl BlockReverter {
@@ -106,6 +111,7 @@ impl BlockReverter {
             state_keeper_cache_path: None,
             merkle_tree_path: None,
             snapshots_object_store: None,
+            mintlayer_revocations: None,
         }
     }
 
@@ -142,6 +148,17 @@ impl BlockReverter {
         self
     }
 
+    /// Enables publishing revocation records on Mintlayer for anchors that were revoked when rolling back Postgres.
+    /// Records are published for all revoked anchors without a record, including ones revoked by previous runs.
+    pub fn enable_publishing_mintlayer_revocations(
+        &mut self,
+        mintlayer_client: Box<dyn MintlayerInterface>,
+        deposit_options: DepositOptions,
+    ) -> &mut Self {
+        self.mintlayer_revocations = Some((mintlayer_client, deposit_options));
+        self
+    }
+
     /// Rolls back previously enabled DBs (Postgres + RocksDB) and the snapshot object store to a previous state.
     pub async fn roll_back(&self, last_l1_batch_to_keep: L1BatchNumber) -> anyhow::Result<()> {
         if !self.allow_rolling_back_executed_batches {
@@ -174,6 +191,11 @@ impl BlockReverter {
             );
         }
 
+        if let Some((mintlayer_client, deposit_options)) = &self.mintlayer_revocations {
+            self.publish_mintlayer_revocations(mintlayer_client.as_ref(), *deposit_options)
+                .await?;
+        }
+
         Ok(())
     }
 
@@ -313,6 +335,11 @@ impl BlockReverter {
             .transactions_dal()
             .reset_transactions_state(last_l2_block_to_keep)
             .await?;
+        tracing::info!("Saving rolled back logs for API log subscriptions");
+        transaction
+            .events_dal()
+            .save_l2_block_rollback(last_l2_block_to_keep)
+            .await?;
         tracing::info!("Rolling back events");
         transaction
             .events_dal()
@@ -343,6 +370,14 @@ impl BlockReverter {
             .eth_sender_dal()
             .delete_eth_txs(last_l1_batch_to_keep)
             .await?;
+        tracing::info!("Revoking Mintlayer anchors");
+        let revoked_anchors = transaction
+            .mintlayer_anchor_dal()
+            .revoke_anchors_after(last_l1_batch_to_keep)
+            .await?;
+        if !revoked_anchors.is_empty() {
+            tracing::info!("Revoked Mintlayer anchors with IDs {revoked_anchors:?}");
+        }
 
         tracing::info!("Rolling back snapshots");
         let deleted_snapshots = transaction
@@ -375,6 +410,42 @@ impl BlockReverter {
         Ok(deleted_snapshots)
     }
 
+    /// Deposits revocation records on Mintlayer for revoked anchors that don't have them yet.
+    async fn publish_mintlayer_revocations(
+        &self,
+        mintlayer_client: &dyn MintlayerInterface,
+        deposit_options: DepositOptions,
+    ) -> anyhow::Result<()> {
+        let mut storage = self.connection_pool.connection().await?;
+        let anchors = storage
+            .mintlayer_anchor_dal()
+            .get_anchors_without_revocation_tx()
+            .await?;
+        for anchor in anchors {
+            let revocation = anchor.revocation();
+            let tx = mintlayer_client
+                .address_deposit_data(&revocation.encode(), deposit_options)
+                .await
+                .with_context(|| {
+                    format!(
+                        "failed depositing revocation record for Mintlayer anchor #{}",
+                        anchor.id
+                    )
+                })?;
+            tracing::info!(
+                "Published revocation record for Mintlayer anchor #{} with root CID {} in tx {}",
+                anchor.id,
+                anchor.root_cid,
+                tx.tx_id
+            );
+            storage
+                .mintlayer_anchor_dal()
+                .set_anchor_revocation_tx_id(anchor.id, &tx.tx_id)
+                .await?;
+        }
+        Ok(())
+    }
+
     async fn delete_snapshot_files(
         object_store: &dyn ObjectStore,
         deleted_snapshots: &[SnapshotMetadata],
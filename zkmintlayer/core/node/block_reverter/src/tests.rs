@@ -8,10 +8,12 @@ use test_casing::test_casing;
 use tokio::sync::watch;
 use zksync_dal::Connection;
 use zksync_merkle_tree::TreeInstruction;
+use zksync_mintlayer_client::clients::MockMintlayerClient;
 use zksync_object_store::{Bucket, MockObjectStore};
 use zksync_state::ReadStorage;
 use zksync_types::{
     block::{L1BatchHeader, L2BlockHeader},
+    mintlayer::MintlayerAnchorRevocation,
     snapshots::SnapshotVersion,
     AccountTreeId, L2BlockNumber, ProtocolVersion, ProtocolVersionId, StorageKey, StorageLog,
 };
@@ -470,3 +472,93 @@ async fn reverter_handles_incomplete_snapshot() {
         assert_matches!(chunk_result.unwrap_err(), ObjectStoreError::KeyNotFound(_));
     }
 }
+
+#[tokio::test]
+async fn revoking_mintlayer_anchors() {
+    let storage_logs = gen_storage_logs();
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    setup_storage(&mut storage, &storage_logs).await;
+
+    for (number, cid) in [(4, "retained_cid"), (7, "reverted_cid"), (8, "queued_cid")] {
+        let eth_tx_id = storage
+            .eth_sender_dal()
+            .save_eth_tx(
+                0,
+                vec![],
+                AggregatedActionType::Commit,
+                Address::default(),
+                0,
+                None,
+                None,
+            )
+            .await
+            .unwrap()
+            .id;
+        storage
+            .mintlayer_anchor_dal()
+            .insert_operation(
+                eth_tx_id,
+                AggregatedActionType::Commit,
+                L1BatchNumber(number)..=L1BatchNumber(number),
+                cid,
+            )
+            .await
+            .unwrap();
+    }
+    let pending = storage
+        .mintlayer_anchor_dal()
+        .get_pending_operations(10)
+        .await
+        .unwrap();
+    let anchor_id = storage
+        .mintlayer_anchor_dal()
+        .insert_anchor(
+            "root_cid",
+            H256::repeat_byte(1),
+            &[pending[0].id, pending[1].id],
+        )
+        .await
+        .unwrap();
+    storage
+        .mintlayer_anchor_dal()
+        .set_anchor_tx_id(anchor_id, "anchor_tx_id")
+        .await
+        .unwrap();
+
+    let mintlayer_client = MockMintlayerClient::default();
+    let mut block_reverter = BlockReverter::new(NodeRole::Main, pool.clone());
+    block_reverter.enable_rolling_back_postgres();
+    block_reverter.enable_publishing_mintlayer_revocations(
+        Box::new(mintlayer_client.clone()),
+        DepositOptions::default(),
+    );
+    block_reverter.roll_back(L1BatchNumber(5)).await.unwrap();
+
+    let anchor = storage
+        .mintlayer_anchor_dal()
+        .get_anchor(anchor_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(anchor.revoked_at.is_some());
+    let deposited_data = mintlayer_client.deposited_data();
+    assert_eq!(deposited_data.len(), 1);
+    let (revocation_tx_id, data) = &deposited_data[0];
+    assert_eq!(anchor.revocation_tx_id.as_ref(), Some(revocation_tx_id));
+    let revocation = MintlayerAnchorRevocation::decode(data).unwrap();
+    assert_eq!(revocation.revoked, anchor.payload());
+
+    // Only the operation for the retained L1 batch must be queued.
+    let pending = storage
+        .mintlayer_anchor_dal()
+        .get_pending_operations(10)
+        .await
+        .unwrap();
+    let cids: Vec<_> = pending.iter().map(|op| op.cid.as_str()).collect();
+    assert_eq!(cids, ["retained_cid"]);
+
+    // Repeated rollback must not publish the revocation again.
+    block_reverter.roll_back(L1BatchNumber(5)).await.unwrap();
+    assert_eq!(mintlayer_client.deposited_data().len(), 1);
+}
@@ -134,16 +134,34 @@ impl DataAvailabilityDispatcher {
             return Ok(());
         };
 
-        let inclusion_data = self
+        let inclusion_data = match self
             .client
             .get_inclusion_data(blob_info.blob_id.as_str())
             .await
-            .with_context(|| {
+        {
+            Err(err) if err.is_dropped() => {
+                // Removing the blob makes `dispatch()` dispatch the batch again.
+                let mut conn = self.pool.connection_tagged("da_dispatcher").await?;
+                conn.data_availability_dal()
+                    .remove_dropped_l1_batch_da(
+                        L1BatchNumber(blob_info.l1_batch_number.0),
+                        blob_info.blob_id.as_str(),
+                    )
+                    .await?;
+                METRICS.dropped_blobs.inc();
+                tracing::warn!(
+                    "DA blob for batch_number: {} was dropped ({err}); it will be dispatched again",
+                    blob_info.l1_batch_number
+                );
+                return Ok(());
+            }
+            res => res.with_context(|| {
                 format!(
                     "failed to get inclusion data for blob_id: {}, batch_number: {}",
                     blob_info.blob_id, blob_info.l1_batch_number
                 )
-            })?;
+            })?,
+        };
 
         let Some(inclusion_data) = inclusion_data else {
             return Ok(());
@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use vise::{Buckets, Gauge, Histogram, Metrics, Unit};
+use vise::{Buckets, Counter, Gauge, Histogram, Metrics, Unit};
 
 /// Buckets for `blob_dispatch_latency` (from 0.1 to 120 seconds).
 const DISPATCH_LATENCIES: Buckets =
@@ -23,6 +23,8 @@ pub(super) struct DataAvailabilityDispatcherMetrics {
     /// Number of transactions resent by the DA dispatcher.
     #[metrics(buckets = Buckets::linear(0.0..=10.0, 1.0))]
     pub dispatch_call_retries: Histogram<usize>,
+    /// Number of blobs dropped by the DA layer and dispatched again.
+    pub dropped_blobs: Counter,
     /// Last L1 batch that was dispatched to the DA layer.
     pub last_dispatched_l1_batch: Gauge<usize>,
     /// Last L1 batch that has its inclusion finalized by DA layer.
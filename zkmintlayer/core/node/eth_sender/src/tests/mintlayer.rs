@@ -0,0 +1,240 @@
+//! End-to-end tests for anchoring operations saved by `EthTxAggregator` on Mintlayer.
+
+use std::time::Duration;
+
+use test_casing::test_casing;
+use tokio::sync::watch;
+use zksync_config::configs::{mintlayer::IpfsBackend, MintlayerConfig};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_ipfs_store::create_ipfs_store;
+use zksync_mintlayer_anchor::{MintlayerAnchorer, MintlayerTxManager};
+use zksync_mintlayer_client::HttpMintlayerClient;
+use zksync_mintlayer_documents::OperationDocument;
+use zksync_node_test_utils::{
+    mock_mintlayer_config, mock_mintlayer_secrets, MockIpfsNode, MockMintlayerWallet,
+};
+use zksync_object_store::MockObjectStore;
+use zksync_types::{
+    aggregated_operations::AggregatedActionType,
+    commitment::L1BatchCommitmentMode,
+    mintlayer::{MintlayerAnchorCommitment, MintlayerAnchorLeaf, MintlayerAnchorPayload},
+    pubdata_da::PubdataDA,
+    L1BatchNumber,
+};
+
+use super::{
+    commit_l1_batch, execute_l1_batches, insert_genesis_protocol_version, insert_l1_batch,
+    prove_l1_batch, EthSenderTester,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+const TEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Commits, proves and executes L1 batches #1 and #2 one by one.
+async fn send_operations(tester: &mut EthSenderTester) {
+    insert_genesis_protocol_version(tester).await;
+    let genesis_l1_batch = insert_l1_batch(tester, L1BatchNumber(0)).await;
+    let first_l1_batch = insert_l1_batch(tester, L1BatchNumber(1)).await;
+    let second_l1_batch = insert_l1_batch(tester, L1BatchNumber(2)).await;
+
+    commit_l1_batch(
+        tester,
+        genesis_l1_batch.clone(),
+        first_l1_batch.clone(),
+        true,
+    )
+    .await;
+    prove_l1_batch(tester, genesis_l1_batch, first_l1_batch.clone(), true).await;
+    execute_l1_batches(tester, vec![first_l1_batch.clone()], true).await;
+    commit_l1_batch(
+        tester,
+        first_l1_batch.clone(),
+        second_l1_batch.clone(),
+        true,
+    )
+    .await;
+    prove_l1_batch(tester, first_l1_batch, second_l1_batch.clone(), true).await;
+    execute_l1_batches(tester, vec![second_l1_batch], true).await;
+}
+
+async fn wait_for(description: &str, mut condition: impl FnMut() -> bool) {
+    tokio::time::timeout(TEST_TIMEOUT, async {
+        while !condition() {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("timed out waiting for {description}"));
+}
+
+async fn run_anchorer(
+    pool: &ConnectionPool<Core>,
+    wallet: &MockMintlayerWallet,
+    config: &MintlayerConfig,
+) {
+    let secrets = mock_mintlayer_secrets();
+    let anchorer = MintlayerAnchorer::new(
+        pool.clone(),
+        MockObjectStore::arc(),
+        PubdataDA::Calldata,
+        create_ipfs_store(config, &secrets).unwrap(),
+        Box::new(HttpMintlayerClient::new(config, &secrets).unwrap()),
+        POLL_INTERVAL,
+        config,
+    )
+    .unwrap();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let anchorer_task = tokio::spawn(anchorer.run(stop_receiver));
+
+    wait_for("anchor deposits", || wallet.deposited_data().len() == 2).await;
+    stop_sender.send_replace(true);
+    anchorer_task.await.unwrap().unwrap();
+}
+
+/// Checks the anchor deposited by the specified transaction and returns its leaves.
+fn check_anchor(ipfs_node: &MockIpfsNode, deposited_data: &[u8]) -> Vec<MintlayerAnchorLeaf> {
+    let payload = MintlayerAnchorPayload::decode(deposited_data).unwrap();
+    assert!(ipfs_node.is_pinned(&payload.root_cid));
+    let root_document = ipfs_node.document(&payload.root_cid).unwrap();
+    let leaves: Vec<MintlayerAnchorLeaf> = serde_json::from_slice(&root_document).unwrap();
+    let commitment = MintlayerAnchorCommitment::new(leaves.clone());
+    assert_eq!(payload.merkle_root, Some(commitment.merkle_root()));
+
+    for leaf in &leaves {
+        assert!(ipfs_node.is_pinned(&leaf.cid));
+        let document = ipfs_node.document(&leaf.cid).unwrap();
+        let document = OperationDocument::decode(&document, leaf.action_type).unwrap();
+        assert_eq!(document.action_type(), leaf.action_type);
+        let l1_batch_numbers: Vec<_> = document
+            .l1_batches()
+            .iter()
+            .map(|l1_batch| l1_batch.header.number)
+            .collect();
+        let expected_numbers: Vec<_> = (leaf.l1_batch_range.start().0
+            ..=leaf.l1_batch_range.end().0)
+            .map(L1BatchNumber)
+            .collect();
+        assert_eq!(l1_batch_numbers, expected_numbers);
+    }
+    leaves
+}
+
+#[test_casing(2, [IpfsBackend::Kubo, IpfsBackend::S3])]
+#[tokio::test]
+async fn anchoring_aggregated_operations(ipfs_backend: IpfsBackend) {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut tester = EthSenderTester::new(
+        pool.clone(),
+        vec![100; 100],
+        false,
+        false,
+        L1BatchCommitmentMode::Rollup,
+    )
+    .await;
+    send_operations(&mut tester).await;
+
+    let wallet = MockMintlayerWallet::spawn().await;
+    let ipfs_node = MockIpfsNode::spawn().await;
+    let config = MintlayerConfig {
+        max_operations_per_anchor: Some(3),
+        // Operations are saved before the anchorer is started, so they are skipped by default.
+        first_anchored_l1_batch: Some(1),
+        confirmations: Some(2),
+        ..mock_mintlayer_config(&wallet, &ipfs_node, ipfs_backend)
+    };
+    run_anchorer(&pool, &wallet, &config).await;
+
+    let deposits = wallet.deposited_data();
+    assert_eq!(deposits.len(), 2);
+    // 6 operation documents + 2 root documents
+    assert_eq!(ipfs_node.document_count(), 8);
+    for (i, (_, data)) in deposits.iter().enumerate() {
+        let l1_batch_number = L1BatchNumber(i as u32 + 1);
+        let leaves = check_anchor(&ipfs_node, data);
+        let leaves: Vec<_> = leaves
+            .iter()
+            .map(|leaf| (leaf.action_type, leaf.l1_batch_range.clone()))
+            .collect();
+        assert_eq!(
+            leaves,
+            [
+                (
+                    AggregatedActionType::Commit,
+                    l1_batch_number..=l1_batch_number
+                ),
+                (
+                    AggregatedActionType::PublishProofOnchain,
+                    l1_batch_number..=l1_batch_number
+                ),
+                (
+                    AggregatedActionType::Execute,
+                    l1_batch_number..=l1_batch_number
+                ),
+            ]
+        );
+    }
+
+    let mut storage = pool.connection().await.unwrap();
+    let anchors = storage
+        .mintlayer_anchor_dal()
+        .get_unconfirmed_anchors()
+        .await
+        .unwrap();
+    let anchor_tx_ids: Vec<_> = anchors
+        .iter()
+        .map(|anchor| anchor.mintlayer_tx_id.clone().unwrap())
+        .collect();
+    let deposit_tx_ids: Vec<_> = deposits.iter().map(|(tx_id, _)| tx_id.clone()).collect();
+    assert_eq!(anchor_tx_ids, deposit_tx_ids);
+    for (anchor, (_, data)) in anchors.iter().zip(&deposits) {
+        assert_eq!(anchor.payload().encode(), *data);
+    }
+    drop(storage);
+
+    // Confirm anchors on Mintlayer and check that they are tracked by the transaction manager.
+    for (tx_id, _) in &deposits {
+        wallet.confirm_tx(tx_id, 10);
+    }
+    wallet.set_best_block_height(11);
+    let tx_manager = MintlayerTxManager::new(
+        pool.clone(),
+        Box::new(HttpMintlayerClient::new(&config, &mock_mintlayer_secrets()).unwrap()),
+        POLL_INTERVAL,
+        &config,
+    )
+    .unwrap();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let tx_manager_task = tokio::spawn(tx_manager.run(stop_receiver));
+
+    tokio::time::timeout(TEST_TIMEOUT, async {
+        loop {
+            let mut storage = pool.connection().await.unwrap();
+            let unconfirmed_anchors = storage
+                .mintlayer_anchor_dal()
+                .get_unconfirmed_anchors()
+                .await
+                .unwrap();
+            if unconfirmed_anchors.is_empty() {
+                break;
+            }
+            drop(storage);
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    })
+    .await
+    .expect("timed out waiting for anchor confirmations");
+    stop_sender.send_replace(true);
+    tx_manager_task.await.unwrap().unwrap();
+
+    let mut storage = pool.connection().await.unwrap();
+    for anchor in &anchors {
+        let anchor = storage
+            .mintlayer_anchor_dal()
+            .get_anchor(anchor.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(anchor.included_at_block, Some(10));
+        assert!(anchor.confirmed_at.is_some());
+    }
+}
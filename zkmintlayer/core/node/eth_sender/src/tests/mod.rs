@@ -30,10 +30,11 @@ use zksync_types::{
 use crate::{
     abstract_l1_interface::{L1BlockNumbers, OperatorType},
     aggregated_operations::AggregatedOperation,
-    //ipfs_api::{IpfsApi, IpfsClient},
     Aggregator, EthSenderError, EthTxAggregator, EthTxManager,
 };
 
+mod mintlayer;
+
 // Alias to conveniently call static methods of `ETHSender`.
 type MockEthTxManager = EthTxManager;
 
@@ -2,7 +2,7 @@
 //! It initializes the Merkle tree with the basic setup (such as fields of special service accounts),
 //! setups the required databases, and outputs the data required to initialize a smart contract.
 
-use std::fmt::Formatter;
+use std::{collections::HashMap, fmt::Formatter};
 
 use anyhow::Context as _;
 use zksync_config::{configs::DatabaseSecrets, GenesisConfig};
@@ -23,7 +23,7 @@ use zksync_types::{
     AccountTreeId, Address, L1BatchNumber, L2BlockNumber, L2ChainId, ProtocolVersion,
     ProtocolVersionId, StorageKey, H256,
 };
-use zksync_utils::{bytecode::hash_bytecode, u256_to_h256};
+use zksync_utils::{be_words_to_bytes, bytecode::hash_bytecode, u256_to_h256};
 
 use crate::utils::{
     add_eth_token, get_deduped_log_queries, get_storage_logs,
@@ -213,25 +213,14 @@ pub async fn insert_genesis_batch(
     .await?;
     tracing::info!("chain_schema_genesis is complete");
 
-    let deduped_log_queries =
-        get_deduped_log_queries(&get_storage_logs(genesis_params.system_contracts()));
-
-    let (deduplicated_writes, _): (Vec<_>, Vec<_>) = deduped_log_queries
-        .into_iter()
-        .partition(|log_query| log_query.rw_flag);
-
-    let storage_logs: Vec<TreeInstruction> = deduplicated_writes
-        .iter()
-        .enumerate()
-        .map(|(index, log)| {
-            TreeInstruction::write(
-                StorageKey::new(AccountTreeId::new(log.address), u256_to_h256(log.key))
-                    .hashed_key_u256(),
-                (index + 1) as u64,
-                u256_to_h256(log.written_value),
-            )
-        })
-        .collect();
+    let storage_logs: Vec<TreeInstruction> =
+        genesis_storage_writes(genesis_params.system_contracts())
+            .into_iter()
+            .enumerate()
+            .map(|(index, (key, value))| {
+                TreeInstruction::write(key.hashed_key_u256(), (index + 1) as u64, value)
+            })
+            .collect();
 
     let metadata = ZkSyncTree::process_genesis_batch(&storage_logs);
     let genesis_root_hash = metadata.root_hash;
@@ -270,6 +259,53 @@ pub async fn insert_genesis_batch(
     })
 }
 
+/// State produced by the genesis L1 batch, computed without accessing the database.
+///
+/// This is the same state that [`insert_genesis_batch()`] persists. It can be used by tools that reconstruct
+/// the node state from scratch (e.g., from published pubdata).
+#[derive(Debug, Clone)]
+pub struct GenesisState {
+    /// Deduplicated storage writes ordered by their enumeration indices (i.e., the first write has index 1).
+    pub storage_writes: Vec<(StorageKey, H256)>,
+    /// Bytecodes of base system contracts and system contracts keyed by the bytecode hash.
+    pub factory_deps: HashMap<H256, Vec<u8>>,
+}
+
+impl GenesisState {
+    pub fn new(genesis_params: &GenesisParams) -> Self {
+        let base_system_contracts = genesis_params.base_system_contracts();
+        let factory_deps = [
+            &base_system_contracts.bootloader,
+            &base_system_contracts.default_aa,
+        ]
+        .into_iter()
+        .map(|contract| (contract.hash, be_words_to_bytes(&contract.code)))
+        .chain(
+            genesis_params
+                .system_contracts()
+                .iter()
+                .map(|contract| (hash_bytecode(&contract.bytecode), contract.bytecode.clone())),
+        )
+        .collect();
+
+        Self {
+            storage_writes: genesis_storage_writes(genesis_params.system_contracts()),
+            factory_deps,
+        }
+    }
+}
+
+fn genesis_storage_writes(system_contracts: &[DeployedContract]) -> Vec<(StorageKey, H256)> {
+    get_deduped_log_queries(&get_storage_logs(system_contracts))
+        .into_iter()
+        .filter(|log_query| log_query.rw_flag)
+        .map(|log| {
+            let key = StorageKey::new(AccountTreeId::new(log.address), u256_to_h256(log.key));
+            (key, u256_to_h256(log.written_value))
+        })
+        .collect()
+}
+
 pub async fn ensure_genesis_state(
     storage: &mut Connection<'_, Core>,
     genesis_params: &GenesisParams,
@@ -26,6 +26,45 @@ async fn running_genesis() {
     assert!(!conn.blocks_dal().is_genesis_needed().await.unwrap());
 }
 
+#[tokio::test]
+async fn genesis_state_matches_inserted_batch() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    let params = GenesisParams::mock();
+    let batch_params = insert_genesis_batch(&mut conn, &params).await.unwrap();
+
+    let state = GenesisState::new(&params);
+    let factory_deps = conn
+        .factory_deps_dal()
+        .dump_all_factory_deps_for_tests()
+        .await;
+    assert_eq!(factory_deps, state.factory_deps);
+
+    let storage_logs = conn
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    for (key, value) in &state.storage_writes {
+        let log = storage_logs
+            .iter()
+            .rev()
+            .find(|log| log.hashed_key == key.hashed_key())
+            .unwrap();
+        assert_eq!(log.value, *value);
+    }
+
+    let instructions: Vec<_> = state
+        .storage_writes
+        .iter()
+        .enumerate()
+        .map(|(i, (key, value))| {
+            TreeInstruction::write(key.hashed_key_u256(), i as u64 + 1, *value)
+        })
+        .collect();
+    let output = ZkSyncTree::process_genesis_batch(&instructions);
+    assert_eq!(output.root_hash, batch_params.root_hash);
+}
+
 #[tokio::test]
 async fn running_genesis_with_big_chain_id() {
     let pool = ConnectionPool::<Core>::test_pool().await;
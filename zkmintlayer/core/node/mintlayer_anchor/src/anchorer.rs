@@ -0,0 +1,396 @@
+use std::{ops, sync::Arc, time::Duration};
+
+use anyhow::Context as _;
+use chrono::Utc;
+use serde::Serialize;
+use tokio::{sync::watch, time::Instant};
+use zksync_config::configs::MintlayerConfig;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_ipfs_store::IpfsStore;
+use zksync_mintlayer_client::{DepositFeePolicy, DepositOptions, MintlayerInterface};
+use zksync_mintlayer_documents::Compression;
+use zksync_object_store::ObjectStore;
+use zksync_types::{
+    mintlayer::{
+        MintlayerAnchorCommitment, MintlayerAnchorOperation, MintlayerAnchorPayload,
+        MintlayerOperationToUpload,
+    },
+    pubdata_da::PubdataDA,
+    L1BatchNumber,
+};
+
+use crate::{
+    documents::OperationDocument,
+    error::{AnchorResult, RetryBackoff},
+    flush_criterion::{self, AnchorFlushCriterion, CountCriterion, DeadlineCriterion},
+    metrics::METRICS,
+};
+
+#[derive(Debug, Default, Serialize)]
+struct MintlayerAnchorHealthDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_uploaded_eth_tx_id: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_mintlayer_tx_id: Option<String>,
+    queued_operations: usize,
+    /// Error returned by the latest iteration, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_error: Option<String>,
+}
+
+/// Component uploading aggregated L1 operations to IPFS and anchoring their CIDs on Mintlayer.
+///
+/// The component polls transactions saved by the eth sender, so anchoring doesn't block L1 operation
+/// aggregation. All progress is persisted in Postgres, so the component can be safely restarted.
+/// When the component starts for the first time, it skips transactions saved before it (or before
+/// the configured first anchored L1 batch); these should be anchored with [`Self::backfill()`].
+///
+/// Anchoring errors never stop the component. Failed iterations are retried with exponential backoff; since progress
+/// is persisted, a retry picks up exactly where the failed iteration stopped. Fatal (i.e., non-transient) errors
+/// additionally mark the component health as affected until an iteration succeeds.
+#[derive(Debug)]
+pub struct MintlayerAnchorer {
+    pool: ConnectionPool<Core>,
+    blob_store: Arc<dyn ObjectStore>,
+    pubdata_da: PubdataDA,
+    /// Compression applied to uploaded operation documents.
+    compression: Compression,
+    ipfs_store: Arc<dyn IpfsStore>,
+    mintlayer_client: Box<dyn MintlayerInterface>,
+    /// Options for anchor deposits; re-deposits with bumped fees are handled by [`MintlayerTxManager`](crate::MintlayerTxManager).
+    deposit_options: DepositOptions,
+    /// Maximum number of queued operations anchored in a single Mintlayer transaction.
+    max_operations_per_anchor: usize,
+    flush_criteria: Vec<Box<dyn AnchorFlushCriterion>>,
+    /// Whether to anchor all queued operations on shutdown.
+    flush_on_shutdown: bool,
+    first_anchored_l1_batch: Option<L1BatchNumber>,
+    poll_interval: Duration,
+    health_updater: HealthUpdater,
+}
+
+impl MintlayerAnchorer {
+    pub fn new(
+        pool: ConnectionPool<Core>,
+        blob_store: Arc<dyn ObjectStore>,
+        pubdata_da: PubdataDA,
+        ipfs_store: Arc<dyn IpfsStore>,
+        mintlayer_client: Box<dyn MintlayerInterface>,
+        poll_interval: Duration,
+        config: &MintlayerConfig,
+    ) -> anyhow::Result<Self> {
+        let max_operations_per_anchor = config.max_operations_per_anchor() as usize;
+        anyhow::ensure!(
+            max_operations_per_anchor > 0,
+            "Maximum number of operations per Mintlayer anchor must be positive"
+        );
+
+        Ok(Self {
+            pool,
+            blob_store,
+            pubdata_da,
+            compression: if config.compress_documents() {
+                Compression::Zstd
+            } else {
+                Compression::None
+            },
+            ipfs_store,
+            mintlayer_client,
+            deposit_options: DepositFeePolicy::new(config)?.options(0),
+            max_operations_per_anchor,
+            flush_criteria: vec![
+                Box::new(CountCriterion {
+                    limit: max_operations_per_anchor,
+                }),
+                Box::new(DeadlineCriterion {
+                    deadline: config.anchor_deadline(),
+                }),
+            ],
+            flush_on_shutdown: config.flush_on_shutdown(),
+            first_anchored_l1_batch: config.first_anchored_l1_batch.map(L1BatchNumber),
+            poll_interval,
+            health_updater: ReactiveHealthCheck::new("mintlayer_anchor").1,
+        })
+    }
+
+    /// Returns a health check for this anchorer.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let mut conn = self.pool.connection_tagged("mintlayer_anchor").await?;
+        let start_eth_tx_id = conn
+            .mintlayer_anchor_dal()
+            .init_anchoring_start(self.first_anchored_l1_batch)
+            .await?;
+        drop(conn);
+        tracing::info!("Anchoring L1 transactions saved after eth_tx {start_eth_tx_id}");
+
+        let mut health_details = MintlayerAnchorHealthDetails::default();
+        self.health_updater
+            .update(Health::from(HealthStatus::Ready).with_details(&health_details));
+        let mut backoff = RetryBackoff::new(self.poll_interval);
+
+        loop {
+            if *stop_receiver.borrow() {
+                break;
+            }
+
+            tokio::select! {
+                res = self.loop_iteration(&mut health_details) => {
+                    let status = backoff.handle_result(
+                        "Mintlayer anchorer",
+                        res,
+                        &mut health_details.last_error,
+                    );
+                    self.health_updater
+                        .update(Health::from(status).with_details(&health_details));
+                }
+                _ = stop_receiver.changed() => {
+                    break;
+                }
+            }
+
+            if tokio::time::timeout(backoff.delay(), stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+
+        tracing::info!("Stop signal received, Mintlayer anchorer is shutting down");
+        if self.flush_on_shutdown {
+            if let Err(err) = self.flush(&mut health_details).await {
+                tracing::error!("Failed anchoring queued operations on shutdown: {err:?}");
+            }
+        }
+        Ok(())
+    }
+
+    async fn loop_iteration(
+        &self,
+        health_details: &mut MintlayerAnchorHealthDetails,
+    ) -> AnchorResult<()> {
+        self.upload_operations(health_details).await?;
+        self.deposit_saved_anchors(health_details).await?;
+        while self.anchor_queued_operations(health_details, false).await? {}
+        Ok(())
+    }
+
+    /// Uploads all pending operations and anchors them regardless of flush criteria.
+    async fn flush(&self, health_details: &mut MintlayerAnchorHealthDetails) -> AnchorResult<()> {
+        self.upload_operations(health_details).await?;
+        self.deposit_saved_anchors(health_details).await?;
+        while self.anchor_queued_operations(health_details, true).await? {}
+        Ok(())
+    }
+
+    /// Uploads operations covering the specified L1 batches that are missing from the anchoring queue (e.g., because
+    /// they were saved by the eth sender before anchoring was enabled), and anchors all queued operations regardless
+    /// of flush criteria. Operations already present in the queue are skipped, so the backfill is idempotent.
+    ///
+    /// Returns the number of uploaded operations.
+    pub async fn backfill(
+        &self,
+        l1_batch_range: ops::RangeInclusive<L1BatchNumber>,
+    ) -> anyhow::Result<usize> {
+        let mut health_details = MintlayerAnchorHealthDetails::default();
+        let mut conn = self.pool.connection_tagged("mintlayer_anchor").await?;
+        let operations = conn
+            .mintlayer_anchor_dal()
+            .get_operations_to_backfill(l1_batch_range.clone())
+            .await?;
+        drop(conn);
+        tracing::info!(
+            "Backfilling {} operations covering L1 batches {l1_batch_range:?}",
+            operations.len()
+        );
+
+        for operation in &operations {
+            self.upload_operation(operation, &mut health_details)
+                .await?;
+        }
+        self.deposit_saved_anchors(&mut health_details).await?;
+        while self
+            .anchor_queued_operations(&mut health_details, true)
+            .await?
+        {}
+        Ok(operations.len())
+    }
+
+    /// Uploads new operations saved by the eth sender to IPFS and adds their CIDs to the anchoring queue.
+    async fn upload_operations(
+        &self,
+        health_details: &mut MintlayerAnchorHealthDetails,
+    ) -> AnchorResult<()> {
+        let mut conn = self.pool.connection_tagged("mintlayer_anchor").await?;
+        let operations = conn
+            .mintlayer_anchor_dal()
+            .get_operations_to_upload(self.max_operations_per_anchor)
+            .await?;
+        drop(conn);
+
+        for operation in &operations {
+            self.upload_operation(operation, health_details).await?;
+        }
+        Ok(())
+    }
+
+    async fn upload_operation(
+        &self,
+        operation: &MintlayerOperationToUpload,
+        health_details: &mut MintlayerAnchorHealthDetails,
+    ) -> AnchorResult<()> {
+        let mut conn = self.pool.connection_tagged("mintlayer_anchor").await?;
+        let document = OperationDocument::load(
+            &mut conn,
+            self.blob_store.as_ref(),
+            self.pubdata_da,
+            self.compression,
+            operation,
+        )
+        .await?;
+        drop(conn);
+
+        let document_size = document.contents.len();
+        let started_at = Instant::now();
+        let cid = self
+            .ipfs_store
+            .put(&document.name, document.contents)
+            .await?;
+        let action_type = operation.action_type.into();
+        METRICS.document_upload_latency[&action_type].observe(started_at.elapsed());
+        METRICS.document_size[&action_type].observe(document_size);
+
+        let mut conn = self.pool.connection_tagged("mintlayer_anchor").await?;
+        conn.mintlayer_anchor_dal()
+            .insert_operation(
+                operation.eth_tx_id,
+                operation.action_type,
+                operation.l1_batch_range.clone(),
+                &cid,
+            )
+            .await?;
+        tracing::info!(
+            "Uploaded {} operation for L1 batches {:?} (eth_tx {}) to IPFS with CID {cid}",
+            operation.action_type,
+            operation.l1_batch_range,
+            operation.eth_tx_id
+        );
+        health_details.last_uploaded_eth_tx_id = Some(operation.eth_tx_id);
+        Ok(())
+    }
+
+    /// Deposits anchors that were saved, but not deposited on Mintlayer before (e.g., because of a restart).
+    async fn deposit_saved_anchors(
+        &self,
+        health_details: &mut MintlayerAnchorHealthDetails,
+    ) -> AnchorResult<()> {
+        let mut conn = self.pool.connection_tagged("mintlayer_anchor").await?;
+        let anchors = conn.mintlayer_anchor_dal().get_anchors_without_tx().await?;
+        drop(conn);
+
+        for anchor in anchors {
+            let tx_id = self.deposit_payload(&anchor.payload()).await?;
+            let mut conn = self.pool.connection_tagged("mintlayer_anchor").await?;
+            conn.mintlayer_anchor_dal()
+                .set_anchor_tx_id(anchor.id, &tx_id)
+                .await?;
+            health_details.last_mintlayer_tx_id = Some(tx_id);
+        }
+        Ok(())
+    }
+
+    /// Anchors queued operations if any of the flush criteria is triggered, or unconditionally if `force` is set.
+    /// Returns `true` if an anchor was created.
+    async fn anchor_queued_operations(
+        &self,
+        health_details: &mut MintlayerAnchorHealthDetails,
+        force: bool,
+    ) -> AnchorResult<bool> {
+        let mut conn = self.pool.connection_tagged("mintlayer_anchor").await?;
+        let mut operations = conn
+            .mintlayer_anchor_dal()
+            .get_pending_operations(self.max_operations_per_anchor)
+            .await?;
+        let queued_operations = conn
+            .mintlayer_anchor_dal()
+            .get_pending_operations_count()
+            .await?;
+        drop(conn);
+
+        health_details.queued_operations = queued_operations;
+        METRICS.queued_operations.set(queued_operations);
+        let operation_count = if force && !operations.is_empty() {
+            METRICS.anchor_flush_reason[&"shutdown"].inc();
+            Some(operations.len())
+        } else {
+            flush_criterion::operations_to_anchor(&self.flush_criteria, &operations, Utc::now())
+        };
+        let Some(operation_count) = operation_count else {
+            return Ok(false);
+        };
+        operations.truncate(operation_count);
+
+        // The root document lists all leaves of the commitment, so that inclusion proofs can be built
+        // from IPFS data alone.
+        let commitment = MintlayerAnchorCommitment::new(
+            operations
+                .iter()
+                .map(MintlayerAnchorOperation::to_leaf)
+                .collect(),
+        );
+        let (first, last) = (&operations[0], &operations[operations.len() - 1]);
+        let title = format!("batch_{}_{}", first.cid, last.cid);
+        let contents =
+            serde_json::to_vec(commitment.leaves()).context("failed serializing root document")?;
+        let root_cid = self.ipfs_store.put(&title, contents).await?;
+        let payload = MintlayerAnchorPayload {
+            merkle_root: Some(commitment.merkle_root()),
+            root_cid,
+        };
+
+        let operation_ids: Vec<_> = operations.iter().map(|op| op.id).collect();
+        let mut conn = self.pool.connection_tagged("mintlayer_anchor").await?;
+        let anchor_id = conn
+            .mintlayer_anchor_dal()
+            .insert_anchor(&payload.root_cid, commitment.merkle_root(), &operation_ids)
+            .await?;
+        drop(conn);
+
+        let tx_id = self.deposit_payload(&payload).await?;
+        let mut conn = self.pool.connection_tagged("mintlayer_anchor").await?;
+        conn.mintlayer_anchor_dal()
+            .set_anchor_tx_id(anchor_id, &tx_id)
+            .await?;
+        METRICS
+            .track_anchored_operations(&mut conn, &operations)
+            .await?;
+
+        health_details.queued_operations -= operations.len();
+        METRICS
+            .queued_operations
+            .set(health_details.queued_operations);
+        health_details.last_mintlayer_tx_id = Some(tx_id);
+        Ok(true)
+    }
+
+    async fn deposit_payload(&self, payload: &MintlayerAnchorPayload) -> AnchorResult<String> {
+        let started_at = Instant::now();
+        let tx = self
+            .mintlayer_client
+            .address_deposit_data(&payload.encode(), self.deposit_options)
+            .await?;
+        let tx_id = tx.tx_id;
+        METRICS.deposit_latency.observe(started_at.elapsed());
+        tracing::info!(
+            "Deposited anchor with Merkle root {:?} and root document {} on Mintlayer in tx {tx_id}",
+            payload.merkle_root,
+            payload.root_cid
+        );
+        Ok(tx_id)
+    }
+}
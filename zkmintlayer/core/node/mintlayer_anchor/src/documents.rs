@@ -0,0 +1,131 @@
+//! Documents with aggregated operation data uploaded to IPFS.
+
+use std::ops;
+
+use anyhow::Context as _;
+use zksync_dal::{Connection, Core, CoreDal};
+use zksync_mintlayer_documents::{
+    CommitOperation, Compression, ExecuteOperation, OperationDocument as DocumentData,
+    ProveOperation,
+};
+use zksync_object_store::{ObjectStore, ObjectStoreError};
+use zksync_prover_interface::outputs::L1BatchProofForL1;
+use zksync_types::{
+    aggregated_operations::AggregatedActionType, commitment::L1BatchWithMetadata,
+    mintlayer::MintlayerOperationToUpload, pubdata_da::PubdataDA, L1BatchNumber,
+};
+
+use crate::error::AnchorResult;
+
+/// Document describing a single aggregated operation.
+#[derive(Debug)]
+pub(crate) struct OperationDocument {
+    pub name: String,
+    pub contents: Vec<u8>,
+}
+
+impl OperationDocument {
+    /// Restores the data of an aggregated operation from Postgres and the object store, and encodes it
+    /// using the latest document format.
+    pub async fn load(
+        storage: &mut Connection<'_, Core>,
+        blob_store: &dyn ObjectStore,
+        pubdata_da: PubdataDA,
+        compression: Compression,
+        operation: &MintlayerOperationToUpload,
+    ) -> AnchorResult<Self> {
+        let range = &operation.l1_batch_range;
+        let name = format!(
+            "{}_block_{}_{}",
+            action_caption(operation.action_type),
+            range.start().0,
+            range.end().0
+        );
+
+        let data = match operation.action_type {
+            AggregatedActionType::Commit => DocumentData::Commit(CommitOperation {
+                prev_l1_batch: load_l1_batch(storage, *range.start() - 1).await?,
+                l1_batches: load_l1_batches(storage, range).await?,
+                pubdata_da,
+            }),
+            AggregatedActionType::PublishProofOnchain => DocumentData::Prove(ProveOperation {
+                prev_l1_batch: load_l1_batch(storage, *range.start() - 1).await?,
+                l1_batches: load_l1_batches(storage, range).await?,
+                proofs: load_proofs(storage, blob_store, range).await?,
+            }),
+            AggregatedActionType::Execute => DocumentData::Execute(ExecuteOperation {
+                l1_batches: load_l1_batches(storage, range).await?,
+            }),
+        };
+        let contents = data.encode(compression).with_context(|| {
+            format!(
+                "failed encoding document for eth_tx {}",
+                operation.eth_tx_id
+            )
+        })?;
+        Ok(Self { name, contents })
+    }
+}
+
+fn action_caption(action_type: AggregatedActionType) -> &'static str {
+    match action_type {
+        AggregatedActionType::Commit => "commit",
+        AggregatedActionType::PublishProofOnchain => "proof",
+        AggregatedActionType::Execute => "execute",
+    }
+}
+
+async fn load_l1_batch(
+    storage: &mut Connection<'_, Core>,
+    number: L1BatchNumber,
+) -> anyhow::Result<L1BatchWithMetadata> {
+    storage
+        .blocks_dal()
+        .get_l1_batch_metadata(number)
+        .await?
+        .with_context(|| format!("L1 batch #{number} is not complete in the DB"))
+}
+
+async fn load_l1_batches(
+    storage: &mut Connection<'_, Core>,
+    range: &ops::RangeInclusive<L1BatchNumber>,
+) -> anyhow::Result<Vec<L1BatchWithMetadata>> {
+    let mut l1_batches = Vec::with_capacity((range.end().0 - range.start().0 + 1) as usize);
+    for number in range.start().0..=range.end().0 {
+        l1_batches.push(load_l1_batch(storage, L1BatchNumber(number)).await?);
+    }
+    Ok(l1_batches)
+}
+
+/// Loads proofs sent to L1 for the specified range. Real proofs are only sent for single-batch operations;
+/// if there is no proof in the object store, the operation was sent without proofs.
+async fn load_proofs(
+    storage: &mut Connection<'_, Core>,
+    blob_store: &dyn ObjectStore,
+    range: &ops::RangeInclusive<L1BatchNumber>,
+) -> AnchorResult<Vec<L1BatchProofForL1>> {
+    if range.start() != range.end() {
+        return Ok(vec![]);
+    }
+    let l1_batch_number = *range.start();
+    let Some(minor_version) = storage
+        .blocks_dal()
+        .get_batch_protocol_version_id(l1_batch_number)
+        .await?
+    else {
+        return Ok(vec![]);
+    };
+
+    let versions = storage.protocol_versions_dal().all_versions().await;
+    for version in versions.into_iter().filter(|v| v.minor == minor_version) {
+        match blob_store.get((l1_batch_number, version)).await {
+            Ok(proof) => return Ok(vec![proof]),
+            Err(ObjectStoreError::KeyNotFound(_)) => (),
+            Err(err) => {
+                tracing::warn!("Failed loading proof for L1 batch #{l1_batch_number}: {err}");
+                return Err(err.into());
+            }
+        }
+    }
+    Ok(vec![])
+}
@@ -0,0 +1,162 @@
+use std::time::Duration;
+
+use zksync_dal::{DalError, SqlxError};
+use zksync_health_check::HealthStatus;
+use zksync_ipfs_store::IpfsStoreError;
+use zksync_mintlayer_client::MintlayerClientError;
+use zksync_object_store::ObjectStoreError;
+
+/// Maximum delay between retries of a failed iteration.
+const MAX_RETRY_INTERVAL: Duration = Duration::from_secs(300);
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum AnchorError {
+    #[error("IPFS error: {0}")]
+    Ipfs(#[from] IpfsStoreError),
+    #[error(transparent)]
+    Mintlayer(#[from] MintlayerClientError),
+    #[error("object store error: {0}")]
+    ObjectStore(#[from] ObjectStoreError),
+    #[error("Postgres error: {0}")]
+    Dal(#[from] DalError),
+    #[error("internal error: {0:#}")]
+    Internal(#[from] anyhow::Error),
+}
+
+impl AnchorError {
+    /// Failures to acquire a connection or to manage a transaction, as well as I/O and pool timeout errors
+    /// for queries are considered transient (e.g., Postgres is restarting, or the pool is exhausted).
+    /// Other Postgres errors (e.g., constraint violations) indicate a bug and are fatal.
+    fn is_transient_dal_error(err: &DalError) -> bool {
+        matches!(err, DalError::Connection(_))
+            || matches!(
+                err.inner(),
+                SqlxError::Io(_) | SqlxError::PoolTimedOut | SqlxError::PoolClosed
+            )
+    }
+
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Dal(err) => Self::is_transient_dal_error(err),
+            Self::Ipfs(err) => err.is_transient(),
+            Self::Mintlayer(err) => err.is_transient(),
+            Self::ObjectStore(err) => err.is_transient(),
+            Self::Internal(_) => false,
+        }
+    }
+}
+
+pub(crate) type AnchorResult<T> = Result<T, AnchorError>;
+
+/// Delay before the next iteration of an anchoring component. Doubles after each transient error, up to
+/// [`MAX_RETRY_INTERVAL`]; fatal errors are retried with the maximum delay, since they usually require an operator
+/// to intervene.
+#[derive(Debug)]
+pub(crate) struct RetryBackoff {
+    poll_interval: Duration,
+    max_interval: Duration,
+    delay: Duration,
+}
+
+impl RetryBackoff {
+    pub fn new(poll_interval: Duration) -> Self {
+        Self {
+            poll_interval,
+            max_interval: MAX_RETRY_INTERVAL.max(poll_interval),
+            delay: poll_interval,
+        }
+    }
+
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+
+    /// Updates the delay based on the error returned by the latest iteration.
+    pub fn update(&mut self, error: Option<&AnchorError>) {
+        self.delay = match error {
+            None => self.poll_interval,
+            Some(err) if err.is_transient() => (self.delay * 2).min(self.max_interval),
+            Some(_) => self.max_interval,
+        };
+    }
+
+    /// Handles the result of an iteration of the `component`: updates the delay, logs and records the error (if any),
+    /// and returns the health status of the component.
+    pub fn handle_result(
+        &mut self,
+        component: &str,
+        result: AnchorResult<()>,
+        last_error: &mut Option<String>,
+    ) -> HealthStatus {
+        self.update(result.as_ref().err());
+        let err = match result {
+            Ok(()) => {
+                *last_error = None;
+                return HealthStatus::Ready;
+            }
+            Err(err) => err,
+        };
+
+        *last_error = Some(err.to_string());
+        if err.is_transient() {
+            tracing::warn!(
+                "Transient error in {component}, retrying in {:?}: {err:?}",
+                self.delay
+            );
+            HealthStatus::Ready
+        } else {
+            tracing::error!(
+                "Fatal error in {component}, retrying in {:?}: {err:?}",
+                self.delay
+            );
+            HealthStatus::Affected
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transient_error() -> AnchorError {
+        IpfsStoreError::Other {
+            source: "timeout".into(),
+            is_transient: true,
+        }
+        .into()
+    }
+
+    #[test]
+    fn backoff_for_errors() {
+        let poll_interval = Duration::from_secs(10);
+        let mut backoff = RetryBackoff::new(poll_interval);
+        assert_eq!(backoff.delay(), poll_interval);
+
+        let err = transient_error();
+        assert!(err.is_transient());
+        backoff.update(Some(&err));
+        assert_eq!(backoff.delay(), poll_interval * 2);
+        backoff.update(Some(&err));
+        assert_eq!(backoff.delay(), poll_interval * 4);
+        for _ in 0..10 {
+            backoff.update(Some(&err));
+        }
+        assert_eq!(backoff.delay(), MAX_RETRY_INTERVAL);
+
+        let mut last_error = None;
+        let status = backoff.handle_result("test", Ok(()), &mut last_error);
+        assert_eq!(status, HealthStatus::Ready);
+        assert_eq!(backoff.delay(), poll_interval);
+
+        let status = backoff.handle_result("test", Err(transient_error()), &mut last_error);
+        assert_eq!(status, HealthStatus::Ready);
+        assert!(last_error.is_some());
+
+        let err = AnchorError::Internal(anyhow::anyhow!("invariant violated"));
+        assert!(!err.is_transient());
+        let status = backoff.handle_result("test", Err(err), &mut last_error);
+        assert_eq!(status, HealthStatus::Affected);
+        assert_eq!(backoff.delay(), MAX_RETRY_INTERVAL);
+        assert!(last_error.unwrap().contains("invariant violated"));
+    }
+}
@@ -0,0 +1,155 @@
+//! Criteria deciding when queued operations are anchored on Mintlayer. Modeled after the L1 batch publish
+//! criteria of the eth sender.
+
+use std::{fmt, time::Duration};
+
+use chrono::{DateTime, Utc};
+use zksync_types::mintlayer::MintlayerAnchorOperation;
+
+use crate::metrics::METRICS;
+
+pub(crate) trait AnchorFlushCriterion: fmt::Debug + Send + Sync {
+    // Takes `&self` receiver for the trait to be object-safe
+    fn name(&self) -> &'static str;
+
+    /// Returns `None` if there is no need to anchor queued operations yet. Otherwise, returns the number
+    /// of operations (counting from the oldest one) that need to be anchored.
+    fn operations_to_anchor(
+        &self,
+        queued_operations: &[MintlayerAnchorOperation],
+        now: DateTime<Utc>,
+    ) -> Option<usize>;
+}
+
+#[derive(Debug)]
+pub(crate) struct CountCriterion {
+    /// Maximum number of operations anchored together.
+    pub limit: usize,
+}
+
+impl AnchorFlushCriterion for CountCriterion {
+    fn name(&self) -> &'static str {
+        "count"
+    }
+
+    fn operations_to_anchor(
+        &self,
+        queued_operations: &[MintlayerAnchorOperation],
+        _now: DateTime<Utc>,
+    ) -> Option<usize> {
+        if queued_operations.len() >= self.limit {
+            tracing::debug!(
+                "`count` anchor flush criterion (limit={}) triggered with {} queued operations",
+                self.limit,
+                queued_operations.len()
+            );
+            Some(self.limit)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct DeadlineCriterion {
+    /// Maximum age of the oldest queued operation. Once reached, all queued operations are anchored.
+    pub deadline: Duration,
+}
+
+impl AnchorFlushCriterion for DeadlineCriterion {
+    fn name(&self) -> &'static str {
+        "deadline"
+    }
+
+    fn operations_to_anchor(
+        &self,
+        queued_operations: &[MintlayerAnchorOperation],
+        now: DateTime<Utc>,
+    ) -> Option<usize> {
+        let oldest_operation = queued_operations.first()?;
+        let age = (now - oldest_operation.created_at)
+            .to_std()
+            .unwrap_or_default();
+        if age >= self.deadline {
+            tracing::debug!(
+                "`deadline` anchor flush criterion triggered with {} queued operations; oldest operation age: {age:?}",
+                queued_operations.len()
+            );
+            Some(queued_operations.len())
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns the number of queued operations to anchor according to the first triggered criterion.
+pub(crate) fn operations_to_anchor(
+    criteria: &[Box<dyn AnchorFlushCriterion>],
+    queued_operations: &[MintlayerAnchorOperation],
+    now: DateTime<Utc>,
+) -> Option<usize> {
+    for criterion in criteria {
+        if let Some(count) = criterion.operations_to_anchor(queued_operations, now) {
+            METRICS.anchor_flush_reason[&criterion.name()].inc();
+            return Some(count.min(queued_operations.len()));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{aggregated_operations::AggregatedActionType, L1BatchNumber};
+
+    use super::*;
+
+    fn queued_operations(count: u32, created_at: DateTime<Utc>) -> Vec<MintlayerAnchorOperation> {
+        (1..=count)
+            .map(|id| MintlayerAnchorOperation {
+                id,
+                eth_tx_id: Some(id),
+                action_type: AggregatedActionType::Commit,
+                l1_batch_range: L1BatchNumber(id)..=L1BatchNumber(id),
+                cid: format!("cid{id}"),
+                anchor_id: None,
+                created_at,
+            })
+            .collect()
+    }
+
+    fn criteria() -> Vec<Box<dyn AnchorFlushCriterion>> {
+        vec![
+            Box::new(CountCriterion { limit: 3 }),
+            Box::new(DeadlineCriterion {
+                deadline: Duration::from_secs(60),
+            }),
+        ]
+    }
+
+    #[test]
+    fn count_criterion() {
+        let now = Utc::now();
+        let criteria = criteria();
+        assert_eq!(operations_to_anchor(&criteria, &[], now), None);
+        let operations = queued_operations(2, now);
+        assert_eq!(operations_to_anchor(&criteria, &operations, now), None);
+        let operations = queued_operations(3, now);
+        assert_eq!(operations_to_anchor(&criteria, &operations, now), Some(3));
+        // Overshooting the limit should still anchor operations.
+        let operations = queued_operations(5, now);
+        assert_eq!(operations_to_anchor(&criteria, &operations, now), Some(3));
+    }
+
+    #[test]
+    fn deadline_criterion() {
+        let now = Utc::now();
+        let criteria = criteria();
+        let operations = queued_operations(2, now - chrono::Duration::seconds(59));
+        assert_eq!(operations_to_anchor(&criteria, &operations, now), None);
+        let operations = queued_operations(2, now - chrono::Duration::seconds(60));
+        assert_eq!(operations_to_anchor(&criteria, &operations, now), Some(2));
+        // Operations from the future (e.g., because of clock skew) must not break the criterion.
+        let operations = queued_operations(2, now + chrono::Duration::seconds(10));
+        assert_eq!(operations_to_anchor(&criteria, &operations, now), None);
+    }
+}
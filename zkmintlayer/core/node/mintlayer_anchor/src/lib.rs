@@ -0,0 +1,15 @@
+//! Anchoring of aggregated L1 operations on the Mintlayer chain.
+//!
+//! Each commit / prove / execute operation saved by the eth sender is uploaded to IPFS as a separate
+//! document. CIDs of these documents are queued in Postgres; once enough operations are queued, or the oldest
+//! queued operation gets too old, they are combined into a root document whose CID is deposited on Mintlayer.
+//! Deposit transactions are tracked until they get enough confirmations, and are re-submitted if dropped.
+
+pub use self::{anchorer::MintlayerAnchorer, tx_manager::MintlayerTxManager};
+
+mod anchorer;
+mod documents;
+mod error;
+mod flush_criterion;
+mod metrics;
+mod tx_manager;
@@ -0,0 +1,104 @@
+//! Metrics for anchoring aggregated L1 operations on Mintlayer.
+
+use std::{fmt, time::Duration};
+
+use chrono::Utc;
+use vise::{
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, LabeledFamily,
+    Metrics, Unit,
+};
+use zksync_dal::{Connection, Core, CoreDal, DalResult};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType, mintlayer::MintlayerAnchorOperation,
+};
+
+/// Buckets for network call latencies (from 0.1 to 120 seconds).
+const CALL_LATENCIES: Buckets =
+    Buckets::values(&[0.1, 0.5, 1.0, 2.0, 5.0, 10.0, 30.0, 60.0, 120.0]);
+/// Buckets for anchoring lag (from 10 seconds to 1 day).
+const ANCHOR_LATENCIES: Buckets = Buckets::values(&[
+    10.0, 30.0, 60.0, 300.0, 600.0, 1_800.0, 3_600.0, 7_200.0, 21_600.0, 86_400.0,
+]);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet, EncodeLabelValue)]
+#[metrics(label = "type")]
+pub(super) struct ActionTypeLabel(AggregatedActionType);
+
+impl From<AggregatedActionType> for ActionTypeLabel {
+    fn from(action_type: AggregatedActionType) -> Self {
+        Self(action_type)
+    }
+}
+
+impl fmt::Display for ActionTypeLabel {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.0.as_str())
+    }
+}
+
+/// Outcome of tracking a Mintlayer anchor transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet, EncodeLabelValue)]
+#[metrics(label = "outcome", rename_all = "snake_case")]
+pub(super) enum AnchorTxOutcome {
+    Confirmed,
+    Rebroadcast,
+    Resubmitted,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_mintlayer_anchor")]
+pub(super) struct MintlayerAnchorMetrics {
+    /// Latency of uploading an operation document to IPFS.
+    #[metrics(buckets = CALL_LATENCIES, unit = Unit::Seconds)]
+    pub document_upload_latency: Family<ActionTypeLabel, Histogram<Duration>>,
+    /// Size of the uploaded operation document.
+    #[metrics(buckets = Buckets::exponential(1_024.0..=16.0 * 1_024.0 * 1_024.0, 2.0), unit = Unit::Bytes)]
+    pub document_size: Family<ActionTypeLabel, Histogram<usize>>,
+    /// Latency of depositing an anchor root on Mintlayer.
+    #[metrics(buckets = CALL_LATENCIES, unit = Unit::Seconds)]
+    pub deposit_latency: Histogram<Duration>,
+    /// Number of operations uploaded to IPFS, but not anchored yet.
+    pub queued_operations: Gauge<usize>,
+    /// Number of anchors created, labeled by the flush criterion that triggered anchoring.
+    #[metrics(labels = ["reason"])]
+    pub anchor_flush_reason: LabeledFamily<&'static str, Counter>,
+    /// Time from sealing an L1 batch to depositing the anchor of an operation covering it.
+    #[metrics(buckets = ANCHOR_LATENCIES, unit = Unit::Seconds)]
+    pub l1_batch_anchor_latency: Family<ActionTypeLabel, Histogram<Duration>>,
+    /// Last L1 batch included into an operation of the specified type that was anchored on Mintlayer.
+    pub last_anchored_l1_batch: Family<ActionTypeLabel, Gauge<usize>>,
+    /// Number of deposited anchors that are not confirmed on Mintlayer yet.
+    pub unconfirmed_anchors: Gauge<usize>,
+    /// Number of anchor transactions that were confirmed, rebroadcast or re-submitted after being dropped.
+    pub anchor_txs: Family<AnchorTxOutcome, Counter>,
+    /// Confirmed coin balance of the Mintlayer account used for anchoring (in coins, not atoms).
+    pub wallet_balance: Gauge<f64>,
+}
+
+impl MintlayerAnchorMetrics {
+    /// Tracks anchoring lag and progress for operations that were just anchored.
+    pub async fn track_anchored_operations(
+        &self,
+        connection: &mut Connection<'_, Core>,
+        operations: &[MintlayerAnchorOperation],
+    ) -> DalResult<()> {
+        let now = Utc::now();
+        for operation in operations {
+            let action_type = ActionTypeLabel::from(operation.action_type);
+            let seal_times = connection
+                .mintlayer_anchor_dal()
+                .get_l1_batch_seal_times(operation.l1_batch_range.clone())
+                .await?;
+            for (_, sealed_at) in seal_times {
+                let latency = (now - sealed_at).to_std().unwrap_or_default();
+                self.l1_batch_anchor_latency[&action_type].observe(latency);
+            }
+            self.last_anchored_l1_batch[&action_type]
+                .set(operation.l1_batch_range.end().0 as usize);
+        }
+        Ok(())
+    }
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<MintlayerAnchorMetrics> = vise::Global::new();
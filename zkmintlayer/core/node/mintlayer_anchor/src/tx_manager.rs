@@ -0,0 +1,552 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context as _;
+use serde::Serialize;
+use tokio::sync::watch;
+use zksync_config::configs::MintlayerConfig;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_mintlayer_client::{DepositFeePolicy, MintlayerInterface, TxState};
+use zksync_types::mintlayer::MintlayerAnchor;
+
+use crate::{
+    error::{AnchorResult, RetryBackoff},
+    metrics::{AnchorTxOutcome, METRICS},
+};
+
+#[derive(Debug, Default, Serialize)]
+struct MintlayerTxManagerHealthDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    best_block_height: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_confirmed_anchor_id: Option<u32>,
+    unconfirmed_anchors: usize,
+    /// Confirmed coin balance of the account as a decimal string.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    balance: Option<String>,
+    /// Error returned by the latest iteration, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_error: Option<String>,
+}
+
+/// Component tracking confirmations of anchor transactions deposited by [`MintlayerAnchorer`](crate::MintlayerAnchorer).
+///
+/// An anchor is confirmed once the block including its transaction has the configured number of confirmations.
+/// If the transaction is abandoned by the wallet or conflicts with another transaction, the same root is deposited
+/// in a new transaction. A transaction that is unknown to the wallet (e.g., because the wallet hasn't indexed it yet)
+/// or inactive (i.e., not in the mempool) may still get included, so it's only re-deposited if it stays in this state
+/// for the configured number of blocks; inactive transactions are rebroadcast in the meantime. Each re-deposit costs
+/// fees and bumps the fee rate according to the configured [`DepositFeePolicy`].
+///
+/// The manager also periodically checks the balance of the account and reports it via metrics and health details.
+///
+/// Like [`MintlayerAnchorer`](crate::MintlayerAnchorer), the manager retries failed iterations with exponential backoff
+/// and reports fatal errors via its health check.
+#[derive(Debug)]
+pub struct MintlayerTxManager {
+    pool: ConnectionPool<Core>,
+    mintlayer_client: Box<dyn MintlayerInterface>,
+    confirmations: u64,
+    resubmission_grace_blocks: u64,
+    fee_policy: DepositFeePolicy,
+    /// Best block heights at which anchor transactions were first observed to be unknown or inactive, keyed by
+    /// the transaction ID. Not persisted, so a restart only delays re-deposits.
+    missing_since_block: HashMap<String, u64>,
+    poll_interval: Duration,
+    balance_check_interval: Duration,
+    health_updater: HealthUpdater,
+}
+
+impl MintlayerTxManager {
+    pub fn new(
+        pool: ConnectionPool<Core>,
+        mintlayer_client: Box<dyn MintlayerInterface>,
+        poll_interval: Duration,
+        config: &MintlayerConfig,
+    ) -> anyhow::Result<Self> {
+        let confirmations = config.confirmations();
+        anyhow::ensure!(
+            confirmations > 0,
+            "number of Mintlayer anchor confirmations must be positive"
+        );
+
+        Ok(Self {
+            pool,
+            mintlayer_client,
+            confirmations: confirmations.into(),
+            resubmission_grace_blocks: config.resubmission_grace_blocks().into(),
+            fee_policy: DepositFeePolicy::new(config)?,
+            missing_since_block: HashMap::new(),
+            poll_interval,
+            balance_check_interval: config.balance_check_interval(),
+            health_updater: ReactiveHealthCheck::new("mintlayer_tx_manager").1,
+        })
+    }
+
+    /// Returns a health check for this manager.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let mut health_details = MintlayerTxManagerHealthDetails::default();
+        self.health_updater
+            .update(Health::from(HealthStatus::Ready).with_details(&health_details));
+        let mut next_balance_check = Instant::now();
+        let mut backoff = RetryBackoff::new(self.poll_interval);
+
+        loop {
+            if *stop_receiver.borrow() {
+                break;
+            }
+
+            tokio::select! {
+                res = self.loop_iteration(&mut health_details) => {
+                    let status = backoff.handle_result(
+                        "Mintlayer tx manager",
+                        res,
+                        &mut health_details.last_error,
+                    );
+                    self.health_updater
+                        .update(Health::from(status).with_details(&health_details));
+                }
+                _ = stop_receiver.changed() => {
+                    break;
+                }
+            }
+
+            if Instant::now() >= next_balance_check {
+                if let Err(err) = self.check_balance(&mut health_details).await {
+                    tracing::warn!("Error checking Mintlayer account balance {err:?}");
+                }
+                next_balance_check = Instant::now() + self.balance_check_interval;
+            }
+
+            if tokio::time::timeout(backoff.delay(), stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+
+        tracing::info!("Stop signal received, Mintlayer tx manager is shutting down");
+        Ok(())
+    }
+
+    async fn check_balance(
+        &self,
+        health_details: &mut MintlayerTxManagerHealthDetails,
+    ) -> anyhow::Result<()> {
+        let balances = self
+            .mintlayer_client
+            .account_balance()
+            .await
+            .context("failed getting Mintlayer account balance")?;
+        let balance: f64 = balances
+            .coins
+            .decimal
+            .parse()
+            .with_context(|| format!("invalid decimal balance `{}`", balances.coins.decimal))?;
+        METRICS.wallet_balance.set(balance);
+        health_details.balance = Some(balances.coins.decimal);
+        Ok(())
+    }
+
+    async fn loop_iteration(
+        &mut self,
+        health_details: &mut MintlayerTxManagerHealthDetails,
+    ) -> AnchorResult<()> {
+        let mut conn = self.pool.connection_tagged("mintlayer_anchor").await?;
+        let anchors = conn
+            .mintlayer_anchor_dal()
+            .get_unconfirmed_anchors()
+            .await?;
+        drop(conn);
+
+        health_details.unconfirmed_anchors = anchors.len();
+        METRICS.unconfirmed_anchors.set(anchors.len());
+        self.missing_since_block.retain(|tx_id, _| {
+            anchors
+                .iter()
+                .any(|anchor| anchor.mintlayer_tx_id.as_ref() == Some(tx_id))
+        });
+        if anchors.is_empty() {
+            return Ok(());
+        }
+
+        let best_block_height = self.mintlayer_client.wallet_best_block().await?.height;
+        health_details.best_block_height = Some(best_block_height);
+
+        for anchor in anchors {
+            let confirmed = self.check_anchor(&anchor, best_block_height).await?;
+            if confirmed {
+                health_details.unconfirmed_anchors -= 1;
+                health_details.last_confirmed_anchor_id = Some(anchor.id);
+            }
+        }
+        METRICS
+            .unconfirmed_anchors
+            .set(health_details.unconfirmed_anchors);
+        Ok(())
+    }
+
+    /// Checks the state of the anchor transaction and updates the anchor accordingly. Returns `true`
+    /// if the anchor got confirmed.
+    async fn check_anchor(
+        &mut self,
+        anchor: &MintlayerAnchor,
+        best_block_height: u64,
+    ) -> AnchorResult<bool> {
+        let tx_id = anchor.mintlayer_tx_id.as_deref().with_context(|| {
+            format!(
+                "unconfirmed Mintlayer anchor #{} has no transaction",
+                anchor.id
+            )
+        })?;
+        let tx_state = self
+            .mintlayer_client
+            .transaction_get(tx_id)
+            .await?
+            .map(|info| info.state);
+
+        if matches!(
+            tx_state,
+            Some(TxState::Confirmed { .. } | TxState::InMempool)
+        ) {
+            self.missing_since_block.remove(tx_id);
+        }
+
+        match tx_state {
+            Some(TxState::Confirmed { block_height }) => {
+                let confirmations = (best_block_height + 1).saturating_sub(block_height);
+                let mut conn = self.pool.connection_tagged("mintlayer_anchor").await?;
+                if confirmations >= self.confirmations {
+                    conn.mintlayer_anchor_dal()
+                        .confirm_anchor(anchor.id, block_height)
+                        .await?;
+                    METRICS.anchor_txs[&AnchorTxOutcome::Confirmed].inc();
+                    tracing::info!(
+                        "Mintlayer anchor #{} with root {} is confirmed in tx {tx_id} (block {block_height})",
+                        anchor.id,
+                        anchor.root_cid
+                    );
+                    return Ok(true);
+                } else if anchor.included_at_block != Some(block_height) {
+                    conn.mintlayer_anchor_dal()
+                        .set_anchor_inclusion(anchor.id, Some(block_height))
+                        .await?;
+                    tracing::info!(
+                        "Mintlayer tx {tx_id} for anchor #{} is included in block {block_height}",
+                        anchor.id
+                    );
+                }
+            }
+            Some(TxState::InMempool) => {
+                if anchor.included_at_block.is_some() {
+                    // The including block was reorged; the transaction is back in the mempool.
+                    let mut conn = self.pool.connection_tagged("mintlayer_anchor").await?;
+                    conn.mintlayer_anchor_dal()
+                        .set_anchor_inclusion(anchor.id, None)
+                        .await?;
+                    tracing::warn!(
+                        "Mintlayer tx {tx_id} for anchor #{} was reorged from block {:?}",
+                        anchor.id,
+                        anchor.included_at_block
+                    );
+                }
+            }
+            Some(TxState::Conflicted | TxState::Abandoned) => {
+                tracing::warn!(
+                    "Mintlayer tx {tx_id} for anchor #{} was dropped (state: {tx_state:?}); re-depositing root {}",
+                    anchor.id,
+                    anchor.root_cid
+                );
+                self.resubmit_anchor(anchor).await?;
+            }
+            None | Some(TxState::Inactive) => {
+                let missing_since_block = *self
+                    .missing_since_block
+                    .entry(tx_id.to_owned())
+                    .or_insert(best_block_height);
+                let missing_blocks = best_block_height.saturating_sub(missing_since_block);
+                if missing_blocks >= self.resubmission_grace_blocks {
+                    tracing::warn!(
+                        "Mintlayer tx {tx_id} for anchor #{} is not in the mempool for {missing_blocks} blocks \
+                         (state: {tx_state:?}); re-depositing root {}",
+                        anchor.id,
+                        anchor.root_cid
+                    );
+                    self.missing_since_block.remove(tx_id);
+                    self.resubmit_anchor(anchor).await?;
+                    return Ok(false);
+                }
+
+                if anchor.included_at_block.is_some() {
+                    let mut conn = self.pool.connection_tagged("mintlayer_anchor").await?;
+                    conn.mintlayer_anchor_dal()
+                        .set_anchor_inclusion(anchor.id, None)
+                        .await?;
+                }
+                if tx_state.is_some() {
+                    // Rebroadcasting is best-effort; if it fails, the root will be re-deposited after the grace period.
+                    match self.mintlayer_client.transaction_rebroadcast(tx_id).await {
+                        Ok(()) => {
+                            METRICS.anchor_txs[&AnchorTxOutcome::Rebroadcast].inc();
+                            tracing::info!(
+                                "Rebroadcast inactive Mintlayer tx {tx_id} for anchor #{}",
+                                anchor.id
+                            );
+                        }
+                        Err(err) => tracing::warn!(
+                            "Failed rebroadcasting Mintlayer tx {tx_id} for anchor #{}: {err}",
+                            anchor.id
+                        ),
+                    }
+                } else {
+                    tracing::info!(
+                        "Mintlayer tx {tx_id} for anchor #{} is unknown to the wallet for {missing_blocks} blocks; \
+                         waiting for it to be indexed",
+                        anchor.id
+                    );
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// Deposits the anchor root in a new transaction with a bumped fee rate.
+    async fn resubmit_anchor(&self, anchor: &MintlayerAnchor) -> AnchorResult<()> {
+        let options = self.fee_policy.options(anchor.resubmissions + 1);
+        let new_tx = self
+            .mintlayer_client
+            .address_deposit_data(&anchor.payload().encode(), options)
+            .await?;
+        let mut conn = self.pool.connection_tagged("mintlayer_anchor").await?;
+        conn.mintlayer_anchor_dal()
+            .record_anchor_resubmission(anchor.id, &new_tx.tx_id)
+            .await?;
+        METRICS.anchor_txs[&AnchorTxOutcome::Resubmitted].inc();
+        tracing::info!(
+            "Re-deposited root {} for anchor #{} in tx {} ({options:?})",
+            anchor.root_cid,
+            anchor.id,
+            new_tx.tx_id
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_mintlayer_client::{
+        clients::MockMintlayerClient, DepositOptions, MintlayerClientError,
+    };
+    use zksync_types::{mintlayer::MintlayerAnchorPayload, H256};
+
+    use super::*;
+
+    async fn create_anchor(pool: &ConnectionPool<Core>, client: &MockMintlayerClient) -> String {
+        let mut conn = pool.connection().await.unwrap();
+        let anchor_id = conn
+            .mintlayer_anchor_dal()
+            .insert_anchor("root_cid", H256::repeat_byte(1), &[])
+            .await
+            .unwrap();
+        let anchor = conn
+            .mintlayer_anchor_dal()
+            .get_anchors_without_tx()
+            .await
+            .unwrap()
+            .remove(0);
+        let tx = client
+            .address_deposit_data(&anchor.payload().encode(), DepositOptions::default())
+            .await
+            .unwrap();
+        conn.mintlayer_anchor_dal()
+            .set_anchor_tx_id(anchor_id, &tx.tx_id)
+            .await
+            .unwrap();
+        tx.tx_id
+    }
+
+    async fn unconfirmed_anchors(pool: &ConnectionPool<Core>) -> Vec<MintlayerAnchor> {
+        let mut conn = pool.connection().await.unwrap();
+        conn.mintlayer_anchor_dal()
+            .get_unconfirmed_anchors()
+            .await
+            .unwrap()
+    }
+
+    fn create_manager(
+        pool: &ConnectionPool<Core>,
+        client: &MockMintlayerClient,
+    ) -> MintlayerTxManager {
+        let config = MintlayerConfig {
+            confirmations: Some(3),
+            resubmission_grace_blocks: Some(5),
+            fee_in_top_x_mb: Some(4),
+            fee_bump_divisor: Some(2),
+            ..MintlayerConfig::for_tests()
+        };
+        MintlayerTxManager::new(
+            pool.clone(),
+            Box::new(client.clone()),
+            Duration::from_millis(10),
+            &config,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn confirming_anchor() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let client = MockMintlayerClient::default();
+        let tx_id = create_anchor(&pool, &client).await;
+        let mut manager = create_manager(&pool, &client);
+        let mut health_details = MintlayerTxManagerHealthDetails::default();
+
+        manager.loop_iteration(&mut health_details).await.unwrap();
+        assert_eq!(health_details.unconfirmed_anchors, 1);
+
+        client.confirm_tx(&tx_id, 10);
+        manager.loop_iteration(&mut health_details).await.unwrap();
+        let anchors = unconfirmed_anchors(&pool).await;
+        assert_eq!(anchors[0].included_at_block, Some(10));
+
+        client.set_best_block_height(12);
+        manager.loop_iteration(&mut health_details).await.unwrap();
+        assert!(unconfirmed_anchors(&pool).await.is_empty());
+        assert_eq!(health_details.unconfirmed_anchors, 0);
+        assert!(health_details.last_confirmed_anchor_id.is_some());
+    }
+
+    #[tokio::test]
+    async fn resubmitting_abandoned_anchor() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let client = MockMintlayerClient::default();
+        let tx_id = create_anchor(&pool, &client).await;
+        let mut manager = create_manager(&pool, &client);
+        let mut health_details = MintlayerTxManagerHealthDetails::default();
+
+        client.set_tx_state(&tx_id, TxState::Abandoned);
+        manager.loop_iteration(&mut health_details).await.unwrap();
+
+        let deposits = client.deposited_data();
+        assert_eq!(deposits.len(), 2);
+        let (new_tx_id, data) = &deposits[1];
+        assert_eq!(*data, deposits[0].1);
+        let payload = MintlayerAnchorPayload::decode(data).unwrap();
+        assert_eq!(payload.merkle_root, Some(H256::repeat_byte(1)));
+        assert_eq!(payload.root_cid, "root_cid");
+        let anchors = unconfirmed_anchors(&pool).await;
+        assert_eq!(anchors[0].mintlayer_tx_id.as_ref(), Some(new_tx_id));
+        assert_eq!(anchors[0].resubmissions, 1);
+        let options = client.deposit_options();
+        assert_eq!(options[1].in_top_x_mb, Some(2));
+
+        client.confirm_tx(new_tx_id, 20);
+        client.set_best_block_height(30);
+        manager.loop_iteration(&mut health_details).await.unwrap();
+        assert!(unconfirmed_anchors(&pool).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn not_resubmitting_briefly_unknown_anchor() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let client = MockMintlayerClient::default();
+        let tx_id = create_anchor(&pool, &client).await;
+        let mut manager = create_manager(&pool, &client);
+        let mut health_details = MintlayerTxManagerHealthDetails::default();
+
+        // The wallet hasn't indexed the transaction yet.
+        client.drop_tx(&tx_id);
+        client.set_best_block_height(10);
+        manager.loop_iteration(&mut health_details).await.unwrap();
+        client.set_best_block_height(14);
+        manager.loop_iteration(&mut health_details).await.unwrap();
+        assert_eq!(client.deposited_data().len(), 1);
+
+        client.set_tx_state(&tx_id, TxState::InMempool);
+        manager.loop_iteration(&mut health_details).await.unwrap();
+        client.set_best_block_height(20);
+        manager.loop_iteration(&mut health_details).await.unwrap();
+        assert_eq!(client.deposited_data().len(), 1);
+        assert!(client.rebroadcasts().is_empty());
+        let anchors = unconfirmed_anchors(&pool).await;
+        assert_eq!(anchors[0].mintlayer_tx_id.as_ref(), Some(&tx_id));
+        assert_eq!(anchors[0].resubmissions, 0);
+
+        // If the transaction stays unknown for the grace period, the root is re-deposited.
+        client.drop_tx(&tx_id);
+        manager.loop_iteration(&mut health_details).await.unwrap();
+        client.set_best_block_height(25);
+        manager.loop_iteration(&mut health_details).await.unwrap();
+        assert_eq!(client.deposited_data().len(), 2);
+        let anchors = unconfirmed_anchors(&pool).await;
+        assert_ne!(anchors[0].mintlayer_tx_id.as_ref(), Some(&tx_id));
+        assert_eq!(anchors[0].resubmissions, 1);
+    }
+
+    #[tokio::test]
+    async fn rebroadcasting_inactive_anchor() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let client = MockMintlayerClient::default();
+        let tx_id = create_anchor(&pool, &client).await;
+        let mut manager = create_manager(&pool, &client);
+        let mut health_details = MintlayerTxManagerHealthDetails::default();
+
+        client.set_tx_state(&tx_id, TxState::Inactive);
+        manager.loop_iteration(&mut health_details).await.unwrap();
+        assert_eq!(client.rebroadcasts(), [tx_id.clone()]);
+        assert_eq!(client.deposited_data().len(), 1);
+
+        client.confirm_tx(&tx_id, 10);
+        client.set_best_block_height(12);
+        manager.loop_iteration(&mut health_details).await.unwrap();
+        assert!(unconfirmed_anchors(&pool).await.is_empty());
+        assert_eq!(client.deposited_data().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn checking_balance() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let client = MockMintlayerClient::default();
+        client.set_balance(250_000_000_000);
+        let manager = create_manager(&pool, &client);
+        let mut health_details = MintlayerTxManagerHealthDetails::default();
+
+        manager.check_balance(&mut health_details).await.unwrap();
+        assert_eq!(health_details.balance.as_deref(), Some("2.50000000000"));
+    }
+
+    #[tokio::test]
+    async fn fatal_error_affects_health() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let client = MockMintlayerClient::default();
+        create_anchor(&pool, &client).await;
+        let manager = create_manager(&pool, &client);
+        client.push_error(MintlayerClientError::InvalidResponse {
+            method: "wallet_best_block",
+            message: "test".to_owned(),
+        });
+
+        let mut health_check = manager.health_check();
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let manager_task = tokio::spawn(manager.run(stop_receiver));
+        let health = health_check
+            .wait_for(|health| matches!(health.status(), HealthStatus::Affected))
+            .await;
+        let health_details = health.details().unwrap();
+        let error = health_details["last_error"].as_str().unwrap();
+        assert!(error.contains("wallet_best_block"), "{error}");
+
+        // The manager must not stop because of the error.
+        assert!(!manager_task.is_finished());
+        stop_sender.send_replace(true);
+        manager_task.await.unwrap().unwrap();
+    }
+}
@@ -0,0 +1,704 @@
+//! Verifier of Mintlayer anchors for external nodes.
+//!
+//! The main node anchors commit / prove / execute operations on Mintlayer: operation documents are uploaded to IPFS,
+//! and a Merkle root over their CIDs (together with the CID of a root document listing all leaves) is deposited
+//! on Mintlayer. The verifier uses the main node only to locate Mintlayer transactions for L1 batches; all anchored data
+//! is read from Mintlayer and IPFS and compared with L1 batches synced by this node.
+
+use std::{collections::VecDeque, fmt, sync::Arc, time::Duration};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use serde::Serialize;
+use tokio::sync::watch;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_ipfs_store::{IpfsStore, IpfsStoreError};
+use zksync_l1_contract_interface::i_executor::structures::StoredBatchInfo;
+use zksync_mintlayer_client::{MintlayerClientError, MintlayerInterface, TxState};
+use zksync_mintlayer_documents::{OperationDocument, ProveOperation};
+use zksync_shared_metrics::{CheckerComponent, EN_METRICS};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType,
+    api,
+    commitment::L1BatchWithMetadata,
+    mintlayer::{MintlayerAnchorCommitment, MintlayerAnchorLeaf, MintlayerAnchorPayload},
+    L1BatchNumber,
+};
+use zksync_web3_decl::{
+    client::{DynClient, L2},
+    error::{ClientRpcContext, EnrichedClientError, EnrichedClientResult},
+    namespaces::ZksNamespaceClient,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Number of recently verified anchors and operation documents cached by the verifier. Adjacent L1 batches
+/// are usually covered by the same anchors and documents, so a small cache avoids most repeated IPFS requests.
+const CACHE_CAPACITY: usize = 4;
+
+#[derive(Debug, thiserror::Error)]
+enum CheckError {
+    #[error("JSON-RPC error communicating with main node")]
+    MainNode(#[from] EnrichedClientError),
+    #[error("error communicating with Mintlayer wallet RPC")]
+    Mintlayer(#[from] MintlayerClientError),
+    /// Transient error fetching a document from IPFS. Other IPFS errors are converted to validation errors.
+    #[error("error fetching document from IPFS")]
+    Ipfs(#[source] IpfsStoreError),
+    /// Error that is caused by the main node or the anchored data being incorrect.
+    #[error("failed validating Mintlayer anchor")]
+    Validation(anyhow::Error),
+}
+
+impl CheckError {
+    fn is_transient(&self) -> bool {
+        match self {
+            Self::MainNode(err) => err.is_transient(),
+            Self::Mintlayer(err) => err.is_transient(),
+            Self::Ipfs(_) => true,
+            Self::Validation(_) => false,
+        }
+    }
+}
+
+impl From<IpfsStoreError> for CheckError {
+    fn from(err: IpfsStoreError) -> Self {
+        if err.is_transient() {
+            Self::Ipfs(err)
+        } else {
+            // Anchored documents must be available and match their CIDs.
+            Self::Validation(err.into())
+        }
+    }
+}
+
+/// Subset of the main node API used by the verifier.
+#[async_trait]
+trait MainNodeClient: fmt::Debug + Send + Sync {
+    async fn mintlayer_anchor(
+        &self,
+        number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<api::MintlayerBatchAnchor>>;
+}
+
+#[async_trait]
+impl MainNodeClient for Box<DynClient<L2>> {
+    async fn mintlayer_anchor(
+        &self,
+        number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<api::MintlayerBatchAnchor>> {
+        self.get_mintlayer_anchor(number)
+            .rpc_context("get_mintlayer_anchor")
+            .with_arg("number", &number)
+            .await
+    }
+}
+
+/// Handler of life cycle events emitted by [`MintlayerAnchorVerifier`].
+trait HandleAnchorVerifierEvent: fmt::Debug + Send + Sync {
+    fn initialize(&mut self);
+
+    fn set_first_batch_to_check(&mut self, first_batch_to_check: L1BatchNumber);
+
+    fn update_checked_batch(&mut self, last_checked_batch: L1BatchNumber);
+
+    fn report_inconsistent_batch(&mut self, number: L1BatchNumber, err: &anyhow::Error);
+}
+
+/// Health details reported by [`MintlayerAnchorVerifier`].
+#[derive(Debug, Default, Serialize)]
+struct AnchorVerifierDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    first_checked_batch: Option<L1BatchNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_checked_batch: Option<L1BatchNumber>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    inconsistent_batches: Vec<L1BatchNumber>,
+}
+
+impl AnchorVerifierDetails {
+    fn health(&self) -> Health {
+        // Inconsistent anchors mean that the data published by the main node cannot be trusted,
+        // so unlike for most other issues, the component is reported as unhealthy.
+        let status = if self.inconsistent_batches.is_empty() {
+            HealthStatus::Ready
+        } else {
+            HealthStatus::NotReady
+        };
+        Health::from(status).with_details(self)
+    }
+}
+
+/// Default [`HandleAnchorVerifierEvent`] implementation that reports the batch number as a metric and via health check details.
+#[derive(Debug)]
+struct AnchorVerifierHealthUpdater {
+    inner: HealthUpdater,
+    current_details: AnchorVerifierDetails,
+}
+
+impl AnchorVerifierHealthUpdater {
+    fn new() -> (ReactiveHealthCheck, Self) {
+        let (health_check, health_updater) = ReactiveHealthCheck::new("mintlayer_anchor_verifier");
+        let this = Self {
+            inner: health_updater,
+            current_details: AnchorVerifierDetails::default(),
+        };
+        (health_check, this)
+    }
+}
+
+impl HandleAnchorVerifierEvent for AnchorVerifierHealthUpdater {
+    fn initialize(&mut self) {
+        self.inner.update(self.current_details.health());
+    }
+
+    fn set_first_batch_to_check(&mut self, first_batch_to_check: L1BatchNumber) {
+        self.current_details.first_checked_batch = Some(first_batch_to_check);
+        self.inner.update(self.current_details.health());
+    }
+
+    fn update_checked_batch(&mut self, last_checked_batch: L1BatchNumber) {
+        tracing::info!("L1 batch #{last_checked_batch} is consistent with Mintlayer anchors");
+        EN_METRICS.last_correct_batch[&CheckerComponent::MintlayerAnchorVerifier]
+            .set(last_checked_batch.0.into());
+        self.current_details.last_checked_batch = Some(last_checked_batch);
+        self.inner.update(self.current_details.health());
+    }
+
+    fn report_inconsistent_batch(&mut self, number: L1BatchNumber, err: &anyhow::Error) {
+        tracing::error!("L1 batch #{number} is inconsistent with Mintlayer anchors: {err:?}");
+        self.current_details.inconsistent_batches.push(number);
+        self.inner.update(self.current_details.health());
+    }
+}
+
+/// Root document of an anchor, verified against the data deposited on Mintlayer.
+#[derive(Debug)]
+enum RootDocument {
+    /// Document listing all leaves of the anchor commitment.
+    Commitment(MintlayerAnchorCommitment),
+    /// Document of a legacy anchor listing CIDs of the covered operations.
+    Legacy(Vec<String>),
+}
+
+impl RootDocument {
+    fn parse(payload: &MintlayerAnchorPayload, bytes: &[u8]) -> anyhow::Result<Self> {
+        let Some(merkle_root) = payload.merkle_root else {
+            let cids =
+                serde_json::from_slice(bytes).context("failed parsing legacy root document")?;
+            return Ok(Self::Legacy(cids));
+        };
+
+        let leaves: Vec<MintlayerAnchorLeaf> =
+            serde_json::from_slice(bytes).context("failed parsing root document")?;
+        let commitment = MintlayerAnchorCommitment::new(leaves);
+        anyhow::ensure!(
+            commitment.merkle_root() == merkle_root,
+            "Merkle root of root document {} differs from the one deposited on Mintlayer; \
+             computed: {:?}, deposited: {merkle_root:?}",
+            payload.root_cid,
+            commitment.merkle_root()
+        );
+        Ok(Self::Commitment(commitment))
+    }
+
+    /// Finds the anchored leaf for the operation reported by the main node.
+    fn find_leaf(
+        &self,
+        reported: &MintlayerAnchorLeaf,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<MintlayerAnchorLeaf> {
+        match self {
+            Self::Commitment(commitment) => {
+                let (leaf, _) = commitment
+                    .find_operation(reported.action_type, l1_batch_number)
+                    .with_context(|| {
+                        format!(
+                            "anchor doesn't cover {} operation for L1 batch #{l1_batch_number}",
+                            reported.action_type
+                        )
+                    })?;
+                anyhow::ensure!(
+                    leaf == reported,
+                    "operation reported by main node differs from the anchored one; \
+                     reported: {reported:?}, anchored: {leaf:?}"
+                );
+                Ok(leaf.clone())
+            }
+            Self::Legacy(cids) => {
+                // Legacy anchors don't commit to action types or batch ranges; these are checked against
+                // the operation document instead.
+                anyhow::ensure!(
+                    cids.contains(&reported.cid),
+                    "legacy anchor doesn't cover document {}",
+                    reported.cid
+                );
+                Ok(reported.clone())
+            }
+        }
+    }
+}
+
+/// Bounded cache of recently fetched items keyed by a string (a transaction ID or CID).
+#[derive(Debug)]
+struct RecentCache<T> {
+    items: VecDeque<(String, Arc<T>)>,
+}
+
+impl<T> Default for RecentCache<T> {
+    fn default() -> Self {
+        Self {
+            items: VecDeque::with_capacity(CACHE_CAPACITY),
+        }
+    }
+}
+
+impl<T> RecentCache<T> {
+    fn get(&self, key: &str) -> Option<Arc<T>> {
+        self.items
+            .iter()
+            .find_map(|(item_key, item)| (item_key == key).then(|| item.clone()))
+    }
+
+    fn insert(&mut self, key: String, item: Arc<T>) {
+        if self.items.len() >= CACHE_CAPACITY {
+            self.items.pop_front();
+        }
+        self.items.push_back((key, item));
+    }
+}
+
+/// Checks that the local L1 batch matches the one from an anchored operation document.
+fn verify_l1_batch(
+    local: &L1BatchWithMetadata,
+    anchored: &L1BatchWithMetadata,
+) -> anyhow::Result<()> {
+    let local_info = StoredBatchInfo::from(local);
+    let anchored_info = StoredBatchInfo::from(anchored);
+    let fields = [
+        (
+            "state root",
+            local_info.batch_hash,
+            anchored_info.batch_hash,
+        ),
+        (
+            "L2-to-L1 logs tree root",
+            local_info.l2_logs_tree_root,
+            anchored_info.l2_logs_tree_root,
+        ),
+        (
+            "commitment",
+            local_info.commitment,
+            anchored_info.commitment,
+        ),
+        (
+            "priority operations hash",
+            local_info.priority_operations_hash,
+            anchored_info.priority_operations_hash,
+        ),
+    ];
+    for (name, local_value, anchored_value) in fields {
+        anyhow::ensure!(
+            local_value == anchored_value,
+            "{name} differs; local: {local_value:?}, anchored: {anchored_value:?}"
+        );
+    }
+
+    // The stored batch hash covers the fields above, as well as the remaining fields of the stored batch info.
+    let (local_hash, anchored_hash) = (local_info.hash(), anchored_info.hash());
+    anyhow::ensure!(
+        local_hash == anchored_hash,
+        "stored batch hash differs; local: {local_hash:?} ({local_info:?}), \
+         anchored: {anchored_hash:?} ({anchored_info:?})"
+    );
+    Ok(())
+}
+
+/// Checks proofs in an anchored prove document for the local L1 batch.
+fn verify_proofs(operation: &ProveOperation, local: &L1BatchWithMetadata) -> anyhow::Result<()> {
+    if operation.proofs.is_empty() {
+        // The operation was sent without proofs (e.g., if proofs are not verified on L1).
+        return Ok(());
+    }
+    anyhow::ensure!(
+        operation.proofs.len() == operation.l1_batches.len(),
+        "prove document has {} proofs for {} L1 batches",
+        operation.proofs.len(),
+        operation.l1_batches.len()
+    );
+
+    let number = local.header.number;
+    let index = operation
+        .l1_batches
+        .iter()
+        .position(|l1_batch| l1_batch.header.number == number)
+        .with_context(|| format!("prove document doesn't contain L1 batch #{number}"))?;
+    let proof_version = operation.proofs[index].protocol_version;
+    if let Some(local_version) = local.header.protocol_version {
+        anyhow::ensure!(
+            proof_version.minor == local_version,
+            "proof for L1 batch #{number} has protocol version {proof_version}, while the batch has {local_version:?}"
+        );
+    }
+    Ok(())
+}
+
+/// Component verifying that data anchored on Mintlayer matches L1 batches synced by the external node.
+///
+/// For each L1 batch, the verifier waits until the main node reports confirmed anchors for all of its
+/// operations (commit, prove and execute). Then, for each operation it:
+///
+/// 1. Reads the payload deposited by the anchor transaction from Mintlayer and checks that the transaction
+///    has enough confirmations.
+/// 2. Fetches the root document from IPFS and checks that it matches the deposited Merkle root.
+/// 3. Fetches the operation document from IPFS and compares the batch in it with the local batch.
+///
+/// Mismatches are reported via the health check. Progress is not persisted; after a restart, the verifier
+/// rechecks a bounded number of recent batches.
+#[derive(Debug)]
+pub struct MintlayerAnchorVerifier {
+    main_node_client: Box<dyn MainNodeClient>,
+    mintlayer_client: Box<dyn MintlayerInterface>,
+    ipfs_store: Arc<dyn IpfsStore>,
+    /// Number of Mintlayer blocks after which an anchor transaction is considered confirmed.
+    confirmations: u64,
+    /// How many past batches to check when starting.
+    max_batches_to_recheck: u32,
+    sleep_interval: Duration,
+    pool: ConnectionPool<Core>,
+    event_handler: Box<dyn HandleAnchorVerifierEvent>,
+    health_check: ReactiveHealthCheck,
+    anchors: RecentCache<RootDocument>,
+    documents: RecentCache<OperationDocument>,
+}
+
+impl MintlayerAnchorVerifier {
+    const DEFAULT_SLEEP_INTERVAL: Duration = Duration::from_secs(10);
+
+    pub fn new(
+        main_node_client: Box<DynClient<L2>>,
+        mintlayer_client: Box<dyn MintlayerInterface>,
+        ipfs_store: Arc<dyn IpfsStore>,
+        confirmations: u32,
+        max_batches_to_recheck: u32,
+        pool: ConnectionPool<Core>,
+    ) -> Self {
+        let (health_check, health_updater) = AnchorVerifierHealthUpdater::new();
+        Self {
+            main_node_client: Box::new(main_node_client.for_component("mintlayer_anchor_verifier")),
+            mintlayer_client,
+            ipfs_store,
+            confirmations: confirmations.into(),
+            max_batches_to_recheck,
+            sleep_interval: Self::DEFAULT_SLEEP_INTERVAL,
+            pool,
+            event_handler: Box::new(health_updater),
+            health_check,
+            anchors: RecentCache::default(),
+            documents: RecentCache::default(),
+        }
+    }
+
+    /// Returns health check associated with this verifier.
+    pub fn health_check(&self) -> &ReactiveHealthCheck {
+        &self.health_check
+    }
+
+    /// Checks all operations of the specified L1 batch. Returns `Ok(false)` if some operations
+    /// are not anchored or confirmed yet.
+    async fn check_l1_batch(&mut self, local: &L1BatchWithMetadata) -> Result<bool, CheckError> {
+        let number = local.header.number;
+        let Some(batch_anchor) = self.main_node_client.mintlayer_anchor(number).await? else {
+            return Ok(false);
+        };
+
+        for action_type in [
+            AggregatedActionType::Commit,
+            AggregatedActionType::PublishProofOnchain,
+            AggregatedActionType::Execute,
+        ] {
+            let operation = batch_anchor.operations.iter().find(|op| {
+                // Revoked operations are superseded by operations queued after the revert.
+                let is_revoked = op
+                    .anchor
+                    .as_ref()
+                    .is_some_and(|anchor| anchor.status == api::MintlayerAnchorStatus::Revoked);
+                op.operation.action_type == action_type && !is_revoked
+            });
+            let Some(operation) = operation else {
+                return Ok(false);
+            };
+            let Some(anchor) = &operation.anchor else {
+                return Ok(false);
+            };
+            if anchor.status != api::MintlayerAnchorStatus::Confirmed {
+                return Ok(false);
+            }
+
+            let tx_id = anchor
+                .mintlayer_tx_id
+                .as_deref()
+                .with_context(|| {
+                    format!("main node reported confirmed {action_type} anchor without a Mintlayer transaction")
+                })
+                .map_err(CheckError::Validation)?;
+            let Some(root_document) = self.verify_anchor(tx_id, anchor).await? else {
+                return Ok(false);
+            };
+            let leaf = root_document
+                .find_leaf(&operation.operation, number)
+                .map_err(CheckError::Validation)?;
+            self.verify_operation(&leaf, local).await?;
+            tracing::debug!(
+                "Verified {action_type} operation for L1 batch #{number} anchored in Mintlayer tx {tx_id}"
+            );
+        }
+        Ok(true)
+    }
+
+    /// Verifies an anchor deposited in the specified Mintlayer transaction and returns its root document.
+    /// Returns `Ok(None)` if the transaction doesn't have enough confirmations according to the Mintlayer wallet.
+    async fn verify_anchor(
+        &mut self,
+        tx_id: &str,
+        reported: &api::MintlayerAnchorInfo,
+    ) -> Result<Option<Arc<RootDocument>>, CheckError> {
+        if let Some(root_document) = self.anchors.get(tx_id) {
+            return Ok(Some(root_document));
+        }
+
+        let tx = self
+            .mintlayer_client
+            .transaction_get(tx_id)
+            .await?
+            .with_context(|| format!("Mintlayer transaction {tx_id} is not known to the wallet"))
+            .map_err(CheckError::Validation)?;
+        let block_height = match tx.state {
+            TxState::Confirmed { block_height } => block_height,
+            TxState::InMempool | TxState::Inactive => return Ok(None),
+            state => {
+                let err = anyhow::anyhow!(
+                    "Mintlayer transaction {tx_id} reported as confirmed by main node has state {state:?}"
+                );
+                return Err(CheckError::Validation(err));
+            }
+        };
+        let best_block = self.mintlayer_client.wallet_best_block().await?;
+        if best_block.height + 1 < block_height + self.confirmations {
+            return Ok(None);
+        }
+
+        let payload = tx
+            .deposited_data
+            .with_context(|| format!("Mintlayer transaction {tx_id} doesn't deposit data"))
+            .and_then(|data| MintlayerAnchorPayload::decode(&data))
+            .map_err(CheckError::Validation)?;
+        if payload.root_cid != reported.root_cid || payload.merkle_root != reported.merkle_root {
+            let err = anyhow::anyhow!(
+                "anchor reported by main node differs from the one deposited in Mintlayer transaction {tx_id}; \
+                 reported: root CID {}, Merkle root {:?}, deposited: {payload:?}",
+                reported.root_cid,
+                reported.merkle_root
+            );
+            return Err(CheckError::Validation(err));
+        }
+
+        let bytes = self.ipfs_store.get(&payload.root_cid).await?;
+        let root_document =
+            RootDocument::parse(&payload, &bytes).map_err(CheckError::Validation)?;
+        let root_document = Arc::new(root_document);
+        self.anchors.insert(tx_id.to_owned(), root_document.clone());
+        Ok(Some(root_document))
+    }
+
+    /// Verifies an anchored operation document against the local L1 batch.
+    async fn verify_operation(
+        &mut self,
+        leaf: &MintlayerAnchorLeaf,
+        local: &L1BatchWithMetadata,
+    ) -> Result<(), CheckError> {
+        let document = if let Some(document) = self.documents.get(&leaf.cid) {
+            document
+        } else {
+            let bytes = self.ipfs_store.get(&leaf.cid).await?;
+            let document = OperationDocument::decode(&bytes, leaf.action_type)
+                .with_context(|| format!("failed decoding operation document {}", leaf.cid))
+                .map_err(CheckError::Validation)?;
+            let document = Arc::new(document);
+            self.documents.insert(leaf.cid.clone(), document.clone());
+            document
+        };
+
+        Self::verify_document(&document, leaf, local)
+            .with_context(|| format!("operation document {} is inconsistent", leaf.cid))
+            .map_err(CheckError::Validation)
+    }
+
+    fn verify_document(
+        document: &OperationDocument,
+        leaf: &MintlayerAnchorLeaf,
+        local: &L1BatchWithMetadata,
+    ) -> anyhow::Result<()> {
+        let l1_batches = document.l1_batches();
+        let (Some(first), Some(last)) = (l1_batches.first(), l1_batches.last()) else {
+            anyhow::bail!("document contains no L1 batches");
+        };
+        let document_range = first.header.number..=last.header.number;
+        anyhow::ensure!(
+            document_range == leaf.l1_batch_range,
+            "document covers L1 batches {document_range:?}, while the anchor claims {:?}",
+            leaf.l1_batch_range
+        );
+
+        let number = local.header.number;
+        let anchored = l1_batches
+            .iter()
+            .find(|l1_batch| l1_batch.header.number == number)
+            .with_context(|| format!("document doesn't contain L1 batch #{number}"))?;
+        verify_l1_batch(local, anchored)?;
+        if let OperationDocument::Prove(operation) = document {
+            verify_proofs(operation, local)?;
+        }
+        Ok(())
+    }
+
+    /// Loads the local L1 batch with the specified number. Returns `Ok(None)` if the batch doesn't have
+    /// all metadata necessary for the check yet.
+    async fn load_l1_batch(
+        &self,
+        number: L1BatchNumber,
+    ) -> anyhow::Result<Option<L1BatchWithMetadata>> {
+        let mut storage = self
+            .pool
+            .connection_tagged("mintlayer_anchor_verifier")
+            .await?;
+        let Some(l1_batch) = storage.blocks_dal().get_l1_batch_metadata(number).await? else {
+            return Ok(None);
+        };
+        // The commitment is computed by the commitment generator, so it may be missing for recent batches.
+        let is_pre_boojum = l1_batch
+            .header
+            .protocol_version
+            .map_or(true, |version| version.is_pre_boojum());
+        let metadata = &l1_batch.metadata;
+        if !is_pre_boojum
+            && (metadata.bootloader_initial_content_commitment.is_none()
+                || metadata.events_queue_commitment.is_none())
+        {
+            return Ok(None);
+        }
+        Ok(Some(l1_batch))
+    }
+
+    async fn first_batch_to_check(&self) -> anyhow::Result<Option<L1BatchNumber>> {
+        let mut storage = self
+            .pool
+            .connection_tagged("mintlayer_anchor_verifier")
+            .await?;
+        let Some(earliest_l1_batch) = storage
+            .blocks_dal()
+            .get_earliest_l1_batch_number_with_metadata()
+            .await?
+        else {
+            return Ok(None);
+        };
+        let last_executed_l1_batch = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_executed_on_eth()
+            .await?
+            .unwrap_or(earliest_l1_batch);
+
+        let first_batch_to_check = last_executed_l1_batch
+            .0
+            .saturating_sub(self.max_batches_to_recheck);
+        // The genesis batch is never anchored.
+        Ok(Some(
+            L1BatchNumber(first_batch_to_check)
+                .max(earliest_l1_batch)
+                .max(L1BatchNumber(1)),
+        ))
+    }
+
+    pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        tracing::info!(
+            "Starting Mintlayer anchor verifier with sleep interval: {:?}, required confirmations: {}, \
+             max historic L1 batches to check: {}",
+            self.sleep_interval,
+            self.confirmations,
+            self.max_batches_to_recheck
+        );
+        self.event_handler.initialize();
+
+        // It doesn't make sense to start the verifier until we have at least one L1 batch with metadata.
+        let first_batch_to_check = loop {
+            if *stop_receiver.borrow() {
+                tracing::info!("Stop signal received, Mintlayer anchor verifier is shutting down");
+                return Ok(());
+            }
+            if let Some(number) = self.first_batch_to_check().await? {
+                break number;
+            }
+            tracing::debug!(
+                "No L1 batches with metadata are present in DB; trying again in {:?}",
+                self.sleep_interval
+            );
+            tokio::time::timeout(self.sleep_interval, stop_receiver.changed())
+                .await
+                .ok();
+        };
+        tracing::info!("Starting Mintlayer anchor checks from L1 batch #{first_batch_to_check}");
+        self.event_handler
+            .set_first_batch_to_check(first_batch_to_check);
+
+        let mut batch_number = first_batch_to_check;
+        while !*stop_receiver.borrow_and_update() {
+            let result = match self.load_l1_batch(batch_number).await? {
+                Some(local) => self.check_l1_batch(&local).await,
+                None => Ok(false),
+            };
+
+            match result {
+                Ok(true) => {
+                    self.event_handler.update_checked_batch(batch_number);
+                    batch_number += 1;
+                    continue;
+                }
+                Ok(false) => {
+                    tracing::debug!(
+                        "L1 batch #{batch_number} is not synced or anchored yet; will retry after a delay"
+                    );
+                }
+                Err(CheckError::Validation(err)) => {
+                    self.event_handler
+                        .report_inconsistent_batch(batch_number, &err);
+                    batch_number += 1; // We don't want to infinitely loop failing the check on the same batch
+                    continue;
+                }
+                Err(err) if err.is_transient() => {
+                    tracing::warn!(
+                        "Transient error while verifying Mintlayer anchors for L1 batch #{batch_number}; \
+                         will retry after a delay: {:#}",
+                        anyhow::Error::from(err)
+                    );
+                }
+                Err(err) => {
+                    let context =
+                        format!("failed verifying Mintlayer anchors for L1 batch #{batch_number}");
+                    return Err(anyhow::Error::from(err).context(context));
+                }
+            }
+
+            if tokio::time::timeout(self.sleep_interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+
+        tracing::info!("Stop signal received, Mintlayer anchor verifier is shutting down");
+        Ok(())
+    }
+}
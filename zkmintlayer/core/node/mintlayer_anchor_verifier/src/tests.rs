@@ -0,0 +1,425 @@
+//! Tests for the Mintlayer anchor verifier.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use assert_matches::assert_matches;
+use tokio::sync::mpsc;
+use zksync_ipfs_store::MockIpfsStore;
+use zksync_mintlayer_client::{clients::MockMintlayerClient, DepositOptions};
+use zksync_mintlayer_documents::{CommitOperation, Compression, ExecuteOperation};
+use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
+use zksync_node_test_utils::{
+    create_l1_batch, create_l1_batch_metadata, l1_batch_metadata_to_commitment_artifacts,
+};
+use zksync_types::{pubdata_da::PubdataDA, H256};
+
+use super::*;
+
+const CONFIRMATIONS: u32 = 2;
+
+#[derive(Debug, Clone, Default)]
+struct MockMainNodeClient(Arc<Mutex<HashMap<L1BatchNumber, api::MintlayerBatchAnchor>>>);
+
+#[async_trait]
+impl MainNodeClient for MockMainNodeClient {
+    async fn mintlayer_anchor(
+        &self,
+        number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<api::MintlayerBatchAnchor>> {
+        Ok(self.0.lock().unwrap().get(&number).cloned())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum VerifierEvent {
+    Checked(L1BatchNumber),
+    Inconsistent(L1BatchNumber),
+}
+
+impl HandleAnchorVerifierEvent for mpsc::UnboundedSender<VerifierEvent> {
+    fn initialize(&mut self) {
+        // Do nothing
+    }
+
+    fn set_first_batch_to_check(&mut self, _first_batch_to_check: L1BatchNumber) {
+        // Do nothing
+    }
+
+    fn update_checked_batch(&mut self, last_checked_batch: L1BatchNumber) {
+        self.send(VerifierEvent::Checked(last_checked_batch)).ok();
+    }
+
+    fn report_inconsistent_batch(&mut self, number: L1BatchNumber, _err: &anyhow::Error) {
+        self.send(VerifierEvent::Inconsistent(number)).ok();
+    }
+}
+
+/// Emulates anchoring on the main node.
+#[derive(Debug, Default)]
+struct TestAnchorer {
+    ipfs_store: MockIpfsStore,
+    mintlayer_client: MockMintlayerClient,
+    main_node_client: MockMainNodeClient,
+}
+
+impl TestAnchorer {
+    async fn upload(&self, document: &OperationDocument) -> MintlayerAnchorLeaf {
+        let contents = document.encode(Compression::Zstd).unwrap();
+        let cid = self.ipfs_store.put("operation", contents).await.unwrap();
+        let l1_batches = document.l1_batches();
+        MintlayerAnchorLeaf {
+            action_type: document.action_type(),
+            l1_batch_range: l1_batches[0].header.number
+                ..=l1_batches[l1_batches.len() - 1].header.number,
+            cid,
+        }
+    }
+
+    /// Uploads commit, prove and execute documents for each of the L1 batches.
+    async fn upload_all(&self, l1_batches: &[L1BatchWithMetadata]) -> Vec<MintlayerAnchorLeaf> {
+        let mut leaves = vec![];
+        for l1_batch in l1_batches {
+            let documents = [
+                OperationDocument::Commit(CommitOperation {
+                    prev_l1_batch: l1_batch.clone(),
+                    l1_batches: vec![l1_batch.clone()],
+                    pubdata_da: PubdataDA::Calldata,
+                }),
+                OperationDocument::Prove(ProveOperation {
+                    prev_l1_batch: l1_batch.clone(),
+                    l1_batches: vec![l1_batch.clone()],
+                    proofs: vec![],
+                }),
+                OperationDocument::Execute(ExecuteOperation {
+                    l1_batches: vec![l1_batch.clone()],
+                }),
+            ];
+            for document in &documents {
+                leaves.push(self.upload(document).await);
+            }
+        }
+        leaves
+    }
+
+    /// Deposits the payload on Mintlayer and reports the anchor for all leaves via the main node API.
+    async fn deposit(
+        &self,
+        payload: &MintlayerAnchorPayload,
+        leaves: &[MintlayerAnchorLeaf],
+        block_height: u64,
+    ) -> String {
+        let tx = self
+            .mintlayer_client
+            .address_deposit_data(&payload.encode(), DepositOptions::default())
+            .await
+            .unwrap();
+        self.mintlayer_client.confirm_tx(&tx.tx_id, block_height);
+
+        let anchor = api::MintlayerAnchorInfo {
+            root_cid: payload.root_cid.clone(),
+            merkle_root: payload.merkle_root,
+            mintlayer_tx_id: Some(tx.tx_id.clone()),
+            status: api::MintlayerAnchorStatus::Confirmed,
+            included_at_block: Some(block_height),
+            confirmed_at: None,
+            revocation_tx_id: None,
+            proof: None,
+        };
+        let mut anchors = self.main_node_client.0.lock().unwrap();
+        for leaf in leaves {
+            let (start, end) = (leaf.l1_batch_range.start().0, leaf.l1_batch_range.end().0);
+            for number in start..=end {
+                let number = L1BatchNumber(number);
+                let batch_anchor =
+                    anchors
+                        .entry(number)
+                        .or_insert_with(|| api::MintlayerBatchAnchor {
+                            l1_batch_number: number,
+                            operations: vec![],
+                        });
+                batch_anchor.operations.push(api::MintlayerOperationInfo {
+                    operation: leaf.clone(),
+                    anchor: Some(anchor.clone()),
+                });
+            }
+        }
+        tx.tx_id
+    }
+
+    async fn anchor(&self, leaves: Vec<MintlayerAnchorLeaf>, block_height: u64) -> String {
+        let commitment = MintlayerAnchorCommitment::new(leaves);
+        let root_document = serde_json::to_vec(commitment.leaves()).unwrap();
+        let root_cid = self.ipfs_store.put("root", root_document).await.unwrap();
+        let payload = MintlayerAnchorPayload {
+            merkle_root: Some(commitment.merkle_root()),
+            root_cid,
+        };
+        self.deposit(&payload, commitment.leaves(), block_height)
+            .await
+    }
+
+    fn create_verifier(
+        &self,
+        pool: ConnectionPool<Core>,
+    ) -> (
+        MintlayerAnchorVerifier,
+        mpsc::UnboundedReceiver<VerifierEvent>,
+    ) {
+        let (events_sender, events_receiver) = mpsc::unbounded_channel();
+        let verifier = MintlayerAnchorVerifier {
+            main_node_client: Box::new(self.main_node_client.clone()),
+            mintlayer_client: Box::new(self.mintlayer_client.clone()),
+            ipfs_store: Arc::new(self.ipfs_store.clone()),
+            confirmations: CONFIRMATIONS.into(),
+            max_batches_to_recheck: 100,
+            sleep_interval: Duration::from_millis(10),
+            pool,
+            event_handler: Box::new(events_sender),
+            health_check: ReactiveHealthCheck::new("mintlayer_anchor_verifier").0,
+            anchors: RecentCache::default(),
+            documents: RecentCache::default(),
+        };
+        (verifier, events_receiver)
+    }
+}
+
+async fn prepare_l1_batches(pool: &ConnectionPool<Core>, count: u32) -> Vec<L1BatchWithMetadata> {
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+
+    let mut l1_batches = vec![];
+    for number in 1..=count {
+        let header = create_l1_batch(number);
+        let metadata = create_l1_batch_metadata(number);
+        storage
+            .blocks_dal()
+            .insert_mock_l1_batch(&header)
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .save_l1_batch_tree_data(header.number, &metadata.tree_data())
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .save_l1_batch_commitment_artifacts(
+                header.number,
+                &l1_batch_metadata_to_commitment_artifacts(&metadata),
+            )
+            .await
+            .unwrap();
+
+        let l1_batch = storage
+            .blocks_dal()
+            .get_l1_batch_metadata(header.number)
+            .await
+            .unwrap()
+            .expect("no L1 batch metadata");
+        l1_batches.push(l1_batch);
+    }
+    l1_batches
+}
+
+async fn run_verifier(
+    verifier: MintlayerAnchorVerifier,
+    mut events: mpsc::UnboundedReceiver<VerifierEvent>,
+    expected_event_count: usize,
+) -> Vec<VerifierEvent> {
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let verifier_task = tokio::spawn(verifier.run(stop_receiver));
+
+    let mut received_events = vec![];
+    while received_events.len() < expected_event_count {
+        received_events.push(events.recv().await.unwrap());
+    }
+    stop_sender.send_replace(true);
+    verifier_task.await.unwrap().unwrap();
+    received_events
+}
+
+#[tokio::test]
+async fn verifying_consistent_anchors() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let l1_batches = prepare_l1_batches(&pool, 3).await;
+    let anchorer = TestAnchorer::default();
+    let leaves = anchorer.upload_all(&l1_batches).await;
+    // Split leaves into 2 anchors, so that operations for L1 batch #2 are covered by different anchors.
+    anchorer.anchor(leaves[..4].to_vec(), 1).await;
+    anchorer.anchor(leaves[4..].to_vec(), 2).await;
+    anchorer.mintlayer_client.set_best_block_height(10);
+
+    let (verifier, events) = anchorer.create_verifier(pool);
+    let events = run_verifier(verifier, events, 3).await;
+    assert_eq!(
+        events,
+        (1..=3)
+            .map(|number| VerifierEvent::Checked(L1BatchNumber(number)))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[tokio::test]
+async fn waiting_for_anchor_confirmations() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let l1_batches = prepare_l1_batches(&pool, 1).await;
+    let anchorer = TestAnchorer::default();
+    let (mut verifier, _events) = anchorer.create_verifier(pool);
+
+    // No anchors are reported by the main node yet.
+    assert!(!verifier.check_l1_batch(&l1_batches[0]).await.unwrap());
+
+    let leaves = anchorer.upload_all(&l1_batches).await;
+    anchorer.anchor(leaves, 5).await;
+    // The anchor transaction has a single confirmation.
+    assert!(!verifier.check_l1_batch(&l1_batches[0]).await.unwrap());
+
+    anchorer.mintlayer_client.set_best_block_height(6);
+    assert!(verifier.check_l1_batch(&l1_batches[0]).await.unwrap());
+}
+
+#[tokio::test]
+async fn detecting_document_mismatch() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let l1_batches = prepare_l1_batches(&pool, 3).await;
+    let anchorer = TestAnchorer::default();
+
+    let mut anchored_l1_batches = l1_batches.clone();
+    anchored_l1_batches[1].metadata.root_hash = H256::repeat_byte(0xff);
+    let leaves = anchorer.upload_all(&anchored_l1_batches).await;
+    anchorer.anchor(leaves, 1).await;
+    anchorer.mintlayer_client.set_best_block_height(10);
+
+    let (verifier, events) = anchorer.create_verifier(pool);
+    let events = run_verifier(verifier, events, 3).await;
+    assert_eq!(
+        events,
+        [
+            VerifierEvent::Checked(L1BatchNumber(1)),
+            VerifierEvent::Inconsistent(L1BatchNumber(2)),
+            VerifierEvent::Checked(L1BatchNumber(3)),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn detecting_forged_anchor() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let l1_batches = prepare_l1_batches(&pool, 1).await;
+    let anchorer = TestAnchorer::default();
+    let leaves = anchorer.upload_all(&l1_batches).await;
+    anchorer.anchor(leaves.clone(), 1).await;
+    anchorer.mintlayer_client.set_best_block_height(10);
+
+    // Make the main node report a root document that is not deposited on Mintlayer.
+    let forged_commitment = MintlayerAnchorCommitment::new(leaves[..1].to_vec());
+    let forged_cid = anchorer
+        .ipfs_store
+        .put(
+            "forged",
+            serde_json::to_vec(forged_commitment.leaves()).unwrap(),
+        )
+        .await
+        .unwrap();
+    let mut anchors = anchorer.main_node_client.0.lock().unwrap();
+    for operation in &mut anchors.get_mut(&L1BatchNumber(1)).unwrap().operations {
+        operation.anchor.as_mut().unwrap().root_cid = forged_cid.clone();
+    }
+    drop(anchors);
+
+    let (mut verifier, _events) = anchorer.create_verifier(pool);
+    let err = verifier.check_l1_batch(&l1_batches[0]).await.unwrap_err();
+    assert_matches!(
+        err,
+        CheckError::Validation(err) if err.to_string().contains("differs from the one deposited")
+    );
+}
+
+#[tokio::test]
+async fn detecting_root_document_mismatch() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let l1_batches = prepare_l1_batches(&pool, 1).await;
+    let anchorer = TestAnchorer::default();
+    let leaves = anchorer.upload_all(&l1_batches).await;
+
+    // Deposit a Merkle root that doesn't match the root document.
+    let root_cid = anchorer
+        .ipfs_store
+        .put("root", serde_json::to_vec(&leaves).unwrap())
+        .await
+        .unwrap();
+    let payload = MintlayerAnchorPayload {
+        merkle_root: Some(H256::repeat_byte(1)),
+        root_cid,
+    };
+    anchorer.deposit(&payload, &leaves, 1).await;
+    anchorer.mintlayer_client.set_best_block_height(10);
+
+    let (mut verifier, _events) = anchorer.create_verifier(pool);
+    let err = verifier.check_l1_batch(&l1_batches[0]).await.unwrap_err();
+    assert_matches!(
+        err,
+        CheckError::Validation(err) if err.to_string().contains("Merkle root of root document")
+    );
+}
+
+#[tokio::test]
+async fn verifying_legacy_anchor() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let l1_batches = prepare_l1_batches(&pool, 1).await;
+    let anchorer = TestAnchorer::default();
+    let l1_batch = &l1_batches[0];
+
+    // Legacy documents are JSON-serialized tuples.
+    let documents = [
+        (
+            AggregatedActionType::Commit,
+            serde_json::to_vec(&(l1_batch, [l1_batch], PubdataDA::Calldata)).unwrap(),
+        ),
+        (
+            AggregatedActionType::PublishProofOnchain,
+            serde_json::to_vec(&(l1_batch, [l1_batch], Vec::<()>::new())).unwrap(),
+        ),
+        (
+            AggregatedActionType::Execute,
+            serde_json::to_vec(&[l1_batch]).unwrap(),
+        ),
+    ];
+    let mut leaves = vec![];
+    for (action_type, contents) in documents {
+        let cid = anchorer.ipfs_store.put("legacy", contents).await.unwrap();
+        leaves.push(MintlayerAnchorLeaf {
+            action_type,
+            l1_batch_range: L1BatchNumber(1)..=L1BatchNumber(1),
+            cid,
+        });
+    }
+    let cids: Vec<_> = leaves.iter().map(|leaf| leaf.cid.clone()).collect();
+    let root_cid = anchorer
+        .ipfs_store
+        .put("root", serde_json::to_vec(&cids).unwrap())
+        .await
+        .unwrap();
+    let payload = MintlayerAnchorPayload {
+        merkle_root: None,
+        root_cid,
+    };
+    anchorer.deposit(&payload, &leaves, 1).await;
+    anchorer.mintlayer_client.set_best_block_height(10);
+
+    let (mut verifier, _events) = anchorer.create_verifier(pool);
+    assert!(verifier.check_l1_batch(l1_batch).await.unwrap());
+}
+
+#[test]
+fn health_of_inconsistent_verifier() {
+    let mut details = AnchorVerifierDetails::default();
+    assert_matches!(details.health().status(), HealthStatus::Ready);
+    details.inconsistent_batches.push(L1BatchNumber(1));
+    assert_matches!(details.health().status(), HealthStatus::NotReady);
+}
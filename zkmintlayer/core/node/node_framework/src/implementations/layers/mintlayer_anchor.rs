@@ -0,0 +1,159 @@
+use anyhow::Context;
+use zksync_circuit_breaker::mintlayer_balance::MintlayerBalanceChecker;
+use zksync_config::configs::{eth_sender::EthConfig, MintlayerConfig, MintlayerSecrets};
+use zksync_ipfs_store::create_ipfs_store;
+use zksync_mintlayer_anchor::{MintlayerAnchorer, MintlayerTxManager};
+use zksync_mintlayer_client::clients::HttpMintlayerClient;
+
+use crate::{
+    implementations::resources::{
+        circuit_breakers::CircuitBreakersResource,
+        healthcheck::AppHealthCheckResource,
+        object_store::ObjectStoreResource,
+        pools::{MasterPool, PoolResource},
+    },
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for anchoring aggregated L1 operations on Mintlayer.
+///
+/// Responsible for initialization and running of [`MintlayerAnchorer`], that uploads `eth_txs`
+/// saved by the eth sender to IPFS and periodically deposits their CIDs on Mintlayer, and
+/// [`MintlayerTxManager`], that tracks confirmations of the deposit transactions. If the minimum account balance
+/// is configured, also adds a circuit breaker tripping once the balance drops below it.
+///
+/// ## Requests resources
+///
+/// - `PoolResource<MasterPool>`
+/// - `ObjectStoreResource`
+/// - `AppHealthCheckResource` (adds a health check)
+/// - `CircuitBreakersResource` (adds a circuit breaker)
+///
+/// ## Adds tasks
+///
+/// - `MintlayerAnchorer`
+/// - `MintlayerTxManager`
+#[derive(Debug)]
+pub struct MintlayerAnchorLayer {
+    config: MintlayerConfig,
+    secrets: MintlayerSecrets,
+    eth_sender_config: EthConfig,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub master_pool: PoolResource<MasterPool>,
+    pub object_store: ObjectStoreResource,
+    #[context(default)]
+    pub app_health: AppHealthCheckResource,
+    #[context(default)]
+    pub circuit_breakers: CircuitBreakersResource,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    #[context(task)]
+    pub mintlayer_anchorer: MintlayerAnchorer,
+    #[context(task)]
+    pub mintlayer_tx_manager: MintlayerTxManager,
+}
+
+impl MintlayerAnchorLayer {
+    pub fn new(
+        config: MintlayerConfig,
+        secrets: MintlayerSecrets,
+        eth_sender_config: EthConfig,
+    ) -> Self {
+        Self {
+            config,
+            secrets,
+            eth_sender_config,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for MintlayerAnchorLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "mintlayer_anchor_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let master_pool = input.master_pool.get_singleton().await?;
+        let sender_config = self.eth_sender_config.sender.context("sender")?;
+
+        let mintlayer_anchorer = MintlayerAnchorer::new(
+            master_pool.clone(),
+            input.object_store.0,
+            sender_config.pubdata_sending_mode.into(),
+            create_ipfs_store(&self.config, &self.secrets)?,
+            Box::new(HttpMintlayerClient::new(&self.config, &self.secrets)?),
+            sender_config.aggregate_tx_poll_period(),
+            &self.config,
+        )?;
+
+        input
+            .app_health
+            .0
+            .insert_component(mintlayer_anchorer.health_check())
+            .map_err(WiringError::internal)?;
+
+        let mintlayer_tx_manager = MintlayerTxManager::new(
+            master_pool,
+            Box::new(HttpMintlayerClient::new(&self.config, &self.secrets)?),
+            sender_config.tx_poll_period(),
+            &self.config,
+        )?;
+        input
+            .app_health
+            .0
+            .insert_component(mintlayer_tx_manager.health_check())
+            .map_err(WiringError::internal)?;
+
+        if let Some(min_balance) = self.config.min_balance_atoms {
+            input
+                .circuit_breakers
+                .breakers
+                .insert(Box::new(MintlayerBalanceChecker {
+                    client: Box::new(HttpMintlayerClient::new(&self.config, &self.secrets)?),
+                    min_balance: min_balance.into(),
+                }))
+                .await;
+        }
+
+        Ok(Output {
+            mintlayer_anchorer,
+            mintlayer_tx_manager,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for MintlayerAnchorer {
+    fn id(&self) -> TaskId {
+        "mintlayer_anchor".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for MintlayerTxManager {
+    fn id(&self) -> TaskId {
+        "mintlayer_tx_manager".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
@@ -0,0 +1,102 @@
+use zksync_config::configs::{MintlayerConfig, MintlayerSecrets};
+use zksync_ipfs_store::create_ipfs_reader;
+use zksync_mintlayer_anchor_verifier::MintlayerAnchorVerifier;
+use zksync_mintlayer_client::clients::HttpMintlayerClient;
+
+use crate::{
+    implementations::resources::{
+        healthcheck::AppHealthCheckResource,
+        main_node_client::MainNodeClientResource,
+        pools::{MasterPool, PoolResource},
+    },
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for the `MintlayerAnchorVerifier` (used by the external node).
+///
+/// The verifier checks that L1 batches are anchored on Mintlayer consistently with the local state.
+/// Anchored documents are fetched through a read-only IPFS reader, so only the Mintlayer RPC secrets are used.
+#[derive(Debug)]
+pub struct MintlayerAnchorVerifierLayer {
+    config: MintlayerConfig,
+    secrets: MintlayerSecrets,
+    max_batches_to_recheck: u32,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub main_node_client: MainNodeClientResource,
+    pub master_pool: PoolResource<MasterPool>,
+    #[context(default)]
+    pub app_health: AppHealthCheckResource,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    #[context(task)]
+    pub mintlayer_anchor_verifier: MintlayerAnchorVerifier,
+}
+
+impl MintlayerAnchorVerifierLayer {
+    pub fn new(
+        config: MintlayerConfig,
+        secrets: MintlayerSecrets,
+        max_batches_to_recheck: u32,
+    ) -> Self {
+        Self {
+            config,
+            secrets,
+            max_batches_to_recheck,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for MintlayerAnchorVerifierLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "mintlayer_anchor_verifier_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let singleton_pool = input.master_pool.get_singleton().await?;
+        let mintlayer_client = HttpMintlayerClient::new(&self.config, &self.secrets)?;
+
+        let mintlayer_anchor_verifier = MintlayerAnchorVerifier::new(
+            input.main_node_client.0,
+            Box::new(mintlayer_client),
+            create_ipfs_reader(&self.config)?,
+            self.config.confirmations(),
+            self.max_batches_to_recheck,
+            singleton_pool,
+        );
+
+        input
+            .app_health
+            .0
+            .insert_component(mintlayer_anchor_verifier.health_check().clone())
+            .map_err(WiringError::internal)?;
+
+        Ok(Output {
+            mintlayer_anchor_verifier,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for MintlayerAnchorVerifier {
+    fn id(&self) -> TaskId {
+        "mintlayer_anchor_verifier".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
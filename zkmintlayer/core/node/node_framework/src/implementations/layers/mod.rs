@@ -16,6 +16,8 @@ pub mod l1_gas;
 pub mod main_node_client;
 pub mod main_node_fee_params_fetcher;
 pub mod metadata_calculator;
+pub mod mintlayer_anchor;
+pub mod mintlayer_anchor_verifier;
 pub mod object_store;
 pub mod pk_signing_eth_client;
 pub mod pools_layer;
@@ -61,6 +61,7 @@ impl L2Parameters {
                 Ok(root_hash.map(|&hash| api::L1BatchDetails {
                     number,
                     base: mock_block_details_base(number.0, Some(hash)),
+                    mintlayer_anchor: None,
                 }))
             })
             .method("zks_getBlockDetails", move |number: L2BlockNumber| {
@@ -176,6 +176,7 @@ pub enum InteractionType {
 pub enum CheckerComponent {
     ConsistencyChecker,
     ReorgDetector,
+    MintlayerAnchorVerifier,
 }
 
 /// General-purpose external node metrics.
@@ -190,7 +191,7 @@ pub struct ExternalNodeMetrics {
     pub synced: Gauge<u64>,
     /// Current sync lag of the external node.
     pub sync_lag: Gauge<u64>,
-    /// Number of the last L1 batch checked by the re-org detector or consistency checker.
+    /// Number of the last L1 batch checked by the re-org detector, consistency checker or Mintlayer anchor verifier.
     pub last_correct_batch: Family<CheckerComponent, Gauge<u64>>,
     /// Number of the last L2 block checked by the re-org detector.
     pub last_correct_l2_block: Family<CheckerComponent, Gauge<u64>>,
@@ -25,6 +25,12 @@ use zksync_types::{
     ProtocolVersionId, StorageLog, H256, U256,
 };
 
+pub use self::mintlayer::{
+    mock_mintlayer_config, mock_mintlayer_secrets, MockIpfsNode, MockMintlayerWallet,
+};
+
+mod mintlayer;
+
 /// Creates an L2 block header with the specified number and deterministic contents.
 pub fn create_l2_block(number: u32) -> L2BlockHeader {
     L2BlockHeader {
@@ -0,0 +1,492 @@
+//! In-process stand-ins for the Mintlayer wallet RPC and IPFS backends, allowing to test anchoring end-to-end
+//! without a Mintlayer node or an IPFS pinning service.
+
+use std::{
+    collections::{HashMap, HashSet},
+    net::{Ipv4Addr, SocketAddr},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post, put},
+    Json, Router,
+};
+use jsonrpsee::{
+    server::{ServerBuilder, ServerHandle},
+    types::{ErrorObject, ErrorObjectOwned},
+    RpcModule,
+};
+use serde::Deserialize;
+use serde_json::json;
+use zksync_config::configs::{
+    mintlayer::{IpfsBackend, DEFAULT_ACCOUNT_INDEX},
+    MintlayerConfig, MintlayerSecrets,
+};
+use zksync_ipfs_store::Cid;
+
+/// Error code returned by [`MockMintlayerWallet`] for unknown transactions.
+const TX_NOT_FOUND_CODE: i32 = 1;
+
+#[derive(Debug)]
+struct WalletTx {
+    id: String,
+    data: Vec<u8>,
+    block_height: Option<u64>,
+}
+
+#[derive(Debug, Default)]
+struct MockWalletState {
+    best_block_height: u64,
+    balance_atoms: u128,
+    txs: Vec<WalletTx>,
+}
+
+impl MockWalletState {
+    fn find_tx(&self, tx_id: &str) -> Result<&WalletTx, ErrorObjectOwned> {
+        self.txs.iter().find(|tx| tx.id == tx_id).ok_or_else(|| {
+            ErrorObject::owned(TX_NOT_FOUND_CODE, "Transaction not found", None::<()>)
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DepositParams {
+    account: u32,
+    data: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TransactionGetParams {
+    account: u32,
+    transaction_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubmitParams {
+    tx: String,
+}
+
+/// Mock Mintlayer wallet RPC server bound to a local port.
+///
+/// The server implements the subset of the wallet RPC used by `HttpMintlayerClient` and returns responses
+/// in the same format as the real wallet. Deposited transactions are put into the mempool; they can be confirmed
+/// using [`Self::confirm_tx()`]. The server is stopped when the mock is dropped.
+#[derive(Debug)]
+pub struct MockMintlayerWallet {
+    state: Arc<Mutex<MockWalletState>>,
+    local_addr: SocketAddr,
+    server_handle: ServerHandle,
+}
+
+impl Drop for MockMintlayerWallet {
+    fn drop(&mut self) {
+        self.server_handle.stop().ok();
+    }
+}
+
+impl MockMintlayerWallet {
+    /// Starts the wallet RPC server on a random local port.
+    pub async fn spawn() -> Self {
+        let state = Arc::<Mutex<MockWalletState>>::default();
+        let mut rpc_module = RpcModule::new(state.clone());
+        rpc_module
+            .register_method("address_deposit_data", |params, state, _ext| {
+                let params: DepositParams = params.parse()?;
+                check_account(params.account)?;
+                let data = hex::decode(&params.data)
+                    .map_err(|err| invalid_params(format!("invalid hex data: {err}")))?;
+                let mut state = state.lock().expect("mock wallet is poisoned");
+                let tx_id = format!("{:064x}", state.txs.len() + 1);
+                state.txs.push(WalletTx {
+                    id: tx_id.clone(),
+                    data,
+                    block_height: None,
+                });
+                Ok::<_, ErrorObjectOwned>(json!({ "tx_id": tx_id, "broadcasted": true }))
+            })
+            .unwrap();
+        rpc_module
+            .register_method("transaction_get", |params, state, _ext| {
+                let params: TransactionGetParams = params.parse()?;
+                check_account(params.account)?;
+                let state = state.lock().expect("mock wallet is poisoned");
+                let tx = state.find_tx(&params.transaction_id)?;
+                let tx_state = match tx.block_height {
+                    Some(height) => json!({ "Confirmed": [height, 1_700_000_000, 0] }),
+                    None => json!({ "InMempool": 0 }),
+                };
+                Ok::<_, ErrorObjectOwned>(json!({
+                    "Tx": {
+                        "tx": {
+                            "V1": {
+                                "version": 1,
+                                "flags": 0,
+                                "inputs": [],
+                                "outputs": [{ "DataDeposit": hex::encode(&tx.data) }],
+                            },
+                        },
+                        "state": tx_state,
+                    },
+                }))
+            })
+            .unwrap();
+        rpc_module
+            .register_method("transaction_get_signed_raw", |params, state, _ext| {
+                let params: TransactionGetParams = params.parse()?;
+                check_account(params.account)?;
+                let state = state.lock().expect("mock wallet is poisoned");
+                let tx = state.find_tx(&params.transaction_id)?;
+                // The mock doesn't sign transactions; the "signed transaction" is just its ID.
+                Ok::<_, ErrorObjectOwned>(tx.id.clone())
+            })
+            .unwrap();
+        rpc_module
+            .register_method("node_submit_transaction", |params, state, _ext| {
+                let params: SubmitParams = params.parse()?;
+                let state = state.lock().expect("mock wallet is poisoned");
+                let tx = state.find_tx(&params.tx)?;
+                Ok::<_, ErrorObjectOwned>(json!({ "tx_id": tx.id, "broadcasted": true }))
+            })
+            .unwrap();
+        rpc_module
+            .register_method("wallet_best_block", |_params, state, _ext| {
+                let height = state
+                    .lock()
+                    .expect("mock wallet is poisoned")
+                    .best_block_height;
+                Ok::<_, ErrorObjectOwned>(
+                    json!({ "id": format!("{height:064x}"), "height": height }),
+                )
+            })
+            .unwrap();
+        rpc_module
+            .register_method("account_balance", |_params, state, _ext| {
+                let atoms = state.lock().expect("mock wallet is poisoned").balance_atoms;
+                // Mintlayer coins have 11 decimals.
+                let decimal = format!(
+                    "{}.{:011}",
+                    atoms / 100_000_000_000,
+                    atoms % 100_000_000_000
+                );
+                Ok::<_, ErrorObjectOwned>(json!({
+                    "coins": { "atoms": atoms.to_string(), "decimal": decimal },
+                    "tokens": {},
+                }))
+            })
+            .unwrap();
+
+        let server = ServerBuilder::default()
+            .http_only()
+            .build((Ipv4Addr::LOCALHOST, 0))
+            .await
+            .expect("failed starting mock Mintlayer wallet");
+        let local_addr = server.local_addr().unwrap();
+        let server_handle = server.start(rpc_module);
+        Self {
+            state,
+            local_addr,
+            server_handle,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MockWalletState> {
+        self.state.lock().expect("mock wallet is poisoned")
+    }
+
+    /// Returns the URL of the wallet RPC.
+    pub fn url(&self) -> String {
+        format!("http://{}/", self.local_addr)
+    }
+
+    /// Returns all deposited data in the order of deposits, together with the transaction IDs.
+    pub fn deposited_data(&self) -> Vec<(String, Vec<u8>)> {
+        self.lock()
+            .txs
+            .iter()
+            .map(|tx| (tx.id.clone(), tx.data.clone()))
+            .collect()
+    }
+
+    /// Sets the height of the best block reported by the wallet.
+    pub fn set_best_block_height(&self, height: u64) {
+        self.lock().best_block_height = height;
+    }
+
+    /// Sets the coin balance of the account.
+    pub fn set_balance(&self, atoms: u128) {
+        self.lock().balance_atoms = atoms;
+    }
+
+    /// Marks the transaction as included into a block at the specified height.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the transaction is unknown.
+    pub fn confirm_tx(&self, tx_id: &str, block_height: u64) {
+        let mut state = self.lock();
+        let tx = state
+            .txs
+            .iter_mut()
+            .find(|tx| tx.id == tx_id)
+            .unwrap_or_else(|| panic!("transaction {tx_id} is unknown"));
+        tx.block_height = Some(block_height);
+        state.best_block_height = state.best_block_height.max(block_height);
+    }
+}
+
+/// The mock wallet only has the default account.
+fn check_account(account: u32) -> Result<(), ErrorObjectOwned> {
+    if account == DEFAULT_ACCOUNT_INDEX {
+        Ok(())
+    } else {
+        Err(invalid_params(format!("account {account} not found")))
+    }
+}
+
+fn invalid_params(message: String) -> ErrorObjectOwned {
+    ErrorObject::owned(
+        jsonrpsee::types::error::INVALID_PARAMS_CODE,
+        message,
+        None::<()>,
+    )
+}
+
+#[derive(Debug, Default)]
+struct MockIpfsState {
+    documents: HashMap<String, Vec<u8>>,
+    pins: HashSet<String>,
+    /// CIDs of objects uploaded via the S3 API keyed by the bucket name and object key.
+    objects: HashMap<(String, String), String>,
+}
+
+impl MockIpfsState {
+    fn add(&mut self, contents: Vec<u8>) -> String {
+        let cid = Cid::compute(&contents).to_string();
+        self.documents.insert(cid.clone(), contents);
+        cid
+    }
+}
+
+type SharedIpfsState = Arc<Mutex<MockIpfsState>>;
+
+#[derive(Debug, Deserialize)]
+struct ArgQuery {
+    arg: String,
+}
+
+/// Mock IPFS node bound to a local port.
+///
+/// The node serves the same documents via the following APIs:
+///
+/// - The subset of the [Kubo RPC API](https://docs.ipfs.tech/reference/kubo/rpc/) used by `KuboIpfsStore`
+///   (`add`, `pin/add` and `cat`).
+/// - The S3 API used by `S3IpfsStore` (putting objects and reading their metadata with path-style bucket URLs).
+///   Like 4EVERLAND, the node pins uploaded objects and reports their CIDs in the `ipfs-hash` metadata field.
+///   Request signatures are not checked.
+/// - The read-only HTTP gateway (`/ipfs/{cid}`).
+///
+/// The node is stopped when the mock is dropped.
+#[derive(Debug)]
+pub struct MockIpfsNode {
+    state: SharedIpfsState,
+    local_addr: SocketAddr,
+    server_task: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for MockIpfsNode {
+    fn drop(&mut self) {
+        self.server_task.abort();
+    }
+}
+
+impl MockIpfsNode {
+    /// Starts the node on a random local port.
+    pub async fn spawn() -> Self {
+        let state = SharedIpfsState::default();
+        let router = Router::new()
+            .route("/api/v0/add", post(kubo_add))
+            .route("/api/v0/pin/add", post(kubo_pin_add))
+            .route("/api/v0/cat", post(kubo_cat))
+            .route("/ipfs/:cid", get(gateway_get))
+            .route("/:bucket/*key", put(s3_put_object).head(s3_head_object))
+            .with_state(state.clone());
+
+        let listener = tokio::net::TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .await
+            .expect("failed binding mock IPFS node");
+        let local_addr = listener.local_addr().unwrap();
+        let server_task = tokio::spawn(async move {
+            axum::serve(listener, router)
+                .await
+                .expect("mock IPFS node failed");
+        });
+        Self {
+            state,
+            local_addr,
+            server_task,
+        }
+    }
+
+    /// Returns the base URL of the node. All APIs supported by the node are served from this URL.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.local_addr)
+    }
+
+    /// Returns a document by its CID.
+    pub fn document(&self, cid: &str) -> Option<Vec<u8>> {
+        let state = self.state.lock().expect("mock IPFS node is poisoned");
+        state.documents.get(cid).cloned()
+    }
+
+    /// Checks whether a document with the specified CID is pinned.
+    pub fn is_pinned(&self, cid: &str) -> bool {
+        let state = self.state.lock().expect("mock IPFS node is poisoned");
+        state.pins.contains(cid)
+    }
+
+    /// Returns the number of stored documents.
+    pub fn document_count(&self) -> usize {
+        let state = self.state.lock().expect("mock IPFS node is poisoned");
+        state.documents.len()
+    }
+}
+
+fn kubo_error(message: &str) -> Response {
+    let body = json!({ "Message": message, "Code": 0, "Type": "error" });
+    (StatusCode::INTERNAL_SERVER_ERROR, Json(body)).into_response()
+}
+
+async fn kubo_add(
+    State(state): State<SharedIpfsState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    let Some((name, contents)) = parse_multipart_file(content_type, &body) else {
+        return kubo_error("failed to decode multipart request");
+    };
+    let size = contents.len();
+    let cid = state
+        .lock()
+        .expect("mock IPFS node is poisoned")
+        .add(contents);
+    let response = json!({ "Name": name, "Hash": cid, "Size": size.to_string() });
+    format!("{response}\n").into_response()
+}
+
+async fn kubo_pin_add(
+    State(state): State<SharedIpfsState>,
+    Query(query): Query<ArgQuery>,
+) -> Response {
+    let mut state = state.lock().expect("mock IPFS node is poisoned");
+    if !state.documents.contains_key(&query.arg) {
+        return kubo_error(&format!("block {} not found", query.arg));
+    }
+    state.pins.insert(query.arg.clone());
+    Json(json!({ "Pins": [query.arg] })).into_response()
+}
+
+async fn kubo_cat(State(state): State<SharedIpfsState>, Query(query): Query<ArgQuery>) -> Response {
+    let state = state.lock().expect("mock IPFS node is poisoned");
+    match state.documents.get(&query.arg) {
+        Some(contents) => contents.clone().into_response(),
+        None => kubo_error(&format!("block {} not found", query.arg)),
+    }
+}
+
+async fn gateway_get(State(state): State<SharedIpfsState>, Path(cid): Path<String>) -> Response {
+    let state = state.lock().expect("mock IPFS node is poisoned");
+    match state.documents.get(&cid) {
+        Some(contents) => contents.clone().into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+async fn s3_put_object(
+    State(state): State<SharedIpfsState>,
+    Path((bucket, key)): Path<(String, String)>,
+    body: Bytes,
+) -> Response {
+    let mut state = state.lock().expect("mock IPFS node is poisoned");
+    let cid = state.add(body.to_vec());
+    state.pins.insert(cid.clone());
+    state.objects.insert((bucket, key), cid.clone());
+    ([(header::ETAG, format!("\"{cid}\""))], ()).into_response()
+}
+
+async fn s3_head_object(
+    State(state): State<SharedIpfsState>,
+    Path((bucket, key)): Path<(String, String)>,
+) -> Response {
+    let state = state.lock().expect("mock IPFS node is poisoned");
+    let Some(cid) = state.objects.get(&(bucket, key)) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let size = state.documents[cid].len();
+    let headers = [
+        (header::ETAG.as_str(), format!("\"{cid}\"")),
+        ("x-amz-meta-ipfs-hash", cid.clone()),
+        ("x-amz-meta-size", size.to_string()),
+    ];
+    (headers, ()).into_response()
+}
+
+/// Extracts the name and contents of the first file in a `multipart/form-data` body.
+fn parse_multipart_file(content_type: &str, body: &[u8]) -> Option<(String, Vec<u8>)> {
+    let boundary = content_type
+        .split(';')
+        .find_map(|param| param.trim().strip_prefix("boundary="))?
+        .trim_matches('"');
+    let delimiter = format!("--{boundary}");
+    let part_start = find_bytes(body, delimiter.as_bytes())? + delimiter.len();
+    let part = &body[part_start..];
+    let headers_end = find_bytes(part, b"\r\n\r\n")?;
+    let part_headers = std::str::from_utf8(&part[..headers_end]).ok()?;
+    let contents = &part[headers_end + 4..];
+    let contents_end = find_bytes(contents, format!("\r\n{delimiter}").as_bytes())?;
+
+    let name = part_headers
+        .split(|ch| matches!(ch, ';' | '\r' | '\n'))
+        .find_map(|param| param.trim().strip_prefix("filename="))
+        .unwrap_or_default()
+        .trim_matches('"');
+    Some((name.to_owned(), contents[..contents_end].to_vec()))
+}
+
+fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// Returns a Mintlayer config using the mock wallet and the specified backend of the mock IPFS node.
+pub fn mock_mintlayer_config(
+    wallet: &MockMintlayerWallet,
+    ipfs_node: &MockIpfsNode,
+    ipfs_backend: IpfsBackend,
+) -> MintlayerConfig {
+    MintlayerConfig {
+        rpc_url: wallet.url(),
+        ipfs_backend: Some(ipfs_backend),
+        ipfs_endpoint: Some(ipfs_node.url()),
+        ipfs_gateway_url: Some(ipfs_node.url()),
+        ipfs_api_url: Some(ipfs_node.url()),
+        ..MintlayerConfig::for_tests()
+    }
+}
+
+/// Returns Mintlayer secrets accepted by the mock wallet and IPFS node.
+pub fn mock_mintlayer_secrets() -> MintlayerSecrets {
+    MintlayerSecrets {
+        rpc_password: None,
+        ipfs_api_key: Some("test-access-key".to_owned().into()),
+        ipfs_secret_key: Some("test-secret-key".to_owned().into()),
+    }
+}
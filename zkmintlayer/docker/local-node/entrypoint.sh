@@ -190,5 +190,6 @@ else
 
 fi
 
-# start server
-zk f zksync_server
+# start server; `mintlayer_anchor` is not a default component, so components are listed explicitly
+SERVER_COMPONENTS=${SERVER_COMPONENTS:-"api,tree,eth,state_keeper,housekeeper,tee_verifier_input_producer,commitment_generator,da_dispatcher,base_token_ratio_persister,mintlayer_anchor"}
+zk f zksync_server --components="$SERVER_COMPONENTS"
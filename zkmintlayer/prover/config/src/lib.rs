@@ -57,6 +57,7 @@ fn load_env_config() -> anyhow::Result<TempConfigStore> {
         commitment_generator: None,
         pruning: None,
         snapshot_recovery: None,
+        mintlayer_config: None,
     })
 }
 